@@ -27,7 +27,7 @@ pub struct StyleBorder {
     pub cap: Cap,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum DashStyle {
     Owned {
         intervals: SmallVec<[Pixel; 8]>,
@@ -87,7 +87,7 @@ pub(super) fn draw_border(canvas: &mut Canvas, mut rrect: RRect, border: &StyleB
     stroke_width
 }
 
-fn parse_dash_style(style: &DashStyle, stroke_width: f32) -> Option<PathEffect> {
+pub(crate) fn parse_dash_style(style: &DashStyle, stroke_width: f32) -> Option<PathEffect> {
     fn slice_dash(intervals: &[Pixel], phase: &Pixel) -> Option<PathEffect> {
         let vec: SmallVec<[f32; 12]> = intervals.iter().map(|px| px.to_physical()).collect();
         PathEffect::dash(&vec, phase.to_physical())
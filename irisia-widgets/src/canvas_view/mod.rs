@@ -0,0 +1,134 @@
+use std::{any::Any, rc::Rc};
+
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    ElModel,
+};
+
+pub use self::painter::Painter;
+
+mod painter;
+
+/// An element drawn by a closure, for custom visuals which do not deserve
+/// an element of their own.
+///
+/// ```ignore
+/// CanvasView {
+///     draw: Painting::new(move |painter| {
+///         let (width, height) = painter.size();
+///         painter.line(
+///             Point(Pixel(0.0), height),
+///             Point(width, Pixel(0.0)),
+///             &Painter::stroke(Color::BLUE, Pixel(2.0)),
+///         );
+///     }),
+///     deps: Deps::new(self.points.clone()),
+/// }
+/// ```
+///
+/// The view is drawn again when `deps` changes, or when it is moved or
+/// resized. A new closure alone does not redraw it, so everything the
+/// closure reads from outside should be in `deps`.
+pub struct CanvasView {
+    props: OwnedProps,
+}
+
+#[irisia::props(updater = "CanvasViewProps", watch)]
+pub struct OwnedProps {
+    #[props(must_init, watch)]
+    draw: Painting,
+
+    #[props(default, watch)]
+    deps: Deps,
+}
+
+/// Draws a [`CanvasView`] with the painter given.
+#[derive(Clone)]
+pub struct Painting(Rc<dyn Fn(&mut Painter)>);
+
+impl Painting {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&mut Painter) + 'static,
+    {
+        Painting(Rc::new(f))
+    }
+}
+
+impl PartialEq for Painting {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A value a [`CanvasView`] is drawn from, of any comparable type. The view
+/// is drawn again once it no longer equals the last one. Defaults to no
+/// dependency.
+#[derive(Clone, Default)]
+pub struct Deps(Option<Rc<dyn ErasedDeps>>);
+
+impl Deps {
+    pub fn new<T>(value: T) -> Self
+    where
+        T: PartialEq + 'static,
+    {
+        Deps(Some(Rc::new(value)))
+    }
+}
+
+impl PartialEq for Deps {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(this), Some(other)) => this.eq_dyn(&**other),
+            (this, other) => this.is_none() && other.is_none(),
+        }
+    }
+}
+
+trait ErasedDeps {
+    fn as_any(&self) -> &dyn Any;
+    fn eq_dyn(&self, other: &dyn ErasedDeps) -> bool;
+}
+
+impl<T> ErasedDeps for T
+where
+    T: PartialEq + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_dyn(&self, other: &dyn ErasedDeps) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
+impl Element for CanvasView {
+    type BlankProps = CanvasViewProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        if region.0 .0 >= region.1 .0 || region.0 .1 >= region.1 .1 {
+            return Ok(());
+        }
+
+        (self.props.draw.0)(&mut Painter::new(content.canvas(), region));
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for CanvasView
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        CanvasView {
+            props: OwnedProps::props_create_with(props),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        // the closure is taken, but only changed dependencies redraw
+        self.props.props_update_with(props).deps_unchanged
+    }
+}
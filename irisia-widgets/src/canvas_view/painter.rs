@@ -0,0 +1,119 @@
+use irisia::{
+    primitive::{Pixel, Point, Region},
+    skia_safe::{self, Canvas, Color, Paint, PaintStyle, Path, RRect, Rect},
+};
+
+use crate::label::{label_font, label_width, to_rect};
+
+/// Canvas of a [`CanvasView`], whose origin is the top left corner of the
+/// view and whose drawing is clipped to the view. Helpers take lengths in
+/// [`Pixel`]s from the origin, while [`Painter::canvas`] gives the skia
+/// canvas for anything else, in physical units.
+///
+/// [`CanvasView`]: super::CanvasView
+pub struct Painter<'a> {
+    canvas: &'a mut Canvas,
+    size: (Pixel, Pixel),
+}
+
+impl<'a> Painter<'a> {
+    /// Translate and clip `canvas` to `region`, until the painter dropped.
+    pub(super) fn new(canvas: &'a mut Canvas, (start, end): Region) -> Self {
+        let size = (end.0 - start.0, end.1 - start.1);
+        canvas.save();
+        canvas.translate((start.0.to_physical(), start.1.to_physical()));
+        canvas.clip_rect(
+            Rect::from_wh(size.0.to_physical(), size.1.to_physical()),
+            None,
+            true,
+        );
+        Painter { canvas, size }
+    }
+
+    /// Width and height of the view.
+    pub fn size(&self) -> (Pixel, Pixel) {
+        self.size
+    }
+
+    pub fn canvas(&mut self) -> &mut Canvas {
+        self.canvas
+    }
+
+    /// A paint filling shapes with `color`.
+    pub fn fill(color: Color) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_color(color);
+        paint
+    }
+
+    /// A paint drawing outlines of shapes `width` wide with `color`.
+    pub fn stroke(color: Color, width: Pixel) -> Paint {
+        let mut paint = Self::fill(color);
+        paint
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(width.to_physical());
+        paint
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        self.canvas.draw_color(color, None);
+    }
+
+    pub fn line(&mut self, from: Point, to: Point, paint: &Paint) {
+        self.canvas.draw_line(from, to, paint);
+    }
+
+    /// A line through all the points.
+    pub fn polyline(&mut self, points: &[Point], paint: &Paint) {
+        let Some((first, rest)) = points.split_first()
+        else {
+            return;
+        };
+
+        let mut path = Path::new();
+        path.move_to(*first);
+        for point in rest {
+            path.line_to(*point);
+        }
+
+        let mut paint = paint.clone();
+        paint.set_style(PaintStyle::Stroke);
+        self.canvas.draw_path(&path, &paint);
+    }
+
+    pub fn rect(&mut self, rect: Region, paint: &Paint) {
+        self.canvas.draw_rect(to_rect(rect), paint);
+    }
+
+    pub fn rounded_rect(&mut self, rect: Region, radius: Pixel, paint: &Paint) {
+        let radius = radius.to_physical();
+        self.canvas
+            .draw_rrect(RRect::new_rect_xy(to_rect(rect), radius, radius), paint);
+    }
+
+    pub fn circle(&mut self, center: Point, radius: Pixel, paint: &Paint) {
+        self.canvas.draw_circle(center, radius.to_physical(), paint);
+    }
+
+    /// Draw `text` in a single line, starting from `origin` on the baseline.
+    pub fn text(&mut self, text: &str, origin: Point, size: Pixel, color: Color) {
+        self.canvas
+            .draw_str(text, origin, &label_font(size), &Self::fill(color));
+    }
+
+    /// Width of `text` drawn by [`Painter::text`].
+    pub fn text_width(&self, text: &str, size: Pixel) -> Pixel {
+        label_width(&label_font(size), text)
+    }
+
+    /// Convert a point in the view to a point of skia, for [`Painter::canvas`].
+    pub fn to_skia(point: Point) -> skia_safe::Point {
+        point.into()
+    }
+}
+
+impl Drop for Painter<'_> {
+    fn drop(&mut self) {
+        self.canvas.restore();
+    }
+}
@@ -0,0 +1,471 @@
+use std::rc::Rc;
+
+use crate::civil::civil_from_days;
+
+/// Number of ticks an axis aims at
+const TICKS: f64 = 5.0;
+const DAY: f64 = 86400.0;
+/// Steps between ticks of a time axis shorter than a day, in seconds
+const TIME_STEPS: [f64; 17] = [
+    1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1800.0, 3600.0, 7200.0,
+    10800.0, 21600.0, 43200.0,
+];
+
+/// How values are placed along an axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AxisScale {
+    #[default]
+    Linear,
+    /// Powers of ten are equally spaced. Values not above zero are left out.
+    Log,
+    /// Values are seconds since the Unix epoch, labelled in UTC.
+    Time,
+}
+
+/// An axis of a [`Chart`], whose range fits the data unless given.
+///
+/// [`Chart`]: super::Chart
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Axis {
+    pub(super) scale: AxisScale,
+    pub(super) range: Option<(f64, f64)>,
+    suffix: String,
+}
+
+impl Axis {
+    pub fn linear() -> Self {
+        Self::default()
+    }
+
+    pub fn log() -> Self {
+        Axis {
+            scale: AxisScale::Log,
+            ..Default::default()
+        }
+    }
+
+    pub fn time() -> Self {
+        Axis {
+            scale: AxisScale::Time,
+            ..Default::default()
+        }
+    }
+
+    /// Show `min..max` rather than fitting the range to the data. Ignored
+    /// unless both ends are finite, and above zero on a log axis.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Text appended to the labels, like a unit.
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+}
+
+/// Maps values along an axis to ratios, where the start of the axis is 0
+/// and the end is 1.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Scale {
+    kind: ScaleKind,
+    min: f64,
+    max: f64,
+    suffix: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ScaleKind {
+    Linear,
+    Log,
+    Time,
+    /// Each category takes a band of the same width, and values are indices
+    Category(Rc<[String]>),
+}
+
+impl Scale {
+    /// Fit `axis` to `values` with round ends, including zero if `zero`.
+    pub(super) fn fit(axis: &Axis, values: impl Iterator<Item = f64>, zero: bool) -> Self {
+        let kind = match axis.scale {
+            AxisScale::Linear => ScaleKind::Linear,
+            AxisScale::Log => ScaleKind::Log,
+            AxisScale::Time => ScaleKind::Time,
+        };
+        let scale = |min, max| Scale {
+            kind: kind.clone(),
+            min,
+            max,
+            suffix: axis.suffix.clone(),
+        };
+
+        match axis.range {
+            Some((min, max)) if !min.is_finite() || !max.is_finite() => {}
+            Some((min, max)) if kind == ScaleKind::Log && (min <= 0.0 || max <= 0.0) => {}
+            Some((min, max)) => return scale(min, max),
+            None => {}
+        }
+
+        let (mut min, mut max) = values
+            .filter(|value| value.is_finite() && (kind != ScaleKind::Log || *value > 0.0))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        if min > max {
+            (min, max) = match kind {
+                ScaleKind::Log => (1.0, 10.0),
+                _ => (0.0, 1.0),
+            };
+        }
+        if zero && kind == ScaleKind::Linear {
+            min = min.min(0.0);
+            max = max.max(0.0);
+        }
+
+        if kind == ScaleKind::Log {
+            min = 10f64.powf(min.log10().floor());
+            max = 10f64.powf(max.log10().ceil()).max(min * 10.0);
+            return scale(min, max);
+        }
+
+        if max - min < f64::EPSILON * max.abs().max(1.0) {
+            let pad = if min == 0.0 { 1.0 } else { min.abs() / 10.0 };
+            min -= pad;
+            max += pad;
+        }
+        let mut fitted = scale(min, max);
+        let step = fitted.step();
+        fitted.min = (min / step).floor() * step;
+        fitted.max = (max / step).ceil() * step;
+        fitted
+    }
+
+    /// A scale of the categories named.
+    pub(super) fn categories(names: Rc<[String]>) -> Self {
+        Scale {
+            min: 0.0,
+            max: names.len().max(1) as f64,
+            kind: ScaleKind::Category(names),
+            suffix: String::new(),
+        }
+    }
+
+    /// Extend both ends by `amount`, so that bars at the ends fit.
+    pub(super) fn pad(&mut self, amount: f64) {
+        if matches!(self.kind, ScaleKind::Linear | ScaleKind::Time) {
+            self.min -= amount;
+            self.max += amount;
+        }
+    }
+
+    /// Ratio of `value` along the axis, `None` if it cannot be placed.
+    pub(super) fn ratio(&self, value: f64) -> Option<f64> {
+        let (value, min, max) = match self.kind {
+            ScaleKind::Log if value <= 0.0 => return None,
+            ScaleKind::Log => (value.log10(), self.min.log10(), self.max.log10()),
+            ScaleKind::Category(_) => (value + 0.5, self.min, self.max),
+            _ => (value, self.min, self.max),
+        };
+
+        if !value.is_finite() {
+            None
+        } else if max - min <= 0.0 {
+            Some(0.5)
+        } else {
+            Some((value - min) / (max - min))
+        }
+    }
+
+    /// Width of the band of a category as a ratio, `None` if not categorical.
+    pub(super) fn band(&self) -> Option<f64> {
+        match self.kind {
+            ScaleKind::Category(_) => Some(1.0 / (self.max - self.min)),
+            _ => None,
+        }
+    }
+
+    /// The value bars and areas start from.
+    pub(super) fn baseline(&self) -> f64 {
+        match self.kind {
+            ScaleKind::Log => self.min,
+            _ => 0.0f64.clamp(self.min.min(self.max), self.max.max(self.min)),
+        }
+    }
+
+    /// Values to label along the axis, in ascending order.
+    pub(super) fn ticks(&self) -> Vec<f64> {
+        match &self.kind {
+            ScaleKind::Category(names) => (0..names.len()).map(|index| index as f64).collect(),
+            ScaleKind::Log => {
+                let first = self.min.log10().ceil() as i32;
+                let last = self.max.log10().floor() as i32;
+                let every = ((last - first) as f64 / TICKS).ceil().max(1.0) as i32;
+                (first..=last)
+                    .filter(|exp| (exp - first) % every == 0)
+                    .map(|exp| 10f64.powi(exp))
+                    .collect()
+            }
+            ScaleKind::Linear | ScaleKind::Time => {
+                let step = self.step();
+                let mut ticks = Vec::new();
+                let mut index = (self.min / step).ceil();
+                while index * step <= self.max + step * 1e-9 && ticks.len() <= TICKS as usize * 4 {
+                    ticks.push(index * step);
+                    index += 1.0;
+                }
+                ticks
+            }
+        }
+    }
+
+    /// Text of `value`, a tick label, or the exact value for a tooltip if `exact`.
+    pub(super) fn label(&self, value: f64, exact: bool) -> String {
+        let text = match &self.kind {
+            ScaleKind::Category(names) => {
+                return names
+                    .get(value.round().max(0.0) as usize)
+                    .cloned()
+                    .unwrap_or_default()
+            }
+            ScaleKind::Time if exact => time_label(value, 0.0),
+            ScaleKind::Time => time_label(value, self.step()),
+            ScaleKind::Log => number_label(value, if exact { value / 1000.0 } else { value }),
+            ScaleKind::Linear if exact => number_label(value, self.step() / 1000.0),
+            ScaleKind::Linear => number_label(value, self.step()),
+        };
+        text + &self.suffix
+    }
+
+    /// A scale between `self` at 0 and `to` at 1, or `to` if they are of
+    /// different kinds.
+    pub(super) fn lerp(&self, to: &Scale, t: f64) -> Scale {
+        if self.kind != to.kind {
+            return to.clone();
+        }
+
+        let (min, max) = match self.kind {
+            ScaleKind::Log => (
+                10f64.powf(lerp(self.min.log10(), to.min.log10(), t)),
+                10f64.powf(lerp(self.max.log10(), to.max.log10(), t)),
+            ),
+            _ => (lerp(self.min, to.min, t), lerp(self.max, to.max, t)),
+        };
+        Scale {
+            min,
+            max,
+            ..to.clone()
+        }
+    }
+
+    /// Distance between ticks of a linear or time axis.
+    fn step(&self) -> f64 {
+        let span = (self.max - self.min).abs();
+        if span == 0.0 || !span.is_finite() {
+            return 1.0;
+        }
+
+        let raw = span / TICKS;
+        if self.kind == ScaleKind::Time {
+            return match TIME_STEPS.iter().find(|step| **step >= raw) {
+                Some(step) => *step,
+                None => (raw / DAY).ceil() * DAY,
+            };
+        }
+
+        let magnitude = 10f64.powf(raw.log10().floor());
+        let step = match raw / magnitude {
+            norm if norm <= 1.0 => 1.0,
+            norm if norm <= 2.0 => 2.0,
+            norm if norm <= 5.0 => 5.0,
+            _ => 10.0,
+        };
+        step * magnitude
+    }
+}
+
+pub(super) fn lerp(from: f64, to: f64, t: f64) -> f64 {
+    from + (to - from) * t
+}
+
+/// `value` with as many decimals as `precision` needs, in thousands,
+/// millions and so on if large.
+fn number_label(value: f64, precision: f64) -> String {
+    const UNITS: [(f64, &str); 4] = [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "k")];
+
+    // rounding may still give `-0`, which is trimmed below
+    let value = if value == 0.0 { 0.0 } else { value };
+    let (divisor, unit) = UNITS
+        .into_iter()
+        .find(|(divisor, _)| value.abs() >= *divisor)
+        .unwrap_or((1.0, ""));
+
+    let precision = precision.abs() / divisor;
+    let decimals = if precision > 0.0 && precision.is_finite() {
+        (-precision.log10().floor()).clamp(0.0, 6.0) as usize
+    } else {
+        0
+    };
+    let text = format!("{:.*}", decimals, value / divisor);
+
+    // trailing zeros of exact values say nothing
+    let text = match text.trim_end_matches('0').trim_end_matches('.') {
+        _ if decimals == 0 => &text,
+        "-0" => "0",
+        trimmed => trimmed,
+    };
+    format!("{text}{unit}")
+}
+
+/// Time of `secs` since the Unix epoch in UTC, as detailed as ticks `step`
+/// seconds apart need, or in full if `step` is zero.
+fn time_label(secs: f64, step: f64) -> String {
+    let secs = secs.round() as i64;
+    let (days, rest) = (secs.div_euclid(DAY as i64), secs.rem_euclid(DAY as i64));
+    let (hour, minute, second) = (rest / 3600, rest / 60 % 60, rest % 60);
+
+    if step == 0.0 {
+        let (year, month, day) = civil_from_days(days);
+        format!("{year}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02}")
+    } else if step < 60.0 {
+        format!("{hour:02}:{minute:02}:{second:02}")
+    } else if step < DAY {
+        format!("{hour:02}:{minute:02}")
+    } else {
+        let (_, month, day) = civil_from_days(days);
+        format!("{month:02}-{day:02}")
+    }
+}
+
+#[cfg(test)]
+fn fit(axis: &Axis, values: &[f64]) -> Scale {
+    Scale::fit(axis, values.iter().copied(), false)
+}
+
+#[test]
+fn test_fit() {
+    let scale = fit(&Axis::linear(), &[3.0, 17.0]);
+    assert_eq!((scale.min, scale.max), (0.0, 20.0));
+    let scale = fit(&Axis::linear(), &[12.0, 18.0, f64::NAN]);
+    assert_eq!((scale.min, scale.max), (12.0, 18.0));
+    let scale = Scale::fit(&Axis::linear(), [12.0, 18.0].into_iter(), true);
+    assert_eq!((scale.min, scale.max), (0.0, 20.0));
+    let scale = fit(&Axis::linear(), &[]);
+    assert_eq!((scale.min, scale.max), (0.0, 1.0));
+
+    // a single value is padded around
+    let scale = fit(&Axis::linear(), &[5.0, 5.0]);
+    assert!(scale.min < 5.0 && scale.max > 5.0);
+
+    let scale = fit(&Axis::linear().range(-5.0, 7.0), &[100.0]);
+    assert_eq!((scale.min, scale.max), (-5.0, 7.0));
+    let scale = fit(&Axis::linear().range(f64::NAN, 7.0), &[3.0, 17.0]);
+    assert_eq!((scale.min, scale.max), (0.0, 20.0));
+}
+
+#[test]
+fn test_fit_log() {
+    let scale = fit(&Axis::log(), &[3.0, 450.0]);
+    assert_eq!((scale.min, scale.max), (1.0, 1000.0));
+    let scale = fit(&Axis::log(), &[-1.0, 0.0, 20.0]);
+    assert_eq!((scale.min, scale.max), (10.0, 100.0));
+    let scale = fit(&Axis::log(), &[0.0]);
+    assert_eq!((scale.min, scale.max), (1.0, 10.0));
+
+    let scale = fit(&Axis::log().range(1.0, 1e4), &[3.0]);
+    assert_eq!((scale.min, scale.max), (1.0, 1e4));
+
+    // ends not above zero cannot be placed, the data decides instead
+    for range in [(0.0, 100.0), (-10.0, 100.0), (1.0, -1.0)] {
+        let scale = fit(&Axis::log().range(range.0, range.1), &[3.0, 450.0]);
+        assert_eq!((scale.min, scale.max), (1.0, 1000.0));
+        assert_eq!(scale.ticks(), [1.0, 10.0, 100.0, 1000.0]);
+        assert!(scale.ratio(10.0).unwrap().is_finite());
+    }
+}
+
+#[test]
+fn test_ticks() {
+    let scale = fit(&Axis::linear(), &[3.0, 17.0]);
+    assert_eq!(scale.ticks(), [0.0, 5.0, 10.0, 15.0, 20.0]);
+    let scale = fit(&Axis::linear().range(1.0, 9.0), &[]);
+    assert_eq!(scale.ticks(), [2.0, 4.0, 6.0, 8.0]);
+
+    let scale = fit(&Axis::log().range(1.0, 1e4), &[]);
+    assert_eq!(scale.ticks(), [1.0, 10.0, 100.0, 1e3, 1e4]);
+    let scale = fit(&Axis::log().range(1.0, 1e12), &[]);
+    assert_eq!(scale.ticks(), [1.0, 1e3, 1e6, 1e9, 1e12]);
+
+    let names: Rc<[String]> = ["a", "b", "c"].map(String::from).into();
+    assert_eq!(Scale::categories(names).ticks(), [0.0, 1.0, 2.0]);
+}
+
+#[test]
+fn test_ratio() {
+    let scale = fit(&Axis::linear(), &[3.0, 17.0]);
+    assert_eq!(scale.ratio(5.0), Some(0.25));
+    assert_eq!(scale.ratio(-10.0), Some(-0.5));
+    assert_eq!(scale.ratio(f64::NAN), None);
+    assert_eq!(
+        fit(&Axis::linear().range(3.0, 3.0), &[]).ratio(8.0),
+        Some(0.5)
+    );
+
+    let scale = fit(&Axis::log(), &[1.0, 1000.0]);
+    assert!((scale.ratio(10.0).unwrap() - 1.0 / 3.0).abs() < 1e-9);
+    assert_eq!(scale.ratio(0.0), None);
+    assert_eq!(scale.ratio(-1.0), None);
+
+    let names: Rc<[String]> = ["a", "b", "c", "d"].map(String::from).into();
+    let scale = Scale::categories(names);
+    assert_eq!(scale.ratio(0.0), Some(0.125));
+    assert_eq!(scale.band(), Some(0.25));
+    assert_eq!(fit(&Axis::linear(), &[]).band(), None);
+}
+
+#[test]
+fn test_step() {
+    let step = |axis: Axis| fit(&axis, &[]).step();
+    assert_eq!(step(Axis::linear().range(0.0, 70.0)), 20.0);
+    assert_eq!(step(Axis::linear().range(0.0, 300.0)), 100.0);
+    assert_eq!(step(Axis::linear().range(0.0, 2000.0)), 500.0);
+    assert_eq!(step(Axis::linear().range(0.0, 5.0)), 1.0);
+    assert_eq!(step(Axis::linear().range(4.0, 4.0)), 1.0);
+
+    assert_eq!(step(Axis::time().range(0.0, 60.0)), 15.0);
+    assert_eq!(step(Axis::time().range(0.0, 600.0)), 120.0);
+    assert_eq!(step(Axis::time().range(0.0, 4.0 * DAY)), DAY);
+    assert_eq!(step(Axis::time().range(0.0, 10.0 * DAY)), 2.0 * DAY);
+}
+
+#[test]
+fn test_label() {
+    let scale = fit(&Axis::linear().range(0.0, 2000.0), &[]);
+    assert_eq!(scale.label(1500.0, false), "1.5k");
+    assert_eq!(scale.label(2000.0, false), "2k");
+    assert_eq!(scale.label(-0.0, false), "0");
+    assert_eq!(scale.label(1234.5, true), "1.2345k");
+
+    let scale = fit(&Axis::linear().range(0.0, 1.0), &[]);
+    assert_eq!(scale.label(0.4, false), "0.4");
+    assert_eq!(scale.label(0.123456, true), "0.1235");
+    let scale = fit(&Axis::linear().range(0.0, 100.0).suffix("%"), &[]);
+    assert_eq!(scale.label(40.0, false), "40%");
+    let scale = fit(&Axis::log().range(1.0, 1e9), &[]);
+    assert_eq!(scale.label(1e6, false), "1M");
+    assert_eq!(scale.label(2.5e9, true), "2.5G");
+
+    let day = 19787.0 * DAY;
+    let scale = fit(&Axis::time().range(0.0, 60.0), &[]);
+    assert_eq!(scale.label(day + 15.0, false), "00:00:15");
+    assert_eq!(scale.label(day + 3723.0, true), "2024-03-05 01:02:03");
+    let scale = fit(&Axis::time().range(0.0, 600.0), &[]);
+    assert_eq!(scale.label(day + 3723.0, false), "01:02");
+    let scale = fit(&Axis::time().range(0.0, 10.0 * DAY), &[]);
+    assert_eq!(scale.label(day, false), "03-05");
+    assert_eq!(scale.label(-1.0, false), "12-31");
+
+    let names: Rc<[String]> = ["a", "b"].map(String::from).into();
+    let scale = Scale::categories(names);
+    assert_eq!(scale.label(1.2, false), "b");
+    assert_eq!(scale.label(9.0, false), "");
+}
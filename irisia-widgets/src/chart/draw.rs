@@ -0,0 +1,539 @@
+use irisia::{
+    primitive::Pixel,
+    skia_safe::{
+        paint::{Cap, Join},
+        Canvas, Color, Font, Paint, PaintStyle, Path, Point as SkiaPoint, RRect, Rect,
+    },
+};
+
+use crate::label::{draw_label, label_width};
+
+use super::{
+    series::{palette, ChartKind, Series},
+    Frame,
+};
+
+const GAP: Pixel = Pixel(6.0);
+const LEGEND_SPACING: Pixel = Pixel(16.0);
+const SWATCH: Pixel = Pixel(10.0);
+const DOT_RADIUS: Pixel = Pixel(3.5);
+const TOOLTIP_PADDING: Pixel = Pixel(8.0);
+/// Distance from the pointer to the tooltip
+const TOOLTIP_OFFSET: Pixel = Pixel(12.0);
+/// Distance a hovered slice of a pie moves out
+const SLICE_LIFT: Pixel = Pixel(6.0);
+/// Part of the space around each x taken by bars
+const BAR_FILL: f32 = 0.8;
+const AREA_ALPHA: u8 = 0x40;
+const BAND_ALPHA: u8 = 0x30;
+
+/// Fonts, colors and widths a chart is drawn with, in physical units.
+pub(super) struct Look {
+    pub font: Font,
+    pub line_height: f32,
+    pub text: Color,
+    pub axis: Color,
+    pub grid: Color,
+    pub crosshair: Color,
+    pub background: Color,
+    pub line_width: f32,
+}
+
+/// Draw the legend at the top of `area`, and give the height it takes.
+/// Entries not fitting in a line wrap to the next one.
+pub(super) fn legend(
+    canvas: &mut Canvas,
+    look: &Look,
+    area: Rect,
+    entries: &[(Color, String)],
+) -> f32 {
+    if entries.is_empty() {
+        return 0.0;
+    }
+
+    let (swatch, gap) = (SWATCH.to_physical(), GAP.to_physical());
+    let mut x = area.left;
+    let mut center = area.top + look.line_height / 2.0;
+    for (color, name) in entries {
+        let width = swatch + gap + text_width(look, name);
+        if x > area.left && x + width > area.right {
+            x = area.left;
+            center += look.line_height;
+        }
+
+        draw_swatch(canvas, *color, x, center);
+        draw_text(canvas, look, name, look.text, x + swatch + gap, center);
+        x += width + LEGEND_SPACING.to_physical();
+    }
+    center + look.line_height / 2.0 + gap - area.top
+}
+
+/// Draw the axes, the series and the crosshair of a line, area or bar chart.
+pub(super) fn cartesian(
+    canvas: &mut Canvas,
+    look: &Look,
+    area: Rect,
+    kind: ChartKind,
+    series: &[Series],
+    frame: &Frame,
+    pointer: Option<SkiaPoint>,
+) {
+    let gap = GAP.to_physical();
+    let y_ticks: Vec<(f64, String)> = frame
+        .y
+        .ticks()
+        .into_iter()
+        .map(|value| (value, frame.y.label(value, false)))
+        .collect();
+    let label_width = y_ticks
+        .iter()
+        .map(|(_, label)| text_width(look, label))
+        .fold(0.0, f32::max);
+
+    let plot = Rect::new(
+        area.left + label_width + gap,
+        area.top + look.line_height / 2.0,
+        area.right - gap,
+        area.bottom - look.line_height - gap,
+    );
+    if plot.width() <= 0.0 || plot.height() <= 0.0 {
+        return;
+    }
+
+    let plot_x = |value| Some(plot.left + frame.x.ratio(value)? as f32 * plot.width());
+    let plot_y = |value| Some(plot.bottom - frame.y.ratio(value)? as f32 * plot.height());
+
+    let mut paint = Paint::default();
+    paint
+        .set_anti_alias(true)
+        .set_style(PaintStyle::Stroke)
+        .set_color(look.grid);
+    for (value, label) in &y_ticks {
+        let Some(y) = plot_y(*value).filter(|y| within(*y, plot.top, plot.bottom))
+        else {
+            continue;
+        };
+
+        canvas.draw_line((plot.left, y), (plot.right, y), &paint);
+        let x = plot.left - gap - text_width(look, label);
+        draw_text(canvas, look, label, look.axis, x, y);
+    }
+
+    // labels overlapping the one before are left out
+    paint.set_color(look.axis);
+    let mut last_end = f32::NEG_INFINITY;
+    for value in frame.x.ticks() {
+        let Some(x) = plot_x(value).filter(|x| within(*x, plot.left, plot.right))
+        else {
+            continue;
+        };
+
+        canvas.draw_line((x, plot.bottom), (x, plot.bottom + gap / 2.0), &paint);
+        let label = frame.x.label(value, false);
+        let width = text_width(look, &label);
+        let start = (x - width / 2.0).min(area.right - width).max(area.left);
+        if start < last_end + gap {
+            continue;
+        }
+
+        let center = plot.bottom + gap + look.line_height / 2.0;
+        draw_text(canvas, look, &label, look.axis, start, center);
+        last_end = start + width;
+    }
+    canvas.draw_line((plot.left, plot.bottom), (plot.right, plot.bottom), &paint);
+    canvas.draw_line((plot.left, plot.top), (plot.left, plot.bottom), &paint);
+
+    let points: Vec<Vec<Option<SkiaPoint>>> = series
+        .iter()
+        .zip(&frame.values)
+        .map(|(series, values)| {
+            series
+                .points
+                .iter()
+                .zip(values)
+                .map(|((x, _), y)| Some(SkiaPoint::new(plot_x(*x)?, plot_y(*y)?)))
+                .collect()
+        })
+        .collect();
+    let base = plot_y(frame.y.baseline()).unwrap_or(plot.bottom);
+
+    canvas.save();
+    canvas.clip_rect(
+        plot.with_outset((look.line_width, look.line_width)),
+        None,
+        true,
+    );
+    match kind {
+        ChartKind::Bar => draw_bars(canvas, frame, plot, base, series, &points),
+        _ => draw_lines(canvas, look, kind == ChartKind::Area, base, series, &points),
+    }
+    canvas.restore();
+
+    if let Some(pointer) = pointer.filter(|pointer| contains(plot, *pointer)) {
+        let hover = Hover {
+            area,
+            plot,
+            pointer,
+        };
+        draw_crosshair(canvas, look, &hover, kind, series, &points, frame);
+    }
+}
+
+/// Draw the slices of the first series around the center of `area`.
+pub(super) fn pie(
+    canvas: &mut Canvas,
+    look: &Look,
+    area: Rect,
+    series: &[Series],
+    frame: &Frame,
+    pointer: Option<SkiaPoint>,
+) {
+    let (Some(series), Some(values)) = (series.first(), frame.values.first())
+    else {
+        return;
+    };
+
+    let total: f64 = values.iter().filter(|value| **value > 0.0).sum();
+    let lift = SLICE_LIFT.to_physical();
+    let radius = area.width().min(area.height()) / 2.0 - lift;
+    if total <= 0.0 || radius <= 0.0 {
+        return;
+    }
+
+    // angles in degrees clockwise from the top
+    let center = area.center();
+    let hovered = pointer.and_then(|pointer| {
+        let delta = pointer - center;
+        (delta.length() <= radius)
+            .then(|| (delta.y.atan2(delta.x).to_degrees() + 90.0).rem_euclid(360.0))
+    });
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    let mut separator = paint.clone();
+    separator
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(Pixel(1.5).to_physical())
+        .set_color(look.background);
+
+    let mut start = 0.0;
+    let mut tip = None;
+    for (index, value) in values.iter().enumerate() {
+        if *value <= 0.0 {
+            continue;
+        }
+
+        let sweep = (*value / total * 360.0) as f32;
+        let mut center = center;
+        if hovered.is_some_and(|angle| angle >= start && angle < start + sweep) {
+            let middle = (start + sweep / 2.0 - 90.0).to_radians();
+            center = SkiaPoint::new(
+                center.x + middle.cos() * lift,
+                center.y + middle.sin() * lift,
+            );
+            tip = Some(index);
+        }
+
+        let oval = Rect::new(
+            center.x - radius,
+            center.y - radius,
+            center.x + radius,
+            center.y + radius,
+        );
+        let mut path = Path::new();
+        path.move_to(center)
+            .arc_to(oval, start - 90.0, sweep, false)
+            .close();
+
+        paint.set_color(palette(index));
+        canvas.draw_path(&path, &paint);
+        canvas.draw_path(&path, &separator);
+        start += sweep;
+    }
+
+    let (Some(index), Some(pointer)) = (tip, pointer)
+    else {
+        return;
+    };
+
+    let (x, y) = series.points[index];
+    let total: f64 = series.points.iter().map(|(_, y)| y.max(0.0)).sum();
+    let share = if total > 0.0 { y / total * 100.0 } else { 0.0 };
+    let row = format!("{} ({share:.1}%)", frame.y.label(y, true));
+    let hover = Hover {
+        area,
+        plot: area,
+        pointer,
+    };
+    draw_tooltip(
+        canvas,
+        look,
+        &hover,
+        &frame.x.label(x, true),
+        &[(palette(index), row)],
+    );
+}
+
+/// Where the pointer hovers, and the areas a tooltip and a crosshair fit in
+struct Hover {
+    area: Rect,
+    plot: Rect,
+    pointer: SkiaPoint,
+}
+
+fn draw_lines(
+    canvas: &mut Canvas,
+    look: &Look,
+    filled: bool,
+    base: f32,
+    series: &[Series],
+    points: &[Vec<Option<SkiaPoint>>],
+) {
+    let mut stroke = Paint::default();
+    stroke
+        .set_anti_alias(true)
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(look.line_width)
+        .set_stroke_join(Join::Round)
+        .set_stroke_cap(Cap::Round);
+    let mut fill = Paint::default();
+    fill.set_anti_alias(true);
+
+    for (index, (series, points)) in series.iter().zip(points).enumerate() {
+        let mut line = Path::new();
+        let mut area = Path::new();
+
+        // lines break where points cannot be placed
+        for run in points.split(Option::is_none) {
+            let mut run = run.iter().flatten();
+            let Some(&first) = run.next()
+            else {
+                continue;
+            };
+
+            line.move_to(first);
+            area.move_to((first.x, base)).line_to(first);
+            let mut last = first;
+            for &point in run {
+                line.line_to(point);
+                area.line_to(point);
+                last = point;
+            }
+            area.line_to((last.x, base)).close();
+
+            // a single point shows as a dot with the round cap
+            if last == first {
+                line.line_to(first);
+            }
+        }
+
+        let color = series.color_at(index);
+        if filled {
+            fill.set_color(color.with_a(AREA_ALPHA));
+            canvas.draw_path(&area, &fill);
+        }
+        stroke.set_color(color);
+        canvas.draw_path(&line, &stroke);
+    }
+}
+
+fn draw_bars(
+    canvas: &mut Canvas,
+    frame: &Frame,
+    plot: Rect,
+    base: f32,
+    series: &[Series],
+    points: &[Vec<Option<SkiaPoint>>],
+) {
+    let band = bar_band(frame, plot, points);
+    let width = band * BAR_FILL / series.len().max(1) as f32;
+    // keep neighbouring bars apart unless too thin
+    let inset = if width > 4.0 { 0.5 } else { 0.0 };
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    for (index, (series, points)) in series.iter().zip(points).enumerate() {
+        paint.set_color(series.color_at(index));
+        let offset = index as f32 * width - band * BAR_FILL / 2.0;
+        for point in points.iter().flatten() {
+            let left = point.x + offset;
+            let bar = Rect::new(left, point.y.min(base), left + width, point.y.max(base));
+            canvas.draw_rect(bar.with_inset((inset, 0.0)), &paint);
+        }
+    }
+}
+
+/// Width of the space around each x which its bars share.
+fn bar_band(frame: &Frame, plot: Rect, points: &[Vec<Option<SkiaPoint>>]) -> f32 {
+    if let Some(band) = frame.x.band() {
+        return band as f32 * plot.width();
+    }
+
+    let mut xs: Vec<f32> = points
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|point| point.x)
+        .collect();
+    xs.sort_by(f32::total_cmp);
+    xs.windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .filter(|spacing| *spacing > 0.5)
+        .reduce(f32::min)
+        .unwrap_or(plot.width() / 2.0)
+}
+
+/// Mark the point nearest to the pointer, and show the values of all series
+/// at its x in a tooltip.
+fn draw_crosshair(
+    canvas: &mut Canvas,
+    look: &Look,
+    hover: &Hover,
+    kind: ChartKind,
+    series: &[Series],
+    points: &[Vec<Option<SkiaPoint>>],
+    frame: &Frame,
+) {
+    let Hover { plot, pointer, .. } = *hover;
+    let distance = |point: &SkiaPoint| ((point.x - pointer.x).abs(), (point.y - pointer.y).abs());
+
+    // nearest along x first, then along y
+    let Some(&nearest) = points.iter().flatten().flatten().min_by(|a, b| {
+        let (a, b) = (distance(a), distance(b));
+        a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+    }) else {
+        return;
+    };
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    if kind == ChartKind::Bar {
+        let half = bar_band(frame, plot, points) / 2.0;
+        paint.set_color(look.crosshair.with_a(BAND_ALPHA));
+        canvas.draw_rect(
+            Rect::new(nearest.x - half, plot.top, nearest.x + half, plot.bottom),
+            &paint,
+        );
+    } else {
+        paint
+            .set_style(PaintStyle::Stroke)
+            .set_color(look.crosshair);
+        canvas.draw_line((nearest.x, plot.top), (nearest.x, plot.bottom), &paint);
+        canvas.draw_line((plot.left, nearest.y), (plot.right, nearest.y), &paint);
+    }
+
+    let mut title = None;
+    let mut rows = Vec::new();
+    for (index, (series, points)) in series.iter().zip(points).enumerate() {
+        let Some((at, point)) = points
+            .iter()
+            .enumerate()
+            .filter_map(|(at, point)| Some((at, (*point)?)))
+            .filter(|(_, point)| (point.x - nearest.x).abs() < 0.5)
+            .min_by(|(_, a), (_, b)| distance(a).1.total_cmp(&distance(b).1))
+        else {
+            continue;
+        };
+
+        let color = series.color_at(index);
+        let (x, y) = series.points[at];
+        title.get_or_insert_with(|| frame.x.label(x, true));
+        let value = frame.y.label(y, true);
+        rows.push(match series.name.as_str() {
+            "" => (color, value),
+            name => (color, format!("{name}: {value}")),
+        });
+
+        if kind != ChartKind::Bar {
+            paint.set_style(PaintStyle::Fill).set_color(look.background);
+            canvas.draw_circle(point, DOT_RADIUS.to_physical() + 1.5, &paint);
+            paint.set_color(color);
+            canvas.draw_circle(point, DOT_RADIUS.to_physical(), &paint);
+        }
+    }
+
+    if let Some(title) = title {
+        draw_tooltip(canvas, look, hover, &title, &rows);
+    }
+}
+
+/// Draw a box of `title` and colored `rows` beside the pointer, flipped to
+/// the other side where it would leave the area.
+fn draw_tooltip(
+    canvas: &mut Canvas,
+    look: &Look,
+    hover: &Hover,
+    title: &str,
+    rows: &[(Color, String)],
+) {
+    let Hover { area, pointer, .. } = *hover;
+    let (swatch, gap) = (SWATCH.to_physical(), GAP.to_physical());
+    let padding = TOOLTIP_PADDING.to_physical();
+    let offset = TOOLTIP_OFFSET.to_physical();
+
+    let width = rows
+        .iter()
+        .map(|(_, row)| swatch + gap + text_width(look, row))
+        .fold(text_width(look, title), f32::max)
+        + padding * 2.0;
+    let height = look.line_height * (rows.len() + 1) as f32 + padding * 2.0;
+
+    let mut left = pointer.x + offset;
+    if left + width > area.right {
+        left = pointer.x - offset - width;
+    }
+    let mut top = pointer.y + offset;
+    if top + height > area.bottom {
+        top = pointer.y - offset - height;
+    }
+    let rect = Rect::from_xywh(left.max(area.left), top.max(area.top), width, height);
+    let rrect = RRect::new_rect_xy(rect, padding / 2.0, padding / 2.0);
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(look.background);
+    canvas.draw_rrect(rrect, &paint);
+    paint.set_style(PaintStyle::Stroke).set_color(look.grid);
+    canvas.draw_rrect(rrect, &paint);
+
+    let x = rect.left + padding;
+    let mut center = rect.top + padding + look.line_height / 2.0;
+    draw_text(canvas, look, title, look.axis, x, center);
+    for (color, row) in rows {
+        center += look.line_height;
+        draw_swatch(canvas, *color, x, center);
+        draw_text(canvas, look, row, look.text, x + swatch + gap, center);
+    }
+}
+
+fn draw_swatch(canvas: &mut Canvas, color: Color, x: f32, center: f32) {
+    let size = SWATCH.to_physical();
+    let rect = Rect::from_xywh(x, center - size / 2.0, size, size);
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(color);
+    canvas.draw_rrect(RRect::new_rect_xy(rect, size / 4.0, size / 4.0), &paint);
+}
+
+/// Draw `text` from `x`, vertically centered at `center`.
+fn draw_text(canvas: &mut Canvas, look: &Look, text: &str, color: Color, x: f32, center: f32) {
+    let center = Pixel::from_physical(center);
+    draw_label(
+        canvas,
+        &look.font,
+        text,
+        color,
+        Pixel::from_physical(x),
+        (center, center),
+    );
+}
+
+fn text_width(look: &Look, text: &str) -> f32 {
+    label_width(&look.font, text).to_physical()
+}
+
+fn within(value: f32, start: f32, end: f32) -> bool {
+    value >= start - 0.5 && value <= end + 0.5
+}
+
+fn contains(rect: Rect, point: SkiaPoint) -> bool {
+    point.x >= rect.left && point.x <= rect.right && point.y >= rect.top && point.y <= rect.bottom
+}
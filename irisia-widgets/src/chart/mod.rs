@@ -0,0 +1,369 @@
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{PointerMove, PointerOut},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Color, Point as SkiaPoint},
+    style::StyleColor,
+    ElModel, StyleReader,
+};
+
+use crate::{
+    label::{label_font, to_rect},
+    popup::styles::StylePopupBackground,
+    textbox::styles::StyleFontSize,
+};
+
+use self::{
+    axis::{lerp, Scale},
+    draw::Look,
+    series::palette,
+    styles::{StyleChartAxis, StyleChartCrosshair, StyleChartGrid, StyleChartLineWidth},
+};
+
+pub use self::{
+    axis::{Axis, AxisScale},
+    series::{ChartKind, Series},
+};
+
+mod axis;
+mod draw;
+mod series;
+pub mod styles;
+
+const PADDING: Pixel = Pixel(8.0);
+const TRANSITION: Duration = Duration::from_millis(300);
+
+/// A chart of series of points, drawn as lines, filled areas, bars or a pie
+/// as `kind` says. The axes fit their ranges to the data with round ends
+/// and label their ticks, and the legend above the plot names the series.
+///
+/// ```ignore
+/// Chart {
+///     kind: ChartKind::Area,
+///     series: vec![Series::new("cpu", samples.iter().copied())],
+///     x_axis: Axis::time(),
+///     y_axis: Axis::linear().range(0.0, 100.0).suffix("%"),
+/// }
+/// ```
+///
+/// Hovering the plot marks the nearest point, and a tooltip shows the
+/// values of all series at its x. When the data changes, points move to
+/// their new values in a short transition, matched by their index in the
+/// series. The chart is drawn on a layer of its own.
+pub struct Chart {
+    props: OwnedProps,
+    categories: Rc<[String]>,
+    /// What the chart shows once the transition ends
+    target: Frame,
+    /// What was drawn last, where a new transition starts from
+    drawn: Frame,
+    transition: Option<Transition>,
+    pointer: Option<Point>,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct ChartStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: StylePopupBackground,
+    grid: StyleChartGrid,
+    axis: StyleChartAxis,
+    crosshair: StyleChartCrosshair,
+    line_width: StyleChartLineWidth,
+}
+
+#[irisia::props(updater = "ChartProps", watch)]
+pub struct OwnedProps {
+    #[props(default)]
+    kind: ChartKind,
+
+    #[props(must_init, watch)]
+    series: Vec<Series>,
+
+    /// Names of the x values 0, 1, 2 and so on. If not empty, each name
+    /// takes a band of the x axis and `x_axis` is ignored.
+    #[props(default, watch)]
+    categories: Vec<String>,
+
+    #[props(default)]
+    x_axis: Axis,
+
+    #[props(default)]
+    y_axis: Axis,
+
+    #[props(default = "true")]
+    legend: bool,
+
+    #[props(read_style(stdin))]
+    style: ChartStyles,
+}
+
+/// Scales and values of the points at a moment.
+#[derive(Clone, PartialEq)]
+struct Frame {
+    x: Scale,
+    y: Scale,
+    /// y of each point of each series
+    values: Vec<Vec<f64>>,
+}
+
+struct Transition {
+    from: Frame,
+    start: Instant,
+}
+
+impl Frame {
+    fn fit(props: &OwnedProps, categories: &Rc<[String]>) -> Self {
+        let points = || {
+            props
+                .series
+                .iter()
+                .flat_map(|series| series.points.iter().copied())
+        };
+
+        let x = if categories.is_empty() {
+            let mut x = Scale::fit(&props.x_axis, points().map(|(x, _)| x), false);
+            if props.kind == ChartKind::Bar && props.x_axis.range.is_none() {
+                if let Some(spacing) = min_spacing(points().map(|(x, _)| x)) {
+                    x.pad(spacing / 2.0);
+                }
+            }
+            x
+        } else {
+            Scale::categories(categories.clone())
+        };
+
+        let zero = props.kind != ChartKind::Line;
+        Frame {
+            x,
+            y: Scale::fit(&props.y_axis, points().map(|(_, y)| y), zero),
+            values: props
+                .series
+                .iter()
+                .map(|series| series.points.iter().map(|(_, y)| *y).collect())
+                .collect(),
+        }
+    }
+
+    /// The frame `t` of the way from `self` to `to`. Points are matched by
+    /// their indices, and new points rise from the baseline.
+    fn lerp(&self, to: &Frame, t: f64) -> Frame {
+        let baseline = to.y.baseline();
+        let values = to
+            .values
+            .iter()
+            .enumerate()
+            .map(|(index, values)| {
+                let from = self.values.get(index).map_or(&[][..], |from| &from[..]);
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(at, value)| lerp(from.get(at).copied().unwrap_or(baseline), *value, t))
+                    .collect()
+            })
+            .collect();
+
+        Frame {
+            x: self.x.lerp(&to.x, t),
+            y: self.y.lerp(&to.y, t),
+            values,
+        }
+    }
+}
+
+/// Shortest distance between two different values.
+fn min_spacing(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let mut values: Vec<f64> = values.filter(|value| value.is_finite()).collect();
+    values.sort_by(f64::total_cmp);
+    values
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .filter(|spacing| *spacing > 0.0)
+        .reduce(f64::min)
+}
+
+impl Element for Chart {
+    type BlankProps = ChartProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let progress = self
+            .transition
+            .as_ref()
+            .map(|transition| transition.start.elapsed().as_secs_f64() / TRANSITION.as_secs_f64());
+        let frame = match (&self.transition, progress) {
+            (Some(transition), Some(progress)) if progress < 1.0 => {
+                // ease out
+                let t = 1.0 - (1.0 - progress).powi(3);
+                transition.from.lerp(&self.target, t)
+            }
+            _ => {
+                if self.transition.take().is_some() {
+                    this.set_animating(false);
+                }
+                self.target.clone()
+            }
+        };
+
+        let region = to_rect(this.draw_region());
+        let mut area = region.with_inset((PADDING.to_physical(), PADDING.to_physical()));
+        if area.width() > 0.0 && area.height() > 0.0 {
+            let look = self.look();
+            let canvas = content.canvas();
+            canvas.save();
+            canvas.clip_rect(region, None, true);
+
+            if self.props.legend {
+                area.top += draw::legend(canvas, &look, area, &self.legend_entries());
+            }
+            let pointer = self.pointer.map(SkiaPoint::from);
+            match self.props.kind {
+                ChartKind::Pie => {
+                    draw::pie(canvas, &look, area, &self.props.series, &frame, pointer)
+                }
+                kind => draw::cartesian(
+                    canvas,
+                    &look,
+                    area,
+                    kind,
+                    &self.props.series,
+                    &frame,
+                    pointer,
+                ),
+            }
+            canvas.restore();
+        }
+
+        self.drawn = frame;
+        Ok(())
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        self.sync_animating(this);
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Chart
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.acquire_indep_layer(true);
+        this.daemon(pointer_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        let categories: Rc<[String]> = props.categories.iter().cloned().collect();
+        let target = Frame::fit(&props, &categories);
+        Chart {
+            drawn: target.clone(),
+            target,
+            categories,
+            props,
+            transition: None,
+            pointer: None,
+        }
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.categories_unchanged {
+            self.categories = self.props.categories.iter().cloned().collect();
+        }
+
+        let data_unchanged = update_result.series_unchanged && update_result.categories_unchanged;
+        if update_result.unchanged && data_unchanged {
+            return true;
+        }
+
+        let target = Frame::fit(&self.props, &self.categories);
+        if target != self.target {
+            self.target = target;
+            self.transition = Some(Transition {
+                from: self.drawn.clone(),
+                start: Instant::now(),
+            });
+            self.sync_animating(this);
+        }
+        false
+    }
+}
+
+impl Chart {
+    fn look(&self) -> Look {
+        let style = &self.props.style;
+        let font_size = style.font_size.map_or(Pixel(12.0), |size| size.0);
+        Look {
+            font: label_font(font_size),
+            line_height: (font_size * 1.5).to_physical(),
+            text: style.color.map_or(Color::BLACK, |color| color.0),
+            axis: style.axis.0,
+            grid: style.grid.0,
+            crosshair: style.crosshair.0,
+            background: style.background.0,
+            line_width: style.line_width.0.to_physical(),
+        }
+    }
+
+    /// Names of the series, or of the slices of a pie.
+    fn legend_entries(&self) -> Vec<(Color, String)> {
+        let series = &self.props.series;
+        if self.props.kind != ChartKind::Pie {
+            return series
+                .iter()
+                .enumerate()
+                .filter(|(_, series)| !series.name.is_empty())
+                .map(|(index, series)| (series.color_at(index), series.name.clone()))
+                .collect();
+        }
+
+        let Some(series) = series.first()
+        else {
+            return Vec::new();
+        };
+        series
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, y))| *y > 0.0)
+            .map(|(index, (x, _))| (palette(index), self.target.x.label(*x, true)))
+            .collect()
+    }
+
+    /// Animate only while shown and in a transition.
+    fn sync_animating(&self, this: &ElModel!()) {
+        this.set_animating(self.transition.is_some() && !this.is_hidden());
+    }
+}
+
+async fn update(this: &ElModel!(Chart), f: impl FnOnce(&mut Chart)) -> bool {
+    let Some(mut chart) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *chart);
+    true
+}
+
+async fn pointer_runtime(this: ElModel!(Chart)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let alive = tokio::select! {
+            pm = ed.recv_trusted::<PointerMove>() => {
+                update(&this, |chart| chart.pointer = Some(pm.position)).await
+            }
+            _ = ed.recv_trusted::<PointerOut>() => {
+                update(&this, |chart| chart.pointer = None).await
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
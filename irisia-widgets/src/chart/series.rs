@@ -0,0 +1,70 @@
+use irisia::skia_safe::Color;
+
+/// Colors of series without a color of their own, and of pie slices
+const PALETTE: [Color; 8] = [
+    Color::from_rgb(0x2f, 0x6f, 0xed),
+    Color::from_rgb(0xf2, 0x8b, 0x1e),
+    Color::from_rgb(0x2e, 0xa8, 0x5a),
+    Color::from_rgb(0xd9, 0x3b, 0x3b),
+    Color::from_rgb(0x8e, 0x5c, 0xd9),
+    Color::from_rgb(0x1f, 0xa9, 0xb8),
+    Color::from_rgb(0xc2, 0x4f, 0x9b),
+    Color::from_rgb(0x8a, 0x8a, 0x8a),
+];
+
+/// How a [`Chart`] shows its series.
+///
+/// [`Chart`]: super::Chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChartKind {
+    /// Lines joining the points of each series
+    #[default]
+    Line,
+    /// Lines with the area down to zero filled
+    Area,
+    /// Bars of all series side by side at each x
+    Bar,
+    /// Slices of the first series, one for each point sized by its y
+    Pie,
+}
+
+/// Points of a [`Chart`] under a name, which the legend and the tooltip
+/// show.
+///
+/// [`Chart`]: super::Chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub name: String,
+    /// Color of the series, or one of a palette if `None`.
+    pub color: Option<Color>,
+    /// Points as `(x, y)`, joined by lines in this order.
+    pub points: Vec<(f64, f64)>,
+}
+
+impl Series {
+    pub fn new<I>(name: impl Into<String>, points: I) -> Self
+    where
+        I: IntoIterator<Item = (f64, f64)>,
+    {
+        Series {
+            name: name.into(),
+            color: None,
+            points: points.into_iter().collect(),
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Color of the series, being the `index`th of the chart.
+    pub(super) fn color_at(&self, index: usize) -> Color {
+        self.color.unwrap_or_else(|| palette(index))
+    }
+}
+
+/// Color of the `index`th series or slice without a color of its own.
+pub(super) fn palette(index: usize) -> Color {
+    PALETTE[index % PALETTE.len()]
+}
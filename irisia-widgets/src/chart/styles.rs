@@ -0,0 +1,21 @@
+use irisia::{primitive::Pixel, skia_safe::Color, Style};
+
+/// Color of the grid lines across the plot.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleChartGrid(#[style(default = "Color::from_rgb(0xeb, 0xeb, 0xeb)")] pub Color);
+
+/// Color of the axes and their labels.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleChartAxis(#[style(default = "Color::from_rgb(0x75, 0x75, 0x75)")] pub Color);
+
+/// Color of the crosshair following the pointer.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleChartCrosshair(#[style(default = "Color::from_rgb(0xa0, 0xa0, 0xa0)")] pub Color);
+
+/// Width of the lines of line and area charts.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleChartLineWidth(#[style(default = "Pixel(2.0)")] pub Pixel);
@@ -0,0 +1,582 @@
+use std::ops::Range;
+
+use irisia::skia_safe::{
+    textlayout::{
+        FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, RectHeightStyle,
+        RectWidthStyle, TextDecoration, TextStyle,
+    },
+    Color,
+};
+
+use crate::label::MONOSPACE;
+
+use super::syntax::{Syntax, Token, TokenKind};
+
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+/// Most lines searched for a matching bracket
+const MATCH_LINES: usize = 5000;
+
+/// A position in the text, as a line and a byte offset in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub(super) struct Pos {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Font and colors of the text, in physical units.
+#[derive(PartialEq)]
+pub(super) struct Look {
+    pub font_size: f32,
+    pub color: Color,
+    pub keyword: Color,
+    pub types: Color,
+    pub function: Color,
+    pub string: Color,
+    pub number: Color,
+    pub comment: Color,
+}
+
+impl Look {
+    fn token_color(&self, kind: TokenKind) -> Color {
+        match kind {
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Type => self.types,
+            TokenKind::Function => self.function,
+            TokenKind::String => self.string,
+            TokenKind::Number => self.number,
+            TokenKind::Comment => self.comment,
+        }
+    }
+}
+
+struct Line {
+    text: String,
+    tokens: Vec<Token>,
+    /// State of the tokenizer at the end of the line
+    state: u32,
+    /// Laid out when shown, and dropped when the text or the tokens change
+    paragraph: Option<Paragraph>,
+}
+
+impl Line {
+    fn new(text: String) -> Self {
+        Line {
+            text,
+            tokens: Vec::new(),
+            state: 0,
+            paragraph: None,
+        }
+    }
+
+    /// Whether the byte at `index` is in a string or a comment.
+    fn in_literal(&self, index: usize) -> bool {
+        self.tokens.iter().any(|token| {
+            token.range.contains(&index)
+                && matches!(token.kind, TokenKind::String | TokenKind::Comment)
+        })
+    }
+}
+
+/// Lines of the text being edited, never empty.
+pub(super) struct Document {
+    lines: Vec<Line>,
+}
+
+impl Document {
+    pub(super) fn new(text: &str, syntax: Option<&Syntax>) -> Self {
+        let lines = text
+            .split('\n')
+            .map(|line| Line::new(line.strip_suffix('\r').unwrap_or(line).to_string()))
+            .collect();
+
+        let mut document = Document { lines };
+        document.retokenize(syntax);
+        document
+    }
+
+    pub(super) fn text(&self) -> String {
+        let lines: Vec<&str> = self.lines.iter().map(|line| line.text.as_str()).collect();
+        lines.join("\n")
+    }
+
+    pub(super) fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub(super) fn line(&self, index: usize) -> &str {
+        &self.lines[index].text
+    }
+
+    pub(super) fn end(&self) -> Pos {
+        let line = self.lines.len() - 1;
+        Pos {
+            line,
+            column: self.lines[line].text.len(),
+        }
+    }
+
+    /// Byte offset of `pos` in the text.
+    pub(super) fn offset(&self, pos: Pos) -> usize {
+        let before: usize = self.lines[..pos.line]
+            .iter()
+            .map(|line| line.text.len() + 1)
+            .sum();
+        before + pos.column
+    }
+
+    /// Text of `range`, with lines ending with `\n`.
+    pub(super) fn slice(&self, range: Range<Pos>) -> String {
+        let Range { start, end } = range;
+        if start.line == end.line {
+            return self.lines[start.line].text[start.column..end.column].to_string();
+        }
+
+        let mut text = self.lines[start.line].text[start.column..].to_string();
+        for line in &self.lines[start.line + 1..end.line] {
+            text.push('\n');
+            text.push_str(&line.text);
+        }
+        text.push('\n');
+        text.push_str(&self.lines[end.line].text[..end.column]);
+        text
+    }
+
+    /// The nearest position in the text to `pos`.
+    pub(super) fn clamp(&self, pos: Pos) -> Pos {
+        let line = pos.line.min(self.lines.len() - 1);
+        let text = &self.lines[line].text;
+        let mut column = pos.column.min(text.len());
+        while !text.is_char_boundary(column) {
+            column -= 1;
+        }
+        Pos { line, column }
+    }
+
+    /// Replace `range` with `text`, and give the end of the inserted text.
+    /// Only the lines replaced are tokenized again, and the lines below as
+    /// far as their tokens change.
+    pub(super) fn replace(
+        &mut self,
+        range: Range<Pos>,
+        text: &str,
+        syntax: Option<&Syntax>,
+    ) -> Pos {
+        let Range { start, end } = range;
+        let suffix = &self.lines[end.line].text[end.column..];
+
+        let mut pieces = text
+            .split('\n')
+            .map(|piece| piece.strip_suffix('\r').unwrap_or(piece));
+        let mut first = self.lines[start.line].text[..start.column].to_string();
+        first.push_str(pieces.next().unwrap_or_default());
+        let mut new_lines: Vec<String> = std::iter::once(first)
+            .chain(pieces.map(String::from))
+            .collect();
+
+        let count = new_lines.len();
+        let last = &mut new_lines[count - 1];
+        let inserted_end = Pos {
+            line: start.line + count - 1,
+            column: last.len(),
+        };
+        last.push_str(suffix);
+
+        // the tokens below were made from where the replaced lines ended
+        let end_state = self.lines[end.line].state;
+        self.lines
+            .splice(start.line..=end.line, new_lines.into_iter().map(Line::new));
+        self.lines[start.line + count - 1].state = end_state;
+
+        self.tokenize(start.line, count, syntax);
+        inserted_end
+    }
+
+    /// Tokenize all lines again, as the syntax changed.
+    pub(super) fn retokenize(&mut self, syntax: Option<&Syntax>) {
+        self.tokenize(0, self.lines.len(), syntax);
+    }
+
+    /// Tokenize `count` lines from `first`, and then the lines below until
+    /// one ends in the same state as before.
+    fn tokenize(&mut self, first: usize, count: usize, syntax: Option<&Syntax>) {
+        let mut state = match first {
+            0 => 0,
+            _ => self.lines[first - 1].state,
+        };
+
+        for index in first..self.lines.len() {
+            let line = &mut self.lines[index];
+            let tokens = match syntax {
+                Some(syntax) => syntax.tokenize(&line.text, &mut state),
+                None => {
+                    state = 0;
+                    Vec::new()
+                }
+            };
+
+            if tokens != line.tokens {
+                line.tokens = tokens;
+                line.paragraph = None;
+            }
+            let old_state = std::mem::replace(&mut line.state, state);
+            if index + 1 >= first + count && state == old_state {
+                break;
+            }
+        }
+    }
+
+    /// Drop the laid out lines, as the look changed.
+    pub(super) fn clear_layout(&mut self) {
+        for line in &mut self.lines {
+            line.paragraph = None;
+        }
+    }
+
+    /// Lay out the line at `index` if not yet.
+    pub(super) fn layout(&mut self, index: usize, look: &Look, fonts: &FontCollection) {
+        let line = &mut self.lines[index];
+        if line.paragraph.is_none() {
+            line.paragraph = Some(layout_line(line, look, fonts));
+        }
+    }
+
+    pub(super) fn paragraph(&self, index: usize) -> Option<&Paragraph> {
+        self.lines[index].paragraph.as_ref()
+    }
+
+    /// Distance from the start of the line to `pos`, `0` if the line is not
+    /// laid out.
+    pub(super) fn x_of(&self, pos: Pos) -> f32 {
+        let line = &self.lines[pos.line];
+        let Some(paragraph) = &line.paragraph
+        else {
+            return 0.0;
+        };
+
+        let end = line.text[..pos.column].encode_utf16().count();
+        paragraph
+            .get_rects_for_range(0..end, RectHeightStyle::Tight, RectWidthStyle::Tight)
+            .iter()
+            .map(|text_box| text_box.rect.right)
+            .fold(0.0, f32::max)
+    }
+
+    /// Column of the line at `index` nearest to `x` from its start.
+    pub(super) fn column_at(&self, index: usize, x: f32) -> usize {
+        let line = &self.lines[index];
+        let Some(paragraph) = &line.paragraph
+        else {
+            return 0;
+        };
+
+        // the paragraph counts in UTF-16 code units
+        let target = paragraph
+            .get_glyph_position_at_coordinate((x, 0.0))
+            .position
+            .max(0) as usize;
+        let mut units = 0;
+        for (column, c) in line.text.char_indices() {
+            if units >= target {
+                return column;
+            }
+            units += c.len_utf16();
+        }
+        line.text.len()
+    }
+
+    /// The position before `pos`, on the line above if at the start.
+    pub(super) fn prev(&self, pos: Pos) -> Pos {
+        match self.line(pos.line)[..pos.column].chars().next_back() {
+            Some(c) => Pos {
+                column: pos.column - c.len_utf8(),
+                ..pos
+            },
+            None if pos.line > 0 => Pos {
+                line: pos.line - 1,
+                column: self.lines[pos.line - 1].text.len(),
+            },
+            None => pos,
+        }
+    }
+
+    /// The position after `pos`, on the line below if at the end.
+    pub(super) fn next(&self, pos: Pos) -> Pos {
+        match self.line(pos.line)[pos.column..].chars().next() {
+            Some(c) => Pos {
+                column: pos.column + c.len_utf8(),
+                ..pos
+            },
+            None if pos.line + 1 < self.lines.len() => Pos {
+                line: pos.line + 1,
+                column: 0,
+            },
+            None => pos,
+        }
+    }
+
+    /// The start of the word before `pos`, skipping spaces.
+    pub(super) fn prev_word(&self, pos: Pos) -> Pos {
+        if pos.column == 0 {
+            return self.prev(pos);
+        }
+
+        let before = self.line(pos.line)[..pos.column].trim_end_matches(char::is_whitespace);
+        let Some(last) = before.chars().next_back()
+        else {
+            return Pos { column: 0, ..pos };
+        };
+
+        let same = |c: char| is_word(c) == is_word(last) && !c.is_whitespace();
+        let column = before.trim_end_matches(same).len();
+        Pos { column, ..pos }
+    }
+
+    /// The end of the word after `pos`, skipping spaces.
+    pub(super) fn next_word(&self, pos: Pos) -> Pos {
+        let text = self.line(pos.line);
+        if pos.column == text.len() {
+            return self.next(pos);
+        }
+
+        let after = text[pos.column..].trim_start_matches(char::is_whitespace);
+        let Some(first) = after.chars().next()
+        else {
+            return Pos {
+                column: text.len(),
+                ..pos
+            };
+        };
+
+        let same = |c: char| is_word(c) == is_word(first) && !c.is_whitespace();
+        let column = text.len() - after.trim_start_matches(same).len();
+        Pos { column, ..pos }
+    }
+
+    /// Position of the bracket matching the one at `pos`. Brackets in
+    /// strings and comments are not matched.
+    pub(super) fn matching_bracket(&self, pos: Pos) -> Option<Pos> {
+        let line = &self.lines[pos.line];
+        let c = line.text[pos.column..].chars().next()?;
+        if line.in_literal(pos.column) {
+            return None;
+        }
+
+        let (open, close, forward) = BRACKETS.iter().find_map(|&(open, close)| {
+            (c == open || c == close).then_some((open, close, c == open))
+        })?;
+
+        let mut depth = 0usize;
+        let mut visit = |index: usize, column: usize, c: char| {
+            if self.lines[index].in_literal(column) {
+                return None;
+            }
+            if c == open {
+                depth = if forward {
+                    depth + 1
+                } else {
+                    depth.checked_sub(1)?
+                };
+            } else if c == close {
+                depth = if forward {
+                    depth.checked_sub(1)?
+                } else {
+                    depth + 1
+                };
+            }
+            (depth == 0).then_some(Pos {
+                line: index,
+                column,
+            })
+        };
+
+        if forward {
+            let last = (pos.line + MATCH_LINES).min(self.lines.len());
+            for index in pos.line..last {
+                let from = if index == pos.line { pos.column } else { 0 };
+                let text = &self.lines[index].text;
+                for (column, next) in text[from..].char_indices() {
+                    if let Some(found) = visit(index, from + column, next) {
+                        return Some(found);
+                    }
+                }
+            }
+        } else {
+            let first = pos.line.saturating_sub(MATCH_LINES);
+            for index in (first..=pos.line).rev() {
+                let text = &self.lines[index].text;
+                let to = if index == pos.line {
+                    pos.column + c.len_utf8()
+                } else {
+                    text.len()
+                };
+                for (column, next) in text[..to].char_indices().rev() {
+                    if let Some(found) = visit(index, column, next) {
+                        return Some(found);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The bracket closing `open`, if it opens one.
+pub(super) fn closing(open: char) -> Option<char> {
+    BRACKETS
+        .iter()
+        .find_map(|&(bracket, close)| (bracket == open).then_some(close))
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn text_style(look: &Look) -> TextStyle {
+    let mut style = TextStyle::new();
+    style
+        .set_font_families(&MONOSPACE)
+        .set_font_size(look.font_size)
+        .set_color(look.color);
+    style
+}
+
+/// Text being composed with an input method, underlined.
+pub(super) fn layout_preedit(text: &str, look: &Look, fonts: &FontCollection) -> Paragraph {
+    let mut style = text_style(look);
+    style.set_decoration_type(TextDecoration::UNDERLINE);
+
+    let mut builder = ParagraphBuilder::new(&ParagraphStyle::new(), fonts);
+    builder.push_style(&style).add_text(text);
+    let mut paragraph = builder.build();
+    paragraph.layout(f32::INFINITY);
+    paragraph
+}
+
+fn layout_line(line: &Line, look: &Look, fonts: &FontCollection) -> Paragraph {
+    let style = text_style(look);
+
+    // a tab takes a single column, as a space of the same length in bytes
+    let text = line.text.replace('\t', " ");
+    let mut builder = ParagraphBuilder::new(&ParagraphStyle::new(), fonts);
+    builder.push_style(&style);
+
+    let mut at = 0;
+    for token in &line.tokens {
+        let range = token.range.start.max(at)..token.range.end.min(text.len());
+        let (Some(before), Some(content)) = (text.get(at..range.start), text.get(range.clone()))
+        else {
+            continue;
+        };
+
+        let mut token_style = style.clone();
+        token_style.set_color(look.token_color(token.kind));
+        builder.add_text(before);
+        builder.push_style(&token_style);
+        builder.add_text(content);
+        builder.pop();
+        at = range.end;
+    }
+    builder.add_text(&text[at..]);
+
+    let mut paragraph = builder.build();
+    paragraph.layout(f32::INFINITY);
+    paragraph
+}
+
+#[cfg(test)]
+fn pos(line: usize, column: usize) -> Pos {
+    Pos { line, column }
+}
+
+#[test]
+fn test_insert_and_delete() {
+    let mut document = Document::new("ab\ncd\nef", None);
+
+    // a line break splits the line
+    assert_eq!(
+        document.replace(pos(0, 1)..pos(0, 1), "\r\n", None),
+        pos(1, 0)
+    );
+    assert_eq!(document.text(), "a\nb\ncd\nef");
+
+    // replacing across lines joins what is left of the first and last
+    assert_eq!(
+        document.replace(pos(1, 1)..pos(3, 1), "X\nY", None),
+        pos(2, 1)
+    );
+    assert_eq!(document.text(), "a\nbX\nYf");
+
+    // deleting a line break joins the lines
+    assert_eq!(document.replace(pos(0, 1)..pos(1, 0), "", None), pos(0, 1));
+    assert_eq!(document.text(), "abX\nYf");
+    assert_eq!(document.line_count(), 2);
+
+    assert_eq!(
+        document.replace(pos(0, 0)..document.end(), "", None),
+        pos(0, 0)
+    );
+    assert_eq!(document.text(), "");
+    assert_eq!(document.line_count(), 1);
+}
+
+#[test]
+fn test_move_across_lines() {
+    let document = Document::new("ab\né\n", None);
+    assert_eq!(document.next(pos(0, 2)), pos(1, 0));
+    assert_eq!(document.prev(pos(1, 0)), pos(0, 2));
+    assert_eq!(document.next(pos(1, 0)), pos(1, 2));
+    assert_eq!(document.prev(pos(0, 0)), pos(0, 0));
+    assert_eq!(document.next(document.end()), pos(2, 0));
+    assert_eq!(document.clamp(pos(1, 1)), pos(1, 0));
+    assert_eq!(document.clamp(pos(5, 5)), pos(2, 0));
+}
+
+#[test]
+fn test_matching_bracket() {
+    let document = Document::new("fn f(a) {\n    [b, (c)]\n}", None);
+    assert_eq!(document.matching_bracket(pos(0, 8)), Some(pos(2, 0)));
+    assert_eq!(document.matching_bracket(pos(2, 0)), Some(pos(0, 8)));
+    assert_eq!(document.matching_bracket(pos(1, 4)), Some(pos(1, 11)));
+    assert_eq!(document.matching_bracket(pos(1, 10)), Some(pos(1, 8)));
+    assert_eq!(document.matching_bracket(pos(0, 0)), None);
+
+    let unmatched = Document::new("(a\n(b)", None);
+    assert_eq!(unmatched.matching_bracket(pos(0, 0)), None);
+    assert_eq!(unmatched.matching_bracket(pos(1, 2)), Some(pos(1, 0)));
+}
+
+#[test]
+fn test_matching_bracket_skips_literals() {
+    use super::syntax::SimpleTokenizer;
+
+    let syntax = Syntax::new(SimpleTokenizer::new().block_comment("/*", "*/"));
+    let document = Document::new("{ \")\" /* }\n { */\n(}", Some(&syntax));
+    assert_eq!(document.matching_bracket(pos(0, 0)), Some(pos(2, 1)));
+    assert_eq!(document.matching_bracket(pos(2, 1)), Some(pos(0, 0)));
+    assert_eq!(document.matching_bracket(pos(0, 3)), None);
+    assert_eq!(document.matching_bracket(pos(2, 0)), None);
+}
+
+#[test]
+fn test_edits_by_offset() {
+    let mut document = Document::new("fn f() {\n    a\n}", None);
+    let mut text = document.text();
+    assert_eq!(document.offset(pos(0, 0)), 0);
+    assert_eq!(document.offset(pos(1, 4)), 13);
+    assert_eq!(document.offset(document.end()), text.len());
+    assert_eq!(document.slice(pos(0, 7)..pos(2, 1)), "{\n    a\n}");
+    assert_eq!(document.slice(pos(1, 4)..pos(1, 5)), "a");
+
+    // replacing the same bytes of a copy of the text keeps it the same
+    for (range, inserted) in [
+        (pos(1, 5)..pos(1, 5), "\r\n    b"),
+        (pos(0, 3)..pos(2, 5), "g"),
+        (pos(0, 0)..pos(0, 0), "é\n"),
+    ] {
+        let bytes = document.offset(range.start)..document.offset(range.end);
+        let end = document.replace(range.clone(), inserted, None);
+        text.replace_range(bytes, &document.slice(range.start..end));
+        assert_eq!(text, document.text());
+    }
+    assert_eq!(text, "é\nfn g\n}");
+}
@@ -0,0 +1,966 @@
+use std::ops::Range;
+
+use arboard::Clipboard;
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{
+        Blured, Focused, PointerCaptureLost, PointerDown, PointerId, PointerMove, PointerUp, Wheel,
+    },
+    primitive::{Pixel, Point, Region},
+    skia_safe::{textlayout::FontCollection, Canvas, Color, Paint, Rect},
+    style::StyleColor,
+    winit::{
+        dpi::PhysicalPosition,
+        event::{ElementState, Ime, KeyboardInput, ModifiersState, MouseButton, VirtualKeyCode},
+    },
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{
+    label::{draw_label, font_collection, label_font, label_width, to_rect},
+    scroll::{clamp_offset, draw_vertical_scrollbar, max_offset, reveal_offset, wheel_delta},
+    textbox::styles::StyleFontSize,
+};
+
+use self::{
+    document::{closing, layout_preedit, Document, Look, Pos},
+    styles::*,
+};
+
+pub use self::syntax::{SimpleTokenizer, Syntax, Token, TokenKind, Tokenizer};
+
+mod document;
+pub mod styles;
+mod syntax;
+
+/// Height of a line relative to the font size
+const LINE_HEIGHT: f32 = 1.4;
+const GUTTER_PADDING: Pixel = Pixel(8.0);
+/// Space between the gutter and the text
+const TEXT_PADDING: Pixel = Pixel(6.0);
+const CARET_WIDTH: Pixel = Pixel(1.5);
+
+/// An editor of code in a monospaced font, with line numbers in a gutter
+/// on the left and tokens highlighted by `syntax`.
+///
+/// ```ignore
+/// CodeEditor {
+///     text: self.query.clone(),
+///     revision: Some(self.revision),
+///     syntax: Syntax::new(SimpleTokenizer::new().keywords(SQL).line_comment("--")),
+/// }
+///
+/// // on `CodeChanged`
+/// self.query.replace_range(changed.range, &changed.inserted);
+/// self.revision = changed.revision;
+/// ```
+///
+/// [`CodeChanged`] is emitted with every edit, which may be applied to a copy
+/// of the text. Passing that text back through `text`, along with the
+/// `revision` of the edit, keeps the caret, while any other text replaces
+/// what is being edited. Lines are laid out only once shown and again only
+/// once edited, so long texts stay fast to edit.
+///
+/// Text is selected with the pointer or with Shift and the arrow keys, and
+/// copied, cut and pasted with the clipboard of the system. Text composed
+/// with an input method is shown underlined at the caret until committed.
+/// [`TextBox`] only shows text, so the editor handles its input itself.
+///
+/// Tab indents by `tab_size` spaces, or indents the selected lines, and
+/// Shift+Tab outdents them. A new line keeps the indentation of the line
+/// before, one level deeper after an opening bracket. The bracket beside
+/// the caret and the one matching it are highlighted. Tabs in the text
+/// are shown as single spaces.
+///
+/// [`TextBox`]: crate::textbox::TextBox
+pub struct CodeEditor {
+    props: OwnedProps,
+    document: Document,
+    font_collection: FontCollection,
+    /// The look lines were laid out with
+    look: Look,
+    /// Where the selection ends and the caret is
+    caret: Pos,
+    /// Where the selection starts, the same as `caret` if nothing is selected
+    anchor: Pos,
+    /// Column in characters kept while moving up and down across shorter lines
+    goal: Option<usize>,
+    scroll_x: Pixel,
+    scroll_y: Pixel,
+    focused: bool,
+    /// Number of edits made
+    revision: u64,
+    /// Text being composed with an input method
+    preedit: String,
+    /// Opened once needed, as some systems serve copied text only while open
+    clipboard: Option<Clipboard>,
+}
+
+/// Emitted whenever the user edits the text, as the bytes `range` of the
+/// text replaced with `inserted`. Lines of the text end with `\n`.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct CodeChanged {
+    /// Number of edits made so far, this one included
+    pub revision: u64,
+    pub range: Range<usize>,
+    pub inserted: String,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct CodeEditorStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    gutter: StyleCodeGutter,
+    line_number: StyleCodeLineNumber,
+    current_line: StyleCodeCurrentLine,
+    selection: StyleCodeSelection,
+    bracket: StyleCodeBracket,
+    keyword: StyleCodeKeyword,
+    types: StyleCodeType,
+    function: StyleCodeFunction,
+    string: StyleCodeString,
+    number: StyleCodeNumber,
+    comment: StyleCodeComment,
+}
+
+#[irisia::props(updater = "CodeEditorProps", watch)]
+pub struct OwnedProps {
+    #[props(must_init, watch)]
+    text: String,
+
+    /// The `revision` of the last [`CodeChanged`] applied to `text`. Text of
+    /// a revision the editor reached is known to be its own, and is kept
+    /// without being compared. Give a newer revision, or none, to replace it.
+    #[props(default)]
+    revision: Option<u64>,
+
+    /// Tokenizes the lines to highlight. Nothing is highlighted without it.
+    #[props(default, watch)]
+    syntax: Option<Syntax>,
+
+    /// Columns of a level of indentation.
+    #[props(default = "4")]
+    tab_size: usize,
+
+    /// Allow moving the caret and selecting, but not editing.
+    #[props(default)]
+    read_only: bool,
+
+    #[props(read_style(stdin))]
+    style: CodeEditorStyles,
+}
+
+enum Input {
+    Key(VirtualKeyCode),
+    Char(char),
+    /// Text committed by an input method
+    Commit(String),
+}
+
+fn fill(canvas: &mut Canvas, rect: Rect, color: Color) {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(color);
+    canvas.draw_rect(rect, &paint);
+}
+
+impl Element for CodeEditor {
+    type BlankProps = CodeEditorProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let rect = to_rect(region);
+        let style = &self.props.style;
+        let line_height = self.line_height().to_physical();
+        let gutter = rect.left + self.gutter_width().to_physical();
+        let text_left = self.text_left(region).to_physical();
+        let scroll_y = self.scroll_y.to_physical();
+        let lines = self.visible_lines(region);
+        let top_of = |index: usize| rect.top + index as f32 * line_height - scroll_y;
+
+        for index in lines.clone() {
+            self.document
+                .layout(index, &self.look, &self.font_collection);
+        }
+
+        let canvas = content.canvas();
+        canvas.save();
+        canvas.clip_rect(rect, None, true);
+
+        let caret_top = top_of(self.caret.line);
+        let current_line = Rect::new(gutter, caret_top, rect.right, caret_top + line_height);
+        fill(canvas, current_line, style.current_line.0);
+
+        canvas.save();
+        canvas.clip_rect(
+            Rect::new(gutter, rect.top, rect.right, rect.bottom),
+            None,
+            true,
+        );
+
+        let Range { start, end } = self.selection();
+        for index in lines.start.max(start.line)..lines.end.min(end.line + 1) {
+            let from = if index == start.line {
+                self.document.x_of(start)
+            } else {
+                0.0
+            };
+            let to = if index == end.line {
+                self.document.x_of(end)
+            } else {
+                // the line break is selected too
+                let line_end = Pos {
+                    line: index,
+                    column: self.document.line(index).len(),
+                };
+                self.document.x_of(line_end) + self.look.font_size / 2.0
+            };
+            let top = top_of(index);
+            let selection = Rect::new(text_left + from, top, text_left + to, top + line_height);
+            fill(canvas, selection, style.selection.0);
+        }
+
+        if let Some(brackets) = self.brackets() {
+            for pos in brackets.into_iter().filter(|pos| lines.contains(&pos.line)) {
+                let next = self.document.next(pos);
+                let top = top_of(pos.line);
+                let bracket = Rect::new(
+                    text_left + self.document.x_of(pos),
+                    top,
+                    text_left + self.document.x_of(next),
+                    top + line_height,
+                );
+                fill(canvas, bracket, style.bracket.0);
+            }
+        }
+
+        for index in lines.clone() {
+            if let Some(paragraph) = self.document.paragraph(index) {
+                let top = top_of(index) + (line_height - paragraph.height()) / 2.0;
+                paragraph.paint(canvas, (text_left, top));
+            }
+        }
+
+        let caret_x = text_left + self.document.x_of(self.caret);
+        if !self.preedit.is_empty() {
+            let paragraph = layout_preedit(&self.preedit, &self.look, &self.font_collection);
+            let top = caret_top + (line_height - paragraph.height()) / 2.0;
+            let composed = Rect::new(
+                caret_x,
+                caret_top,
+                caret_x + paragraph.max_intrinsic_width(),
+                caret_top + line_height,
+            );
+            fill(canvas, composed, style.gutter.0);
+            paragraph.paint(canvas, (caret_x, top));
+        } else if self.focused && lines.contains(&self.caret.line) {
+            let caret = Rect::new(
+                caret_x,
+                caret_top,
+                caret_x + CARET_WIDTH.to_physical(),
+                caret_top + line_height,
+            );
+            fill(canvas, caret, self.look.color);
+        }
+        canvas.restore();
+
+        // candidates of the input method are shown below the caret
+        if self.focused {
+            this.window()
+                .set_ime_position(PhysicalPosition::new(caret_x, caret_top + line_height));
+        }
+
+        fill(
+            canvas,
+            Rect::new(rect.left, rect.top, gutter, rect.bottom),
+            style.gutter.0,
+        );
+        let font_size = style.font_size.map_or(Pixel(14.0), |size| size.0);
+        let font = label_font(font_size);
+        for index in lines.clone() {
+            let number = (index + 1).to_string();
+            let x = Pixel::from_physical(gutter) - GUTTER_PADDING - label_width(&font, &number);
+            let top = Pixel::from_physical(top_of(index));
+            let color = if index == self.caret.line {
+                self.look.color
+            } else {
+                style.line_number.0
+            };
+            draw_label(
+                canvas,
+                &font,
+                &number,
+                color,
+                x,
+                (top, top + self.line_height()),
+            );
+        }
+        canvas.restore();
+
+        draw_vertical_scrollbar(canvas, region, self.content_height(), self.scroll_y);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for CodeEditor
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        // lines may always be scrolled sideways
+        this.consume_wheel(true);
+        this.daemon(pointer_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        CodeEditor {
+            document: Document::new(&props.text, props.syntax.as_ref()),
+            look: look_of(&props.style),
+            props,
+            font_collection: font_collection(),
+            caret: Pos::default(),
+            anchor: Pos::default(),
+            goal: None,
+            scroll_x: Pixel(0.0),
+            scroll_y: Pixel(0.0),
+            focused: false,
+            revision: 0,
+            preedit: String::new(),
+            clipboard: None,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if update_result.unchanged {
+            return true;
+        }
+
+        let syntax = self.props.syntax.as_ref();
+        let own_text = matches!(self.props.revision, Some(revision) if revision <= self.revision);
+        if !update_result.text_unchanged && !own_text && self.props.text != self.document.text() {
+            self.document = Document::new(&self.props.text, syntax);
+            self.caret = self.document.clamp(self.caret);
+            self.anchor = self.document.clamp(self.anchor);
+            self.goal = None;
+            if let Some(revision) = self.props.revision {
+                self.revision = revision;
+            }
+        } else if !update_result.syntax_unchanged {
+            self.document.retokenize(syntax);
+        }
+
+        let look = look_of(&self.props.style);
+        if look != self.look {
+            self.look = look;
+            self.document.clear_layout();
+        }
+        false
+    }
+}
+
+fn look_of(style: &CodeEditorStyles) -> Look {
+    Look {
+        font_size: style
+            .font_size
+            .map_or(Pixel(14.0), |size| size.0)
+            .to_physical(),
+        color: style.color.map_or(Color::BLACK, |color| color.0),
+        keyword: style.keyword.0,
+        types: style.types.0,
+        function: style.function.0,
+        string: style.string.0,
+        number: style.number.0,
+        comment: style.comment.0,
+    }
+}
+
+impl CodeEditor {
+    fn line_height(&self) -> Pixel {
+        Pixel::from_physical(self.look.font_size * LINE_HEIGHT)
+    }
+
+    /// Width of the gutter, fitting the number of the last line.
+    fn gutter_width(&self) -> Pixel {
+        let font_size = self
+            .props
+            .style
+            .font_size
+            .map_or(Pixel(14.0), |size| size.0);
+        let digits = self.document.line_count().to_string().len().max(2);
+        label_width(&label_font(font_size), &"0".repeat(digits)) + GUTTER_PADDING * 2.0
+    }
+
+    /// Where lines start, scrolled horizontally.
+    fn text_left(&self, region: Region) -> Pixel {
+        region.0 .0 + self.gutter_width() + TEXT_PADDING - self.scroll_x
+    }
+
+    fn visible_lines(&self, region: Region) -> Range<usize> {
+        let line_height = self.line_height().0;
+        let first = (self.scroll_y.0 / line_height).floor().max(0.0) as usize;
+        let last = ((self.scroll_y + region.1 .1 - region.0 .1).0 / line_height).ceil() as usize;
+        first.min(self.document.line_count())..last.min(self.document.line_count())
+    }
+
+    /// The selection in order, empty at the caret if nothing is selected.
+    fn selection(&self) -> Range<Pos> {
+        self.anchor.min(self.caret)..self.anchor.max(self.caret)
+    }
+
+    /// The bracket beside the caret and the one matching it. The bracket
+    /// after the caret is preferred.
+    fn brackets(&self) -> Option<[Pos; 2]> {
+        let before = self.document.prev(self.caret);
+        [self.caret, before]
+            .into_iter()
+            .filter(|pos| pos.line == self.caret.line)
+            .find_map(|pos| Some([pos, self.document.matching_bracket(pos)?]))
+    }
+
+    /// Position of the text at `point` of the window.
+    fn pos_at(&mut self, region: Region, point: Point) -> Pos {
+        let y = point.1 - region.0 .1 + self.scroll_y;
+        let line = (y.0 / self.line_height().0).floor().max(0.0) as usize;
+        let line = line.min(self.document.line_count() - 1);
+
+        self.document
+            .layout(line, &self.look, &self.font_collection);
+        let x = (point.0 - self.text_left(region)).to_physical();
+        Pos {
+            line,
+            column: self.document.column_at(line, x),
+        }
+    }
+
+    fn content_height(&self) -> Pixel {
+        self.line_height() * self.document.line_count() as f32
+    }
+
+    fn max_scroll(&self, region: Region) -> Pixel {
+        max_offset(self.content_height(), region.1 .1 - region.0 .1)
+    }
+
+    fn clamp_scroll(&mut self, region: Region) {
+        self.scroll_y = clamp_offset(self.scroll_y, self.max_scroll(region));
+        self.scroll_x = self.scroll_x.max(Pixel(0.0));
+    }
+
+    fn scroll_to_caret(&mut self, region: Region) {
+        let line_height = self.line_height();
+        let top = line_height * self.caret.line as f32;
+        self.scroll_y = reveal_offset(
+            self.scroll_y,
+            region.1 .1 - region.0 .1,
+            top,
+            top + line_height,
+        );
+
+        self.document
+            .layout(self.caret.line, &self.look, &self.font_collection);
+        let x = Pixel::from_physical(self.document.x_of(self.caret));
+        let width = region.1 .0 - region.0 .0 - self.gutter_width() - TEXT_PADDING * 2.0;
+        self.scroll_x = reveal_offset(self.scroll_x, width, x, x);
+        self.clamp_scroll(region);
+    }
+
+    /// Move the caret to `pos`, selecting from the anchor if `extend`.
+    fn move_to(&mut self, pos: Pos, extend: bool) {
+        self.caret = pos;
+        if !extend {
+            self.anchor = pos;
+        }
+        self.goal = None;
+    }
+
+    /// Move the caret `lines` up or down, keeping its column in characters.
+    fn move_lines(&mut self, lines: isize, extend: bool) {
+        let text = self.document.line(self.caret.line);
+        let goal = self
+            .goal
+            .unwrap_or_else(|| text[..self.caret.column].chars().count());
+
+        let last = self.document.line_count() - 1;
+        let pos = match self.caret.line.checked_add_signed(lines) {
+            Some(line) if line <= last => {
+                let text = self.document.line(line);
+                let column = text
+                    .char_indices()
+                    .nth(goal)
+                    .map_or(text.len(), |(at, _)| at);
+                Pos { line, column }
+            }
+            // past the first or the last line
+            Some(_) => self.document.end(),
+            None => Pos::default(),
+        };
+        self.move_to(pos, extend);
+        self.goal = Some(goal);
+    }
+
+    /// The first character of the line which is not a space, or the start
+    /// of the line if the caret is there already.
+    fn home(&self) -> Pos {
+        let text = self.document.line(self.caret.line);
+        let indent = text.len() - text.trim_start().len();
+        let column = if self.caret.column == indent {
+            0
+        } else {
+            indent
+        };
+        Pos {
+            column,
+            ..self.caret
+        }
+    }
+
+    fn indent_unit(&self) -> String {
+        " ".repeat(self.props.tab_size.max(1))
+    }
+
+    /// Replace `range` with `text`, tell the edit, and give the end of the
+    /// inserted text.
+    fn edit(&mut self, this: &ElModel!(), range: Range<Pos>, text: &str) -> Pos {
+        let bytes = self.document.offset(range.start)..self.document.offset(range.end);
+        let end = self
+            .document
+            .replace(range.clone(), text, self.props.syntax.as_ref());
+
+        self.revision += 1;
+        this.event_dispatcher().emit(CodeChanged {
+            revision: self.revision,
+            range: bytes,
+            inserted: self.document.slice(range.start..end),
+        });
+        end
+    }
+
+    /// Replace `range` with `text` and put the caret after it.
+    fn replace(&mut self, this: &ElModel!(), range: Range<Pos>, text: &str) {
+        let end = self.edit(this, range, text);
+        self.move_to(end, false);
+    }
+
+    fn clipboard(&mut self) -> Option<&mut Clipboard> {
+        if self.clipboard.is_none() {
+            self.clipboard = Clipboard::new().ok();
+        }
+        self.clipboard.as_mut()
+    }
+
+    /// Put the selection on the clipboard, and give whether there was any.
+    fn copy(&mut self) -> bool {
+        let selection = self.selection();
+        if selection.is_empty() {
+            return false;
+        }
+
+        let text = self.document.slice(selection);
+        if let Some(clipboard) = self.clipboard() {
+            // nothing is copied if the system refuses
+            let _ = clipboard.set_text(text);
+        }
+        true
+    }
+
+    fn paste(&mut self, this: &ElModel!()) {
+        let Some(text) = self
+            .clipboard()
+            .and_then(|clipboard| clipboard.get_text().ok())
+        else {
+            return;
+        };
+        self.replace(this, self.selection(), &text);
+    }
+
+    fn type_char(&mut self, this: &ElModel!(), c: char) {
+        let selection = self.selection();
+        let before = &self.document.line(self.caret.line)[..self.caret.column];
+
+        // a closing bracket at the start of a line takes a level off
+        let closes = matches!(c, ')' | ']' | '}');
+        if closes && selection.is_empty() && !before.is_empty() && before.bytes().all(|b| b == b' ')
+        {
+            let width = (before.len() - 1) % self.props.tab_size.max(1) + 1;
+            let start = Pos {
+                column: self.caret.column - width,
+                ..self.caret
+            };
+            self.replace(this, start..self.caret, &c.to_string());
+            return;
+        }
+
+        self.replace(this, selection, &c.to_string());
+    }
+
+    /// Break the line, keeping its indentation, one level deeper after an
+    /// opening bracket. The bracket closing it goes to a line of its own.
+    fn newline(&mut self, this: &ElModel!()) {
+        let Range { start, end } = self.selection();
+        let text = self.document.line(start.line);
+        let indent_len = text.len() - text.trim_start().len();
+        let indent = &text[..indent_len.min(start.column)];
+        let opener = text[..start.column].trim_end().chars().next_back();
+        let after = self.document.line(end.line)[end.column..]
+            .trim_start()
+            .chars()
+            .next();
+
+        let unit = self.indent_unit();
+        let mut inserted = format!("\n{indent}");
+        match opener.and_then(closing) {
+            Some(close) if after == Some(close) => {
+                inserted.push_str(&unit);
+                let caret = Pos {
+                    line: start.line + 1,
+                    column: inserted.len() - 1,
+                };
+                inserted.push_str(&format!("\n{indent}"));
+                self.replace(this, start..end, &inserted);
+                self.move_to(caret, false);
+            }
+            Some(_) => {
+                inserted.push_str(&unit);
+                self.replace(this, start..end, &inserted);
+            }
+            None => self.replace(this, start..end, &inserted),
+        }
+    }
+
+    fn backspace(&mut self, this: &ElModel!()) {
+        let selection = self.selection();
+        if !selection.is_empty() {
+            self.replace(this, selection, "");
+            return;
+        }
+
+        // in the indentation, take off a level rather than a space
+        let before = &self.document.line(self.caret.line)[..self.caret.column];
+        let start = if !before.is_empty() && before.bytes().all(|b| b == b' ') {
+            let width = (before.len() - 1) % self.props.tab_size.max(1) + 1;
+            Pos {
+                column: self.caret.column - width,
+                ..self.caret
+            }
+        } else {
+            self.document.prev(self.caret)
+        };
+
+        if start != self.caret {
+            self.replace(this, start..self.caret, "");
+        }
+    }
+
+    fn delete(&mut self, this: &ElModel!()) {
+        let selection = self.selection();
+        let range = if selection.is_empty() {
+            self.caret..self.document.next(self.caret)
+        } else {
+            selection
+        };
+        if !range.is_empty() {
+            self.replace(this, range, "");
+        }
+    }
+
+    /// Indent the selected lines by a level, or take a level off if
+    /// `outdent`. A selection ending at the start of a line leaves it out.
+    fn indent_lines(&mut self, this: &ElModel!(), outdent: bool) {
+        let Range { start, end } = self.selection();
+        let last = if end.line > start.line && end.column == 0 {
+            end.line - 1
+        } else {
+            end.line
+        };
+
+        let unit = self.indent_unit();
+        let mut changed = false;
+        for line in start.line..=last {
+            let text = self.document.line(line);
+            let (range, shift) = if outdent {
+                let spaces = text.len() - text.trim_start_matches(' ').len();
+                let width = if text.starts_with('\t') {
+                    1
+                } else {
+                    spaces.min(unit.len())
+                };
+                (0..width, -(width as isize))
+            } else if text.is_empty() {
+                (0..0, 0)
+            } else {
+                (0..0, unit.len() as isize)
+            };
+            if shift == 0 {
+                continue;
+            }
+
+            let range = Pos {
+                line,
+                column: range.start,
+            }..Pos {
+                line,
+                column: range.end,
+            };
+            let inserted = if outdent { "" } else { unit.as_str() };
+            self.edit(this, range, inserted);
+            changed = true;
+
+            for pos in [&mut self.caret, &mut self.anchor] {
+                if pos.line == line {
+                    pos.column = pos.column.saturating_add_signed(shift);
+                }
+            }
+        }
+
+        if changed {
+            self.goal = None;
+        }
+    }
+
+    fn tab(&mut self, this: &ElModel!(), shift: bool) {
+        let Range { start, end } = self.selection();
+        if shift || start.line != end.line {
+            self.indent_lines(this, shift);
+            return;
+        }
+
+        let tab_size = self.props.tab_size.max(1);
+        let column = self.document.line(start.line)[..start.column]
+            .chars()
+            .count();
+        let spaces = " ".repeat(tab_size - column % tab_size);
+        self.replace(this, start..end, &spaces);
+    }
+
+    fn input(&mut self, this: &ElModel!(), input: Input, modifiers: ModifiersState) {
+        let shift = modifiers.shift();
+        let ctrl = modifiers.ctrl() || modifiers.logo();
+        let editable = !self.props.read_only;
+        let selection = self.selection();
+        let region = this.draw_region();
+
+        match input {
+            Input::Char(c) if editable => self.type_char(this, c),
+            Input::Commit(text) if editable => {
+                self.preedit.clear();
+                self.replace(this, selection, &text);
+            }
+            Input::Char(_) | Input::Commit(_) => return,
+            Input::Key(key) => match key {
+                VirtualKeyCode::Left => {
+                    let pos = match (shift, selection.is_empty(), ctrl) {
+                        (false, false, _) => selection.start,
+                        (_, _, true) => self.document.prev_word(self.caret),
+                        (_, _, false) => self.document.prev(self.caret),
+                    };
+                    self.move_to(pos, shift);
+                }
+                VirtualKeyCode::Right => {
+                    let pos = match (shift, selection.is_empty(), ctrl) {
+                        (false, false, _) => selection.end,
+                        (_, _, true) => self.document.next_word(self.caret),
+                        (_, _, false) => self.document.next(self.caret),
+                    };
+                    self.move_to(pos, shift);
+                }
+                VirtualKeyCode::Up => self.move_lines(-1, shift),
+                VirtualKeyCode::Down => self.move_lines(1, shift),
+                VirtualKeyCode::PageUp | VirtualKeyCode::PageDown => {
+                    let viewport = region.1 .1 - region.0 .1;
+                    let lines = ((viewport.0 / self.line_height().0).floor() as isize).max(1);
+                    let lines = match key {
+                        VirtualKeyCode::PageUp => -lines,
+                        _ => lines,
+                    };
+                    self.move_lines(lines, shift);
+                }
+                VirtualKeyCode::Home if ctrl => self.move_to(Pos::default(), shift),
+                VirtualKeyCode::Home => self.move_to(self.home(), shift),
+                VirtualKeyCode::End if ctrl => self.move_to(self.document.end(), shift),
+                VirtualKeyCode::End => {
+                    let column = self.document.line(self.caret.line).len();
+                    self.move_to(
+                        Pos {
+                            column,
+                            ..self.caret
+                        },
+                        shift,
+                    );
+                }
+                VirtualKeyCode::A if ctrl => {
+                    self.move_to(Pos::default(), false);
+                    self.move_to(self.document.end(), true);
+                }
+                VirtualKeyCode::C if ctrl => {
+                    self.copy();
+                    return;
+                }
+                VirtualKeyCode::X if ctrl && editable => {
+                    if self.copy() {
+                        self.replace(this, selection, "");
+                    }
+                }
+                VirtualKeyCode::V if ctrl && editable => self.paste(this),
+                VirtualKeyCode::Escape => self.move_to(self.caret, false),
+                VirtualKeyCode::Back if editable => self.backspace(this),
+                VirtualKeyCode::Delete if editable => self.delete(this),
+                VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter if editable => {
+                    self.newline(this)
+                }
+                VirtualKeyCode::Tab if editable => self.tab(this, shift),
+                _ => return,
+            },
+        }
+
+        self.scroll_to_caret(region);
+    }
+}
+
+async fn update(this: &ElModel!(CodeEditor), f: impl FnOnce(&mut CodeEditor)) -> bool {
+    let Some(mut editor) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *editor);
+    true
+}
+
+async fn pointer_runtime(this: ElModel!(CodeEditor)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let alive = tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => {
+                if pd.button != MouseButton::Left {
+                    continue;
+                }
+
+                this.focus();
+                let placed = update(&this, |editor| {
+                    let pos = editor.pos_at(this.draw_region(), pd.position);
+                    editor.move_to(pos, pd.modifiers.shift());
+                });
+                if !placed.await {
+                    return;
+                }
+
+                // keep selecting after the pointer leaves the editor
+                this.capture_pointer(pd.pointer_id);
+                select_to_pointer(&this, pd.pointer_id).await
+            }
+            wheel = ed.recv_trusted::<Wheel>() => {
+                let (x, y) = wheel_delta(wheel.delta);
+                // the wheel scrolls sideways while Shift is held
+                let (x, y) = if wheel.modifiers.shift() { (y, x) } else { (x, y) };
+                update(&this, |editor| {
+                    editor.scroll_x = editor.scroll_x + x;
+                    editor.scroll_y = editor.scroll_y + y;
+                    editor.clamp_scroll(this.draw_region());
+                })
+                .await
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
+
+/// Move the caret with the pointer until it is released, and give whether
+/// the editor is alive.
+async fn select_to_pointer(this: &ElModel!(CodeEditor), pointer_id: PointerId) -> bool {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        tokio::select! {
+            pm = ed.recv_trusted::<PointerMove>() => {
+                if pm.pointer_id != pointer_id {
+                    continue;
+                }
+
+                let moved = update(this, |editor| {
+                    let region = this.draw_region();
+                    let pos = editor.pos_at(region, pm.position);
+                    editor.move_to(pos, true);
+                    editor.scroll_to_caret(region);
+                });
+                if !moved.await {
+                    return false;
+                }
+            }
+            pu = ed.recv_trusted::<PointerUp>() => {
+                if pu.pointer_id == pointer_id {
+                    return true;
+                }
+            }
+            pcl = ed.recv_trusted::<PointerCaptureLost>() => {
+                if pcl.pointer_id == pointer_id {
+                    return true;
+                }
+            }
+        }
+    }
+}
+
+async fn keyboard_runtime(this: ElModel!(CodeEditor)) {
+    let ed = this.event_dispatcher().clone();
+    let mut modifiers = ModifiersState::empty();
+
+    loop {
+        ed.recv_trusted::<Focused>().await;
+        this.window().set_ime_allowed(true);
+        if !update(&this, |editor| editor.focused = true).await {
+            return;
+        }
+
+        loop {
+            let input = tokio::select! {
+                event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                    StaticWindowEvent::ModifiersChanged(state) => {
+                        modifiers = state;
+                        continue;
+                    }
+                    StaticWindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => Input::Key(key),
+                    StaticWindowEvent::ReceivedCharacter(c) if !c.is_control() => Input::Char(c),
+                    StaticWindowEvent::Ime(Ime::Commit(text)) => Input::Commit(text),
+                    StaticWindowEvent::Ime(ime @ (Ime::Preedit(..) | Ime::Disabled)) => {
+                        let preedit = match ime {
+                            Ime::Preedit(text, _) => text,
+                            _ => String::new(),
+                        };
+                        if !update(&this, |editor| editor.preedit = preedit).await {
+                            return;
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                },
+                _ = ed.recv_trusted::<Blured>() => break,
+            };
+
+            if !update(&this, |editor| editor.input(&this, input, modifiers)).await {
+                return;
+            }
+        }
+
+        this.window().set_ime_allowed(false);
+        let blured = update(&this, |editor| {
+            editor.focused = false;
+            editor.preedit.clear();
+        });
+        if !blured.await {
+            return;
+        }
+    }
+}
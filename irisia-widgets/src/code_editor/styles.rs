@@ -0,0 +1,57 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Background of the line numbers.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeGutter(#[style(default = "Color::from_rgb(0xf5, 0xf5, 0xf5)")] pub Color);
+
+/// Color of the line numbers but the one of the caret, which takes the
+/// color of the text.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeLineNumber(#[style(default = "Color::from_rgb(0x9e, 0x9e, 0x9e)")] pub Color);
+
+/// Background of the line of the caret.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeCurrentLine(
+    #[style(default = "Color::from_argb(0x0c, 0x00, 0x00, 0x00)")] pub Color,
+);
+
+/// Background of the selected text.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeSelection(
+    #[style(default = "Color::from_argb(0x50, 0x2f, 0x6f, 0xed)")] pub Color,
+);
+
+/// Background of the bracket beside the caret and the one matching it.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeBracket(
+    #[style(default = "Color::from_argb(0x30, 0x00, 0x00, 0x00)")] pub Color,
+);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeKeyword(#[style(default = "Color::from_rgb(0xaf, 0x00, 0xdb)")] pub Color);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeType(#[style(default = "Color::from_rgb(0x26, 0x7f, 0x99)")] pub Color);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeFunction(#[style(default = "Color::from_rgb(0x79, 0x5e, 0x26)")] pub Color);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeString(#[style(default = "Color::from_rgb(0xa3, 0x15, 0x15)")] pub Color);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeNumber(#[style(default = "Color::from_rgb(0x09, 0x86, 0x58)")] pub Color);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCodeComment(#[style(default = "Color::from_rgb(0x00, 0x80, 0x00)")] pub Color);
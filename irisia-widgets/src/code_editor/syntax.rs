@@ -0,0 +1,267 @@
+use std::{collections::HashSet, ops::Range, rc::Rc};
+
+/// Tokenizer state inside a block comment
+const IN_COMMENT: u32 = 1;
+
+/// What a token is, which picks its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Keyword,
+    Type,
+    Function,
+    String,
+    Number,
+    Comment,
+}
+
+/// A highlighted part of a line. Text not covered by any token is plain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// Byte range in the line
+    pub range: Range<usize>,
+    pub kind: TokenKind,
+}
+
+/// Splits lines of code into tokens to highlight.
+///
+/// Lines are tokenized one by one from the top. `state` is where the
+/// tokenizer was at the end of the line before, `0` for the first line, and
+/// is left where it is at the end of this line, like inside a block
+/// comment. After an edit only the edited lines are tokenized again, and
+/// then the lines below until a line ends in the same state as before.
+pub trait Tokenizer {
+    /// Tokens of `line` in ascending order, not overlapping.
+    fn tokenize(&self, line: &str, state: &mut u32) -> Vec<Token>;
+}
+
+/// A [`Tokenizer`] shared with a [`CodeEditor`].
+///
+/// ```ignore
+/// Syntax::new(SimpleTokenizer::new().keywords(["select", "from"]).line_comment("--"))
+/// ```
+///
+/// [`CodeEditor`]: super::CodeEditor
+#[derive(Clone)]
+pub struct Syntax(Rc<dyn Tokenizer>);
+
+impl Syntax {
+    pub fn new(tokenizer: impl Tokenizer + 'static) -> Self {
+        Syntax(Rc::new(tokenizer))
+    }
+
+    pub(super) fn tokenize(&self, line: &str, state: &mut u32) -> Vec<Token> {
+        self.0.tokenize(line, state)
+    }
+}
+
+impl PartialEq for Syntax {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A tokenizer of keywords, types, comments, strings and numbers, enough
+/// for most languages with a C-like or SQL-like lexical structure. A word
+/// followed by `(` is a function.
+///
+/// Strings are closed by the quote they start with and end at the end of
+/// the line if not closed, with `\` escaping the next character.
+#[derive(Debug, Clone)]
+pub struct SimpleTokenizer {
+    keywords: HashSet<String>,
+    types: HashSet<String>,
+    line_comments: Vec<String>,
+    block_comment: Option<(String, String)>,
+    quotes: Vec<char>,
+    case_sensitive: bool,
+}
+
+impl Default for SimpleTokenizer {
+    fn default() -> Self {
+        SimpleTokenizer {
+            keywords: HashSet::new(),
+            types: HashSet::new(),
+            line_comments: Vec::new(),
+            block_comment: None,
+            quotes: vec!['"', '\''],
+            case_sensitive: true,
+        }
+    }
+}
+
+impl SimpleTokenizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn keywords<'a>(mut self, keywords: impl IntoIterator<Item = &'a str>) -> Self {
+        let keywords = keywords.into_iter().map(|word| self.normalize(word));
+        self.keywords = keywords.collect();
+        self
+    }
+
+    pub fn types<'a>(mut self, types: impl IntoIterator<Item = &'a str>) -> Self {
+        let types = types.into_iter().map(|word| self.normalize(word));
+        self.types = types.collect();
+        self
+    }
+
+    /// Start of a comment to the end of the line, like `//`. Can be given
+    /// more than once.
+    pub fn line_comment(mut self, start: &str) -> Self {
+        self.line_comments.push(start.to_string());
+        self
+    }
+
+    /// Delimiters of a comment which may span lines, like `/*` and `*/`.
+    pub fn block_comment(mut self, start: &str, end: &str) -> Self {
+        self.block_comment = Some((start.to_string(), end.to_string()));
+        self
+    }
+
+    /// Characters starting and ending strings, `"` and `'` by default.
+    pub fn quotes(mut self, quotes: impl IntoIterator<Item = char>) -> Self {
+        self.quotes = quotes.into_iter().collect();
+        self
+    }
+
+    /// Match keywords and types regardless of case, as in SQL.
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_sensitive = false;
+        self.keywords = self
+            .keywords
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect();
+        self.types = self.types.iter().map(|word| word.to_lowercase()).collect();
+        self
+    }
+
+    fn normalize(&self, word: &str) -> String {
+        if self.case_sensitive {
+            word.to_string()
+        } else {
+            word.to_lowercase()
+        }
+    }
+
+    fn word_kind(&self, word: &str, rest: &str) -> Option<TokenKind> {
+        let word = self.normalize(word);
+        if self.keywords.contains(&word) {
+            Some(TokenKind::Keyword)
+        } else if self.types.contains(&word) {
+            Some(TokenKind::Type)
+        } else if rest.trim_start().starts_with('(') {
+            Some(TokenKind::Function)
+        } else {
+            None
+        }
+    }
+}
+
+impl Tokenizer for SimpleTokenizer {
+    fn tokenize(&self, line: &str, state: &mut u32) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut at = 0;
+        let token = |range, kind| Token { range, kind };
+
+        if *state == IN_COMMENT {
+            let Some((_, end)) = &self.block_comment
+            else {
+                *state = 0;
+                return tokens;
+            };
+
+            match line.find(end.as_str()) {
+                Some(index) => {
+                    at = index + end.len();
+                    tokens.push(token(0..at, TokenKind::Comment));
+                    *state = 0;
+                }
+                None => {
+                    tokens.push(token(0..line.len(), TokenKind::Comment));
+                    return tokens;
+                }
+            }
+        }
+
+        while let Some(c) = line[at..].chars().next() {
+            let rest = &line[at..];
+
+            if self
+                .line_comments
+                .iter()
+                .any(|start| rest.starts_with(start.as_str()))
+            {
+                tokens.push(token(at..line.len(), TokenKind::Comment));
+                break;
+            }
+
+            if let Some((start, end)) = &self.block_comment {
+                if rest.starts_with(start.as_str()) {
+                    let body = at + start.len();
+                    match line[body..].find(end.as_str()) {
+                        Some(index) => {
+                            let close = body + index + end.len();
+                            tokens.push(token(at..close, TokenKind::Comment));
+                            at = close;
+                            continue;
+                        }
+                        None => {
+                            tokens.push(token(at..line.len(), TokenKind::Comment));
+                            *state = IN_COMMENT;
+                            break;
+                        }
+                    }
+                }
+            }
+
+            let start = at;
+            if self.quotes.contains(&c) {
+                at += c.len_utf8();
+                let mut escaped = false;
+                for next in line[at..].chars() {
+                    at += next.len_utf8();
+                    match next {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        _ if next == c => break,
+                        _ => {}
+                    }
+                }
+                tokens.push(token(start..at, TokenKind::String));
+            } else if c.is_ascii_digit() {
+                at += word_len(rest);
+                // a fraction or an exponent with a sign
+                while let Some(next) = line[at..].chars().next() {
+                    let exponent = matches!(line[..at].chars().last(), Some('e' | 'E'));
+                    let sign = exponent && matches!(next, '+' | '-');
+                    if (next == '.' || sign)
+                        && line[at + 1..].starts_with(|c: char| c.is_ascii_digit())
+                    {
+                        at += 1;
+                        at += word_len(&line[at..]);
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(token(start..at, TokenKind::Number));
+            } else if c.is_alphabetic() || c == '_' {
+                at += word_len(rest);
+                if let Some(kind) = self.word_kind(&line[start..at], &line[at..]) {
+                    tokens.push(token(start..at, kind));
+                }
+            } else {
+                at += c.len_utf8();
+            }
+        }
+
+        tokens
+    }
+}
+
+/// Length in bytes of the word `text` starts with.
+fn word_len(text: &str) -> usize {
+    text.find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(text.len())
+}
@@ -0,0 +1,198 @@
+/// A color by hue in degrees, saturation, value and alpha, the last three
+/// in `0.0..=1.0`. The picker keeps its color this way so that the hue and
+/// the saturation are not lost once the color turns gray or black.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Hsva {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+    pub a: f32,
+}
+
+impl Hsva {
+    /// Converts red, green, blue and alpha, taking the hue and the
+    /// saturation of `keep` where the color has none.
+    pub fn from_rgba([r, g, b, a]: [f32; 4], keep: Hsva) -> Self {
+        let (r, g, b) = (r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0));
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+
+        let h = if delta <= 0.0 {
+            keep.h
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let s = if max <= 0.0 { keep.s } else { delta / max };
+
+        Hsva {
+            h,
+            s,
+            v: max,
+            a: a.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn to_rgba(self) -> [f32; 4] {
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let c = self.v * self.s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = self.v - c;
+        [r + m, g + m, b + m, self.a]
+    }
+
+    /// Saturation and lightness of the color as in HSL.
+    pub fn to_hsl(self) -> (f32, f32) {
+        let l = self.v * (1.0 - self.s / 2.0);
+        let range = l.min(1.0 - l);
+        let s = if range <= 0.0 {
+            0.0
+        } else {
+            (self.v - l) / range
+        };
+        (s, l)
+    }
+
+    /// Changes the saturation and the lightness as in HSL, keeping the hue.
+    pub fn with_hsl(self, s: f32, l: f32) -> Self {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let v = l + s * l.min(1.0 - l);
+        Hsva {
+            s: if v <= 0.0 {
+                self.s
+            } else {
+                2.0 * (1.0 - l / v)
+            },
+            v,
+            ..self
+        }
+    }
+}
+
+/// A channel in `0.0..=1.0` as a byte.
+pub(super) fn to_byte(channel: f32) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// The color as `rrggbb`, without alpha.
+pub(super) fn format_hex([r, g, b, _]: [f32; 4]) -> String {
+    format!("{:02x}{:02x}{:02x}", to_byte(r), to_byte(g), to_byte(b))
+}
+
+/// Parses `rgb`, `rgba`, `rrggbb` or `rrggbbaa`, with or without a leading
+/// `#`, into red, green and blue, and alpha if given.
+pub(super) fn parse_hex(text: &str) -> Option<([f32; 3], Option<f32>)> {
+    let text = text.trim();
+    let text = text.strip_prefix('#').unwrap_or(text);
+    if !text.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let digits: Vec<u8> = match text.len() {
+        3 | 4 => text
+            .chars()
+            .map(|c| c.to_digit(16).unwrap() as u8 * 0x11)
+            .collect(),
+        6 | 8 => (0..text.len())
+            .step_by(2)
+            .map(|at| u8::from_str_radix(&text[at..at + 2], 16).unwrap())
+            .collect(),
+        _ => return None,
+    };
+
+    let channel = |index: usize| digits[index] as f32 / 255.0;
+    let alpha = (digits.len() == 4).then(|| channel(3));
+    Some(([channel(0), channel(1), channel(2)], alpha))
+}
+
+#[cfg(test)]
+fn assert_near(left: &[f32], right: &[f32]) {
+    let near = left.iter().zip(right).all(|(a, b)| (a - b).abs() < 1e-4);
+    assert!(near, "{left:?} is not near {right:?}");
+}
+
+#[test]
+fn test_hue_boundaries() {
+    let color = |h: f32| Hsva {
+        h,
+        s: 1.0,
+        v: 1.0,
+        a: 1.0,
+    };
+    let expected = [
+        (0.0, [1.0, 0.0, 0.0]),
+        (60.0, [1.0, 1.0, 0.0]),
+        (120.0, [0.0, 1.0, 0.0]),
+        (180.0, [0.0, 1.0, 1.0]),
+        (240.0, [0.0, 0.0, 1.0]),
+        (300.0, [1.0, 0.0, 1.0]),
+        (360.0, [1.0, 0.0, 0.0]),
+        (-60.0, [1.0, 0.0, 1.0]),
+    ];
+
+    for (h, [r, g, b]) in expected {
+        assert_near(&color(h).to_rgba(), &[r, g, b, 1.0]);
+
+        let back = Hsva::from_rgba([r, g, b, 1.0], color(0.0));
+        assert_near(&[back.h], &[h.rem_euclid(360.0)]);
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let keep = Hsva {
+        h: 0.0,
+        s: 0.0,
+        v: 0.0,
+        a: 1.0,
+    };
+    for h in [0.0, 0.5, 59.5, 60.0, 119.9, 180.0, 239.5, 300.0, 359.5] {
+        for (s, v) in [(1.0, 1.0), (0.5, 0.8), (0.25, 0.3)] {
+            let color = Hsva { h, s, v, a: 0.5 };
+            let back = Hsva::from_rgba(color.to_rgba(), keep);
+            assert_near(&[back.h, back.s, back.v, back.a], &[h, s, v, 0.5]);
+        }
+    }
+}
+
+#[test]
+fn test_gray_keeps_hue() {
+    let keep = Hsva {
+        h: 200.0,
+        s: 0.6,
+        v: 1.0,
+        a: 1.0,
+    };
+    let gray = Hsva::from_rgba([0.5, 0.5, 0.5, 1.0], keep);
+    assert_near(&[gray.h, gray.s, gray.v], &[200.0, 0.0, 0.5]);
+
+    let black = Hsva::from_rgba([0.0, 0.0, 0.0, 1.0], keep);
+    assert_near(&[black.h, black.s, black.v], &[200.0, 0.6, 0.0]);
+}
+
+#[test]
+fn test_hex() {
+    assert_eq!(format_hex([1.0, 0.5, 0.0, 1.0]), "ff8000");
+    assert_eq!(
+        parse_hex("#f80"),
+        Some(([1.0, 0x88 as f32 / 255.0, 0.0], None))
+    );
+    assert_eq!(
+        parse_hex("ff000080"),
+        Some(([1.0, 0.0, 0.0], Some(0x80 as f32 / 255.0)))
+    );
+    assert_eq!(parse_hex("ff00"), Some(([1.0, 1.0, 0.0], Some(0.0))));
+    assert_eq!(parse_hex("#ff00g0"), None);
+    assert_eq!(parse_hex("ff000"), None);
+}
@@ -0,0 +1,992 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{
+        Blured, Click, Focused, PointerCaptureLost, PointerDown, PointerId, PointerMove, PointerUp,
+    },
+    primitive::{Pixel, Point, Region},
+    skia_safe::{
+        paint::Cap, Canvas, Color, Color4f, Paint, PaintStyle, RRect, Rect, Shader, TileMode,
+    },
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, ModifiersState, MouseButton, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{
+    label::{draw_label, label_font, label_width, to_rect},
+    popup::region_contains,
+    textbox::styles::StyleFontSize,
+};
+
+use self::{
+    hsv::{format_hex, parse_hex, to_byte, Hsva},
+    styles::*,
+};
+
+mod hsv;
+pub mod styles;
+
+const GAP: Pixel = Pixel(8.0);
+/// Height of the hue and the alpha sliders
+const BAR_HEIGHT: Pixel = Pixel(12.0);
+/// Height of a row of fields relative to the font size
+const ROW_HEIGHT: f32 = 2.0;
+const RADIUS: Pixel = Pixel(4.0);
+const CHECKER_SIZE: Pixel = Pixel(5.0);
+const FIELD_PADDING: Pixel = Pixel(6.0);
+const CARET_WIDTH: Pixel = Pixel(1.5);
+/// Characters a field takes, enough for `#rrggbbaa`
+const MAX_INPUT: usize = 9;
+
+/// Picks a color with a saturation and value square, a hue slider and an
+/// alpha slider, or by typing it in hex, RGB or HSL, or from anywhere in
+/// the window with the eyedropper.
+///
+/// ```ignore
+/// ColorPicker {
+///     value: theme.accent,
+/// }
+/// ```
+///
+/// [`ColorChanged`] is emitted whenever the user changes the color, and
+/// the color is shown as given by `value` again unless passed back. The
+/// value is a [`Color4f`], which converts into a [`StyleColor`] to style
+/// other elements with, like `color: (picked,)` in a style block.
+///
+/// From top to bottom, the picker shows the square, the sliders, a swatch
+/// of the color with the eyedropper button beside, and the fields: hex and
+/// alpha in percent, red, green and blue in `0..=255`, then hue in degrees
+/// with saturation and lightness in percent. The square takes the height
+/// left by the others.
+///
+/// Clicking a field selects its text to be replaced by typing. Enter
+/// applies it, Tab applies it and moves to the next field, the arrows up
+/// and down change a number by 1, or by 10 with Shift, and Escape gives
+/// up. Text which is not a color is dropped once the field is left.
+///
+/// Clicking the eyedropper lets the mouse pick a color from what the
+/// window shows, keeping the alpha. The swatch follows the color under the
+/// mouse until the left button picks it, while the right button or Escape
+/// gives up.
+pub struct ColorPicker {
+    props: OwnedProps,
+    hsva: Hsva,
+    editing: Option<Editing>,
+    picking: bool,
+    /// Color under the mouse while picking
+    sampled: Option<Color>,
+}
+
+/// Emitted whenever the user changes the color.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ColorChanged {
+    pub value: Color4f,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct ColorPickerStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    border: StyleColorPickerBorder,
+    accent: StyleColorPickerAccent,
+    label: StyleColorPickerLabel,
+}
+
+#[irisia::props(updater = "ColorPickerProps", watch)]
+pub struct OwnedProps {
+    #[props(must_init, watch)]
+    value: Color4f,
+
+    #[props(read_style(stdin))]
+    style: ColorPickerStyles,
+}
+
+/// A field being typed in.
+struct Editing {
+    field: Field,
+    text: String,
+    /// Whether all the text is selected, to be replaced by what is typed
+    selected: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Hex,
+    Alpha,
+    Red,
+    Green,
+    Blue,
+    Hue,
+    Saturation,
+    Lightness,
+}
+
+/// Fields in the order Tab moves through
+const FIELDS: [Field; 8] = [
+    Field::Hex,
+    Field::Alpha,
+    Field::Red,
+    Field::Green,
+    Field::Blue,
+    Field::Hue,
+    Field::Saturation,
+    Field::Lightness,
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Part {
+    Square,
+    Hue,
+    Alpha,
+    Eyedropper,
+    Field(Field),
+}
+
+struct Parts {
+    square: Region,
+    hue: Region,
+    alpha: Region,
+    swatch: Region,
+    eyedropper: Region,
+    /// In the order of [`FIELDS`]
+    fields: [Region; 8],
+}
+
+enum Input {
+    Key(VirtualKeyCode),
+    Char(char),
+}
+
+impl Field {
+    fn label(self) -> &'static str {
+        match self {
+            Field::Hex => "#",
+            Field::Alpha => "A",
+            Field::Red => "R",
+            Field::Green => "G",
+            Field::Blue => "B",
+            Field::Hue => "H",
+            Field::Saturation => "S",
+            Field::Lightness => "L",
+        }
+    }
+
+    /// Row of the field, and the first and the last of the three columns
+    /// it spans.
+    fn cell(self) -> (usize, usize, usize) {
+        match self {
+            Field::Hex => (0, 0, 1),
+            Field::Alpha => (0, 2, 2),
+            Field::Red => (1, 0, 0),
+            Field::Green => (1, 1, 1),
+            Field::Blue => (1, 2, 2),
+            Field::Hue => (2, 0, 0),
+            Field::Saturation => (2, 1, 1),
+            Field::Lightness => (2, 2, 2),
+        }
+    }
+
+    fn next(self, backward: bool) -> Field {
+        let step = if backward { FIELDS.len() - 1 } else { 1 };
+        FIELDS[(self as usize + step) % FIELDS.len()]
+    }
+
+    fn text(self, hsva: Hsva) -> String {
+        let rgba = hsva.to_rgba();
+        let (s, l) = hsva.to_hsl();
+        let percent = |value: f32| format!("{}", (value * 100.0).round());
+
+        match self {
+            Field::Hex => format_hex(rgba),
+            Field::Alpha => percent(hsva.a),
+            Field::Red => to_byte(rgba[0]).to_string(),
+            Field::Green => to_byte(rgba[1]).to_string(),
+            Field::Blue => to_byte(rgba[2]).to_string(),
+            Field::Hue => (hsva.h.round() as u32 % 360).to_string(),
+            Field::Saturation => percent(s),
+            Field::Lightness => percent(l),
+        }
+    }
+
+    /// The color with this field set to `text`, or `None` if it is not
+    /// valid. Numbers out of range are clamped.
+    fn apply(self, text: &str, hsva: Hsva) -> Option<Hsva> {
+        let number = || {
+            let text = text.trim().trim_end_matches(&['%', '°'][..]).trim_end();
+            text.parse::<f32>().ok().filter(|number| number.is_finite())
+        };
+
+        match self {
+            Field::Hex => {
+                let ([r, g, b], a) = parse_hex(text)?;
+                Some(Hsva::from_rgba([r, g, b, a.unwrap_or(hsva.a)], hsva))
+            }
+            Field::Alpha => Some(Hsva {
+                a: (number()? / 100.0).clamp(0.0, 1.0),
+                ..hsva
+            }),
+            Field::Red | Field::Green | Field::Blue => {
+                let mut rgba = hsva.to_rgba();
+                rgba[self as usize - Field::Red as usize] = (number()? / 255.0).clamp(0.0, 1.0);
+                Some(Hsva::from_rgba(rgba, hsva))
+            }
+            Field::Hue => Some(Hsva {
+                h: number()?.rem_euclid(360.0),
+                ..hsva
+            }),
+            Field::Saturation => Some(hsva.with_hsl(number()? / 100.0, hsva.to_hsl().1)),
+            Field::Lightness => Some(hsva.with_hsl(hsva.to_hsl().0, number()? / 100.0)),
+        }
+    }
+
+    /// The color with the number of this field changed by `step`.
+    fn nudge(self, hsva: Hsva, step: f32) -> Option<Hsva> {
+        if self == Field::Hex {
+            return None;
+        }
+        let number = self.text(hsva).parse::<f32>().ok()?;
+        self.apply(&(number + step).to_string(), hsva)
+    }
+}
+
+fn rounded(region: Region, radius: Pixel) -> RRect {
+    let radius = radius.to_physical();
+    RRect::new_rect_xy(to_rect(region), radius, radius)
+}
+
+fn fill(canvas: &mut Canvas, rrect: &RRect, color: Color) {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(color);
+    canvas.draw_rrect(rrect, &paint);
+}
+
+fn stroke(canvas: &mut Canvas, rrect: &RRect, color: Color) {
+    let mut paint = Paint::default();
+    paint
+        .set_anti_alias(true)
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(Pixel(1.0).to_physical())
+        .set_color(color);
+    canvas.draw_rrect(rrect, &paint);
+}
+
+/// Fill `rrect` with gray and white squares, shown through translucent colors.
+fn draw_checker(canvas: &mut Canvas, rrect: &RRect) {
+    let rect = *rrect.rect();
+    let size = CHECKER_SIZE.to_physical();
+    let columns = (rect.width() / size).ceil() as usize;
+    let rows = (rect.height() / size).ceil() as usize;
+
+    fill(canvas, rrect, Color::WHITE);
+    canvas.save();
+    canvas.clip_rrect(rrect, None, true);
+
+    let mut paint = Paint::default();
+    paint.set_color(Color::from_rgb(0xcc, 0xcc, 0xcc));
+    for row in 0..rows {
+        for column in (row % 2..columns).step_by(2) {
+            let (x, y) = (column as f32 * size, row as f32 * size);
+            canvas.draw_rect(
+                Rect::from_xywh(rect.left + x, rect.top + y, size, size),
+                &paint,
+            );
+        }
+    }
+    canvas.restore();
+}
+
+/// Fill `rrect` with a gradient from left to right.
+fn fill_gradient(canvas: &mut Canvas, rrect: &RRect, colors: &[Color]) {
+    let rect = rrect.rect();
+    let shader = Shader::linear_gradient(
+        ((rect.left, rect.top), (rect.right, rect.top)),
+        colors,
+        None,
+        TileMode::Clamp,
+        None,
+        None,
+    );
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    if let Some(shader) = shader {
+        paint.set_shader(shader);
+    }
+    canvas.draw_rrect(rrect, &paint);
+}
+
+/// Where `at` is between `from` and `to`, from `0.0` to `1.0`.
+fn ratio(at: Pixel, from: Pixel, to: Pixel) -> f32 {
+    if to <= from {
+        0.0
+    } else {
+        ((at - from).0 / (to - from).0).clamp(0.0, 1.0)
+    }
+}
+
+impl Element for ColorPicker {
+    type BlankProps = ColorPickerProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let parts = self.parts(region);
+        let style = &self.props.style;
+        let text_color = style.color.map_or(Color::BLACK, |color| color.0);
+        let canvas = content.canvas();
+
+        self.draw_square(canvas, parts.square);
+        self.draw_bars(canvas, parts.hue, parts.alpha);
+
+        let swatch = match self.sampled {
+            Some(sampled) if self.picking => Color4f {
+                a: self.hsva.a,
+                ..Color4f::from(sampled)
+            },
+            _ => self.value(),
+        };
+        let swatch_rrect = rounded(parts.swatch, RADIUS);
+        draw_checker(canvas, &swatch_rrect);
+        fill(canvas, &swatch_rrect, swatch.to_color());
+        stroke(canvas, &swatch_rrect, style.border.0);
+
+        let eyedropper_color = if self.picking {
+            style.accent.0
+        } else {
+            text_color
+        };
+        self.draw_eyedropper(canvas, parts.eyedropper, eyedropper_color);
+
+        let font = label_font(self.font_size());
+        for (field, region) in FIELDS.into_iter().zip(parts.fields) {
+            let editing = self
+                .editing
+                .as_ref()
+                .filter(|editing| editing.field == field);
+
+            let rrect = rounded(region, RADIUS);
+            let border = if editing.is_some() {
+                style.accent.0
+            } else {
+                style.border.0
+            };
+            stroke(canvas, &rrect, border);
+
+            let rows = (region.0 .1, region.1 .1);
+            let label_x = region.0 .0 + FIELD_PADDING;
+            draw_label(canvas, &font, field.label(), style.label.0, label_x, rows);
+
+            let text = match editing {
+                Some(editing) => editing.text.clone(),
+                None => field.text(self.hsva),
+            };
+            let x = label_x + label_width(&font, "M") + FIELD_PADDING / 2.0;
+            let width = label_width(&font, &text);
+
+            canvas.save();
+            canvas.clip_rrect(rrect, None, true);
+            if let Some(editing) = editing {
+                let (top, bottom) = (region.0 .1 + FIELD_PADDING, region.1 .1 - FIELD_PADDING);
+                if editing.selected {
+                    let selection = style.accent.0.with_a(0x50);
+                    let rect = to_rect((Point(x, top), Point(x + width, bottom)));
+                    fill(canvas, &RRect::new_rect(rect), selection);
+                } else {
+                    let left = x + width;
+                    let rect = to_rect((Point(left, top), Point(left + CARET_WIDTH, bottom)));
+                    fill(canvas, &RRect::new_rect(rect), text_color);
+                }
+            }
+            draw_label(canvas, &font, &text, text_color, x, rows);
+            canvas.restore();
+        }
+
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for ColorPicker
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        let none = Hsva {
+            h: 0.0,
+            s: 0.0,
+            v: 0.0,
+            a: 1.0,
+        };
+        ColorPicker {
+            hsva: Hsva::from_rgba(*props.value.as_array(), none),
+            props,
+            editing: None,
+            picking: false,
+            sampled: None,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if update_result.unchanged {
+            return true;
+        }
+
+        // a value passed back is the same, and keeps the hue of grays
+        if !update_result.value_unchanged && self.props.value != self.value() {
+            self.hsva = Hsva::from_rgba(*self.props.value.as_array(), self.hsva);
+            if let Some(editing) = &mut self.editing {
+                editing.text = editing.field.text(self.hsva);
+                editing.selected = true;
+            }
+        }
+        false
+    }
+}
+
+impl ColorPicker {
+    fn value(&self) -> Color4f {
+        let [r, g, b, a] = self.hsva.to_rgba();
+        Color4f::new(r, g, b, a)
+    }
+
+    /// The pure color of the hue, fully saturated and bright.
+    fn hue_color(&self) -> Color {
+        let [r, g, b, _] = Hsva {
+            s: 1.0,
+            v: 1.0,
+            a: 1.0,
+            ..self.hsva
+        }
+        .to_rgba();
+        Color4f::new(r, g, b, 1.0).to_color()
+    }
+
+    fn font_size(&self) -> Pixel {
+        self.props
+            .style
+            .font_size
+            .map_or(Pixel(14.0), |size| size.0)
+    }
+
+    fn parts(&self, region: Region) -> Parts {
+        let (Point(left, top), Point(right, bottom)) = region;
+        let row = self.font_size() * ROW_HEIGHT;
+
+        let below_square = BAR_HEIGHT * 2.0 + row * 4.0 + GAP * 6.0;
+        let mut y = (bottom - below_square).max(top);
+        let square = (Point(left, top), Point(right, y));
+
+        let mut next = |height: Pixel| {
+            let band = (y + GAP, y + GAP + height);
+            y = band.1;
+            band
+        };
+        let (hue, alpha, swatch) = (next(BAR_HEIGHT), next(BAR_HEIGHT), next(row));
+        let rows = [next(row), next(row), next(row)];
+
+        let column_width = ((right - left - GAP * 2.0) / 3.0).max(Pixel(0.0));
+        let column_left = |index: usize| left + (column_width + GAP) * index as f32;
+        let fields = FIELDS.map(|field| {
+            let (row, first, last) = field.cell();
+            (
+                Point(column_left(first), rows[row].0),
+                Point(column_left(last) + column_width, rows[row].1),
+            )
+        });
+
+        let band = |(top, bottom): (Pixel, Pixel)| (Point(left, top), Point(right, bottom));
+        let eyedropper_left = (right - row).max(left);
+        Parts {
+            square,
+            hue: band(hue),
+            alpha: band(alpha),
+            swatch: (
+                Point(left, swatch.0),
+                Point((eyedropper_left - GAP).max(left), swatch.1),
+            ),
+            eyedropper: (Point(eyedropper_left, swatch.0), Point(right, swatch.1)),
+            fields,
+        }
+    }
+
+    fn part_at(&self, region: Region, position: Point) -> Option<Part> {
+        let parts = self.parts(region);
+        let field = FIELDS
+            .into_iter()
+            .zip(parts.fields)
+            .find(|(_, region)| region_contains(*region, position));
+
+        if let Some((field, _)) = field {
+            Some(Part::Field(field))
+        } else if region_contains(parts.square, position) {
+            Some(Part::Square)
+        } else if region_contains(parts.hue, position) {
+            Some(Part::Hue)
+        } else if region_contains(parts.alpha, position) {
+            Some(Part::Alpha)
+        } else if region_contains(parts.eyedropper, position) {
+            Some(Part::Eyedropper)
+        } else {
+            None
+        }
+    }
+
+    fn draw_square(&self, canvas: &mut Canvas, square: Region) {
+        let rect = to_rect(square);
+        if rect.is_empty() {
+            return;
+        }
+
+        let rrect = rounded(square, RADIUS);
+        fill(canvas, &rrect, self.hue_color());
+        fill_gradient(canvas, &rrect, &[Color::WHITE, Color::WHITE.with_a(0)]);
+
+        // the same gradient from top to bottom, turned by a quarter
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        let shader = Shader::linear_gradient(
+            ((rect.left, rect.top), (rect.left, rect.bottom)),
+            &[Color::BLACK.with_a(0), Color::BLACK][..],
+            None,
+            TileMode::Clamp,
+            None,
+            None,
+        );
+        if let Some(shader) = shader {
+            paint.set_shader(shader);
+        }
+        canvas.draw_rrect(rrect, &paint);
+
+        let x = rect.left + rect.width() * self.hsva.s;
+        let y = rect.top + rect.height() * (1.0 - self.hsva.v);
+        draw_thumb(canvas, (x, y), self.value().to_opaque().to_color());
+    }
+
+    fn draw_bars(&self, canvas: &mut Canvas, hue: Region, alpha: Region) {
+        let hue_rrect = rounded(hue, BAR_HEIGHT / 2.0);
+        let colors = [
+            0xff0000, 0xffff00, 0x00ff00, 0x00ffff, 0x0000ff, 0xff00ff, 0xff0000,
+        ]
+        .map(|rgb| Color::new(0xff000000 | rgb));
+        fill_gradient(canvas, &hue_rrect, &colors);
+
+        let alpha_rrect = rounded(alpha, BAR_HEIGHT / 2.0);
+        let opaque = self.value().to_opaque().to_color();
+        draw_checker(canvas, &alpha_rrect);
+        fill_gradient(canvas, &alpha_rrect, &[opaque.with_a(0), opaque]);
+
+        // thumbs stay inside the ends of the bars
+        let radius = BAR_HEIGHT / 2.0;
+        let thumb_x = |(start, end): Region, ratio: f32| {
+            let (from, to) = (start.0 + radius, (end.0 - radius).max(start.0 + radius));
+            (from + (to - from) * ratio).to_physical()
+        };
+        let center_y = |(start, end): Region| ((start.1 + end.1) / 2.0).to_physical();
+
+        draw_thumb(
+            canvas,
+            (thumb_x(hue, self.hsva.h / 360.0), center_y(hue)),
+            self.hue_color(),
+        );
+        draw_thumb(
+            canvas,
+            (thumb_x(alpha, self.hsva.a), center_y(alpha)),
+            self.value().to_color(),
+        );
+    }
+
+    fn draw_eyedropper(&self, canvas: &mut Canvas, region: Region, color: Color) {
+        let rrect = rounded(region, RADIUS);
+        let border = if self.picking {
+            color
+        } else {
+            self.props.style.border.0
+        };
+        stroke(canvas, &rrect, border);
+
+        // a pipette leaning to the right, its bulb at the top
+        let rect = rrect.rect();
+        let unit = rect.width().min(rect.height()) / 8.0;
+        let (cx, cy) = (rect.center_x(), rect.center_y());
+        let mut paint = Paint::default();
+        paint
+            .set_anti_alias(true)
+            .set_color(color)
+            .set_stroke_cap(Cap::Round)
+            .set_stroke_width(unit * 0.8);
+        canvas.draw_line(
+            (cx - unit * 2.5, cy + unit * 2.5),
+            (cx + unit, cy - unit),
+            &paint,
+        );
+        canvas.draw_circle((cx + unit * 1.5, cy - unit * 1.5), unit * 1.2, &paint);
+    }
+
+    /// Change the color, and emit [`ColorChanged`] if it changed.
+    fn set_hsva(&mut self, this: &ElModel!(), hsva: Hsva) {
+        if hsva == self.hsva {
+            return;
+        }
+        self.hsva = hsva;
+        this.event_dispatcher().emit(ColorChanged {
+            value: self.value(),
+        });
+    }
+
+    /// Set the color by the pointer at `position` on a square or a slider.
+    fn drag_to(&mut self, this: &ElModel!(), part: Part, position: Point) {
+        let parts = self.parts(this.draw_region());
+        let horizontal = |(start, end): Region| ratio(position.0, start.0, end.0);
+        let hsva = match part {
+            Part::Square => Hsva {
+                s: horizontal(parts.square),
+                v: 1.0 - ratio(position.1, parts.square.0 .1, parts.square.1 .1),
+                ..self.hsva
+            },
+            Part::Hue => Hsva {
+                h: horizontal(parts.hue) * 360.0,
+                ..self.hsva
+            },
+            Part::Alpha => Hsva {
+                a: horizontal(parts.alpha),
+                ..self.hsva
+            },
+            _ => return,
+        };
+        self.set_hsva(this, hsva);
+    }
+
+    /// Handle the left button pressed at `position`, and give which part of
+    /// the picker it is on.
+    fn press(&mut self, this: &ElModel!(), position: Point) -> Option<Part> {
+        let part = self.part_at(this.draw_region(), position);
+        let editing = self.editing.as_ref().map(|editing| editing.field);
+        if part != editing.map(Part::Field) {
+            self.commit(this);
+            self.editing = None;
+        }
+
+        match part {
+            Some(Part::Field(field)) => {
+                this.focus();
+                if editing != Some(field) {
+                    self.edit(field);
+                } else if let Some(editing) = &mut self.editing {
+                    editing.selected = false;
+                }
+            }
+            Some(Part::Eyedropper) => this.focus(),
+            Some(part) => self.drag_to(this, part, position),
+            None => {}
+        }
+        part
+    }
+
+    fn edit(&mut self, field: Field) {
+        self.editing = Some(Editing {
+            field,
+            text: field.text(self.hsva),
+            selected: true,
+        });
+    }
+
+    /// Apply the text of the field being edited if it is valid.
+    fn commit(&mut self, this: &ElModel!()) {
+        let Some(Editing { field, text, .. }) = &self.editing
+        else {
+            return;
+        };
+
+        // an untouched field would round the color
+        if *text == field.text(self.hsva) {
+            return;
+        }
+        if let Some(hsva) = field.apply(text, self.hsva) {
+            self.set_hsva(this, hsva);
+        }
+    }
+
+    /// Set the color to one picked from the window, keeping the alpha.
+    fn pick(&mut self, this: &ElModel!(), color: Color) {
+        let Color4f { r, g, b, .. } = Color4f::from(color);
+        self.set_hsva(this, Hsva::from_rgba([r, g, b, self.hsva.a], self.hsva));
+    }
+
+    fn input(&mut self, this: &ElModel!(), input: Input, modifiers: ModifiersState) {
+        if let Input::Key(VirtualKeyCode::Escape) = input {
+            if self.picking {
+                // ends picking in the pointer runtime
+                this.release_pointer(PointerId::Mouse);
+            } else {
+                self.editing = None;
+            }
+            return;
+        }
+
+        let Some(editing) = &mut self.editing
+        else {
+            return;
+        };
+        let field = editing.field;
+
+        match input {
+            Input::Char(c) => {
+                if editing.selected {
+                    editing.text.clear();
+                    editing.selected = false;
+                }
+                if editing.text.chars().count() < MAX_INPUT {
+                    editing.text.push(c);
+                }
+            }
+            Input::Key(key) => match key {
+                VirtualKeyCode::Back => {
+                    if editing.selected {
+                        editing.text.clear();
+                    } else {
+                        editing.text.pop();
+                    }
+                    editing.selected = false;
+                }
+                VirtualKeyCode::Left
+                | VirtualKeyCode::Right
+                | VirtualKeyCode::Home
+                | VirtualKeyCode::End => editing.selected = false,
+                VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                    self.commit(this);
+                    self.edit(field);
+                }
+                VirtualKeyCode::Tab => {
+                    self.commit(this);
+                    self.edit(field.next(modifiers.shift()));
+                }
+                VirtualKeyCode::Up | VirtualKeyCode::Down => {
+                    let step = if modifiers.shift() { 10.0 } else { 1.0 };
+                    let step = if key == VirtualKeyCode::Down {
+                        -step
+                    } else {
+                        step
+                    };
+
+                    self.commit(this);
+                    if let Some(hsva) = field.nudge(self.hsva, step) {
+                        self.set_hsva(this, hsva);
+                    }
+                    self.edit(field);
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Draw a ring filled with `color` for where the pointer picks.
+fn draw_thumb(canvas: &mut Canvas, center: (f32, f32), color: Color) {
+    let radius = (BAR_HEIGHT / 2.0 + Pixel(1.0)).to_physical();
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(color);
+    canvas.draw_circle(center, radius, &paint);
+
+    paint
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(Pixel(3.0).to_physical())
+        .set_color(Color::WHITE);
+    canvas.draw_circle(center, radius, &paint);
+
+    paint
+        .set_stroke_width(Pixel(1.0).to_physical())
+        .set_color(Color::from_argb(0x50, 0, 0, 0));
+    canvas.draw_circle(center, radius + Pixel(1.5).to_physical(), &paint);
+}
+
+async fn update(this: &ElModel!(ColorPicker), f: impl FnOnce(&mut ColorPicker)) -> bool {
+    let Some(mut picker) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *picker);
+    true
+}
+
+async fn pointer_runtime(this: ElModel!(ColorPicker)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let alive = tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => {
+                if pd.button != MouseButton::Left {
+                    continue;
+                }
+
+                let mut part = None;
+                if !update(&this, |picker| part = picker.press(&this, pd.position)).await {
+                    return;
+                }
+
+                match part {
+                    Some(part @ (Part::Square | Part::Hue | Part::Alpha)) => {
+                        // keep dragging after the pointer leaves the picker
+                        this.capture_pointer(pd.pointer_id);
+                        drag(&this, part, pd.pointer_id).await
+                    }
+                    _ => true,
+                }
+            }
+            click = ed.recv_trusted::<Click>() => {
+                let on_eyedropper = click.button == MouseButton::Left
+                    && click.pointer_id == PointerId::Mouse
+                    && matches!(
+                        this.el_read().await.map(|picker| {
+                            picker.part_at(this.draw_region(), click.position)
+                        }),
+                        Some(Some(Part::Eyedropper))
+                    );
+
+                if on_eyedropper {
+                    pick_from_window(&this).await
+                } else {
+                    true
+                }
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
+
+/// Drag `part` with the pointer until it is released, and give whether
+/// the picker is alive.
+async fn drag(this: &ElModel!(ColorPicker), part: Part, pointer_id: PointerId) -> bool {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        tokio::select! {
+            pm = ed.recv_trusted::<PointerMove>() => {
+                if pm.pointer_id != pointer_id {
+                    continue;
+                }
+                if !update(this, |picker| picker.drag_to(this, part, pm.position)).await {
+                    return false;
+                }
+            }
+            pu = ed.recv_trusted::<PointerUp>() => {
+                if pu.pointer_id == pointer_id {
+                    return true;
+                }
+            }
+            pcl = ed.recv_trusted::<PointerCaptureLost>() => {
+                if pcl.pointer_id == pointer_id {
+                    return true;
+                }
+            }
+        }
+    }
+}
+
+/// Let the mouse pick a color from the window until it is pressed, and
+/// give whether the picker is alive.
+async fn pick_from_window(this: &ElModel!(ColorPicker)) -> bool {
+    let ed = this.event_dispatcher().clone();
+    if !update(this, |picker| picker.picking = true).await {
+        return false;
+    }
+
+    // the mouse moving over other elements comes here too
+    this.capture_pointer(PointerId::Mouse);
+
+    // the capture is lost once Escape pressed or the mouse leaves the window
+    while this.has_pointer_capture(PointerId::Mouse) {
+        tokio::select! {
+            pm = ed.recv_trusted::<PointerMove>() => {
+                if pm.pointer_id != PointerId::Mouse {
+                    continue;
+                }
+                let sampled = this.global().sample_frame(pm.position).await;
+                if !update(this, |picker| picker.sampled = sampled).await {
+                    return false;
+                }
+            }
+            pd = ed.recv_trusted::<PointerDown>() => {
+                if pd.pointer_id != PointerId::Mouse {
+                    continue;
+                }
+                if pd.button == MouseButton::Left {
+                    let sampled = this.global().sample_frame(pd.position).await;
+                    let picked = update(this, |picker| {
+                        if let Some(color) = sampled {
+                            picker.pick(this, color);
+                        }
+                    });
+                    if !picked.await {
+                        return false;
+                    }
+                }
+                break;
+            }
+            pcl = ed.recv_trusted::<PointerCaptureLost>() => {
+                if pcl.pointer_id == PointerId::Mouse {
+                    break;
+                }
+            }
+        }
+    }
+
+    this.release_pointer(PointerId::Mouse);
+    update(this, |picker| {
+        picker.picking = false;
+        picker.sampled = None;
+    })
+    .await
+}
+
+async fn keyboard_runtime(this: ElModel!(ColorPicker)) {
+    let ed = this.event_dispatcher().clone();
+    let mut modifiers = ModifiersState::empty();
+
+    loop {
+        ed.recv_trusted::<Focused>().await;
+
+        loop {
+            let input = tokio::select! {
+                event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                    StaticWindowEvent::ModifiersChanged(state) => {
+                        modifiers = state;
+                        continue;
+                    }
+                    StaticWindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => Input::Key(key),
+                    StaticWindowEvent::ReceivedCharacter(c) if !c.is_control() => Input::Char(c),
+                    _ => continue,
+                },
+                _ = ed.recv_trusted::<Blured>() => break,
+            };
+
+            if !update(&this, |picker| picker.input(&this, input, modifiers)).await {
+                return;
+            }
+        }
+
+        let left = update(&this, |picker| {
+            picker.commit(&this);
+            picker.editing = None;
+        });
+        if !left.await {
+            return;
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Color of the borders of the fields, the swatch and the eyedropper.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleColorPickerBorder(
+    #[style(default = "Color::from_rgb(0xc8, 0xc8, 0xc8)")] pub Color,
+);
+
+/// Color of the border of the field being edited, the selected text and
+/// the eyedropper while picking.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleColorPickerAccent(
+    #[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color,
+);
+
+/// Color of the labels in front of the fields.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleColorPickerLabel(#[style(default = "Color::from_rgb(0x9e, 0x9e, 0x9e)")] pub Color);
@@ -0,0 +1,420 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Click, PointerMove, PointerOut, Wheel, WheelDelta},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Canvas, Color, Font, Paint, PaintStyle, Path},
+    winit::event::MouseButton,
+    ElModel, Event,
+};
+
+use crate::{
+    label::{draw_label, label_font, label_width, to_rect},
+    popup::{draw_surface, region_contains, PortalOwner},
+};
+
+use super::{
+    date::{Date, Weekday},
+    locale::DateLocale,
+};
+
+const CELL: Pixel = Pixel(32.0);
+const PADDING: Pixel = Pixel(8.0);
+const HEADER_HEIGHT: Pixel = Pixel(36.0);
+const WEEKDAYS_HEIGHT: Pixel = Pixel(24.0);
+const ARROW_WIDTH: Pixel = Pixel(28.0);
+/// Weeks shown, enough for any month
+const ROWS: usize = 6;
+
+/// Colors and font size shared by the date box and its calendar.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) struct DateLook {
+    pub font_size: Pixel,
+    pub color: Color,
+    pub background: Color,
+    pub radius: Pixel,
+    pub border: Color,
+    pub accent: Color,
+    pub highlight: Color,
+}
+
+/// The pointer moved onto a day, or off the days if `date` is `None`.
+#[derive(Event, Clone, Copy)]
+pub(super) struct CalendarHover {
+    pub date: Option<Date>,
+}
+
+/// A day was clicked.
+#[derive(Event, Clone, Copy)]
+pub(super) struct CalendarPick {
+    pub date: Date,
+}
+
+/// An arrow was clicked or the wheel turned, to show the month `months`
+/// months later.
+#[derive(Event, Clone, Copy)]
+pub(super) struct CalendarNavigate {
+    pub months: i32,
+}
+
+/// Size of the calendar.
+pub(super) fn calendar_size() -> (Pixel, Pixel) {
+    (
+        CELL * 7.0 + PADDING * 2.0,
+        HEADER_HEIGHT + WEEKDAYS_HEIGHT + CELL * ROWS as f32 + PADDING * 2.0,
+    )
+}
+
+/// Whether `date` is between `min` and `max` if given.
+pub(super) fn within(date: Date, min: Option<Date>, max: Option<Date>) -> bool {
+    min.map_or(true, |min| date >= min) && max.map_or(true, |max| date <= max)
+}
+
+/// Whether any day of the month starting at `month` is within `min` and `max`.
+pub(super) fn month_within(month: Date, min: Option<Date>, max: Option<Date>) -> bool {
+    let last = month.add_months(1).add_days(-1);
+    min.map_or(true, |min| last >= min) && max.map_or(true, |max| month <= max)
+}
+
+/// The day in the top left cell of the month starting at `month`.
+pub(super) fn first_cell(month: Date, week_start: Weekday) -> Date {
+    let offset = (month.weekday().index() + 7 - week_start.index()) % 7;
+    month.add_days(-(offset as i64))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Target {
+    Day(Date),
+    Navigate(i32),
+}
+
+/// Days of a month in a grid of weeks, mounted in the portal of the owner.
+pub(super) struct Calendar {
+    props: OwnedProps,
+    font: Font,
+}
+
+#[irisia::props(updater = "CalendarProps", watch)]
+pub(super) struct OwnedProps {
+    /// First day of the month shown
+    #[props(must_init)]
+    month: Date,
+
+    /// Selected days, both ends included
+    #[props(default)]
+    selection: Option<(Date, Date)>,
+
+    /// Day of the keyboard or the pointer
+    #[props(default)]
+    highlighted: Option<Date>,
+
+    #[props(must_init)]
+    today: Date,
+
+    #[props(default)]
+    min: Option<Date>,
+
+    #[props(default)]
+    max: Option<Date>,
+
+    #[props(must_init)]
+    week_start: Weekday,
+
+    #[props(must_init)]
+    locale: DateLocale,
+
+    /// Receives [`CalendarHover`], [`CalendarPick`] and [`CalendarNavigate`]
+    #[props(must_init)]
+    owner: PortalOwner,
+
+    #[props(must_init)]
+    look: DateLook,
+}
+
+impl Element for Calendar {
+    type BlankProps = CalendarProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let OwnedProps {
+            month,
+            selection,
+            highlighted,
+            today,
+            min,
+            max,
+            week_start,
+            ref locale,
+            look,
+            ..
+        } = self.props;
+        let canvas = content.canvas();
+        draw_surface(canvas, region, look.background, look.radius, true);
+
+        let (left, top) = (region.0 .0 + PADDING, region.0 .1 + PADDING);
+        let header = (top, top + HEADER_HEIGHT);
+        let title = locale.title(month.year(), month.month());
+        let title_x = (region.0 .0 + region.1 .0 - label_width(&self.font, &title)) / 2.0;
+        draw_label(canvas, &self.font, &title, look.color, title_x, header);
+
+        for (months, button) in nav_buttons(region) {
+            let enabled = month_within(month.add_months(months), min, max);
+            let color = if enabled {
+                look.color
+            } else {
+                look.color.with_a(0x40)
+            };
+            draw_chevrons(canvas, button, months, color);
+        }
+
+        let weekdays = (header.1, header.1 + WEEKDAYS_HEIGHT);
+        for column in 0..7 {
+            let name = locale.weekday(week_start.add(column).index());
+            let center = left + CELL * (column as f32 + 0.5);
+            let x = center - label_width(&self.font, name) / 2.0;
+            draw_label(
+                canvas,
+                &self.font,
+                name,
+                look.color.with_a(0x90),
+                x,
+                weekdays,
+            );
+        }
+
+        let first = first_cell(month, week_start);
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        for index in 0..ROWS * 7 {
+            let date = first.add_days(index as i64);
+            let cell = cell_region(region, index);
+            let (cell_top, cell_bottom) = (cell.0 .1, cell.1 .1);
+            let center = Point(
+                (cell.0 .0 + cell.1 .0) / 2.0,
+                (cell_top + cell_bottom) / 2.0,
+            );
+            let radius = CELL / 2.0 - Pixel(2.0);
+
+            let selected_end = selection.is_some_and(|(start, end)| date == start || date == end);
+            if let Some((start, end)) = selection.filter(|(start, end)| start < end) {
+                if date >= start && date <= end {
+                    // a band joining the days of the range, cut at the ends
+                    let from = if date == start { center.0 } else { cell.0 .0 };
+                    let to = if date == end { center.0 } else { cell.1 .0 };
+                    paint.set_color(look.highlight);
+                    canvas.draw_rect(
+                        to_rect((Point(from, center.1 - radius), Point(to, center.1 + radius))),
+                        &paint,
+                    );
+                }
+            }
+
+            if selected_end {
+                paint.set_color(look.accent);
+                canvas.draw_circle(center, radius.to_physical(), &paint);
+            } else if highlighted == Some(date) {
+                paint.set_color(look.highlight);
+                canvas.draw_circle(center, radius.to_physical(), &paint);
+            }
+
+            if highlighted == Some(date) {
+                let mut ring = Paint::default();
+                ring.set_anti_alias(true)
+                    .set_style(PaintStyle::Stroke)
+                    .set_stroke_width(Pixel(1.5).to_physical())
+                    .set_color(look.accent);
+                canvas.draw_circle(center, radius.to_physical(), &ring);
+            }
+
+            let color = if selected_end {
+                Color::WHITE
+            } else if date == today {
+                look.accent
+            } else {
+                look.color
+            };
+            let color = if !within(date, min, max) {
+                color.with_a(0x40)
+            } else if date.month() != month.month() {
+                color.with_a(0x80)
+            } else {
+                color
+            };
+
+            let text = date.day().to_string();
+            let x = center.0 - label_width(&self.font, &text) / 2.0;
+            draw_label(canvas, &self.font, &text, color, x, (cell_top, cell_bottom));
+        }
+
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Calendar
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.consume_wheel(true);
+        this.daemon(pointer_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        Calendar {
+            font: label_font(props.look.font_size),
+            props,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let font_size = self.props.look.font_size;
+        let unchanged = self.props.props_update_with(props).unchanged;
+        if self.props.look.font_size != font_size {
+            self.font = label_font(self.props.look.font_size);
+        }
+        unchanged
+    }
+}
+
+impl Calendar {
+    /// Enabled day or arrow at `point`.
+    fn target_at(&self, region: Region, point: Point) -> Option<Target> {
+        let OwnedProps {
+            month, min, max, ..
+        } = self.props;
+
+        if let Some((months, _)) = nav_buttons(region)
+            .into_iter()
+            .find(|(_, button)| region_contains(*button, point))
+        {
+            return month_within(month.add_months(months), min, max)
+                .then_some(Target::Navigate(months));
+        }
+
+        let date = first_cell(month, self.props.week_start);
+        (0..ROWS * 7)
+            .find(|&index| region_contains(cell_region(region, index), point))
+            .map(|index| date.add_days(index as i64))
+            .filter(|&date| within(date, min, max))
+            .map(Target::Day)
+    }
+}
+
+/// Arrows to the year and the month before on the left, and to the month
+/// and the year after on the right.
+fn nav_buttons(region: Region) -> [(i32, Region); 4] {
+    let top = region.0 .1 + PADDING;
+    let bottom = top + HEADER_HEIGHT;
+    let button = |left: Pixel| (Point(left, top), Point(left + ARROW_WIDTH, bottom));
+    let (left, right) = (region.0 .0 + PADDING, region.1 .0 - PADDING);
+    [
+        (-12, button(left)),
+        (-1, button(left + ARROW_WIDTH)),
+        (1, button(right - ARROW_WIDTH * 2.0)),
+        (12, button(right - ARROW_WIDTH)),
+    ]
+}
+
+fn cell_region(region: Region, index: usize) -> Region {
+    let (row, column) = (index / 7, index % 7);
+    let left = region.0 .0 + PADDING + CELL * column as f32;
+    let top = region.0 .1 + PADDING + HEADER_HEIGHT + WEEKDAYS_HEIGHT + CELL * row as f32;
+    (Point(left, top), Point(left + CELL, top + CELL))
+}
+
+/// One chevron for a month, two for a year, pointing backward if `months`
+/// is negative.
+fn draw_chevrons(canvas: &mut Canvas, button: Region, months: i32, color: Color) {
+    let center = Point(
+        (button.0 .0 + button.1 .0) / 2.0,
+        (button.0 .1 + button.1 .1) / 2.0,
+    );
+    let dx = if months < 0 { Pixel(-2.5) } else { Pixel(2.5) };
+    let offsets: &[f32] = if months.abs() >= 12 {
+        &[-3.0, 3.0]
+    } else {
+        &[0.0]
+    };
+
+    let mut path = Path::new();
+    for &offset in offsets {
+        let x = center.0 + Pixel(offset);
+        path.move_to(Point(x - dx, center.1 - Pixel(5.0)))
+            .line_to(Point(x + dx, center.1))
+            .line_to(Point(x - dx, center.1 + Pixel(5.0)));
+    }
+
+    let mut paint = Paint::default();
+    paint
+        .set_anti_alias(true)
+        .set_color(color)
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(Pixel(1.5).to_physical());
+    canvas.draw_path(&path, &paint);
+}
+
+async fn pointer_runtime(this: ElModel!(Calendar)) {
+    let ed = this.event_dispatcher().clone();
+    let mut hovered = None;
+    // pixels scrolled by a touchpad toward the next month
+    let mut wheel_pixels = Pixel(0.0);
+
+    loop {
+        let (position, clicked) = tokio::select! {
+            pm = ed.recv_trusted::<PointerMove>() => (Some(pm.position), false),
+            click = ed.recv_trusted::<Click>() => {
+                if click.button != MouseButton::Left {
+                    continue;
+                }
+                (Some(click.position), true)
+            }
+            _ = ed.recv_trusted::<PointerOut>() => (None, false),
+            wheel = ed.recv_trusted::<Wheel>() => {
+                let months = match wheel.delta {
+                    WheelDelta::Line(_, y) if y != 0.0 => -y.signum() as i32,
+                    WheelDelta::Pixel(_, y) => {
+                        wheel_pixels = wheel_pixels - y;
+                        if wheel_pixels.0.abs() < CELL.0 {
+                            continue;
+                        }
+                        let months = wheel_pixels.0.signum() as i32;
+                        wheel_pixels = Pixel(0.0);
+                        months
+                    }
+                    _ => continue,
+                };
+
+                let Some(calendar) = this.el_read().await
+                else {
+                    return;
+                };
+                calendar.props.owner.0.emit(CalendarNavigate { months });
+                continue;
+            }
+        };
+
+        let Some(calendar) = this.el_read().await
+        else {
+            return;
+        };
+
+        let target = position.and_then(|point| calendar.target_at(this.draw_region(), point));
+        let owner = &calendar.props.owner;
+        if clicked {
+            match target {
+                Some(Target::Day(date)) => owner.0.emit(CalendarPick { date }),
+                Some(Target::Navigate(months)) => owner.0.emit(CalendarNavigate { months }),
+                None => {}
+            }
+        } else {
+            let day = match target {
+                Some(Target::Day(date)) => Some(date),
+                _ => None,
+            };
+            if day != hovered {
+                hovered = day;
+                owner.0.emit(CalendarHover { date: day });
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::civil::{civil_from_days, days_from_civil};
+
+/// A day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// Days since Monday.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The day `days` days later.
+    pub fn add(self, days: usize) -> Weekday {
+        Self::ALL[(self.index() + days) % 7]
+    }
+}
+
+/// A day of the Gregorian calendar, extended to the years before it.
+/// Dates are ordered by time, and shown as `2024-03-05`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Returns `None` if the month is not in `1..=12` or the day is not
+    /// in the month.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        ((1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month)).then_some(
+            Date {
+                year,
+                month: month as u8,
+                day: day as u8,
+            },
+        )
+    }
+
+    /// The current day in UTC.
+    pub fn today_utc() -> Date {
+        let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() as i64,
+            Err(before) => -(before.duration().as_secs() as i64),
+        };
+        Date::from_days(seconds.div_euclid(86400))
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// From 1 for January to 12 for December.
+    pub fn month(self) -> u32 {
+        self.month as u32
+    }
+
+    pub fn day(self) -> u32 {
+        self.day as u32
+    }
+
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        Weekday::Thursday.add(self.days().rem_euclid(7) as usize)
+    }
+
+    pub fn add_days(self, days: i64) -> Date {
+        Date::from_days(self.days() + days)
+    }
+
+    /// The same day `months` months later, or the last day of that month
+    /// if it is shorter.
+    pub fn add_months(self, months: i32) -> Date {
+        let index = self.year as i64 * 12 + self.month as i64 - 1 + months as i64;
+        let (year, month) = (index.div_euclid(12) as i32, index.rem_euclid(12) as u32 + 1);
+        Date {
+            year,
+            month: month as u8,
+            day: self.day.min(days_in_month(year, month) as u8),
+        }
+    }
+
+    pub fn first_of_month(self) -> Date {
+        Date { day: 1, ..self }
+    }
+
+    /// Days since 1970-01-01.
+    fn days(self) -> i64 {
+        days_from_civil(self.year as i64, self.month as u32, self.day as u32)
+    }
+
+    fn from_days(days: i64) -> Date {
+        let (year, month, day) = civil_from_days(days);
+        Date {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Days in `month` of `year`, `0` if the month is not in `1..=12`.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+fn date(year: i32, month: u32, day: u32) -> Date {
+    Date::new(year, month, day).unwrap()
+}
+
+#[test]
+fn test_round_trip_month_ends() {
+    for year in [1969, 2023, 2024] {
+        for month in 1..=12 {
+            let last = date(year, month, days_in_month(year, month));
+            let next = last.add_days(1);
+            assert_eq!(next, last.add_months(1).first_of_month());
+            assert_eq!(next.day(), 1);
+            assert_eq!(next.add_days(-1), last);
+        }
+    }
+
+    let mut day = date(1899, 12, 25);
+    for _ in 0..200_000 {
+        let next = day.add_days(1);
+        assert!(next > day);
+        assert_eq!(Date::from_days(next.days()), next);
+        assert_eq!(next.days() - day.days(), 1);
+        day = next;
+    }
+}
+
+#[test]
+fn test_leap_years() {
+    assert!(is_leap_year(2024) && is_leap_year(2000) && is_leap_year(0));
+    assert!(!is_leap_year(2023) && !is_leap_year(1900) && !is_leap_year(2100));
+
+    assert_eq!(Date::new(2024, 2, 29).map(Date::day), Some(29));
+    assert_eq!(Date::new(2023, 2, 29), None);
+    assert_eq!(Date::new(1900, 2, 29), None);
+    assert_eq!(date(2024, 2, 28).add_days(1), date(2024, 2, 29));
+    assert_eq!(date(2023, 2, 28).add_days(1), date(2023, 3, 1));
+    assert_eq!(date(2000, 3, 1).add_days(-1), date(2000, 2, 29));
+    assert_eq!(date(2024, 12, 31).add_days(-365), date(2024, 1, 1));
+
+    // the day is kept within shorter months
+    assert_eq!(date(2024, 1, 31).add_months(1), date(2024, 2, 29));
+    assert_eq!(date(2024, 2, 29).add_months(12), date(2025, 2, 28));
+    assert_eq!(date(2024, 3, 31).add_months(-13), date(2023, 2, 28));
+}
+
+#[test]
+fn test_bounds() {
+    assert_eq!(Date::new(2024, 0, 1), None);
+    assert_eq!(Date::new(2024, 13, 1), None);
+    assert_eq!(Date::new(2024, 1, 0), None);
+    assert_eq!(Date::new(2024, 4, 31), None);
+    assert_eq!(days_in_month(2024, 13), 0);
+
+    assert_eq!(date(1970, 1, 1).days(), 0);
+    assert_eq!(date(1970, 1, 1).weekday(), Weekday::Thursday);
+    assert_eq!(date(1969, 12, 31).weekday(), Weekday::Wednesday);
+    assert_eq!(date(2024, 3, 5).weekday(), Weekday::Tuesday);
+    assert_eq!(date(-1, 12, 31).add_days(1), date(0, 1, 1));
+    assert_eq!(date(0, 1, 1).add_months(-1), date(-1, 12, 1));
+
+    for year in [-400_000, 400_000] {
+        let first = date(year, 1, 1);
+        assert_eq!(Date::from_days(first.days()), first);
+        assert_eq!(first.add_days(-1), date(year - 1, 12, 31));
+    }
+    assert_eq!(date(2024, 3, 5).to_string(), "2024-03-05");
+}
+
+#[test]
+fn test_min_max() {
+    use super::calendar::{month_within, within};
+
+    let (min, max) = (date(2024, 1, 31), date(2024, 3, 1));
+    assert!(within(min, Some(min), Some(max)));
+    assert!(within(max, Some(min), Some(max)));
+    assert!(!within(min.add_days(-1), Some(min), None));
+    assert!(!within(max.add_days(1), None, Some(max)));
+    assert!(within(date(1, 1, 1), None, None));
+
+    // a month is within the bounds if any of its days is
+    assert!(month_within(date(2024, 1, 1), Some(min), Some(max)));
+    assert!(month_within(date(2024, 3, 1), Some(min), Some(max)));
+    assert!(!month_within(date(2023, 12, 1), Some(min), Some(max)));
+    assert!(!month_within(date(2024, 4, 1), Some(min), Some(max)));
+    assert!(month_within(
+        date(2024, 2, 1),
+        Some(date(2024, 2, 29)),
+        None
+    ));
+    assert!(!month_within(
+        date(2024, 2, 1),
+        Some(date(2024, 3, 1)),
+        None
+    ));
+}
@@ -0,0 +1,180 @@
+/// Names of the months and the weekdays shown by a [`DatePicker`], how the
+/// month and the year are put together above the calendar, and the names
+/// of the halves of a day shown by a 12-hour [`TimePicker`].
+///
+/// ```ignore
+/// DateLocale::new(
+///     ["tammikuu", "helmikuu", /* ... */ "joulukuu"],
+///     ["ma", "ti", "ke", "to", "pe", "la", "su"],
+/// )
+/// ```
+///
+/// [`DatePicker`]: super::DatePicker
+/// [`TimePicker`]: crate::time_picker::TimePicker
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateLocale {
+    months: [String; 12],
+    weekdays: [String; 7],
+    /// Follows the year, like `年`, if the year comes first
+    year_first: Option<String>,
+    /// Before and after noon
+    periods: [String; 2],
+}
+
+impl Default for DateLocale {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl DateLocale {
+    /// `months` from January, and short `weekdays` from Monday.
+    pub fn new(months: [&str; 12], weekdays: [&str; 7]) -> Self {
+        DateLocale {
+            months: months.map(String::from),
+            weekdays: weekdays.map(String::from),
+            year_first: None,
+            periods: ["AM", "PM"].map(String::from),
+        }
+    }
+
+    /// Show the year before the month, followed by `suffix`, as in
+    /// `2024年3月`.
+    pub fn year_first(mut self, suffix: &str) -> Self {
+        self.year_first = Some(suffix.to_string());
+        self
+    }
+
+    /// Names of the times before and after noon, `AM` and `PM` by default.
+    pub fn periods(mut self, before_noon: &str, after_noon: &str) -> Self {
+        self.periods = [before_noon, after_noon].map(String::from);
+        self
+    }
+
+    pub fn english() -> Self {
+        Self::new(
+            [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+            ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"],
+        )
+    }
+
+    pub fn german() -> Self {
+        Self::new(
+            [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+        )
+    }
+
+    pub fn french() -> Self {
+        Self::new(
+            [
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+            ["lu", "ma", "me", "je", "ve", "sa", "di"],
+        )
+    }
+
+    pub fn spanish() -> Self {
+        Self::new(
+            [
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+            ["lu", "ma", "mi", "ju", "vi", "sá", "do"],
+        )
+    }
+
+    pub fn chinese() -> Self {
+        Self::new(
+            [
+                "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月",
+                "12月",
+            ],
+            ["一", "二", "三", "四", "五", "六", "日"],
+        )
+        .year_first("年")
+        .periods("上午", "下午")
+    }
+
+    pub fn japanese() -> Self {
+        Self::new(
+            [
+                "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月",
+                "12月",
+            ],
+            ["月", "火", "水", "木", "金", "土", "日"],
+        )
+        .year_first("年")
+        .periods("午前", "午後")
+    }
+
+    /// Name of `month`, from 1 for January.
+    pub fn month(&self, month: u32) -> &str {
+        &self.months[(month as usize).clamp(1, 12) - 1]
+    }
+
+    /// Short name of the weekday `index` days after Monday.
+    pub(super) fn weekday(&self, index: usize) -> &str {
+        &self.weekdays[index % 7]
+    }
+
+    /// Name of the half of the day, before noon if `after_noon` is false.
+    pub fn period(&self, after_noon: bool) -> &str {
+        &self.periods[after_noon as usize]
+    }
+
+    /// The month and the year above the calendar.
+    pub(super) fn title(&self, year: i32, month: u32) -> String {
+        match &self.year_first {
+            Some(suffix) => format!("{year}{suffix}{}", self.month(month)),
+            None => format!("{} {year}", self.month(month)),
+        }
+    }
+}
@@ -0,0 +1,602 @@
+use irisia::{
+    build,
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Blured, Focused, PointerDown},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Color, Font, Paint, PaintStyle, Path, RRect},
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, ModifiersState, MouseButton, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{
+    label::{draw_label, label_font, to_rect},
+    popup::{
+        dismiss_runtime, place, region_contains,
+        styles::{StylePopupBackground, StylePopupGap, StylePopupRadius},
+        window_region, z_index, Align, Dismiss, PortalOwner, Side,
+    },
+    textbox::styles::StyleFontSize,
+};
+
+use self::{
+    calendar::{
+        calendar_size, month_within, within, Calendar, CalendarHover, CalendarNavigate,
+        CalendarPick, DateLook,
+    },
+    styles::{StyleDateAccent, StyleDateBorder, StyleDateHighlight},
+};
+
+pub use self::{
+    date::{days_in_month, is_leap_year, Date, Weekday},
+    locale::DateLocale,
+};
+
+mod calendar;
+mod date;
+mod locale;
+pub mod styles;
+
+const FIELD_PADDING: Pixel = Pixel(10.0);
+const ICON_COLUMN: Pixel = Pixel(28.0);
+
+/// A box showing the selected day, which opens a calendar of a month to
+/// pick a day from when clicked.
+///
+/// ```ignore
+/// DatePicker {
+///     value: meeting.day,
+///     min: Date::new(2024, 1, 1),
+///     week_start: Weekday::Sunday,
+///     locale: DateLocale::german(),
+/// }
+/// ```
+///
+/// The selection is controlled: [`DateChanged`] is emitted when the user
+/// changes it, and the owner is expected to pass the new selection back
+/// through `value`, and `end` if `range`. In a range, the first day picked
+/// is one end and the second day the other, with the days between
+/// highlighted while the pointer or the keyboard moves to the second.
+///
+/// The arrows above the calendar and the wheel show other months and
+/// years. While opened, the arrow keys move the highlighted day by a day
+/// or a week, Home and End to the ends of the week, Page Up and Page Down
+/// by a month, or by a year with Shift, and Enter or Space picks it. While
+/// closed, Delete clears the selection.
+pub struct DatePicker {
+    props: OwnedProps,
+    font: Font,
+    /// Selected days, both ends included, the same day twice unless `range`
+    value: Option<(Date, Date)>,
+    open: bool,
+    focused: bool,
+    /// First day of the month shown
+    month: Date,
+    /// Day of the keyboard or the pointer
+    highlighted: Option<Date>,
+    /// The day picked first while picking a range
+    anchor: Option<Date>,
+}
+
+/// Emitted on the date picker's event dispatcher whenever the user changes
+/// the selection.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct DateChanged {
+    /// The selected day, or the first day of the selected range.
+    pub value: Option<Date>,
+    /// The last day of the selected range if `range`, `None` otherwise.
+    pub end: Option<Date>,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct DatePickerStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: StylePopupBackground,
+    radius: StylePopupRadius,
+    gap: StylePopupGap,
+    border: StyleDateBorder,
+    accent: StyleDateAccent,
+    highlight: StyleDateHighlight,
+}
+
+#[irisia::props(updater = "DatePickerProps", watch)]
+pub struct OwnedProps {
+    /// The selected day, or the first day of the selected range.
+    #[props(default, watch)]
+    value: Option<Date>,
+
+    /// The last day of the selected range, if `range`.
+    #[props(default, watch)]
+    end: Option<Date>,
+
+    /// Select a range of days by picking both ends.
+    #[props(default, watch)]
+    range: bool,
+
+    /// Days before `min` cannot be picked.
+    #[props(default)]
+    min: Option<Date>,
+
+    /// Days after `max` cannot be picked.
+    #[props(default)]
+    max: Option<Date>,
+
+    /// The day of the leftmost column of the calendar.
+    #[props(default = "Weekday::Monday")]
+    week_start: Weekday,
+
+    /// Names of the months and the weekdays, English by default.
+    #[props(default)]
+    locale: DateLocale,
+
+    /// Shown when nothing is selected.
+    #[props(updated, default)]
+    placeholder: String,
+
+    #[props(read_style(stdin))]
+    style: DatePickerStyles,
+}
+
+impl Element for DatePicker {
+    type BlankProps = DatePickerProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let look = self.look();
+        let canvas = content.canvas();
+        let radius = Pixel(4.0).to_physical();
+        let rrect = RRect::new_rect_xy(to_rect(region), radius, radius);
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_color(look.background);
+        canvas.draw_rrect(rrect, &paint);
+
+        paint
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(Pixel(1.0).to_physical())
+            .set_color(if self.focused {
+                look.accent
+            } else {
+                look.border
+            });
+        canvas.draw_rrect(rrect, &paint);
+
+        let text_region = (
+            Point(region.0 .0 + FIELD_PADDING, region.0 .1),
+            Point(region.1 .0 - ICON_COLUMN, region.1 .1),
+        );
+        let x = text_region.0 .0;
+        let rows = (region.0 .1, region.1 .1);
+
+        canvas.save();
+        canvas.clip_rect(to_rect(text_region), None, true);
+        let summary = self.summary();
+        if summary.is_empty() {
+            let placeholder = &self.props.placeholder;
+            draw_label(
+                canvas,
+                &self.font,
+                placeholder,
+                look.color.with_a(0x80),
+                x,
+                rows,
+            );
+        } else {
+            draw_label(canvas, &self.font, &summary, look.color, x, rows);
+        }
+        canvas.restore();
+
+        // a sheet of calendar with two rings on the top
+        let icon_x = region.1 .0 - ICON_COLUMN / 2.0;
+        let center = (region.0 .1 + region.1 .1) / 2.0;
+        let sheet = (
+            Point(icon_x - Pixel(6.0), center - Pixel(5.0)),
+            Point(icon_x + Pixel(6.0), center + Pixel(6.0)),
+        );
+        let mut path = Path::new();
+        path.add_rrect(
+            RRect::new_rect_xy(to_rect(sheet), radius / 2.0, radius / 2.0),
+            None,
+        )
+        .move_to(Point(icon_x - Pixel(6.0), center - Pixel(1.5)))
+        .line_to(Point(icon_x + Pixel(6.0), center - Pixel(1.5)));
+        for ring_x in [icon_x - Pixel(3.0), icon_x + Pixel(3.0)] {
+            path.move_to(Point(ring_x, center - Pixel(7.0)))
+                .line_to(Point(ring_x, center - Pixel(3.5)));
+        }
+
+        let mut stroke = Paint::default();
+        stroke
+            .set_anti_alias(true)
+            .set_color(look.color.with_a(0xa0))
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(Pixel(1.5).to_physical());
+        canvas.draw_path(&path, &stroke);
+        Ok(())
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        if self.open {
+            self.show(this);
+        }
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for DatePicker
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+        this.daemon(dismiss_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        let mut date_picker = DatePicker {
+            font: label_font(props.style.font_size.map_or(Pixel(14.0), |size| size.0)),
+            props,
+            value: None,
+            open: false,
+            focused: false,
+            month: Date::today_utc().first_of_month(),
+            highlighted: None,
+            anchor: None,
+        };
+        date_picker.sync_with_props();
+        date_picker
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if update_result.unchanged {
+            return true;
+        }
+
+        self.font = label_font(self.look().font_size);
+        if !(update_result.value_unchanged
+            && update_result.end_unchanged
+            && update_result.range_unchanged)
+        {
+            self.sync_with_props();
+            self.anchor = None;
+        }
+        if self.open {
+            self.show(this);
+        }
+        false
+    }
+}
+
+impl DatePicker {
+    fn sync_with_props(&mut self) {
+        let OwnedProps {
+            value, end, range, ..
+        } = self.props;
+
+        self.value = value.map(|start| {
+            let end = if range { end.unwrap_or(start) } else { start };
+            (start.min(end), start.max(end))
+        });
+    }
+
+    fn look(&self) -> DateLook {
+        let style = &self.props.style;
+        DateLook {
+            font_size: style.font_size.map_or(Pixel(14.0), |size| size.0),
+            color: style.color.map_or(Color::BLACK, |color| color.0),
+            background: style.background.0,
+            radius: style.radius.0,
+            border: style.border.0,
+            accent: style.accent.0,
+            highlight: style.highlight.0,
+        }
+    }
+
+    fn summary(&self) -> String {
+        match self.value {
+            Some((start, end)) if self.props.range => format!("{start} – {end}"),
+            Some((day, _)) => day.to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// Days shown as selected, including the range being picked.
+    fn selection(&self) -> Option<(Date, Date)> {
+        match self.anchor {
+            Some(anchor) => {
+                let other = self.highlighted.unwrap_or(anchor);
+                Some((anchor.min(other), anchor.max(other)))
+            }
+            None => self.value,
+        }
+    }
+
+    /// The nearest day within `min` and `max`.
+    fn clamp(&self, date: Date) -> Date {
+        let date = self.props.min.map_or(date, |min| date.max(min));
+        self.props.max.map_or(date, |max| date.min(max))
+    }
+
+    fn changed_event(&self) -> DateChanged {
+        DateChanged {
+            value: self.value.map(|(start, _)| start),
+            end: self.value.filter(|_| self.props.range).map(|(_, end)| end),
+        }
+    }
+
+    fn open(&mut self, this: &ElModel!()) {
+        if self.open {
+            return;
+        }
+
+        let day = self.value.map_or_else(Date::today_utc, |(start, _)| start);
+        let day = self.clamp(day);
+        self.open = true;
+        self.anchor = None;
+        self.highlighted = Some(day);
+        self.month = day.first_of_month();
+        self.show(this);
+    }
+
+    fn close(&mut self, this: &ElModel!()) {
+        if !self.open {
+            return;
+        }
+
+        self.open = false;
+        self.anchor = None;
+        self.highlighted = None;
+        this.remove_portal();
+    }
+
+    fn show(&mut self, this: &ElModel!()) {
+        let region = place(
+            this.draw_region(),
+            calendar_size(),
+            Side::Bottom,
+            Align::Start,
+            self.props.style.gap.0,
+            window_region(this.window()),
+        );
+
+        this.set_portal(
+            z_index::POPOVER,
+            build! {
+                Calendar {
+                    month: self.month,
+                    selection: self.selection(),
+                    highlighted: self.highlighted,
+                    today: Date::today_utc(),
+                    min: self.props.min,
+                    max: self.props.max,
+                    week_start: self.props.week_start,
+                    locale: self.props.locale.clone(),
+                    owner: PortalOwner(this.event_dispatcher().clone()),
+                    look: self.look(),
+                }
+            },
+        )
+        .layout_once(region)
+        .unwrap();
+    }
+
+    /// Show the month `months` months later, if any day of it can be picked.
+    fn navigate(&mut self, this: &ElModel!(), months: i32) {
+        let month = self.month.add_months(months);
+        if !month_within(month, self.props.min, self.props.max) {
+            return;
+        }
+
+        self.month = month;
+        self.highlighted = self
+            .highlighted
+            .map(|day| self.clamp(day.add_months(months)));
+        self.show(this);
+    }
+
+    /// Move the highlighted day, showing its month.
+    fn highlight(&mut self, this: &ElModel!(), day: Date) {
+        let day = self.clamp(day);
+        self.highlighted = Some(day);
+        self.month = day.first_of_month();
+        self.show(this);
+    }
+
+    /// Pick `date` as the selected day, or as an end of the range.
+    fn pick(&mut self, this: &ElModel!(), date: Date) {
+        if !within(date, self.props.min, self.props.max) {
+            return;
+        }
+
+        if self.props.range && self.anchor.is_none() {
+            self.anchor = Some(date);
+            self.highlighted = Some(date);
+            self.show(this);
+            return;
+        }
+
+        let start = self.anchor.take().unwrap_or(date);
+        let value = Some((start.min(date), start.max(date)));
+        let unchanged = self.value == value;
+        self.value = value;
+        self.close(this);
+        if !unchanged {
+            this.event_dispatcher().emit(self.changed_event());
+        }
+    }
+
+    fn input(&mut self, this: &ElModel!(), key: VirtualKeyCode, modifiers: ModifiersState) {
+        let Some(day) = self.highlighted.filter(|_| self.open)
+        else {
+            match key {
+                VirtualKeyCode::Down
+                | VirtualKeyCode::Up
+                | VirtualKeyCode::Return
+                | VirtualKeyCode::NumpadEnter
+                | VirtualKeyCode::Space => self.open(this),
+                VirtualKeyCode::Back | VirtualKeyCode::Delete if self.value.is_some() => {
+                    self.value = None;
+                    this.event_dispatcher().emit(self.changed_event());
+                }
+                _ => {}
+            }
+            return;
+        };
+
+        let into_week = (day.weekday().index() + 7 - self.props.week_start.index()) % 7;
+        let page = if modifiers.shift() { 12 } else { 1 };
+        match key {
+            VirtualKeyCode::Left => self.highlight(this, day.add_days(-1)),
+            VirtualKeyCode::Right => self.highlight(this, day.add_days(1)),
+            VirtualKeyCode::Up => self.highlight(this, day.add_days(-7)),
+            VirtualKeyCode::Down => self.highlight(this, day.add_days(7)),
+            VirtualKeyCode::Home => self.highlight(this, day.add_days(-(into_week as i64))),
+            VirtualKeyCode::End => self.highlight(this, day.add_days(6 - into_week as i64)),
+            VirtualKeyCode::PageUp => self.highlight(this, day.add_months(-page)),
+            VirtualKeyCode::PageDown => self.highlight(this, day.add_months(page)),
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Space => {
+                self.pick(this, day)
+            }
+            // give up the range being picked first
+            VirtualKeyCode::Escape if self.anchor.is_some() => {
+                self.anchor = None;
+                self.show(this);
+            }
+            VirtualKeyCode::Escape => self.close(this),
+            _ => {}
+        }
+    }
+}
+
+async fn update(this: &ElModel!(DatePicker), f: impl FnOnce(&mut DatePicker)) -> bool {
+    let Some(mut date_picker) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *date_picker);
+    true
+}
+
+async fn pointer_runtime(this: ElModel!(DatePicker)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let alive = tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => {
+                if pd.button != MouseButton::Left {
+                    continue;
+                }
+
+                this.focus();
+                update(&this, |date_picker| {
+                    if date_picker.open {
+                        date_picker.close(&this);
+                    } else {
+                        date_picker.open(&this);
+                    }
+                })
+                .await
+            }
+            (hover, _) = ed.recv::<CalendarHover>() => {
+                // keep the highlighted day when the pointer leaves the days
+                let Some(date) = hover.date
+                else {
+                    continue;
+                };
+
+                update(&this, |date_picker| {
+                    if date_picker.open && date_picker.highlighted != Some(date) {
+                        date_picker.highlighted = Some(date);
+                        date_picker.show(&this);
+                    }
+                })
+                .await
+            }
+            (pick, _) = ed.recv::<CalendarPick>() => {
+                update(&this, |date_picker| {
+                    if date_picker.open {
+                        date_picker.pick(&this, pick.date);
+                    }
+                })
+                .await
+            }
+            (navigate, _) = ed.recv::<CalendarNavigate>() => {
+                update(&this, |date_picker| {
+                    if date_picker.open {
+                        date_picker.navigate(&this, navigate.months);
+                    }
+                })
+                .await
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
+
+impl Dismiss for DatePicker {
+    // pressing on the date box is left to the pointer runtime, which toggles the calendar
+    fn dismissed_by(&self, this: &ElModel!(), point: Point) -> bool {
+        self.open && !region_contains(this.draw_region(), point)
+    }
+
+    fn dismiss(&mut self, this: &ElModel!()) {
+        self.close(this);
+    }
+}
+
+async fn keyboard_runtime(this: ElModel!(DatePicker)) {
+    let ed = this.event_dispatcher().clone();
+    let mut modifiers = ModifiersState::empty();
+
+    loop {
+        ed.recv_trusted::<Focused>().await;
+        if !update(&this, |date_picker| date_picker.focused = true).await {
+            return;
+        }
+
+        loop {
+            let key = tokio::select! {
+                event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                    StaticWindowEvent::ModifiersChanged(state) => {
+                        modifiers = state;
+                        continue;
+                    }
+                    StaticWindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => key,
+                    _ => continue,
+                },
+                _ = ed.recv_trusted::<Blured>() => break,
+            };
+
+            if !update(&this, |date_picker| {
+                date_picker.input(&this, key, modifiers)
+            })
+            .await
+            {
+                return;
+            }
+        }
+
+        let blured = update(&this, |date_picker| {
+            date_picker.focused = false;
+            date_picker.close(&this);
+        });
+        if !blured.await {
+            return;
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Color of the border of the date and time boxes.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleDateBorder(#[style(default = "Color::from_rgb(0xc8, 0xc8, 0xc8)")] pub Color);
+
+/// Color of the focus ring, the selected days and today.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleDateAccent(#[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color);
+
+/// Background of the days between the ends of a range and of the
+/// highlighted day.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleDateHighlight(#[style(default = "Color::from_rgb(0xd6, 0xe6, 0xfd)")] pub Color);
@@ -0,0 +1,482 @@
+use std::{cell::OnceCell, rc::Rc};
+
+use irisia::{
+    build,
+    element::{props::PropsUpdateWith, DetachedPortal, Element, ElementUpdate, GlobalContent},
+    event::{
+        standard::{ElementAbandoned, WindowDestroyed},
+        EventDispatcher,
+    },
+    primitive::{Pixel, Point, Region},
+    skia_safe::{textlayout::FontCollection, Color},
+    structure::Chain,
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{
+    label::{build_paragraph, font_collection, label_font},
+    popup::{
+        styles::{StylePopupBackground, StylePopupRadius},
+        window_region, z_index, PortalOwner,
+    },
+    textbox::styles::StyleFontSize,
+};
+
+use self::{
+    parts::{
+        button_width, title_height, ButtonActivate, ButtonFocus, DialogBackdrop, DialogButton,
+        DialogFrame, DialogLook, BUTTON_GAP, BUTTON_HEIGHT, GAP, PADDING,
+    },
+    styles::{StyleDialogAccent, StyleDialogBackdrop},
+};
+
+mod parts;
+pub mod styles;
+
+/// A modal dialog shown in the middle of the window while `open` is `true`.
+///
+/// A backdrop dims the window and blocks pointer events to everything
+/// beneath. Tab and Shift+Tab move the keyboard focus between the buttons
+/// without leaving the dialog, Enter presses the focused button, and Escape
+/// dismisses the dialog if `dismissible`, even if one of the children is
+/// focused. Children are shown between the
+/// message and the buttons, in a region `content_height` high. Once hidden,
+/// the focus goes back to the element focused before the dialog shown.
+///
+/// Once closed by the user, [`DialogClosed`] is emitted, and the owner is
+/// expected to set `open` to `false` in response.
+pub struct Dialog {
+    props: OwnedProps,
+    font_collection: FontCollection,
+    shown: bool,
+    /// Index of the button holding the keyboard focus
+    focused: Option<usize>,
+    /// Element focused before the dialog shown
+    saved_focus: Option<EventDispatcher>,
+}
+
+/// Emitted on the dialog's event dispatcher when it was closed by the user.
+#[derive(Event, Clone, Copy, PartialEq)]
+pub struct DialogClosed {
+    /// Index of the pressed button, `None` if dismissed by Escape.
+    pub button: Option<usize>,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct DialogStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: StylePopupBackground,
+    radius: StylePopupRadius,
+    backdrop: StyleDialogBackdrop,
+    accent: StyleDialogAccent,
+}
+
+#[irisia::props(updater = "DialogProps", watch)]
+pub struct OwnedProps {
+    #[props(default, watch)]
+    open: bool,
+
+    #[props(updated, default)]
+    title: String,
+
+    #[props(updated, default)]
+    message: String,
+
+    /// Labels of the buttons, from left to right.
+    #[props(default = r#"vec![String::from("OK")]"#)]
+    buttons: Vec<String>,
+
+    /// Index of the highlighted button, which is focused once shown.
+    /// The last button is focused if `None`.
+    #[props(default)]
+    primary: Option<usize>,
+
+    /// Whether Escape closes the dialog.
+    #[props(default = "true")]
+    dismissible: bool,
+
+    #[props(default = "Pixel(360.0)")]
+    width: Pixel,
+
+    /// Height of the region for children.
+    #[props(default)]
+    content_height: Pixel,
+
+    #[props(read_style(stdin))]
+    style: DialogStyles,
+}
+
+struct DialogLayout {
+    frame: Region,
+    content: Region,
+    buttons: Vec<Region>,
+}
+
+impl Element for Dialog {
+    type BlankProps = DialogProps;
+
+    fn set_children(&self, this: &ElModel!()) {
+        // children are shown in the portal
+        this.set_children(()).layout(|()| unreachable!()).unwrap();
+        if self.shown {
+            self.show(this);
+        }
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Dialog
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(owner_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+
+        let mut dialog = Dialog {
+            props: OwnedProps::props_create_with(props),
+            font_collection: font_collection(),
+            shown: false,
+            focused: None,
+            saved_focus: None,
+        };
+        dialog.sync_with_props(this);
+        dialog
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.unchanged || !update_result.open_unchanged {
+            self.sync_with_props(this);
+        }
+        update_result.unchanged && update_result.open_unchanged
+    }
+}
+
+impl Dialog {
+    /// Show a dialog with `message` and the buttons "Cancel" and "OK" above
+    /// the window, and wait for the user. Returns whether "OK" is pressed,
+    /// or `None` if the window is closed first.
+    pub async fn confirm(global: &Rc<GlobalContent>, message: impl Into<String>) -> Option<bool> {
+        let message: String = message.into();
+        let ed = Rc::new(OnceCell::new());
+        let ed_setter = ed.clone();
+
+        let portal = DetachedPortal::new(global);
+        portal
+            .set(
+                z_index::DIALOG,
+                build! {
+                    Dialog {
+                        open: true,
+                        message: message,
+                        buttons: vec![String::from("Cancel"), String::from("OK")],
+                        primary: Some(1),
+                        +oncreate: move |em| {
+                            let _ = ed_setter.set(em.event_dispatcher().clone());
+                        },
+                    }
+                },
+            )
+            .layout_once(window_region(global.window()))
+            .unwrap();
+
+        let ed = ed.get().expect("dialog not created").clone();
+        let global_ed = global.event_dispatcher().clone();
+        let confirmed = tokio::select! {
+            (closed, _) = ed.recv::<DialogClosed>() => Some(closed.button == Some(1)),
+            _ = ed.recv_trusted::<ElementAbandoned>() => None,
+            _ = global_ed.recv_trusted::<WindowDestroyed>() => None,
+        };
+        drop(portal);
+        confirmed
+    }
+
+    fn look(&self) -> DialogLook {
+        let style = &self.props.style;
+        DialogLook {
+            font_size: style.font_size.map_or(Pixel(14.0), |size| size.0),
+            color: style.color.map_or(Color::BLACK, |color| color.0),
+            background: style.background.0,
+            radius: style.radius.0,
+            accent: style.accent.0,
+        }
+    }
+
+    fn sync_with_props(&mut self, this: &ElModel!()) {
+        match (self.props.open, self.shown) {
+            (true, false) => {
+                self.shown = true;
+                self.saved_focus = this.global().focused();
+                this.capture_keys();
+                self.focused = self
+                    .props
+                    .primary
+                    .or_else(|| self.props.buttons.len().checked_sub(1));
+                self.show(this);
+            }
+            (true, true) => self.show(this),
+            (false, true) => self.hide(this),
+            (false, false) => {}
+        }
+    }
+
+    fn layout(&self, window: Region) -> DialogLayout {
+        let OwnedProps {
+            title,
+            message,
+            buttons,
+            width,
+            content_height,
+            ..
+        } = &self.props;
+        let look = self.look();
+
+        let window_width = window.1 .0 - window.0 .0;
+        let window_height = window.1 .1 - window.0 .1;
+        let width = width.min(window_width - PADDING * 2.0).max(Pixel(0.0));
+
+        // offsets from the top of the frame
+        let mut bottom = PADDING;
+        if !title.is_empty() {
+            bottom = bottom + title_height(&look) + GAP;
+        }
+        if !message.is_empty() {
+            let paragraph = build_paragraph(
+                &self.font_collection,
+                message,
+                look.font_size,
+                look.color,
+                (width - PADDING * 2.0).max(Pixel(0.0)),
+            );
+            bottom = bottom + Pixel::from_physical(paragraph.height().ceil()) + GAP;
+        }
+
+        let content_top = bottom;
+        if *content_height > Pixel(0.0) {
+            bottom = bottom + *content_height + GAP;
+        }
+
+        let buttons_top = bottom;
+        let height = if buttons.is_empty() {
+            bottom - GAP + PADDING
+        } else {
+            bottom + BUTTON_HEIGHT + PADDING
+        };
+
+        let start = Point(
+            window.0 .0 + ((window_width - width) / 2.0).max(Pixel(0.0)),
+            window.0 .1 + ((window_height - height) / 2.0).max(Pixel(0.0)),
+        );
+        let end = Point(start.0 + width, start.1 + height);
+
+        let font = label_font(look.font_size);
+        let mut right = end.0 - PADDING;
+        let mut button_regions: Vec<Region> = buttons
+            .iter()
+            .rev()
+            .map(|label| {
+                let left = right - button_width(&font, label);
+                let region = (
+                    Point(left, start.1 + buttons_top),
+                    Point(right, start.1 + buttons_top + BUTTON_HEIGHT),
+                );
+                right = left - BUTTON_GAP;
+                region
+            })
+            .collect();
+        button_regions.reverse();
+
+        DialogLayout {
+            frame: (start, end),
+            content: (
+                Point(start.0 + PADDING, start.1 + content_top),
+                Point(end.0 - PADDING, start.1 + content_top + *content_height),
+            ),
+            buttons: button_regions,
+        }
+    }
+
+    fn show(&self, this: &ElModel!()) {
+        let window = window_region(this.window());
+        let layout = self.layout(window);
+        let look = self.look();
+        let owner = PortalOwner(this.event_dispatcher().clone());
+        let OwnedProps {
+            title,
+            message,
+            buttons,
+            primary,
+            style,
+            ..
+        } = &self.props;
+
+        // the backdrop, the frame and the buttons, then children
+        let mut regions = [window, layout.frame]
+            .into_iter()
+            .chain(layout.buttons.iter().copied());
+
+        this.set_portal(
+            z_index::DIALOG,
+            Chain::new(
+                build! {
+                    DialogBackdrop {
+                        color: style.backdrop.0,
+                    }
+
+                    DialogFrame {
+                        title: title,
+                        message: message,
+                        look: look,
+                    }
+
+                    for (index, label) in buttons.iter().enumerate() {
+                        @key index;
+                        DialogButton {
+                            label: label,
+                            index: index,
+                            primary: *primary == Some(index),
+                            focused: self.focused == Some(index),
+                            owner: owner.clone(),
+                            look: look,
+                        }
+                    }
+                },
+                this.slot(),
+            ),
+        )
+        .layout(|()| Some(regions.next().unwrap_or(layout.content)))
+        .unwrap();
+    }
+
+    fn hide(&mut self, this: &ElModel!()) {
+        self.shown = false;
+        self.focused = None;
+        this.remove_portal();
+        this.release_keys();
+
+        // the dialog is modal, so the focus can only have moved into it
+        match self.saved_focus.take() {
+            Some(ed) => this.global().focus(ed),
+            None => this.global().blur(),
+        }
+    }
+
+    fn close(&mut self, this: &ElModel!(), button: Option<usize>) {
+        self.hide(this);
+        this.event_dispatcher().emit(DialogClosed { button });
+    }
+
+    /// Move the focus to the next or previous button, wrapping around.
+    fn move_focus(&mut self, this: &ElModel!(), forward: bool) {
+        let len = self.props.buttons.len();
+        if len == 0 {
+            return;
+        }
+
+        self.focused = Some(match (self.focused, forward) {
+            (Some(index), true) => (index + 1) % len,
+            (Some(index), false) => (index + len - 1) % len,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        });
+        self.show(this);
+    }
+}
+
+/// Handles events reported by the parts of the dialog.
+async fn owner_runtime(this: ElModel!(Dialog)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        tokio::select! {
+            (activate, _) = ed.recv::<ButtonActivate>() => {
+                let Some(mut dialog) = this.el_write().await
+                else {
+                    return;
+                };
+
+                if dialog.shown {
+                    dialog.close(&this, Some(activate.index));
+                }
+            }
+            (focus, _) = ed.recv::<ButtonFocus>() => {
+                let Some(mut dialog) = this.el_write().await
+                else {
+                    return;
+                };
+
+                if !dialog.shown {
+                    continue;
+                }
+
+                // focus may also move to children of the dialog
+                if focus.focused {
+                    dialog.focused = Some(focus.index);
+                } else if dialog.focused == Some(focus.index) {
+                    dialog.focused = None;
+                }
+                dialog.show(&this);
+            }
+        }
+    }
+}
+
+async fn keyboard_runtime(this: ElModel!(Dialog)) {
+    let ed = this.event_dispatcher().clone();
+    let global_ed = this.global().event_dispatcher().clone();
+    let mut shift = false;
+
+    loop {
+        let key = tokio::select! {
+            // keys are captured while shown, no matter which element inside
+            // the dialog is focused
+            event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                StaticWindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(key),
+                            ..
+                        },
+                    ..
+                } => Some(key),
+                _ => continue,
+            },
+            // the modifiers may be changed before shown
+            event = global_ed.recv_trusted::<StaticWindowEvent>() => match event {
+                StaticWindowEvent::ModifiersChanged(modifiers) => {
+                    shift = modifiers.shift();
+                    continue;
+                }
+                StaticWindowEvent::Resized(_) => None,
+                _ => continue,
+            },
+        };
+
+        match this.el_read().await {
+            Some(dialog) if dialog.shown => {}
+            Some(_) => continue,
+            None => return,
+        }
+
+        let Some(mut dialog) = this.el_write().await
+        else {
+            return;
+        };
+
+        match key {
+            None => dialog.show(&this),
+            Some(VirtualKeyCode::Tab) => dialog.move_focus(&this, !shift),
+            Some(VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Space) => {
+                if let Some(index) = dialog.focused {
+                    dialog.close(&this, Some(index));
+                }
+            }
+            Some(VirtualKeyCode::Escape) if dialog.props.dismissible => dialog.close(&this, None),
+            Some(_) => {}
+        }
+    }
+}
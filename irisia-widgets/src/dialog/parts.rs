@@ -0,0 +1,366 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Blured, Click, Focused, PointerDown, PointerEntered, PointerOut},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{
+        textlayout::{FontCollection, Paragraph},
+        Color, Font, Paint, PaintStyle, RRect,
+    },
+    winit::event::MouseButton,
+    ElModel, Event,
+};
+
+use crate::{
+    label::{build_paragraph, draw_label, font_collection, label_font, label_width, to_rect},
+    popup::{draw_surface, PortalOwner},
+};
+
+pub(super) const PADDING: Pixel = Pixel(20.0);
+pub(super) const GAP: Pixel = Pixel(12.0);
+pub(super) const BUTTON_HEIGHT: Pixel = Pixel(32.0);
+pub(super) const BUTTON_GAP: Pixel = Pixel(8.0);
+const BUTTON_MIN_WIDTH: Pixel = Pixel(72.0);
+
+/// Colors and font size shared by every part of a dialog.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) struct DialogLook {
+    pub font_size: Pixel,
+    pub color: Color,
+    pub background: Color,
+    pub radius: Pixel,
+    pub accent: Color,
+}
+
+/// A button of the dialog was clicked.
+#[derive(Event, Clone, Copy)]
+pub(super) struct ButtonActivate {
+    pub index: usize,
+}
+
+/// A button of the dialog gained or lost focus.
+#[derive(Event, Clone, Copy)]
+pub(super) struct ButtonFocus {
+    pub index: usize,
+    pub focused: bool,
+}
+
+pub(super) fn title_font_size(look: &DialogLook) -> Pixel {
+    look.font_size * 1.25
+}
+
+pub(super) fn title_height(look: &DialogLook) -> Pixel {
+    title_font_size(look) * 1.5
+}
+
+pub(super) fn button_width(font: &Font, label: &str) -> Pixel {
+    (label_width(font, label) + Pixel(32.0)).max(BUTTON_MIN_WIDTH)
+}
+
+/// Dims the whole window and takes every pointer event beneath it.
+pub(super) struct DialogBackdrop {
+    props: BackdropProps,
+}
+
+#[irisia::props(updater = "DialogBackdropProps", watch)]
+pub(super) struct BackdropProps {
+    #[props(must_init)]
+    color: Color,
+}
+
+impl Element for DialogBackdrop {
+    type BlankProps = DialogBackdropProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let mut paint = Paint::default();
+        paint.set_color(self.props.color);
+        content.canvas().draw_rect(to_rect(region), &paint);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for DialogBackdrop
+where
+    BackdropProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        DialogBackdrop {
+            props: BackdropProps::props_create_with(props),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
+
+/// Surface of the dialog with the title and the message.
+pub(super) struct DialogFrame {
+    props: FrameProps,
+    font_collection: FontCollection,
+    message: Option<Paragraph>,
+}
+
+#[irisia::props(updater = "DialogFrameProps", watch)]
+pub(super) struct FrameProps {
+    #[props(updated, default)]
+    title: String,
+
+    #[props(updated, default)]
+    message: String,
+
+    #[props(must_init)]
+    look: DialogLook,
+}
+
+impl Element for DialogFrame {
+    type BlankProps = DialogFrameProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let FrameProps {
+            title,
+            message,
+            look,
+        } = &self.props;
+        let canvas = content.canvas();
+        draw_surface(canvas, region, look.background, look.radius, true);
+
+        let left = region.0 .0 + PADDING;
+        let mut top = region.0 .1 + PADDING;
+
+        if !title.is_empty() {
+            let bottom = top + title_height(look);
+            draw_label(
+                canvas,
+                &label_font(title_font_size(look)),
+                title,
+                look.color,
+                left,
+                (top, bottom),
+            );
+            top = bottom + GAP;
+        }
+
+        if !message.is_empty() {
+            let paragraph = self.message.get_or_insert_with(|| {
+                build_paragraph(
+                    &self.font_collection,
+                    message,
+                    look.font_size,
+                    look.color,
+                    (region.1 .0 - region.0 .0 - PADDING * 2.0).max(Pixel(0.0)),
+                )
+            });
+            paragraph.paint(canvas, Point(left, top));
+        }
+        Ok(())
+    }
+
+    fn draw_region_changed(&mut self, _: &ElModel!(), _: Region) {
+        self.message = None;
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for DialogFrame
+where
+    FrameProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        // move the focus into the dialog, even if there is no button to
+        // take it over
+        this.focus();
+        DialogFrame {
+            props: FrameProps::props_create_with(props),
+            font_collection: font_collection(),
+            message: None,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let unchanged = self.props.props_update_with(props).unchanged;
+        if !unchanged {
+            self.message = None;
+        }
+        unchanged
+    }
+}
+
+/// One of the buttons in the bottom row of the dialog.
+pub(super) struct DialogButton {
+    props: ButtonProps,
+    font: Font,
+    hovered: bool,
+}
+
+#[irisia::props(updater = "DialogButtonProps", watch)]
+pub(super) struct ButtonProps {
+    #[props(updated, must_init)]
+    label: String,
+
+    #[props(must_init)]
+    index: usize,
+
+    #[props(default)]
+    primary: bool,
+
+    /// Takes the keyboard focus once set.
+    #[props(default, watch)]
+    focused: bool,
+
+    /// Receives [`ButtonActivate`] and [`ButtonFocus`]
+    #[props(must_init)]
+    owner: PortalOwner,
+
+    #[props(must_init)]
+    look: DialogLook,
+}
+
+impl Element for DialogButton {
+    type BlankProps = DialogButtonProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let ButtonProps {
+            label,
+            primary,
+            focused,
+            look,
+            ..
+        } = &self.props;
+        let canvas = content.canvas();
+        let radius = Pixel(6.0).to_physical();
+        let rrect = RRect::new_rect_xy(to_rect(region), radius, radius);
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        if *primary {
+            paint.set_color(look.accent);
+        } else {
+            paint.set_color(Color::from_rgb(0xf0, 0xf0, 0xf0));
+        }
+        canvas.draw_rrect(rrect, &paint);
+
+        if self.hovered {
+            let mut paint = Paint::default();
+            paint
+                .set_anti_alias(true)
+                .set_color(Color::from_argb(0x14, 0, 0, 0));
+            canvas.draw_rrect(rrect, &paint);
+        }
+
+        if *focused {
+            let outset = Pixel(2.0).to_physical();
+            let mut paint = Paint::default();
+            paint
+                .set_anti_alias(true)
+                .set_color(look.accent.with_a(0x80))
+                .set_style(PaintStyle::Stroke)
+                .set_stroke_width(Pixel(2.0).to_physical());
+            canvas.draw_rrect(rrect.with_outset((outset, outset)), &paint);
+        }
+
+        let color = if *primary { Color::WHITE } else { look.color };
+        let x = (region.0 .0 + region.1 .0 - label_width(&self.font, label)) / 2.0;
+        draw_label(
+            canvas,
+            &self.font,
+            label,
+            color,
+            x,
+            (region.0 .1, region.1 .1),
+        );
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for DialogButton
+where
+    ButtonProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(button_runtime(this.clone()));
+
+        let props = ButtonProps::props_create_with(props);
+        if props.focused {
+            this.focus();
+        }
+        DialogButton {
+            font: label_font(props.look.font_size),
+            props,
+            hovered: false,
+        }
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.unchanged {
+            self.font = label_font(self.props.look.font_size);
+        }
+        if !update_result.focused_unchanged && self.props.focused {
+            this.focus();
+        }
+        update_result.unchanged && update_result.focused_unchanged
+    }
+}
+
+async fn button_runtime(this: ElModel!(DialogButton)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let alive = tokio::select! {
+            click = ed.recv_trusted::<Click>() => {
+                if click.button != MouseButton::Left {
+                    continue;
+                }
+                notify_owner(&this, |index| ButtonActivate { index }).await
+            }
+            _ = ed.recv_trusted::<PointerDown>() => {
+                this.focus();
+                true
+            }
+            _ = ed.recv_trusted::<Focused>() => {
+                notify_owner(&this, |index| ButtonFocus { index, focused: true }).await
+            }
+            _ = ed.recv_trusted::<Blured>() => {
+                notify_owner(&this, |index| ButtonFocus { index, focused: false }).await
+            }
+            _ = ed.recv_trusted::<PointerEntered>() => set_hovered(&this, true).await,
+            _ = ed.recv_trusted::<PointerOut>() => set_hovered(&this, false).await,
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
+
+async fn notify_owner<E, F>(this: &ElModel!(DialogButton), event: F) -> bool
+where
+    E: Event,
+    F: FnOnce(usize) -> E,
+{
+    let Some(button) = this.el_read().await
+    else {
+        return false;
+    };
+
+    button.props.owner.0.emit(event(button.props.index));
+    true
+}
+
+async fn set_hovered(this: &ElModel!(DialogButton), hovered: bool) -> bool {
+    let Some(mut button) = this.el_write().await
+    else {
+        return false;
+    };
+
+    button.hovered = hovered;
+    true
+}
@@ -0,0 +1,11 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Color of the backdrop dimming the window beneath a dialog.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleDialogBackdrop(#[style(default = "Color::from_argb(0x66, 0, 0, 0)")] pub Color);
+
+/// Color of the primary button and the focus ring.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleDialogAccent(#[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color);
@@ -0,0 +1,72 @@
+use irisia::{
+    primitive::{Pixel, Region},
+    skia_safe::{
+        textlayout::{FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, TextStyle},
+        Canvas, Color, Font, FontMgr, Paint, Rect, Typeface,
+    },
+};
+
+/// Families tried in order for monospaced text, like code.
+pub(crate) const MONOSPACE: [&str; 4] = ["monospace", "Menlo", "Consolas", "Courier New"];
+
+/// Skia rect covering the region, in physical pixels.
+pub(crate) fn to_rect((start, end): Region) -> Rect {
+    Rect::new(
+        start.0.to_physical(),
+        start.1.to_physical(),
+        end.0.to_physical(),
+        end.1.to_physical(),
+    )
+}
+
+/// Font of single line labels drawn by widgets, like menu items and table cells.
+pub(crate) fn label_font(size: Pixel) -> Font {
+    Font::new(Typeface::default(), size.to_physical())
+}
+
+pub(crate) fn label_width(font: &Font, text: &str) -> Pixel {
+    Pixel::from_physical(font.measure_str(text, None).0)
+}
+
+/// Draw `text` in a single line starting from `x`, vertically centered in `top..bottom`.
+pub(crate) fn draw_label(
+    canvas: &mut Canvas,
+    font: &Font,
+    text: &str,
+    color: Color,
+    x: Pixel,
+    (top, bottom): (Pixel, Pixel),
+) {
+    let (_, metrics) = font.metrics();
+    let center = ((top + bottom) / 2.0).to_physical();
+    let baseline = center - (metrics.ascent + metrics.descent) / 2.0;
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(color);
+    canvas.draw_str(text, (x.to_physical(), baseline), font, &paint);
+}
+
+pub(crate) fn font_collection() -> FontCollection {
+    let mut font_collection = FontCollection::new();
+    font_collection.set_default_font_manager(FontMgr::new(), None);
+    font_collection
+}
+
+/// Text wrapped in multiple lines no wider than `max_width`.
+pub(crate) fn build_paragraph(
+    font_collection: &FontCollection,
+    text: &str,
+    font_size: Pixel,
+    color: Color,
+    max_width: Pixel,
+) -> Paragraph {
+    let mut text_style = TextStyle::new();
+    text_style
+        .set_font_size(font_size.to_physical())
+        .set_color(color);
+
+    let mut pb = ParagraphBuilder::new(&ParagraphStyle::new(), font_collection);
+    let mut paragraph = pb.push_style(&text_style).add_text(text).build();
+    paragraph.layout(max_width.to_physical());
+    paragraph
+}
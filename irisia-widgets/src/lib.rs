@@ -1,4 +1,29 @@
 pub mod box_styles;
+pub mod canvas_view;
+pub mod chart;
+mod civil;
+pub mod code_editor;
+pub mod color_picker;
+pub mod date_picker;
+pub mod dialog;
+mod label;
+pub mod markdown;
+pub mod menu;
+pub mod popover;
+pub mod popup;
+pub mod progress;
+mod scroll;
+pub mod select;
+pub mod shapes;
+pub mod slider;
+pub mod split_pane;
+pub mod table;
+pub mod tabs;
 pub mod textbox;
+pub mod time_picker;
+pub mod toast;
+pub mod tooltip;
+pub mod tree_view;
+pub mod virtual_list;
 
 pub use irisia::*;
@@ -0,0 +1,394 @@
+use std::ops::Range;
+
+use irisia::{
+    primitive::Pixel,
+    skia_safe::{
+        font_style::{Slant, Weight, Width},
+        textlayout::{
+            FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, PlaceholderAlignment,
+            PlaceholderStyle, RectHeightStyle, RectWidthStyle, TextBaseline, TextDecoration,
+            TextStyle,
+        },
+        Canvas, Color, FontStyle, Image, Paint, Point as SkiaPoint, RRect, Rect,
+    },
+};
+
+use crate::label::MONOSPACE;
+
+use super::{
+    parse::{Block, Inline},
+    ImageSource,
+};
+
+const INDENT: Pixel = Pixel(24.0);
+const MARKER_GAP: Pixel = Pixel(6.0);
+const CODE_PADDING: Pixel = Pixel(8.0);
+const QUOTE_BAR: Pixel = Pixel(3.0);
+/// Space between blocks, relative to the font size
+const BLOCK_GAP: f32 = 0.75;
+/// Space between items of a list, relative to the font size
+const ITEM_GAP: f32 = 0.25;
+const HEADING_SCALES: [f32; 6] = [2.0, 1.6, 1.3, 1.15, 1.0, 0.9];
+const CODE_SCALE: f32 = 0.9;
+/// Bullets of lists by how deep they are nested
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+/// Fonts and colors of a document, in physical units.
+pub(super) struct Look {
+    pub font_size: f32,
+    pub color: Color,
+    pub link: Color,
+    pub code_background: Color,
+    pub quote: Color,
+}
+
+/// A document laid out in a width, placed relative to its top left corner.
+pub(super) struct Layout {
+    pub width: f32,
+    pub height: f32,
+    pieces: Vec<Piece>,
+}
+
+enum Piece {
+    Text {
+        origin: SkiaPoint,
+        paragraph: Paragraph,
+        /// Ranges of links in UTF-16 code units, which the paragraph counts in
+        links: Vec<(Range<usize>, String)>,
+        /// Images in the order of their placeholders
+        images: Vec<Image>,
+    },
+    /// Backgrounds of code blocks, bars of quotes and rules
+    Fill { rect: Rect, color: Color },
+}
+
+impl Layout {
+    pub(super) fn new(
+        blocks: &[Block],
+        width: f32,
+        look: &Look,
+        fonts: &FontCollection,
+        images: Option<&ImageSource>,
+    ) -> Self {
+        let mut builder = Builder {
+            look,
+            fonts,
+            images,
+            pieces: Vec::new(),
+            y: 0.0,
+            depth: 0,
+        };
+        builder.blocks(blocks, 0.0, width);
+
+        Layout {
+            width,
+            height: builder.y,
+            pieces: builder.pieces,
+        }
+    }
+
+    /// Draw the pieces from `top` to `bottom` of the document, whose top
+    /// left corner is at `origin`.
+    pub(super) fn draw(&self, canvas: &mut Canvas, origin: SkiaPoint, (top, bottom): (f32, f32)) {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+
+        for piece in &self.pieces {
+            match piece {
+                Piece::Text {
+                    origin: at,
+                    paragraph,
+                    images,
+                    ..
+                } => {
+                    if at.y > bottom || at.y + paragraph.height() < top {
+                        continue;
+                    }
+
+                    let at = origin + *at;
+                    paragraph.paint(canvas, at);
+                    let placeholders = paragraph.get_rects_for_placeholders();
+                    for (placeholder, image) in placeholders.iter().zip(images) {
+                        let rect = placeholder.rect.with_offset(at);
+                        canvas.draw_image_rect(image, None, rect, &paint);
+                    }
+                }
+                Piece::Fill { rect, color } => {
+                    if rect.top > bottom || rect.bottom < top {
+                        continue;
+                    }
+
+                    let radius = rect.width().min(rect.height()).min(QUOTE_BAR.to_physical()) / 2.0;
+                    let rect = rect.with_offset(origin);
+                    paint.set_color(*color);
+                    canvas.draw_rrect(RRect::new_rect_xy(rect, radius, radius), &paint);
+                }
+            }
+        }
+    }
+
+    /// Url of the link at `point` of the document.
+    pub(super) fn link_at(&self, point: SkiaPoint) -> Option<&str> {
+        self.pieces.iter().find_map(|piece| {
+            let Piece::Text {
+                origin,
+                paragraph,
+                links,
+                ..
+            } = piece
+            else {
+                return None;
+            };
+
+            let point = point - *origin;
+            let (_, url) = links.iter().find(|(range, _)| {
+                paragraph
+                    .get_rects_for_range(range.clone(), RectHeightStyle::Max, RectWidthStyle::Tight)
+                    .iter()
+                    .any(|text_box| contains(text_box.rect, point))
+            })?;
+            Some(url.as_str())
+        })
+    }
+}
+
+struct Builder<'a> {
+    look: &'a Look,
+    fonts: &'a FontCollection,
+    images: Option<&'a ImageSource>,
+    pieces: Vec<Piece>,
+    /// Top of the next block
+    y: f32,
+    /// How deep the list being laid out is nested
+    depth: usize,
+}
+
+impl Builder<'_> {
+    fn blocks(&mut self, blocks: &[Block], left: f32, width: f32) {
+        for (index, block) in blocks.iter().enumerate() {
+            if index > 0 {
+                self.y += self.look.font_size * BLOCK_GAP;
+            }
+            self.block(block, left, width);
+        }
+    }
+
+    fn block(&mut self, block: &Block, left: f32, width: f32) {
+        let size = self.look.font_size;
+        match block {
+            Block::Heading { level, content } => {
+                let scale = HEADING_SCALES[(*level).clamp(1, 6) as usize - 1];
+                let style = self.text_style(size * scale, Weight::BOLD);
+                self.text(content, &style, left, width);
+            }
+            Block::Paragraph(content) => {
+                let style = self.text_style(size, Weight::NORMAL);
+                self.text(content, &style, left, width);
+            }
+            Block::Code(code) => {
+                let padding = CODE_PADDING.to_physical();
+                let (top, at) = (self.y, self.pieces.len());
+                let mut style = self.text_style(size * CODE_SCALE, Weight::NORMAL);
+                style.set_font_families(&MONOSPACE);
+
+                self.y += padding;
+                let content = [Inline::Text(code.clone())];
+                self.text(&content, &style, left + padding, width - padding * 2.0);
+                self.y += padding;
+
+                let rect = Rect::new(left, top, left + width, self.y);
+                let color = self.look.code_background;
+                self.pieces.insert(at, Piece::Fill { rect, color });
+            }
+            Block::Quote(blocks) => {
+                let top = self.y;
+                let indent = INDENT.to_physical() * 0.75;
+                self.blocks(blocks, left + indent, width - indent);
+
+                let rect = Rect::new(left, top, left + QUOTE_BAR.to_physical(), self.y);
+                let color = self.look.quote;
+                self.pieces.push(Piece::Fill { rect, color });
+            }
+            Block::List { start, items } => {
+                let indent = INDENT.to_physical();
+                self.depth += 1;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        self.y += size * ITEM_GAP;
+                    }
+
+                    let marker = match start {
+                        Some(start) => format!("{}.", start + index as u64),
+                        None => BULLETS[(self.depth - 1) % BULLETS.len()].to_string(),
+                    };
+                    let top = self.y;
+                    let marker_bottom = self.marker(&marker, left + indent);
+                    self.y = top;
+                    self.blocks(item, left + indent, width - indent);
+                    self.y = self.y.max(marker_bottom);
+                }
+                self.depth -= 1;
+            }
+            Block::Rule => {
+                let y = self.y + size / 2.0;
+                let rect = Rect::new(left, y, left + width, y + Pixel(1.0).to_physical());
+                let color = self.look.quote;
+                self.pieces.push(Piece::Fill { rect, color });
+                self.y += size;
+            }
+        }
+    }
+
+    /// Lay out `content` as a paragraph below the blocks before.
+    fn text(&mut self, content: &[Inline], style: &TextStyle, left: f32, width: f32) {
+        let mut runs = Runs {
+            builder: ParagraphBuilder::new(&ParagraphStyle::new(), self.fonts),
+            look: self.look,
+            images: self.images,
+            width,
+            offset: 0,
+            links: Vec::new(),
+            placed: Vec::new(),
+        };
+        runs.styled(content, style);
+
+        let mut paragraph = runs.builder.build();
+        paragraph.layout(width.max(1.0));
+        let height = paragraph.height();
+        self.pieces.push(Piece::Text {
+            origin: SkiaPoint::new(left, self.y),
+            paragraph,
+            links: runs.links,
+            images: runs.placed,
+        });
+        self.y += height;
+    }
+
+    /// Place `marker` of a list item ending at `right`, and give its bottom.
+    fn marker(&mut self, marker: &str, right: f32) -> f32 {
+        let content = [Inline::Text(marker.to_string())];
+        let style = self.text_style(self.look.font_size, Weight::NORMAL);
+        let width = INDENT.to_physical() * 4.0;
+        self.text(&content, &style, 0.0, width);
+
+        let Some(Piece::Text {
+            origin, paragraph, ..
+        }) = self.pieces.last_mut()
+        else {
+            unreachable!("a text was just pushed");
+        };
+        origin.x = right - MARKER_GAP.to_physical() - paragraph.max_intrinsic_width();
+        self.y
+    }
+
+    fn text_style(&self, size: f32, weight: Weight) -> TextStyle {
+        let mut style = TextStyle::new();
+        style
+            .set_font_size(size)
+            .set_color(self.look.color)
+            .set_font_style(FontStyle::new(weight, Width::NORMAL, Slant::Upright));
+        style
+    }
+}
+
+/// Adds inlines to a paragraph, and keeps where links and images went.
+struct Runs<'a> {
+    builder: ParagraphBuilder,
+    look: &'a Look,
+    images: Option<&'a ImageSource>,
+    /// Width of the paragraph, which images are scaled down to
+    width: f32,
+    /// Length of the text added, in UTF-16 code units
+    offset: usize,
+    links: Vec<(Range<usize>, String)>,
+    placed: Vec<Image>,
+}
+
+impl Runs<'_> {
+    fn styled(&mut self, inlines: &[Inline], style: &TextStyle) {
+        self.builder.push_style(style);
+        for inline in inlines {
+            self.inline(inline, style);
+        }
+        self.builder.pop();
+    }
+
+    fn inline(&mut self, inline: &Inline, style: &TextStyle) {
+        let font_style = style.font_style();
+        match inline {
+            Inline::Text(text) => self.add(text),
+            Inline::LineBreak => self.add("\n"),
+            Inline::Emphasis(content) => {
+                let mut emphasis = style.clone();
+                emphasis.set_font_style(FontStyle::new(
+                    font_style.weight(),
+                    Width::NORMAL,
+                    Slant::Italic,
+                ));
+                self.styled(content, &emphasis);
+            }
+            Inline::Strong(content) => {
+                let mut strong = style.clone();
+                strong.set_font_style(FontStyle::new(
+                    Weight::BOLD,
+                    Width::NORMAL,
+                    font_style.slant(),
+                ));
+                self.styled(content, &strong);
+            }
+            Inline::Code(code) => {
+                let mut background = Paint::default();
+                background.set_color(self.look.code_background);
+                let mut code_style = style.clone();
+                code_style
+                    .set_font_families(&MONOSPACE)
+                    .set_font_size(style.font_size() * CODE_SCALE)
+                    .set_background_paint(&background);
+
+                self.builder.push_style(&code_style);
+                self.add(code);
+                self.builder.pop();
+            }
+            Inline::Link { url, content } => {
+                let mut link = style.clone();
+                link.set_color(self.look.link);
+                link.set_decoration_type(TextDecoration::UNDERLINE);
+                link.set_decoration_color(self.look.link);
+
+                let start = self.offset;
+                self.styled(content, &link);
+                self.links.push((start..self.offset, url.clone()));
+            }
+            Inline::Image { url, alt } => match self.images.and_then(|images| images.get(url)) {
+                Some(image) => {
+                    // a pixel of an image is a pixel of the window
+                    let width = Pixel(image.width() as f32).to_physical();
+                    let height = Pixel(image.height() as f32).to_physical();
+                    let scale = (self.width / width).min(1.0);
+                    self.builder.add_placeholder(&PlaceholderStyle::new(
+                        width * scale,
+                        height * scale,
+                        PlaceholderAlignment::AboveBaseline,
+                        TextBaseline::Alphabetic,
+                        0.0,
+                    ));
+                    self.offset += 1;
+                    self.placed.push(image);
+                }
+                None => {
+                    let alt = Inline::Emphasis(vec![Inline::Text(alt.clone())]);
+                    self.inline(&alt, style);
+                }
+            },
+        }
+    }
+
+    fn add(&mut self, text: &str) {
+        self.builder.add_text(text);
+        self.offset += text.encode_utf16().count();
+    }
+}
+
+fn contains(rect: Rect, point: SkiaPoint) -> bool {
+    point.x >= rect.left && point.x <= rect.right && point.y >= rect.top && point.y <= rect.bottom
+}
@@ -0,0 +1,272 @@
+use std::rc::Rc;
+
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Click, Wheel},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{textlayout::FontCollection, Color, Image},
+    style::StyleColor,
+    winit::event::MouseButton,
+    ElModel, Event, StyleReader,
+};
+
+use crate::{
+    label::{font_collection, to_rect},
+    scroll::{clamp_offset, draw_vertical_scrollbar, max_offset, wheel_delta, SCROLLBAR_WIDTH},
+    textbox::styles::StyleFontSize,
+};
+
+use self::{
+    layout::{Layout, Look},
+    parse::{parse, Block},
+    styles::{StyleMarkdownCodeBackground, StyleMarkdownLink, StyleMarkdownQuote},
+};
+
+mod layout;
+mod parse;
+pub mod styles;
+
+/// A document written in Markdown: headings, paragraphs with emphasis,
+/// inline code and links, code blocks, lists, quotes, rules and images.
+/// Text wraps to the width of the element, and the document scrolls with
+/// the wheel when taller.
+///
+/// ```ignore
+/// Markdown {
+///     source: "# Title\n\nSee the [guide](https://example.com).".to_string(),
+///     images: ImageSource::new(|url| icons.get(url).cloned()),
+/// }
+/// ```
+///
+/// Clicking a link emits [`LinkClicked`] with its url; nothing is opened.
+/// Images are taken from `images` by their urls and scaled down to fit the
+/// width, and an image not found shows its alt text instead.
+pub struct Markdown {
+    props: OwnedProps,
+    blocks: Vec<Block>,
+    font_collection: FontCollection,
+    /// Dropped whenever the source, the styles or the width change
+    layout: Option<Layout>,
+    scroll: Pixel,
+}
+
+/// Emitted whenever the user clicks a link.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct LinkClicked {
+    pub url: String,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct MarkdownStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    link: StyleMarkdownLink,
+    code_background: StyleMarkdownCodeBackground,
+    quote: StyleMarkdownQuote,
+}
+
+#[irisia::props(updater = "MarkdownProps", watch)]
+pub struct OwnedProps {
+    #[props(must_init, watch)]
+    source: String,
+
+    /// Gives the images of the document. Without it, all images show their
+    /// alt text.
+    #[props(default)]
+    images: Option<ImageSource>,
+
+    #[props(read_style(stdin))]
+    style: MarkdownStyles,
+}
+
+/// Gives the image of a url in a document, or `None` if there is not one.
+/// Images are asked for whenever the document is laid out, so they should
+/// be loaded beforehand.
+///
+/// ```ignore
+/// ImageSource::new(move |url| images.get(url).cloned())
+/// ```
+#[derive(Clone)]
+pub struct ImageSource(Rc<dyn Fn(&str) -> Option<Image>>);
+
+impl ImageSource {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&str) -> Option<Image> + 'static,
+    {
+        ImageSource(Rc::new(f))
+    }
+
+    fn get(&self, url: &str) -> Option<Image> {
+        (self.0)(url)
+    }
+}
+
+impl PartialEq for ImageSource {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Element for Markdown {
+    type BlankProps = MarkdownProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+        this.consume_wheel(self.max_scroll(region) > Pixel(0.0));
+
+        let rect = to_rect(region);
+        let width = (rect.width() - (SCROLLBAR_WIDTH * 2.0).to_physical()).max(0.0);
+        if self
+            .layout
+            .as_ref()
+            .map_or(true, |layout| layout.width != width)
+        {
+            self.layout = Some(Layout::new(
+                &self.blocks,
+                width,
+                &self.look(),
+                &self.font_collection,
+                self.props.images.as_ref(),
+            ));
+            self.clamp_scroll(region);
+        }
+        let Some(layout) = &self.layout
+        else {
+            return Ok(());
+        };
+
+        let scroll = self.scroll.to_physical();
+        let canvas = content.canvas();
+        canvas.save();
+        canvas.clip_rect(rect, None, true);
+        layout.draw(
+            canvas,
+            (rect.left, rect.top - scroll).into(),
+            (scroll, scroll + rect.height()),
+        );
+        canvas.restore();
+
+        draw_vertical_scrollbar(canvas, region, self.height(), self.scroll);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Markdown
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        Markdown {
+            blocks: parse(&props.source),
+            props,
+            font_collection: font_collection(),
+            layout: None,
+            scroll: Pixel(0.0),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if update_result.unchanged {
+            return true;
+        }
+
+        if !update_result.source_unchanged {
+            self.blocks = parse(&self.props.source);
+            self.scroll = Pixel(0.0);
+        }
+        self.layout = None;
+        false
+    }
+}
+
+impl Markdown {
+    fn look(&self) -> Look {
+        let style = &self.props.style;
+        Look {
+            font_size: style
+                .font_size
+                .map_or(Pixel(14.0), |size| size.0)
+                .to_physical(),
+            color: style.color.map_or(Color::BLACK, |color| color.0),
+            link: style.link.0,
+            code_background: style.code_background.0,
+            quote: style.quote.0,
+        }
+    }
+
+    /// Url of the link at `point` of the window.
+    fn link_at(&self, region: Region, point: Point) -> Option<&str> {
+        let layout = self.layout.as_ref()?;
+        let x = point.0 - region.0 .0;
+        let y = point.1 - region.0 .1 + self.scroll;
+        layout.link_at((x.to_physical(), y.to_physical()).into())
+    }
+
+    fn height(&self) -> Pixel {
+        self.layout
+            .as_ref()
+            .map_or(Pixel(0.0), |layout| Pixel::from_physical(layout.height))
+    }
+
+    fn max_scroll(&self, region: Region) -> Pixel {
+        max_offset(self.height(), region.1 .1 - region.0 .1)
+    }
+
+    fn clamp_scroll(&mut self, region: Region) {
+        self.scroll = clamp_offset(self.scroll, self.max_scroll(region));
+    }
+}
+
+async fn update(this: &ElModel!(Markdown), f: impl FnOnce(&mut Markdown)) -> bool {
+    let Some(mut markdown) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *markdown);
+    true
+}
+
+async fn pointer_runtime(this: ElModel!(Markdown)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let alive = tokio::select! {
+            click = ed.recv_trusted::<Click>() => {
+                if click.button != MouseButton::Left {
+                    continue;
+                }
+
+                let Some(markdown) = this.el_read().await
+                else {
+                    return;
+                };
+
+                let url = markdown.link_at(this.draw_region(), click.position).map(String::from);
+                drop(markdown);
+                if let Some(url) = url {
+                    this.event_dispatcher().emit(LinkClicked { url });
+                }
+                true
+            }
+            wheel = ed.recv_trusted::<Wheel>() => {
+                let (_, delta) = wheel_delta(wheel.delta);
+                update(&this, |markdown| {
+                    markdown.scroll = markdown.scroll + delta;
+                    markdown.clamp_scroll(this.draw_region());
+                })
+                .await
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
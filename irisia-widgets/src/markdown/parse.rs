@@ -0,0 +1,884 @@
+// A parser of the part of CommonMark a `Markdown` draws. Link reference
+// definitions and HTML are not supported, and stay as plain text.
+
+/// A block of a document.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Block {
+    Heading {
+        level: u8,
+        content: Vec<Inline>,
+    },
+    Paragraph(Vec<Inline>),
+    /// A fenced or indented code block
+    Code(String),
+    Quote(Vec<Block>),
+    /// An ordered list numbered from `start`, or a bullet list if `None`
+    List {
+        start: Option<u64>,
+        items: Vec<Vec<Block>>,
+    },
+    Rule,
+}
+
+/// A run of text in a block.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Inline {
+    Text(String),
+    Emphasis(Vec<Inline>),
+    Strong(Vec<Inline>),
+    Code(String),
+    Link { url: String, content: Vec<Inline> },
+    Image { url: String, alt: String },
+    LineBreak,
+}
+
+pub(super) fn parse(source: &str) -> Vec<Block> {
+    let lines: Vec<String> = source.lines().map(expand_tabs).collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    blocks(&lines)
+}
+
+fn expand_tabs(line: &str) -> String {
+    let mut expanded = String::with_capacity(line.len());
+    let mut column = 0;
+    for ch in line.chars() {
+        if ch == '\t' {
+            let width = 4 - column % 4;
+            expanded.extend(std::iter::repeat(' ').take(width));
+            column += width;
+        } else {
+            expanded.push(ch);
+            column += 1;
+        }
+    }
+    expanded
+}
+
+fn blocks(lines: &[&str]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim_start_matches(' ');
+        if is_blank(line) {
+            index += 1;
+            continue;
+        }
+
+        let (block, next) = if indent(line) >= 4 {
+            indented_code(lines, index)
+        } else if let Some(fence) = fence(trimmed) {
+            fenced_code(lines, index, fence)
+        } else if let Some((level, text)) = heading(trimmed) {
+            let content = inlines(text);
+            (Block::Heading { level, content }, index + 1)
+        } else if is_rule(trimmed) {
+            (Block::Rule, index + 1)
+        } else if trimmed.starts_with('>') {
+            quote(lines, index)
+        } else if let Some(marker) = list_marker(line) {
+            list(lines, index, marker)
+        } else {
+            paragraph(lines, index)
+        };
+
+        blocks.push(block);
+        index = next;
+    }
+    blocks
+}
+
+fn indented_code(lines: &[&str], start: usize) -> (Block, usize) {
+    // blank lines at the end are not part of the code
+    let mut last = start;
+    let mut index = start;
+    while index < lines.len() && (is_blank(lines[index]) || indent(lines[index]) >= 4) {
+        if !is_blank(lines[index]) {
+            last = index;
+        }
+        index += 1;
+    }
+
+    let code: Vec<&str> = lines[start..=last]
+        .iter()
+        .map(|line| line.get(4..).unwrap_or(""))
+        .collect();
+    (Block::Code(code.join("\n")), last + 1)
+}
+
+fn fenced_code(lines: &[&str], start: usize, (ch, len): (char, usize)) -> (Block, usize) {
+    let fence_indent = indent(lines[start]);
+    let mut code = Vec::new();
+    let mut index = start + 1;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim_start_matches(' ');
+        index += 1;
+
+        let after = trimmed.trim_start_matches(ch);
+        if indent(line) < 4 && trimmed.len() - after.len() >= len && after.trim().is_empty() {
+            break;
+        }
+
+        // the indentation of the fence is removed from the code
+        code.push(&line[fence_indent.min(indent(line))..]);
+    }
+    (Block::Code(code.join("\n")), index)
+}
+
+fn quote(lines: &[&str], start: usize) -> (Block, usize) {
+    let mut inner: Vec<&str> = Vec::new();
+    let mut index = start;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim_start_matches(' ');
+        if indent(line) < 4 && trimmed.starts_with('>') {
+            let rest = &trimmed[1..];
+            inner.push(rest.strip_prefix(' ').unwrap_or(rest));
+        } else if !is_blank(line) && inner.last().is_some_and(|last| !is_blank(last)) {
+            // a paragraph in the quote may go on without the marker
+            if interrupts(line) {
+                break;
+            }
+            inner.push(line);
+        } else {
+            break;
+        }
+        index += 1;
+    }
+    (Block::Quote(blocks(&inner)), index)
+}
+
+fn list(lines: &[&str], start: usize, first: Marker) -> (Block, usize) {
+    let mut items = Vec::new();
+    let mut marker = first;
+    let mut index = start;
+
+    loop {
+        let mut item = vec![lines[index].get(marker.offset..).unwrap_or("")];
+        index += 1;
+
+        while index < lines.len() {
+            let line = lines[index];
+            if is_blank(line) {
+                // blank lines belong to the item only if it goes on after them
+                let goes_on = next_line(lines, index)
+                    .is_some_and(|next| indent(lines[next]) >= marker.offset);
+                if !goes_on {
+                    break;
+                }
+                item.push("");
+            } else if indent(line) >= marker.offset {
+                item.push(&line[marker.offset..]);
+            } else if item.last().is_some_and(|last| !is_blank(last))
+                && !interrupts(line)
+                && list_marker(line).is_none()
+            {
+                // a paragraph in the item may go on without the indentation,
+                // while any marker starts the next item
+                item.push(line.trim_start_matches(' '));
+            } else {
+                break;
+            }
+            index += 1;
+        }
+        items.push(blocks(&item));
+
+        // the list goes on with markers of the same kind
+        let Some(next) = next_line(lines, index)
+        else {
+            break;
+        };
+        match list_marker(lines[next]) {
+            Some(next_marker)
+                if next_marker.symbol == marker.symbol
+                    && next_marker.start.is_some() == marker.start.is_some()
+                    && !is_rule(lines[next].trim_start_matches(' ')) =>
+            {
+                marker = next_marker;
+                index = next;
+            }
+            _ => break,
+        }
+    }
+
+    let list = Block::List {
+        start: first.start,
+        items,
+    };
+    (list, index)
+}
+
+fn paragraph(lines: &[&str], start: usize) -> (Block, usize) {
+    let mut text = vec![lines[start].trim_start_matches(' ')];
+    let mut index = start + 1;
+
+    while index < lines.len() {
+        let line = lines[index];
+        if let Some(level) = setext_underline(line) {
+            let content = inlines(text.join("\n").trim_end());
+            return (Block::Heading { level, content }, index + 1);
+        }
+        if is_blank(line) || interrupts(line) {
+            break;
+        }
+
+        text.push(line.trim_start_matches(' '));
+        index += 1;
+    }
+    (Block::Paragraph(inlines(text.join("\n").trim_end())), index)
+}
+
+/// Index of the first line not blank from `start` on.
+fn next_line(lines: &[&str], start: usize) -> Option<usize> {
+    lines[start..]
+        .iter()
+        .position(|line| !is_blank(line))
+        .map(|at| start + at)
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Whether `line` starts a block which ends a paragraph before it.
+fn interrupts(line: &str) -> bool {
+    let trimmed = line.trim_start_matches(' ');
+    indent(line) < 4
+        && (fence(trimmed).is_some()
+            || heading(trimmed).is_some()
+            || is_rule(trimmed)
+            || trimmed.starts_with('>')
+            || list_marker(line)
+                .is_some_and(|marker| !marker.empty && matches!(marker.start, None | Some(1))))
+}
+
+/// Character and length of the fence opening a code block.
+fn fence(trimmed: &str) -> Option<(char, usize)> {
+    let ch = trimmed
+        .chars()
+        .next()
+        .filter(|ch| *ch == '`' || *ch == '~')?;
+    let after = trimmed.trim_start_matches(ch);
+    let len = trimmed.len() - after.len();
+    (len >= 3 && !(ch == '`' && after.contains('`'))).then_some((ch, len))
+}
+
+/// Level and text of an ATX heading, like `## Title`.
+fn heading(trimmed: &str) -> Option<(u8, &str)> {
+    let after = trimmed.trim_start_matches('#');
+    let level = trimmed.len() - after.len();
+    if !(1..=6).contains(&level) || !(after.is_empty() || after.starts_with(' ')) {
+        return None;
+    }
+
+    // the closing sequence of `#` is not a part of the text
+    let text = after.trim();
+    let text = match text.trim_end_matches('#') {
+        "" => "",
+        stripped if stripped.ends_with(' ') => stripped.trim_end(),
+        _ => text,
+    };
+    Some((level as u8, text))
+}
+
+/// Level of the heading made by underlining a paragraph with `=` or `-`.
+fn setext_underline(line: &str) -> Option<u8> {
+    let trimmed = line.trim();
+    if indent(line) >= 4 {
+        return None;
+    }
+
+    match trimmed.chars().next()? {
+        '=' if trimmed.chars().all(|ch| ch == '=') => Some(1),
+        '-' if trimmed.chars().all(|ch| ch == '-') => Some(2),
+        _ => None,
+    }
+}
+
+fn is_rule(trimmed: &str) -> bool {
+    let Some(ch) = trimmed
+        .chars()
+        .next()
+        .filter(|ch| matches!(ch, '-' | '*' | '_'))
+    else {
+        return false;
+    };
+    trimmed.chars().all(|other| other == ch || other == ' ')
+        && trimmed.chars().filter(|other| *other == ch).count() >= 3
+}
+
+#[derive(Clone, Copy)]
+struct Marker {
+    /// Number of an ordered item
+    start: Option<u64>,
+    /// The bullet, or the delimiter after the number
+    symbol: char,
+    /// Column the content of the item starts from
+    offset: usize,
+    empty: bool,
+}
+
+fn list_marker(line: &str) -> Option<Marker> {
+    let indent = indent(line);
+    if indent >= 4 {
+        return None;
+    }
+
+    let rest = &line[indent..];
+    let (start, symbol, width) = match rest.chars().next()? {
+        ch @ ('-' | '*' | '+') => (None, ch, 1),
+        _ => {
+            let digits = rest.len()
+                - rest
+                    .trim_start_matches(|ch: char| ch.is_ascii_digit())
+                    .len();
+            if !(1..=9).contains(&digits) {
+                return None;
+            }
+            let symbol = rest[digits..]
+                .chars()
+                .next()
+                .filter(|ch| *ch == '.' || *ch == ')')?;
+            (Some(rest[..digits].parse().ok()?), symbol, digits + 1)
+        }
+    };
+
+    let after = &rest[width..];
+    let spaces = after.len() - after.trim_start_matches(' ').len();
+    let empty = after.trim().is_empty();
+    if spaces == 0 && !after.is_empty() {
+        return None;
+    }
+
+    // content after five spaces or more is indented code, one space from the marker
+    let spaces = if empty || spaces > 4 { 1 } else { spaces };
+    Some(Marker {
+        start,
+        symbol,
+        offset: indent + width + spaces,
+        empty,
+    })
+}
+
+enum Node {
+    Inline(Inline),
+    /// A run of `*` or `_`, which may become emphasis
+    Delim {
+        ch: char,
+        count: usize,
+        open: bool,
+        close: bool,
+    },
+}
+
+/// Parse the text of a block into inlines.
+fn inlines(text: &str) -> Vec<Inline> {
+    let mut nodes = Vec::new();
+    let mut buffer = String::new();
+    let mut rest = text;
+
+    while let Some(ch) = rest.chars().next() {
+        match ch {
+            '\\' => match rest[1..].chars().next() {
+                Some('\n') => {
+                    flush(&mut buffer, &mut nodes);
+                    nodes.push(Node::Inline(Inline::LineBreak));
+                    rest = &rest[2..];
+                    continue;
+                }
+                Some(escaped) if escaped.is_ascii_punctuation() => {
+                    buffer.push(escaped);
+                    rest = &rest[2..];
+                    continue;
+                }
+                _ => {}
+            },
+            '`' => {
+                if let Some((code, len)) = code_span(rest) {
+                    flush(&mut buffer, &mut nodes);
+                    nodes.push(Node::Inline(Inline::Code(code)));
+                    rest = &rest[len..];
+                } else {
+                    // a run without a closing run of the same length is text
+                    let after = rest.trim_start_matches('`');
+                    buffer.push_str(&rest[..rest.len() - after.len()]);
+                    rest = after;
+                }
+                continue;
+            }
+            '!' if rest[1..].starts_with('[') => {
+                if let Some((alt, url, len)) = link(&rest[1..]) {
+                    flush(&mut buffer, &mut nodes);
+                    let alt = plain(&inlines(alt));
+                    nodes.push(Node::Inline(Inline::Image { url, alt }));
+                    rest = &rest[1 + len..];
+                    continue;
+                }
+            }
+            '[' => {
+                if let Some((label, url, len)) = link(rest) {
+                    flush(&mut buffer, &mut nodes);
+                    let content = inlines(label);
+                    nodes.push(Node::Inline(Inline::Link { url, content }));
+                    rest = &rest[len..];
+                    continue;
+                }
+            }
+            '<' => {
+                if let Some((url, len)) = autolink(rest) {
+                    flush(&mut buffer, &mut nodes);
+                    let content = vec![Inline::Text(url.to_string())];
+                    let url = url.to_string();
+                    nodes.push(Node::Inline(Inline::Link { url, content }));
+                    rest = &rest[len..];
+                    continue;
+                }
+            }
+            '*' | '_' => {
+                let after = rest.trim_start_matches(ch);
+                let count = rest.len() - after.len();
+                let before = text[..text.len() - rest.len()].chars().next_back();
+                let (open, close) = flanking(ch, before, after.chars().next());
+
+                flush(&mut buffer, &mut nodes);
+                nodes.push(Node::Delim {
+                    ch,
+                    count,
+                    open,
+                    close,
+                });
+                rest = after;
+                continue;
+            }
+            '\n' => {
+                // two spaces or more at the end of a line break it
+                let hard = buffer.ends_with("  ");
+                buffer.truncate(buffer.trim_end_matches(' ').len());
+                if hard {
+                    flush(&mut buffer, &mut nodes);
+                    nodes.push(Node::Inline(Inline::LineBreak));
+                } else {
+                    buffer.push(' ');
+                }
+                rest = rest[1..].trim_start_matches(' ');
+                continue;
+            }
+            _ => {}
+        }
+
+        buffer.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    flush(&mut buffer, &mut nodes);
+    emphasize(nodes)
+}
+
+fn flush(buffer: &mut String, nodes: &mut Vec<Node>) {
+    if !buffer.is_empty() {
+        nodes.push(Node::Inline(Inline::Text(std::mem::take(buffer))));
+    }
+}
+
+/// Whether a run of `ch` between `before` and `after` can open and close
+/// emphasis.
+fn flanking(ch: char, before: Option<char>, after: Option<char>) -> (bool, bool) {
+    let space = |ch: Option<char>| ch.map_or(true, char::is_whitespace);
+    let punct = |ch: Option<char>| ch.is_some_and(|ch| ch.is_ascii_punctuation());
+
+    let left = !space(after) && (!punct(after) || space(before) || punct(before));
+    let right = !space(before) && (!punct(before) || space(after) || punct(after));
+    if ch == '_' {
+        // not inside words
+        (
+            left && (!right || punct(before)),
+            right && (!left || punct(after)),
+        )
+    } else {
+        (left, right)
+    }
+}
+
+/// Pair runs of delimiters into emphasis, from the first closing run on.
+fn emphasize(mut nodes: Vec<Node>) -> Vec<Inline> {
+    let mut closer = 0;
+
+    while closer < nodes.len() {
+        let Node::Delim {
+            ch,
+            count,
+            open: closer_open,
+            close: true,
+        } = nodes[closer]
+        else {
+            closer += 1;
+            continue;
+        };
+
+        let opener = (0..closer).rev().find_map(|at| match nodes[at] {
+            Node::Delim {
+                ch: other,
+                count: other_count,
+                open: true,
+                close: opener_close,
+            } if other == ch && other_count > 0 && count > 0 => {
+                // runs which can both open and close pair only if their
+                // lengths do not add up to a multiple of three
+                let both = closer_open || opener_close;
+                let multiple = (other_count + count) % 3 == 0;
+                let exempt = other_count % 3 == 0 && count % 3 == 0;
+                (!both || !multiple || exempt).then_some((at, other_count))
+            }
+            _ => None,
+        });
+        let Some((opener, open_count)) = opener
+        else {
+            closer += 1;
+            continue;
+        };
+
+        let used = if open_count >= 2 && count >= 2 { 2 } else { 1 };
+        let content = to_inlines(nodes.drain(opener + 1..closer).collect());
+        let inline = if used == 2 {
+            Inline::Strong(content)
+        } else {
+            Inline::Emphasis(content)
+        };
+        nodes.insert(opener + 1, Node::Inline(inline));
+
+        for at in [opener, opener + 2] {
+            if let Node::Delim { count, .. } = &mut nodes[at] {
+                *count -= used;
+            }
+        }
+        closer = opener + 2;
+    }
+
+    to_inlines(nodes)
+}
+
+/// Turn runs of delimiters left into text.
+fn to_inlines(nodes: Vec<Node>) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    for node in nodes {
+        match node {
+            Node::Delim { ch, count, .. } => push_text(&mut inlines, &ch.to_string().repeat(count)),
+            Node::Inline(Inline::Text(text)) => push_text(&mut inlines, &text),
+            Node::Inline(inline) => inlines.push(inline),
+        }
+    }
+    inlines
+}
+
+fn push_text(inlines: &mut Vec<Inline>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+
+    match inlines.last_mut() {
+        Some(Inline::Text(last)) => last.push_str(text),
+        _ => inlines.push(Inline::Text(text.to_string())),
+    }
+}
+
+/// A code span at the start of `text`, as its code and its length.
+fn code_span(text: &str) -> Option<(String, usize)> {
+    let ticks = text.len() - text.trim_start_matches('`').len();
+    let mut at = ticks;
+
+    while let Some(found) = text[at..].find('`') {
+        let start = at + found;
+        let run = text[start..].len() - text[start..].trim_start_matches('`').len();
+        if run == ticks {
+            let code = text[ticks..start].replace('\n', " ");
+            // one space on both sides lets code start or end with backticks
+            let padded = code.len() >= 2
+                && code.starts_with(' ')
+                && code.ends_with(' ')
+                && !code.trim().is_empty();
+            let code = if padded {
+                code[1..code.len() - 1].to_string()
+            } else {
+                code
+            };
+            return Some((code, start + run));
+        }
+        at = start + run;
+    }
+    None
+}
+
+/// A link like `[label](url "title")` at the start of `text`, as its label,
+/// its url and its length.
+fn link(text: &str) -> Option<(&str, String, usize)> {
+    let close = closing_bracket(text)?;
+    let rest = text[close + 1..].strip_prefix('(')?;
+    let (url, len) = destination(rest)?;
+    Some((&text[1..close], url, close + 2 + len))
+}
+
+/// Index of the bracket closing the one `text` starts with.
+fn closing_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut at = 0;
+
+    while let Some(ch) = text[at..].chars().next() {
+        match ch {
+            '\\' => {
+                at += 1 + text[at + 1..].chars().next().map_or(0, char::len_utf8);
+                continue;
+            }
+            '`' => {
+                let rest = &text[at..];
+                let run = rest.len() - rest.trim_start_matches('`').len();
+                at += code_span(rest).map_or(run, |(_, len)| len);
+                continue;
+            }
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(at);
+                }
+            }
+            _ => {}
+        }
+        at += ch.len_utf8();
+    }
+    None
+}
+
+/// The url and the title of a link up to the closing parenthesis, as the url
+/// and the length. The title is not shown.
+fn destination(text: &str) -> Option<(String, usize)> {
+    let trimmed = text.trim_start();
+    let mut at = text.len() - trimmed.len();
+
+    let url = if let Some(rest) = trimmed.strip_prefix('<') {
+        let end = rest.find('>').filter(|end| !rest[..*end].contains('\n'))?;
+        at += end + 2;
+        &rest[..end]
+    } else {
+        let mut depth = 0;
+        let mut end = trimmed.len();
+        for (index, ch) in trimmed.char_indices() {
+            match ch {
+                '(' => depth += 1,
+                ')' if depth == 0 => {
+                    end = index;
+                    break;
+                }
+                ')' => depth -= 1,
+                ch if ch.is_whitespace() => {
+                    end = index;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        at += end;
+        &trimmed[..end]
+    };
+
+    let rest = text[at..].trim_start();
+    at = text.len() - rest.len();
+    if let Some(quote) = rest
+        .chars()
+        .next()
+        .filter(|ch| matches!(ch, '"' | '\'' | '('))
+    {
+        let close = if quote == '(' { ')' } else { quote };
+        let end = rest[1..].find(close)?;
+        at = text.len() - rest[end + 2..].trim_start().len();
+    }
+
+    text[at..]
+        .starts_with(')')
+        .then(|| (url.to_string(), at + 1))
+}
+
+/// An autolink like `<https://example.com>` at the start of `text`, as its
+/// url and its length.
+fn autolink(text: &str) -> Option<(&str, usize)> {
+    let end = text.find('>')?;
+    let url = &text[1..end];
+    let (scheme, _) = url.split_once(':')?;
+
+    let valid = (2..=32).contains(&scheme.len())
+        && scheme.starts_with(|ch: char| ch.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '.' | '-'))
+        && !url.contains(|ch: char| ch.is_whitespace() || ch == '<');
+    valid.then_some((url, end + 1))
+}
+
+/// Text of inlines without their styles.
+fn plain(inlines: &[Inline]) -> String {
+    let mut text = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(run) | Inline::Code(run) => text.push_str(run),
+            Inline::Emphasis(content) | Inline::Strong(content) => text.push_str(&plain(content)),
+            Inline::Link { content, .. } => text.push_str(&plain(content)),
+            Inline::Image { alt, .. } => text.push_str(alt),
+            Inline::LineBreak => text.push(' '),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+fn text(text: &str) -> Inline {
+    Inline::Text(text.to_string())
+}
+
+#[cfg(test)]
+fn paragraph_of(source: &str) -> Vec<Inline> {
+    match parse(source).as_slice() {
+        [Block::Paragraph(content)] => content.clone(),
+        other => panic!("expected a paragraph, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_emphasis_nesting() {
+    assert_eq!(
+        paragraph_of("*a **b** c*"),
+        [Inline::Emphasis(vec![
+            text("a "),
+            Inline::Strong(vec![text("b")]),
+            text(" c"),
+        ])]
+    );
+    assert_eq!(
+        paragraph_of("***a***"),
+        [Inline::Emphasis(vec![Inline::Strong(vec![text("a")])])]
+    );
+    assert_eq!(
+        paragraph_of("**a *b***"),
+        [Inline::Strong(vec![
+            text("a "),
+            Inline::Emphasis(vec![text("b")]),
+        ])]
+    );
+
+    // `_` does not open emphasis inside words
+    assert_eq!(paragraph_of("snake_case_name"), [text("snake_case_name")]);
+    assert_eq!(
+        paragraph_of("a*b*c"),
+        [text("a"), Inline::Emphasis(vec![text("b")]), text("c")]
+    );
+}
+
+#[test]
+fn test_links() {
+    assert_eq!(
+        paragraph_of("see [the *docs*](https://a.b/c \"title\") now"),
+        [
+            text("see "),
+            Inline::Link {
+                url: "https://a.b/c".to_string(),
+                content: vec![text("the "), Inline::Emphasis(vec![text("docs")])],
+            },
+            text(" now"),
+        ]
+    );
+    assert_eq!(
+        paragraph_of("[a](<b c>)"),
+        [Inline::Link {
+            url: "b c".to_string(),
+            content: vec![text("a")],
+        }]
+    );
+    assert_eq!(
+        paragraph_of("![an *image*](x.png)"),
+        [Inline::Image {
+            url: "x.png".to_string(),
+            alt: "an image".to_string(),
+        }]
+    );
+    assert_eq!(
+        paragraph_of("<https://a.b>"),
+        [Inline::Link {
+            url: "https://a.b".to_string(),
+            content: vec![text("https://a.b")],
+        }]
+    );
+}
+
+#[test]
+fn test_lists() {
+    assert_eq!(
+        parse("- a\n- b\n\n  c\n* d"),
+        [
+            Block::List {
+                start: None,
+                items: vec![
+                    vec![Block::Paragraph(vec![text("a")])],
+                    vec![
+                        Block::Paragraph(vec![text("b")]),
+                        Block::Paragraph(vec![text("c")]),
+                    ],
+                ],
+            },
+            Block::List {
+                start: None,
+                items: vec![vec![Block::Paragraph(vec![text("d")])]],
+            },
+        ]
+    );
+    assert_eq!(
+        parse("3. a\n4. b\n   - c"),
+        [Block::List {
+            start: Some(3),
+            items: vec![
+                vec![Block::Paragraph(vec![text("a")])],
+                vec![
+                    Block::Paragraph(vec![text("b")]),
+                    Block::List {
+                        start: None,
+                        items: vec![vec![Block::Paragraph(vec![text("c")])]],
+                    },
+                ],
+            ],
+        }]
+    );
+
+    // only a list starting from 1 interrupts a paragraph
+    assert_eq!(parse("a\n2. b"), [Block::Paragraph(vec![text("a 2. b")])]);
+}
+
+#[test]
+fn test_code_spans() {
+    assert_eq!(
+        paragraph_of("a `b *c*` d"),
+        [text("a "), Inline::Code("b *c*".to_string()), text(" d")]
+    );
+    assert_eq!(paragraph_of("`` a`b ``"), [Inline::Code("a`b".to_string())]);
+    assert_eq!(paragraph_of("`a\nb`"), [Inline::Code("a b".to_string())]);
+}
+
+#[test]
+fn test_unterminated() {
+    assert_eq!(paragraph_of("*a"), [text("*a")]);
+    assert_eq!(
+        paragraph_of("**a*"),
+        [text("*"), Inline::Emphasis(vec![text("a")])]
+    );
+    assert_eq!(paragraph_of("``a`"), [text("``a`")]);
+    assert_eq!(paragraph_of("[a](b"), [text("[a](b")]);
+    assert_eq!(paragraph_of("[a"), [text("[a")]);
+    assert_eq!(
+        parse("```\ncode\n\nmore"),
+        [Block::Code("code\n\nmore".to_string())]
+    );
+}
@@ -0,0 +1,18 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Color of links.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleMarkdownLink(#[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color);
+
+/// Background of inline code and code blocks.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleMarkdownCodeBackground(
+    #[style(default = "Color::from_rgb(0xf2, 0xf2, 0xf2)")] pub Color,
+);
+
+/// Color of the bar beside block quotes, and of rules.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleMarkdownQuote(#[style(default = "Color::from_rgb(0xd6, 0xd6, 0xd6)")] pub Color);
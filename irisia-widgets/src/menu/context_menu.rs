@@ -0,0 +1,126 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, LayoutElements, RenderElement},
+    event::standard::PointerDown,
+    primitive::{Point, Region},
+    winit::event::MouseButton,
+    ElModel,
+};
+
+use crate::popup::{region_contains, window_region};
+
+use super::{menu_runtime, nav::MenuNav, panel::MenuLook, MenuHost, MenuItem, MenuStyles};
+
+/// Wraps its children and opens a menu at the pointer when they are right-clicked.
+///
+/// Selecting an item emits [`MenuSelected`](super::MenuSelected) on the
+/// event dispatcher of this element.
+pub struct ContextMenu {
+    props: OwnedProps,
+    nav: MenuNav,
+}
+
+#[irisia::props(updater = "ContextMenuProps", watch)]
+pub struct OwnedProps {
+    #[props(default, watch)]
+    items: Vec<MenuItem>,
+
+    #[props(read_style(stdin))]
+    style: MenuStyles,
+}
+
+impl Element for ContextMenu {
+    type BlankProps = ContextMenuProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        this.set_interact_region(Some(this.draw_region()));
+        content.render_children()
+    }
+
+    fn set_children(&self, this: &ElModel!()) {
+        layout_children(this, this.set_children(this.slot()));
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        if let Some(lc) = this.layout_children() {
+            layout_children(this, lc);
+        }
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for ContextMenu
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(menu_runtime(this.clone()));
+        this.daemon(open_runtime(this.clone()));
+
+        ContextMenu {
+            props: OwnedProps::props_create_with(props),
+            nav: MenuNav::new(),
+        }
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.items_unchanged {
+            self.close_menu(this);
+        } else if !update_result.unchanged && self.nav.is_open() {
+            self.remount_menu(this);
+        }
+        update_result.unchanged && update_result.items_unchanged
+    }
+}
+
+impl MenuHost for ContextMenu {
+    fn menu(&mut self) -> (&[MenuItem], &mut MenuNav, MenuLook) {
+        (&self.props.items, &mut self.nav, self.props.style.look())
+    }
+
+    fn menu_nav(&self) -> &MenuNav {
+        &self.nav
+    }
+
+    fn close_menu(&mut self, this: &ElModel!()) {
+        self.nav.close();
+        this.remove_portal();
+    }
+
+    fn handles_press(&self, this: &ElModel!(), point: Point, button: MouseButton) -> bool {
+        // reopened at the new position by `open_runtime`
+        button == MouseButton::Right && region_contains(this.draw_region(), point)
+    }
+}
+
+fn layout_children(this: &ElModel!(ContextMenu), lc: LayoutElements) {
+    let region = this.draw_region();
+    lc.layout(|()| Some(region)).unwrap();
+}
+
+async fn open_runtime(this: ElModel!(ContextMenu)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let pd = ed.recv_trusted::<PointerDown>().await;
+        if pd.button != MouseButton::Right {
+            continue;
+        }
+
+        let Some(mut guard) = this.el_write().await
+        else {
+            return;
+        };
+
+        let menu = &mut *guard;
+        let look = menu.props.style.look();
+        let anchor = (pd.position, pd.position);
+        menu.nav.open(
+            &menu.props.items,
+            anchor,
+            window_region(this.window()),
+            &look,
+            false,
+        );
+        menu.remount_menu(&this);
+    }
+}
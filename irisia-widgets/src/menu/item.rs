@@ -0,0 +1,130 @@
+use std::{any::Any, rc::Rc};
+
+use irisia::event::EventDispatcher;
+
+use super::MenuSelected;
+
+/// An entry of a menu. Built with [`MenuItem::action`], [`MenuItem::check`],
+/// [`MenuItem::submenu`] or [`MenuItem::separator`].
+#[derive(Clone, PartialEq)]
+pub struct MenuItem {
+    pub(super) kind: ItemKind,
+    pub(super) label: String,
+    pub(super) accelerator: Option<String>,
+    pub(super) enabled: bool,
+}
+
+#[derive(Clone, PartialEq)]
+pub(super) enum ItemKind {
+    Action(ItemId),
+    Check(ItemId, bool),
+    Submenu(Vec<MenuItem>),
+    Separator,
+}
+
+impl MenuItem {
+    fn new(kind: ItemKind, label: String) -> Self {
+        MenuItem {
+            kind,
+            label,
+            accelerator: None,
+            enabled: true,
+        }
+    }
+
+    /// An item emitting [`MenuSelected<Id>`] with `checked` being `None` once selected.
+    pub fn action<Id>(id: Id, label: impl Into<String>) -> Self
+    where
+        Id: Clone + PartialEq + Send + Unpin + 'static,
+    {
+        Self::new(ItemKind::Action(ItemId(Rc::new(id))), label.into())
+    }
+
+    /// An item with a check mark. Selecting it emits [`MenuSelected<Id>`]
+    /// with the toggled state, the owner is expected to update the item.
+    pub fn check<Id>(id: Id, label: impl Into<String>, checked: bool) -> Self
+    where
+        Id: Clone + PartialEq + Send + Unpin + 'static,
+    {
+        Self::new(ItemKind::Check(ItemId(Rc::new(id)), checked), label.into())
+    }
+
+    pub fn submenu(label: impl Into<String>, items: impl IntoIterator<Item = MenuItem>) -> Self {
+        Self::new(ItemKind::Submenu(items.into_iter().collect()), label.into())
+    }
+
+    pub fn separator() -> Self {
+        Self::new(ItemKind::Separator, String::new())
+    }
+
+    /// Shortcut text shown at the end of the item, like `"Ctrl+S"`.
+    /// It is a label only, binding the shortcut is left to the owner.
+    pub fn accelerator(mut self, accelerator: impl Into<String>) -> Self {
+        self.accelerator = Some(accelerator.into());
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn disabled(self) -> Self {
+        self.enabled(false)
+    }
+
+    /// Whether the item can be highlighted and selected
+    pub(super) fn is_selectable(&self) -> bool {
+        self.enabled && !matches!(self.kind, ItemKind::Separator)
+    }
+
+    pub(super) fn submenu_items(&self) -> Option<&[MenuItem]> {
+        match &self.kind {
+            ItemKind::Submenu(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Type-erased id of an item, so that items with different id types
+/// can be put in one menu while staying comparable.
+#[derive(Clone)]
+pub(super) struct ItemId(Rc<dyn ErasedId>);
+
+impl ItemId {
+    pub fn emit(&self, ed: &EventDispatcher, checked: Option<bool>) {
+        self.0.emit(ed, checked)
+    }
+}
+
+impl PartialEq for ItemId {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_dyn(&*other.0)
+    }
+}
+
+trait ErasedId {
+    fn emit(&self, ed: &EventDispatcher, checked: Option<bool>);
+    fn as_any(&self) -> &dyn Any;
+    fn eq_dyn(&self, other: &dyn ErasedId) -> bool;
+}
+
+impl<Id> ErasedId for Id
+where
+    Id: Clone + PartialEq + Send + Unpin + 'static,
+{
+    fn emit(&self, ed: &EventDispatcher, checked: Option<bool>) {
+        ed.emit(MenuSelected {
+            id: self.clone(),
+            checked,
+        });
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_dyn(&self, other: &dyn ErasedId) -> bool {
+        other.as_any().downcast_ref::<Id>() == Some(self)
+    }
+}
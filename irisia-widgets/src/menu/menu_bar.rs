@@ -0,0 +1,310 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{PointerDown, PointerMove, PointerOut},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Font, Paint, RRect},
+    style::StyleColor,
+    winit::event::MouseButton,
+    ElModel, StyleReader,
+};
+
+use crate::{
+    label::{draw_label, label_font, label_width, to_rect},
+    popup::{region_contains, styles::StylePopupBackground, window_region},
+    textbox::styles::StyleFontSize,
+};
+
+use super::{
+    item::{ItemKind, MenuItem},
+    menu_look, menu_runtime,
+    nav::MenuNav,
+    panel::MenuLook,
+    styles::{StyleMenuBarBackground, StyleMenuHighlight},
+    MenuHost,
+};
+
+const TITLE_PADDING: Pixel = Pixel(10.0);
+
+/// A horizontal bar of menus. Each submenu item of `items` is shown as a title
+/// opening its items below, and action items act like buttons.
+///
+/// Selecting an item emits [`MenuSelected`](super::MenuSelected) on the
+/// event dispatcher of this element.
+pub struct MenuBar {
+    props: OwnedProps,
+    nav: MenuNav,
+    font: Font,
+    open_index: Option<usize>,
+    hovered: Option<usize>,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct MenuBarStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    bar_background: StyleMenuBarBackground,
+    background: StylePopupBackground,
+    highlight: StyleMenuHighlight,
+}
+
+#[irisia::props(updater = "MenuBarProps", watch)]
+pub struct OwnedProps {
+    #[props(default, watch)]
+    items: Vec<MenuItem>,
+
+    #[props(read_style(stdin))]
+    style: MenuBarStyles,
+}
+
+impl Element for MenuBar {
+    type BlankProps = MenuBarProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let look = self.look();
+        let canvas = content.canvas();
+
+        let mut paint = Paint::default();
+        paint.set_color(self.props.style.bar_background.0);
+        canvas.draw_rect(to_rect(region), &paint);
+
+        let active = self.open_index.or(self.hovered);
+        for (index, (item, title)) in self
+            .props
+            .items
+            .iter()
+            .zip(self.title_regions(region))
+            .enumerate()
+        {
+            if active == Some(index) && item.enabled {
+                let radius = Pixel(4.0).to_physical();
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true).set_color(look.highlight);
+                canvas.draw_rrect(RRect::new_rect_xy(to_rect(title), radius, radius), &paint);
+            }
+
+            let color = if item.enabled {
+                look.color
+            } else {
+                look.color.with_a(0x60)
+            };
+            draw_label(
+                canvas,
+                &self.font,
+                &item.label,
+                color,
+                title.0 .0 + TITLE_PADDING,
+                (title.0 .1, title.1 .1),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for MenuBar
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(menu_runtime(this.clone()));
+        this.daemon(bar_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        MenuBar {
+            font: label_font(props.style.font_size.map_or(Pixel(14.0), |size| size.0)),
+            props,
+            nav: MenuNav::new(),
+            open_index: None,
+            hovered: None,
+        }
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.unchanged {
+            self.font = label_font(self.look().font_size);
+        }
+
+        if !update_result.items_unchanged {
+            self.close_menu(this);
+        } else if !update_result.unchanged && self.nav.is_open() {
+            self.remount_menu(this);
+        }
+        update_result.unchanged && update_result.items_unchanged
+    }
+}
+
+impl MenuBar {
+    fn look(&self) -> MenuLook {
+        let style = &self.props.style;
+        menu_look(
+            style.font_size,
+            style.color,
+            style.background,
+            style.highlight,
+        )
+    }
+
+    fn title_regions(&self, (start, end): Region) -> impl Iterator<Item = Region> + '_ {
+        let mut left = start.0 + Pixel(4.0);
+        self.props.items.iter().map(move |item| {
+            let right = left + label_width(&self.font, &item.label) + TITLE_PADDING * 2.0;
+            let title = (Point(left, start.1), Point(right, end.1));
+            left = right;
+            title
+        })
+    }
+
+    fn title_at(&self, this: &ElModel!(), point: Point) -> Option<usize> {
+        self.title_regions(this.draw_region())
+            .position(|title| region_contains(title, point))
+    }
+
+    /// Open the menu of the title `index`, or select it if it is an action item.
+    fn open(&mut self, this: &ElModel!(), index: usize, highlight_first: bool) {
+        let look = self.look();
+        let Some((item, title)) = self
+            .props
+            .items
+            .iter()
+            .zip(self.title_regions(this.draw_region()))
+            .nth(index)
+            .filter(|(item, _)| item.enabled)
+        else {
+            return;
+        };
+
+        match &item.kind {
+            ItemKind::Submenu(items) => {
+                self.nav.open(
+                    items,
+                    title,
+                    window_region(this.window()),
+                    &look,
+                    highlight_first,
+                );
+                self.open_index = Some(index);
+                self.remount_menu(this);
+            }
+            ItemKind::Action(id) => {
+                let id = id.clone();
+                self.close_menu(this);
+                id.emit(this.event_dispatcher(), None);
+            }
+            ItemKind::Check(id, checked) => {
+                let (id, checked) = (id.clone(), !checked);
+                self.close_menu(this);
+                id.emit(this.event_dispatcher(), Some(checked));
+            }
+            ItemKind::Separator => {}
+        }
+    }
+}
+
+impl MenuHost for MenuBar {
+    fn menu(&mut self) -> (&[MenuItem], &mut MenuNav, MenuLook) {
+        let look = self.look();
+        let root = self
+            .open_index
+            .and_then(|index| self.props.items[index].submenu_items())
+            .unwrap_or(&[]);
+        (root, &mut self.nav, look)
+    }
+
+    fn menu_nav(&self) -> &MenuNav {
+        &self.nav
+    }
+
+    fn close_menu(&mut self, this: &ElModel!()) {
+        self.open_index = None;
+        self.nav.close();
+        this.remove_portal();
+    }
+
+    fn switch_menu(&mut self, this: &ElModel!(), forward: bool) {
+        let Some(current) = self.open_index
+        else {
+            return;
+        };
+
+        let items = &self.props.items;
+        let len = items.len();
+        let next = (1..len)
+            .map(|offset| {
+                if forward {
+                    (current + offset) % len
+                } else {
+                    (current + len - offset) % len
+                }
+            })
+            .find(|&index| items[index].enabled && items[index].submenu_items().is_some());
+
+        if let Some(index) = next {
+            self.open(this, index, true);
+        }
+    }
+
+    fn handles_press(&self, this: &ElModel!(), point: Point, button: MouseButton) -> bool {
+        // toggled by `bar_runtime`
+        button == MouseButton::Left && self.title_at(this, point).is_some()
+    }
+}
+
+async fn bar_runtime(this: ElModel!(MenuBar)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => {
+                if pd.button != MouseButton::Left {
+                    continue;
+                }
+
+                let Some(mut bar) = this.el_write().await
+                else {
+                    return;
+                };
+
+                match bar.title_at(&this, pd.position) {
+                    Some(index) if bar.open_index == Some(index) => bar.close_menu(&this),
+                    Some(index) => bar.open(&this, index, false),
+                    None => {}
+                }
+            }
+            pm = ed.recv_trusted::<PointerMove>() => {
+                let Some(bar) = this.el_read().await
+                else {
+                    return;
+                };
+
+                let hovered = bar.title_at(&this, pm.position);
+                if hovered == bar.hovered {
+                    continue;
+                }
+                drop(bar);
+
+                let Some(mut bar) = this.el_write().await
+                else {
+                    return;
+                };
+
+                bar.hovered = hovered;
+                // switch between menus by hovering once any of them is open
+                if let (Some(index), Some(open_index)) = (hovered, bar.open_index) {
+                    if index != open_index {
+                        bar.open(&this, index, false);
+                    }
+                }
+            }
+            _ = ed.recv_trusted::<PointerOut>() => {
+                let Some(mut bar) = this.el_write().await
+                else {
+                    return;
+                };
+                bar.hovered = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use irisia::{
+    element::Element,
+    event::standard::PortalPressedOutside,
+    primitive::{Pixel, Point},
+    skia_safe::Color,
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, MouseButton, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{popup::styles::StylePopupBackground, textbox::styles::StyleFontSize};
+
+use self::{
+    nav::{MenuNav, NavOutcome},
+    panel::{MenuLook, PanelActivate, PanelHover},
+    styles::StyleMenuHighlight,
+};
+
+pub use self::{
+    context_menu::{ContextMenu, ContextMenuProps},
+    item::MenuItem,
+    menu_bar::{MenuBar, MenuBarProps},
+};
+
+mod context_menu;
+mod item;
+mod menu_bar;
+mod nav;
+mod panel;
+pub mod styles;
+
+/// Emitted on the event dispatcher of the [`ContextMenu`] or [`MenuBar`]
+/// when an item with id of type `Id` is selected.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct MenuSelected<Id: Clone + Send + Unpin + 'static> {
+    pub id: Id,
+    /// The new state of a check item, `None` for other items.
+    pub checked: Option<bool>,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct MenuStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: StylePopupBackground,
+    highlight: StyleMenuHighlight,
+}
+
+impl MenuStyles {
+    fn look(&self) -> MenuLook {
+        menu_look(self.font_size, self.color, self.background, self.highlight)
+    }
+}
+
+fn menu_look(
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: StylePopupBackground,
+    highlight: StyleMenuHighlight,
+) -> MenuLook {
+    MenuLook {
+        font_size: font_size.map_or(Pixel(14.0), |size| size.0),
+        color: color.map_or(Color::BLACK, |color| color.0),
+        background: background.0,
+        highlight: highlight.0,
+    }
+}
+
+/// Elements owning a menu, sharing the input handling of opened panels.
+trait MenuHost: Element {
+    /// Items of the root panel, the navigation state and the look of panels
+    fn menu(&mut self) -> (&[MenuItem], &mut MenuNav, MenuLook);
+
+    fn menu_nav(&self) -> &MenuNav;
+
+    fn close_menu(&mut self, this: &ElModel!());
+
+    /// Left or right pressed where the menu has nothing to open or close.
+    fn switch_menu(&mut self, _this: &ElModel!(), _forward: bool) {}
+
+    /// Whether a press outside the panels is handled by the host itself,
+    /// rather than closing the menu.
+    fn handles_press(&self, this: &ElModel!(), point: Point, button: MouseButton) -> bool;
+
+    fn remount_menu(&mut self, this: &ElModel!()) {
+        let (root, nav, look) = self.menu();
+        nav.mount(this, root, look);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum MenuInput {
+    Hover(PanelHover),
+    Activate(PanelActivate),
+    Key(VirtualKeyCode),
+    Press(Point, MouseButton),
+}
+
+async fn menu_runtime<El: MenuHost>(this: ElModel!(El)) {
+    let ed = this.event_dispatcher().clone();
+    let global_ed = this.global().event_dispatcher().clone();
+
+    loop {
+        let input = tokio::select! {
+            (hover, _) = ed.recv::<PanelHover>() => MenuInput::Hover(hover),
+            (activate, _) = ed.recv::<PanelActivate>() => MenuInput::Activate(activate),
+            pressed = ed.recv_trusted::<PortalPressedOutside>() => {
+                MenuInput::Press(pressed.position, pressed.button)
+            }
+            // keys are left to popups opened above the menu
+            event = global_ed.recv_trusted::<StaticWindowEvent>() => match event {
+                StaticWindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(key),
+                            ..
+                        },
+                    ..
+                } if this.portal_on_top() => MenuInput::Key(key),
+                _ => continue,
+            },
+        };
+
+        // avoid setting the host dirty for inputs while closed
+        match this.el_read().await {
+            Some(host) if host.menu_nav().is_open() => {}
+            Some(_) => continue,
+            None => return,
+        }
+
+        let Some(mut host) = this.el_write().await
+        else {
+            return;
+        };
+
+        let outcome = {
+            let (root, nav, _) = host.menu();
+            match input {
+                MenuInput::Hover(PanelHover { level, index }) => {
+                    nav.hover(root, level, index);
+                    NavOutcome::None
+                }
+                MenuInput::Activate(PanelActivate { level, index }) => {
+                    nav.activate(root, level, index)
+                }
+                MenuInput::Key(key) => nav.key(root, key),
+                MenuInput::Press(..) => NavOutcome::Close,
+            }
+        };
+
+        match outcome {
+            NavOutcome::None => host.remount_menu(&this),
+            NavOutcome::Close => {
+                if let MenuInput::Press(point, button) = input {
+                    if host.handles_press(&this, point, button) {
+                        continue;
+                    }
+                }
+                host.close_menu(&this);
+            }
+            NavOutcome::Select(id, checked) => {
+                host.close_menu(&this);
+                id.emit(this.event_dispatcher(), checked);
+            }
+            NavOutcome::Prev => host.switch_menu(&this, false),
+            NavOutcome::Next => host.switch_menu(&this, true),
+        }
+    }
+}
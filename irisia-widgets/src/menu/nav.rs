@@ -0,0 +1,285 @@
+use irisia::{
+    build,
+    element::Element,
+    primitive::{Pixel, Point, Region},
+    skia_safe::Font,
+    winit::event::VirtualKeyCode,
+    ElModel,
+};
+
+use crate::{
+    label::label_font,
+    popup::{place, z_index, Align, PortalOwner, Side},
+};
+
+use super::{
+    item::{ItemId, ItemKind, MenuItem},
+    panel::{panel_size, row_regions, MenuLook, MenuPanel},
+};
+
+/// Opened panels of a menu, from the root panel to the innermost submenu.
+///
+/// Panel `n + 1` is always the submenu of the highlighted item of panel `n`.
+pub(super) struct MenuNav {
+    levels: Vec<Level>,
+    font: Font,
+    bounds: Region,
+}
+
+#[derive(Clone, Copy)]
+struct Level {
+    highlighted: Option<usize>,
+    region: Region,
+}
+
+/// What the owner should do after the menu handled an input.
+pub(super) enum NavOutcome {
+    None,
+    Close,
+    Select(ItemId, Option<bool>),
+    /// Left pressed on the root panel, used by the menu bar to switch menus
+    Prev,
+    /// Right pressed on an item without submenu
+    Next,
+}
+
+impl MenuNav {
+    pub fn new() -> Self {
+        MenuNav {
+            levels: Vec::new(),
+            font: label_font(Pixel(14.0)),
+            bounds: (Point(Pixel(0.0), Pixel(0.0)), Point(Pixel(0.0), Pixel(0.0))),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.levels.is_empty()
+    }
+
+    /// Open the root panel below `anchor`, with the first item highlighted
+    /// if `highlight_first` is set.
+    pub fn open(
+        &mut self,
+        root: &[MenuItem],
+        anchor: Region,
+        bounds: Region,
+        look: &MenuLook,
+        highlight_first: bool,
+    ) {
+        self.font = label_font(look.font_size);
+        self.bounds = bounds;
+        self.levels.clear();
+        if root.is_empty() {
+            return;
+        }
+
+        let region = place(
+            anchor,
+            panel_size(root, &self.font),
+            Side::Bottom,
+            Align::Start,
+            Pixel(0.0),
+            bounds,
+        );
+        self.levels.push(Level {
+            highlighted: highlight_first
+                .then(|| next_selectable(root, None, true))
+                .flatten(),
+            region,
+        });
+    }
+
+    pub fn close(&mut self) {
+        self.levels.clear();
+    }
+
+    fn items_at<'a>(&self, root: &'a [MenuItem], level: usize) -> &'a [MenuItem] {
+        let mut items = root;
+        for parent in &self.levels[..level] {
+            match parent
+                .highlighted
+                .and_then(|index| items[index].submenu_items())
+            {
+                Some(submenu) => items = submenu,
+                None => return &[],
+            }
+        }
+        items
+    }
+
+    /// Open the submenu of the highlighted item of the innermost panel, if it has one.
+    fn open_submenu(&mut self, root: &[MenuItem], highlight_first: bool) -> bool {
+        let level = self.levels.len() - 1;
+        let parent = self.levels[level];
+        let items = self.items_at(root, level);
+
+        let Some(index) = parent.highlighted
+        else {
+            return false;
+        };
+
+        let item = &items[index];
+        let Some(submenu) = item.submenu_items().filter(|_| item.enabled)
+        else {
+            return false;
+        };
+
+        let row = row_regions(parent.region, items).nth(index).unwrap();
+        let region = place(
+            row,
+            panel_size(submenu, &self.font),
+            Side::Right,
+            Align::Start,
+            Pixel(0.0),
+            self.bounds,
+        );
+        self.levels.push(Level {
+            highlighted: highlight_first
+                .then(|| next_selectable(submenu, None, true))
+                .flatten(),
+            region,
+        });
+        true
+    }
+
+    /// Pointer moved onto the row `index` of the panel `level`, or out of it.
+    pub fn hover(&mut self, root: &[MenuItem], level: usize, index: Option<usize>) {
+        if level >= self.levels.len() {
+            return;
+        }
+
+        let Some(index) = index
+        else {
+            // keep the highlight of the item whose submenu is opened
+            if level + 1 == self.levels.len() {
+                self.levels[level].highlighted = None;
+            }
+            return;
+        };
+
+        if self.levels[level].highlighted == Some(index) && level + 1 < self.levels.len() {
+            return;
+        }
+
+        let selectable = self.items_at(root, level)[index].is_selectable();
+        self.levels.truncate(level + 1);
+        self.levels[level].highlighted = selectable.then_some(index);
+        self.open_submenu(root, false);
+    }
+
+    /// Row `index` of the panel `level` was clicked.
+    pub fn activate(&mut self, root: &[MenuItem], level: usize, index: usize) -> NavOutcome {
+        if level >= self.levels.len() {
+            return NavOutcome::None;
+        }
+
+        let Some(item) = self.items_at(root, level).get(index)
+        else {
+            return NavOutcome::None;
+        };
+
+        if !item.enabled {
+            return NavOutcome::None;
+        }
+
+        match &item.kind {
+            ItemKind::Action(id) => NavOutcome::Select(id.clone(), None),
+            ItemKind::Check(id, checked) => NavOutcome::Select(id.clone(), Some(!checked)),
+            ItemKind::Submenu(_) => {
+                self.levels.truncate(level + 1);
+                self.levels[level].highlighted = Some(index);
+                self.open_submenu(root, true);
+                NavOutcome::None
+            }
+            ItemKind::Separator => NavOutcome::None,
+        }
+    }
+
+    pub fn key(&mut self, root: &[MenuItem], key: VirtualKeyCode) -> NavOutcome {
+        let Some(last) = self.levels.len().checked_sub(1)
+        else {
+            return NavOutcome::None;
+        };
+
+        let items = self.items_at(root, last);
+        let highlighted = self.levels[last].highlighted;
+
+        match key {
+            VirtualKeyCode::Down | VirtualKeyCode::Up => {
+                self.levels[last].highlighted =
+                    next_selectable(items, highlighted, key == VirtualKeyCode::Down);
+                NavOutcome::None
+            }
+            VirtualKeyCode::Right => {
+                if self.open_submenu(root, true) {
+                    NavOutcome::None
+                } else {
+                    NavOutcome::Next
+                }
+            }
+            VirtualKeyCode::Left if last > 0 => {
+                self.levels.pop();
+                NavOutcome::None
+            }
+            VirtualKeyCode::Left => NavOutcome::Prev,
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Space => {
+                match highlighted {
+                    Some(index) => self.activate(root, last, index),
+                    None => NavOutcome::None,
+                }
+            }
+            VirtualKeyCode::Escape if last > 0 => {
+                self.levels.pop();
+                NavOutcome::None
+            }
+            VirtualKeyCode::Escape => NavOutcome::Close,
+            _ => NavOutcome::None,
+        }
+    }
+
+    /// Mount a panel for each level in the portal of `this`, or remove the portal if closed.
+    pub fn mount<El>(&self, this: &ElModel!(El), root: &[MenuItem], look: MenuLook)
+    where
+        El: Element,
+    {
+        if !self.is_open() {
+            this.remove_portal();
+            return;
+        }
+
+        let owner = PortalOwner(this.event_dispatcher().clone());
+        let mut regions = self.levels.iter().map(|level| level.region);
+
+        this.set_portal(
+            z_index::MENU,
+            build! {
+                for (level, state) in self.levels.iter().enumerate() {
+                    @key level;
+                    MenuPanel {
+                        items: self.items_at(root, level).to_vec(),
+                        highlighted: state.highlighted,
+                        level: level,
+                        owner: owner.clone(),
+                        look: look,
+                    }
+                }
+            },
+        )
+        .layout(|()| regions.next())
+        .unwrap();
+    }
+}
+
+/// Next selectable item after `from` in the direction, wrapping around.
+/// Starts from either end if `from` is `None`.
+fn next_selectable(items: &[MenuItem], from: Option<usize>, forward: bool) -> Option<usize> {
+    let len = items.len();
+    (1..=len)
+        .map(|offset| match (from, forward) {
+            (Some(index), true) => (index + offset) % len,
+            (Some(index), false) => (index + len - offset) % len,
+            (None, true) => offset - 1,
+            (None, false) => len - offset,
+        })
+        .find(|&index| items[index].is_selectable())
+}
@@ -0,0 +1,294 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Click, PointerMove, PointerOut},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Canvas, Color, Font, Paint, PaintStyle, Path, RRect},
+    winit::event::MouseButton,
+    ElModel, Event,
+};
+
+use crate::{
+    label::{draw_label, label_font, label_width, to_rect},
+    popup::{draw_surface, region_contains, PortalOwner},
+};
+
+use super::item::{ItemKind, MenuItem};
+
+const PADDING: Pixel = Pixel(4.0);
+const ROW_HEIGHT: Pixel = Pixel(26.0);
+const SEPARATOR_HEIGHT: Pixel = Pixel(9.0);
+const CHECK_COLUMN: Pixel = Pixel(28.0);
+const ARROW_COLUMN: Pixel = Pixel(24.0);
+const ACCELERATOR_GAP: Pixel = Pixel(24.0);
+const MIN_WIDTH: Pixel = Pixel(160.0);
+
+/// Colors and font size shared by every panel of a menu.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) struct MenuLook {
+    pub font_size: Pixel,
+    pub color: Color,
+    pub background: Color,
+    pub highlight: Color,
+}
+
+/// The pointer moved onto a row of the panel of `level`, or left it if `index` is `None`.
+#[derive(Event, Clone, Copy)]
+pub(super) struct PanelHover {
+    pub level: usize,
+    pub index: Option<usize>,
+}
+
+/// A row of the panel of `level` was clicked.
+#[derive(Event, Clone, Copy)]
+pub(super) struct PanelActivate {
+    pub level: usize,
+    pub index: usize,
+}
+
+pub(super) fn panel_size(items: &[MenuItem], font: &Font) -> (Pixel, Pixel) {
+    let mut width = Pixel(0.0);
+    let mut height = PADDING * 2.0;
+    for item in items {
+        height = height + row_height(item);
+        let accelerator = item
+            .accelerator
+            .as_deref()
+            .map_or(Pixel(0.0), |text| ACCELERATOR_GAP + label_width(font, text));
+        width = width.max(label_width(font, &item.label) + accelerator);
+    }
+    ((width + CHECK_COLUMN + ARROW_COLUMN).max(MIN_WIDTH), height)
+}
+
+fn row_height(item: &MenuItem) -> Pixel {
+    match item.kind {
+        ItemKind::Separator => SEPARATOR_HEIGHT,
+        _ => ROW_HEIGHT,
+    }
+}
+
+/// Regions of each item inside the panel region
+pub(super) fn row_regions(panel: Region, items: &[MenuItem]) -> impl Iterator<Item = Region> + '_ {
+    let mut top = panel.0 .1 + PADDING;
+    items.iter().map(move |item| {
+        let bottom = top + row_height(item);
+        let row = (Point(panel.0 .0, top), Point(panel.1 .0, bottom));
+        top = bottom;
+        row
+    })
+}
+
+fn row_at(panel: Region, items: &[MenuItem], point: Point) -> Option<usize> {
+    row_regions(panel, items).position(|row| region_contains(row, point))
+}
+
+/// One level of an opened menu, mounted in the portal of the owner.
+pub(super) struct MenuPanel {
+    props: OwnedProps,
+    font: Font,
+}
+
+#[irisia::props(updater = "MenuPanelProps", watch)]
+pub(super) struct OwnedProps {
+    #[props(must_init)]
+    items: Vec<MenuItem>,
+
+    #[props(default)]
+    highlighted: Option<usize>,
+
+    #[props(must_init)]
+    level: usize,
+
+    /// Receives [`PanelHover`] and [`PanelActivate`]
+    #[props(must_init)]
+    owner: PortalOwner,
+
+    #[props(must_init)]
+    look: MenuLook,
+}
+
+impl Element for MenuPanel {
+    type BlankProps = MenuPanelProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let OwnedProps {
+            items,
+            highlighted,
+            look,
+            ..
+        } = &self.props;
+
+        let canvas = content.canvas();
+        draw_surface(canvas, region, look.background, Pixel(6.0), true);
+        for (index, (item, row)) in items.iter().zip(row_regions(region, items)).enumerate() {
+            draw_row(
+                canvas,
+                &self.font,
+                item,
+                row,
+                *highlighted == Some(index),
+                look,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for MenuPanel
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        MenuPanel {
+            font: label_font(props.look.font_size),
+            props,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let font_size = self.props.look.font_size;
+        let unchanged = self.props.props_update_with(props).unchanged;
+        if self.props.look.font_size != font_size {
+            self.font = label_font(self.props.look.font_size);
+        }
+        unchanged
+    }
+}
+
+fn draw_row(
+    canvas: &mut Canvas,
+    font: &Font,
+    item: &MenuItem,
+    (start, end): Region,
+    highlighted: bool,
+    look: &MenuLook,
+) {
+    if let ItemKind::Separator = item.kind {
+        let y = (start.1 + end.1) / 2.0;
+        let mut paint = Paint::default();
+        paint.set_color(Color::from_argb(0x30, 0, 0, 0));
+        canvas.draw_rect(
+            to_rect((
+                Point(start.0 + PADDING * 2.0, y),
+                Point(end.0 - PADDING * 2.0, y + Pixel(1.0)),
+            )),
+            &paint,
+        );
+        return;
+    }
+
+    if highlighted && item.enabled {
+        let radius = Pixel(4.0).to_physical();
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_color(look.highlight);
+        canvas.draw_rrect(
+            RRect::new_rect_xy(
+                to_rect((
+                    Point(start.0 + PADDING, start.1),
+                    Point(end.0 - PADDING, end.1),
+                )),
+                radius,
+                radius,
+            ),
+            &paint,
+        );
+    }
+
+    let color = if item.enabled {
+        look.color
+    } else {
+        look.color.with_a(0x60)
+    };
+    let center = (start.1 + end.1) / 2.0;
+
+    let mut stroke = Paint::default();
+    stroke
+        .set_anti_alias(true)
+        .set_color(color)
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(Pixel(1.5).to_physical());
+
+    if let ItemKind::Check(_, true) = item.kind {
+        let x = start.0 + Pixel(10.0);
+        let mut path = Path::new();
+        path.move_to(Point(x, center))
+            .line_to(Point(x + Pixel(3.0), center + Pixel(3.0)))
+            .line_to(Point(x + Pixel(9.0), center - Pixel(4.0)));
+        canvas.draw_path(&path, &stroke);
+    }
+
+    draw_label(
+        canvas,
+        font,
+        &item.label,
+        color,
+        start.0 + CHECK_COLUMN,
+        (start.1, end.1),
+    );
+
+    if let Some(accelerator) = &item.accelerator {
+        let x = end.0 - ARROW_COLUMN - label_width(font, accelerator);
+        draw_label(
+            canvas,
+            font,
+            accelerator,
+            color.with_a(color.a() / 2),
+            x,
+            (start.1, end.1),
+        );
+    }
+
+    if let ItemKind::Submenu(_) = item.kind {
+        let x = end.0 - ARROW_COLUMN / 2.0;
+        let mut path = Path::new();
+        path.move_to(Point(x - Pixel(2.0), center - Pixel(4.0)))
+            .line_to(Point(x + Pixel(2.0), center))
+            .line_to(Point(x - Pixel(2.0), center + Pixel(4.0)));
+        canvas.draw_path(&path, &stroke);
+    }
+}
+
+async fn pointer_runtime(this: ElModel!(MenuPanel)) {
+    let ed = this.event_dispatcher().clone();
+    let mut hovered = None;
+
+    loop {
+        let (position, clicked) = tokio::select! {
+            pm = ed.recv_trusted::<PointerMove>() => (Some(pm.position), false),
+            click = ed.recv_trusted::<Click>() => {
+                if click.button != MouseButton::Left {
+                    continue;
+                }
+                (Some(click.position), true)
+            }
+            _ = ed.recv_trusted::<PointerOut>() => (None, false),
+        };
+
+        let Some(panel) = this.el_read().await
+        else {
+            return;
+        };
+
+        let OwnedProps {
+            ref items,
+            level,
+            ref owner,
+            ..
+        } = panel.props;
+        let index = position.and_then(|point| row_at(this.draw_region(), items, point));
+
+        if clicked {
+            if let Some(index) = index {
+                owner.0.emit(PanelActivate { level, index });
+            }
+        } else if index != hovered {
+            hovered = index;
+            owner.0.emit(PanelHover { level, index });
+        }
+    }
+}
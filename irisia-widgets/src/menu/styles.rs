@@ -0,0 +1,13 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Background of the highlighted menu item.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleMenuHighlight(#[style(default = "Color::from_rgb(0xd6, 0xe6, 0xfd)")] pub Color);
+
+/// Background of the menu bar.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleMenuBarBackground(
+    #[style(default = "Color::from_rgb(0xf3, 0xf3, 0xf3)")] pub Color,
+);
@@ -0,0 +1,203 @@
+use irisia::{
+    element::{one_child, props::PropsUpdateWith, Element, ElementUpdate},
+    primitive::{Pixel, Point, Region},
+    structure::Once,
+    winit::event::{ElementState, KeyboardInput, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::popup::{
+    dismiss_runtime, place, region_contains,
+    styles::{StylePopupBackground, StylePopupGap, StylePopupRadius},
+    window_region, z_index, Align, Dismiss, PopupSurface, PopupSurfaceProps, Side,
+};
+
+/// Shows its children in a popup beside the anchor while `open` is `true`.
+///
+/// The anchor is the draw region of the popover unless specified by the
+/// `anchor` prop. Clicking outside the popup and the anchor, or pressing
+/// Escape while no other popup is above, hides the popup and emits
+/// [`PopoverDismissed`], and the owner is expected to set `open` to `false`
+/// in response.
+pub struct Popover {
+    props: OwnedProps,
+    shown: bool,
+}
+
+/// Emitted on the popover's event dispatcher when it was dismissed by the user.
+#[derive(Event, Clone, Copy, PartialEq)]
+pub struct PopoverDismissed;
+
+#[derive(StyleReader, PartialEq)]
+struct PopoverStyles {
+    background: StylePopupBackground,
+    radius: StylePopupRadius,
+    gap: StylePopupGap,
+}
+
+#[irisia::props(updater = "PopoverProps", watch)]
+pub struct OwnedProps {
+    #[props(default, watch)]
+    open: bool,
+
+    /// Preferred side, flipped if there is no enough room.
+    #[props(default = "Side::Bottom")]
+    side: Side,
+
+    #[props(default)]
+    align: Align,
+
+    /// Region to place the popup beside, in window coordinates.
+    /// Defaults to the draw region of the popover.
+    #[props(default)]
+    anchor: Option<Region>,
+
+    #[props(default = "Pixel(240.0)")]
+    width: Pixel,
+
+    #[props(default = "Pixel(160.0)")]
+    height: Pixel,
+
+    #[props(default = "Pixel(8.0)")]
+    padding: Pixel,
+
+    #[props(read_style(stdin))]
+    style: PopoverStyles,
+}
+
+impl Element for Popover {
+    type BlankProps = PopoverProps;
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        if self.shown {
+            self.show(this);
+        }
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Popover
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(dismiss_runtime(this.clone()));
+        this.daemon(escape_runtime(this.clone()));
+
+        let mut popover = Popover {
+            props: OwnedProps::props_create_with(props),
+            shown: false,
+        };
+        popover.sync_with_props(this);
+        popover
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+
+        // a dismissed popover stays hidden until `open` set again
+        if !update_result.open_unchanged {
+            self.sync_with_props(this);
+        } else if !update_result.unchanged && self.shown {
+            self.show(this);
+        }
+        update_result.unchanged && update_result.open_unchanged
+    }
+}
+
+impl Popover {
+    fn sync_with_props(&mut self, this: &ElModel!()) {
+        self.shown = self.props.open;
+        if self.shown {
+            self.show(this);
+        } else {
+            self.hide(this);
+        }
+    }
+
+    fn anchor(&self, this: &ElModel!()) -> Region {
+        self.props.anchor.unwrap_or_else(|| this.draw_region())
+    }
+
+    fn show(&mut self, this: &ElModel!()) {
+        let OwnedProps {
+            side,
+            align,
+            width,
+            height,
+            padding,
+            ref style,
+            ..
+        } = self.props;
+
+        let region = place(
+            self.anchor(this),
+            (width, height),
+            side,
+            align,
+            style.gap.0,
+            window_region(this.window()),
+        );
+
+        this.set_portal(
+            z_index::POPOVER,
+            Once(one_child::<PopupSurface, _, _, _, _>(
+                PopupSurfaceProps::default()
+                    .background(style.background.0)
+                    .radius(style.radius.0)
+                    .padding(padding),
+                (),
+                this.slot(),
+                |_: &_| {},
+            )),
+        )
+        .layout_once(region)
+        .unwrap();
+    }
+
+    fn hide(&mut self, this: &ElModel!()) {
+        this.remove_portal();
+    }
+}
+
+impl Dismiss for Popover {
+    // pressing on the anchor is left to the owner, which usually toggles the popover
+    fn dismissed_by(&self, this: &ElModel!(), point: Point) -> bool {
+        self.shown && !region_contains(self.anchor(this), point)
+    }
+
+    fn dismiss(&mut self, this: &ElModel!()) {
+        self.shown = false;
+        self.hide(this);
+        this.event_dispatcher().emit(PopoverDismissed);
+    }
+}
+
+/// Dismiss the popover on Escape, unless another popup is above it.
+async fn escape_runtime(this: ElModel!(Popover)) {
+    let global_ed = this.global().event_dispatcher().clone();
+
+    loop {
+        let event = global_ed.recv_trusted::<StaticWindowEvent>().await;
+        let StaticWindowEvent::KeyboardInput {
+            input:
+                KeyboardInput {
+                    state: ElementState::Pressed,
+                    virtual_keycode: Some(VirtualKeyCode::Escape),
+                    ..
+                },
+            ..
+        } = event
+        else {
+            continue;
+        };
+
+        if !this.portal_on_top() {
+            continue;
+        }
+
+        match this.el_write().await {
+            Some(mut popover) => popover.dismiss(&this),
+            None => return,
+        }
+    }
+}
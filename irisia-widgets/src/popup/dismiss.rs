@@ -0,0 +1,35 @@
+use irisia::{element::Element, event::standard::PortalPressedOutside, primitive::Point, ElModel};
+
+/// Elements closing the popup they mounted on the overlay when a pointer
+/// pressed outside of it.
+pub(crate) trait Dismiss: Element {
+    /// Whether a press at `point`, which is outside the popup, closes the
+    /// popup. Usually `false` if closed already, or if pressed on the
+    /// element opening the popup, which toggles the popup by itself.
+    fn dismissed_by(&self, this: &ElModel!(), point: Point) -> bool;
+
+    fn dismiss(&mut self, this: &ElModel!());
+}
+
+pub(crate) async fn dismiss_runtime<El: Dismiss>(this: ElModel!(El)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let pressed = ed.recv_trusted::<PortalPressedOutside>().await;
+
+        // avoid setting the element dirty for presses not closing the popup
+        let Some(el) = this.el_read().await
+        else {
+            return;
+        };
+        if !el.dismissed_by(&this, pressed.position) {
+            continue;
+        }
+        drop(el);
+
+        match this.el_write().await {
+            Some(mut el) => el.dismiss(&this),
+            None => return,
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use irisia::{
+    event::EventDispatcher,
+    primitive::{Pixel, Point, Region},
+    skia_safe::{BlurStyle, Canvas, Color, MaskFilter, Paint, RRect},
+    WinitWindow,
+};
+
+use crate::label::to_rect;
+
+pub use surface::{PopupSurface, PopupSurfaceProps};
+
+pub(crate) use dismiss::{dismiss_runtime, Dismiss};
+
+mod dismiss;
+pub mod styles;
+mod surface;
+
+/// Z-index of the overlay portals mounted by widgets of this crate.
+pub mod z_index {
+    pub const POPOVER: i32 = 100;
+    pub const MENU: i32 = 200;
+    pub const DIALOG: i32 = 300;
+    pub const TOAST: i32 = 400;
+    pub const TOOLTIP: i32 = 500;
+}
+
+/// Side of the anchor a popup is placed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    pub fn opposite(self) -> Self {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+/// Alignment of a popup along the side of the anchor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    Start,
+    #[default]
+    Center,
+    End,
+}
+
+/// Region of the whole window in logical pixels.
+pub fn window_region(window: &WinitWindow) -> Region {
+    let size = window.inner_size();
+    (
+        Point(Pixel(0.0), Pixel(0.0)),
+        Point(
+            Pixel::from_physical(size.width as _),
+            Pixel::from_physical(size.height as _),
+        ),
+    )
+}
+
+/// Place a popup of `size` on the preferred `side` of `anchor`, `gap` away from it.
+///
+/// If the popup would leave `bounds` on the preferred side while fitting on the
+/// opposite side, it flips. Then it is shifted along both axes to stay inside
+/// `bounds` as much as possible.
+pub fn place(
+    anchor: Region,
+    size: (Pixel, Pixel),
+    side: Side,
+    align: Align,
+    gap: Pixel,
+    bounds: Region,
+) -> Region {
+    let fits = |side| {
+        let (start, end) = place_on(anchor, size, side, align, gap);
+        match side {
+            Side::Top => start.1 >= bounds.0 .1,
+            Side::Bottom => end.1 <= bounds.1 .1,
+            Side::Left => start.0 >= bounds.0 .0,
+            Side::Right => end.0 <= bounds.1 .0,
+        }
+    };
+
+    let side = if !fits(side) && fits(side.opposite()) {
+        side.opposite()
+    } else {
+        side
+    };
+
+    let (start, end) = place_on(anchor, size, side, align, gap);
+    let offset = Point(
+        shift_into(start.0, end.0, bounds.0 .0, bounds.1 .0),
+        shift_into(start.1, end.1, bounds.0 .1, bounds.1 .1),
+    );
+    (start + offset, end + offset)
+}
+
+fn place_on(
+    anchor: Region,
+    (width, height): (Pixel, Pixel),
+    side: Side,
+    align: Align,
+    gap: Pixel,
+) -> Region {
+    let cross = |start: Pixel, end: Pixel, len: Pixel| match align {
+        Align::Start => start,
+        Align::Center => (start + end - len) / 2.0,
+        Align::End => end - len,
+    };
+
+    let (a0, a1) = anchor;
+    let start = match side {
+        Side::Top => Point(cross(a0.0, a1.0, width), a0.1 - gap - height),
+        Side::Bottom => Point(cross(a0.0, a1.0, width), a1.1 + gap),
+        Side::Left => Point(a0.0 - gap - width, cross(a0.1, a1.1, height)),
+        Side::Right => Point(a1.0 + gap, cross(a0.1, a1.1, height)),
+    };
+    (start, Point(start.0 + width, start.1 + height))
+}
+
+/// Offset moving `start..end` into `min..max`. The start edge
+/// wins if the segment is longer than the bound.
+fn shift_into(start: Pixel, end: Pixel, min: Pixel, max: Pixel) -> Pixel {
+    let mut offset = Pixel(0.0);
+    if end > max {
+        offset = max - end;
+    }
+    if start + offset < min {
+        offset = min - start;
+    }
+    offset
+}
+
+/// Event dispatcher of the element owning a portal, passed to elements
+/// in the portal as a prop so that they can report to the owner.
+#[derive(Clone)]
+pub(crate) struct PortalOwner(pub EventDispatcher);
+
+impl PartialEq for PortalOwner {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.is_same(&other.0)
+    }
+}
+
+pub(crate) fn region_contains(region: Region, point: Point) -> bool {
+    point.abs_ge(region.0) && point.abs_le(region.1)
+}
+
+/// Draw the rounded background of a popup, with an optional drop shadow.
+pub(crate) fn draw_surface(
+    canvas: &mut Canvas,
+    region: Region,
+    background: Color,
+    radius: Pixel,
+    shadow: bool,
+) {
+    let rrect = RRect::new_rect_xy(to_rect(region), radius.to_physical(), radius.to_physical());
+
+    if shadow {
+        let mut paint = Paint::default();
+        paint
+            .set_anti_alias(true)
+            .set_color(Color::from_argb(0x40, 0, 0, 0))
+            .set_mask_filter(MaskFilter::blur(
+                BlurStyle::Normal,
+                Pixel(4.0).to_physical(),
+                true,
+            ));
+        canvas.draw_rrect(rrect.with_offset((0.0, Pixel(2.0).to_physical())), &paint);
+    }
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(background);
+    canvas.draw_rrect(rrect, &paint);
+}
@@ -0,0 +1,14 @@
+use irisia::{primitive::Pixel, skia_safe::Color, Style};
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StylePopupBackground(#[style(default = "Color::WHITE")] pub Color);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StylePopupRadius(#[style(default = "Pixel(6.0)")] pub Pixel);
+
+/// Distance between a popup and its anchor.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StylePopupGap(#[style(default = "Pixel(4.0)")] pub Pixel);
@@ -0,0 +1,89 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, LayoutElements, RenderElement},
+    primitive::{Pixel, Point, Region},
+    skia_safe::Color,
+    ElModel,
+};
+
+use super::draw_surface;
+
+/// Rounded background of popups, laying out every child element
+/// over its draw region inset by the padding.
+pub struct PopupSurface {
+    props: OwnedProps,
+}
+
+#[irisia::props(updater = "PopupSurfaceProps", watch)]
+pub struct OwnedProps {
+    #[props(default = "Color::WHITE")]
+    background: Color,
+
+    #[props(default = "Pixel(6.0)")]
+    radius: Pixel,
+
+    #[props(default)]
+    padding: Pixel,
+
+    #[props(default = "true")]
+    shadow: bool,
+}
+
+impl Element for PopupSurface {
+    type BlankProps = PopupSurfaceProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let OwnedProps {
+            background,
+            radius,
+            shadow,
+            ..
+        } = self.props;
+        draw_surface(content.canvas(), region, background, radius, shadow);
+        content.render_children()
+    }
+
+    fn set_children(&self, this: &ElModel!()) {
+        self.layout(this, this.set_children(this.slot()));
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        if let Some(lc) = this.layout_children() {
+            self.layout(this, lc);
+        }
+    }
+}
+
+impl PopupSurface {
+    fn layout(&self, this: &ElModel!(), lc: LayoutElements) {
+        let (start, end) = this.draw_region();
+        let padding = self.props.padding;
+
+        let inner_start = Point(start.0 + padding, start.1 + padding);
+        let inner = (
+            inner_start,
+            Point(
+                (end.0 - padding).max(inner_start.0),
+                (end.1 - padding).max(inner_start.1),
+            ),
+        );
+        lc.layout(|()| Some(inner)).unwrap();
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for PopupSurface
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        PopupSurface {
+            props: OwnedProps::props_create_with(props),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
@@ -0,0 +1,114 @@
+use std::time::{Duration, Instant};
+
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    primitive::Point,
+    skia_safe::{Paint, RRect},
+    ElModel, StyleReader,
+};
+
+use crate::label::to_rect;
+
+use super::{
+    cycle,
+    styles::{StyleProgressAccent, StyleProgressTrack},
+};
+
+const PERIOD: Duration = Duration::from_millis(1500);
+/// Width of the moving segment of an indeterminate bar, relative to the bar
+const SEGMENT: f32 = 0.3;
+
+/// A bar filled as far as the task is done, taking the whole draw region.
+///
+/// Without `value`, the progress is unknown and a segment keeps sliding
+/// through the bar instead. Only then the bar is redrawn in every frame,
+/// on a layer of its own.
+pub struct ProgressBar {
+    props: OwnedProps,
+    start: Instant,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct BarStyles {
+    track: StyleProgressTrack,
+    accent: StyleProgressAccent,
+}
+
+#[irisia::props(updater = "ProgressBarProps", watch)]
+pub struct OwnedProps {
+    /// Part of the task done, from 0 to 1, `None` if unknown.
+    #[props(default)]
+    value: Option<f32>,
+
+    #[props(read_style(stdin))]
+    style: BarStyles,
+}
+
+impl Element for ProgressBar {
+    type BlankProps = ProgressBarProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        let (left, right) = (region.0 .0, region.1 .0);
+        let width = right - left;
+        let radius = ((region.1 .1 - region.0 .1) / 2.0).to_physical();
+        let track = RRect::new_rect_xy(to_rect(region), radius, radius);
+
+        let canvas = content.canvas();
+        let mut paint = Paint::default();
+        paint
+            .set_anti_alias(true)
+            .set_color(self.props.style.track.0);
+        canvas.draw_rrect(track, &paint);
+
+        let (start, end) = match self.props.value {
+            Some(value) => (left, left + width * value.clamp(0.0, 1.0)),
+            None => {
+                let segment = width * SEGMENT;
+                let start = left - segment + (width + segment) * cycle(self.start, PERIOD);
+                (start, start + segment)
+            }
+        };
+        if end <= start {
+            return Ok(());
+        }
+
+        canvas.save();
+        canvas.clip_rrect(track, None, true);
+        paint.set_color(self.props.style.accent.0);
+        let done = (Point(start, region.0 .1), Point(end, region.1 .1));
+        canvas.draw_rrect(RRect::new_rect_xy(to_rect(done), radius, radius), &paint);
+        canvas.restore();
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for ProgressBar
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.acquire_indep_layer(true);
+        let bar = ProgressBar {
+            props: OwnedProps::props_create_with(props),
+            start: Instant::now(),
+        };
+        bar.sync_animating(this);
+        bar
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let unchanged = self.props.props_update_with(props).unchanged;
+        if !unchanged {
+            self.sync_animating(this);
+        }
+        unchanged
+    }
+}
+
+impl ProgressBar {
+    /// Animate only while the progress is unknown.
+    fn sync_animating(&self, this: &ElModel!()) {
+        this.set_animating(self.props.value.is_none());
+    }
+}
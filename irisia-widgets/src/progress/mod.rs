@@ -0,0 +1,20 @@
+use std::time::{Duration, Instant};
+
+pub use self::{
+    bar::{ProgressBar, ProgressBarProps},
+    skeleton::{Skeleton, SkeletonProps},
+    spinner::{Spinner, SpinnerProps},
+};
+
+mod bar;
+mod skeleton;
+mod spinner;
+pub mod styles;
+
+// Widgets here draw on their own layers and are redrawn by the window in
+// every frame while animating and shown, see `ElementModel::set_animating`.
+
+/// Part of the current cycle of an animation started at `start`, from 0 to 1.
+fn cycle(start: Instant, period: Duration) -> f32 {
+    (start.elapsed().as_secs_f32() / period.as_secs_f32()).fract()
+}
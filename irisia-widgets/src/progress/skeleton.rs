@@ -0,0 +1,107 @@
+use std::time::{Duration, Instant};
+
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    primitive::Pixel,
+    skia_safe::{Paint, RRect, Shader, TileMode},
+    ElModel, StyleReader,
+};
+
+use crate::label::to_rect;
+
+use super::{
+    cycle,
+    styles::{StyleSkeletonColor, StyleSkeletonRadius, StyleSkeletonShimmer},
+};
+
+const PERIOD: Duration = Duration::from_millis(1500);
+const MIN_SHIMMER_WIDTH: Pixel = Pixel(80.0);
+
+/// A placeholder in the shape of content being loaded, taking the whole
+/// draw region, with a shimmer passing over it from left to right. It is
+/// redrawn in every frame while shown, on a layer of its own.
+pub struct Skeleton {
+    props: OwnedProps,
+    start: Instant,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct SkeletonStyles {
+    color: StyleSkeletonColor,
+    shimmer: StyleSkeletonShimmer,
+    radius: StyleSkeletonRadius,
+}
+
+#[irisia::props(updater = "SkeletonProps", watch)]
+pub struct OwnedProps {
+    /// Draws a circle, like in place of an avatar, instead of a rounded rectangle.
+    #[props(default)]
+    circle: bool,
+
+    #[props(read_style(stdin))]
+    style: SkeletonStyles,
+}
+
+impl Element for Skeleton {
+    type BlankProps = SkeletonProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        let SkeletonStyles {
+            color,
+            shimmer,
+            radius,
+        } = self.props.style;
+
+        let (width, height) = (region.1 .0 - region.0 .0, region.1 .1 - region.0 .1);
+        let rect = to_rect(region);
+        let shape = if self.props.circle {
+            RRect::new_oval(rect)
+        } else {
+            let radius = radius.0.min(width / 2.0).min(height / 2.0).to_physical();
+            RRect::new_rect_xy(rect, radius, radius)
+        };
+
+        // the shimmer enters from beyond the left edge and leaves beyond the right edge
+        let band = (width / 2.0).max(MIN_SHIMMER_WIDTH);
+        let center = region.0 .0 - band + (width + band * 2.0) * cycle(self.start, PERIOD);
+        let (y, band) = (rect.center_y(), (band / 2.0).to_physical());
+        let shader = Shader::linear_gradient(
+            (
+                (center.to_physical() - band, y),
+                (center.to_physical() + band, y),
+            ),
+            &[color.0, shimmer.0, color.0][..],
+            None,
+            TileMode::Clamp,
+            None,
+            None,
+        );
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_color(color.0);
+        if let Some(shader) = shader {
+            paint.set_shader(shader);
+        }
+        content.canvas().draw_rrect(shape, &paint);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Skeleton
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.acquire_indep_layer(true);
+        this.set_animating(true);
+        Skeleton {
+            props: OwnedProps::props_create_with(props),
+            start: Instant::now(),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
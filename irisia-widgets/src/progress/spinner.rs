@@ -0,0 +1,108 @@
+use std::{
+    f32::consts::TAU,
+    time::{Duration, Instant},
+};
+
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    primitive::Point,
+    skia_safe::{Paint, PaintCap, PaintStyle},
+    ElModel, StyleReader,
+};
+
+use crate::label::to_rect;
+
+use super::{
+    cycle,
+    styles::{StyleProgressAccent, StyleProgressTrack, StyleSpinnerThickness},
+};
+
+/// Time of a turn of the arc
+const TURN: Duration = Duration::from_millis(1000);
+/// Time the arc takes to grow and shrink again
+const BREATH: Duration = Duration::from_millis(1600);
+const MIN_SWEEP: f32 = 30.0;
+const MAX_SWEEP: f32 = 270.0;
+
+/// An arc running around a ring, in the largest square centered in the
+/// draw region, for tasks whose progress is unknown. It is redrawn in every
+/// frame while shown, on a layer of its own.
+pub struct Spinner {
+    props: OwnedProps,
+    start: Instant,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct SpinnerStyles {
+    track: StyleProgressTrack,
+    accent: StyleProgressAccent,
+    thickness: StyleSpinnerThickness,
+}
+
+#[irisia::props(updater = "SpinnerProps", watch)]
+pub struct OwnedProps {
+    #[props(read_style(stdin))]
+    style: SpinnerStyles,
+}
+
+impl Element for Spinner {
+    type BlankProps = SpinnerProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let (start, end) = this.draw_region();
+        let SpinnerStyles {
+            track,
+            accent,
+            thickness,
+        } = self.props.style;
+
+        let size = (end.0 - start.0).min(end.1 - start.1);
+        let radius = (size - thickness.0) / 2.0;
+        if radius.0 <= 0.0 {
+            return Ok(());
+        }
+
+        let center = Point((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+        let oval = to_rect((
+            Point(center.0 - radius, center.1 - radius),
+            Point(center.0 + radius, center.1 + radius),
+        ));
+
+        let canvas = content.canvas();
+        let mut paint = Paint::default();
+        paint
+            .set_anti_alias(true)
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(thickness.0.to_physical())
+            .set_stroke_cap(PaintCap::Round)
+            .set_color(track.0);
+        canvas.draw_oval(oval, &paint);
+
+        // the arc grows and shrinks while turning
+        let breath = (1.0 - (cycle(self.start, BREATH) * TAU).cos()) / 2.0;
+        let sweep = MIN_SWEEP + (MAX_SWEEP - MIN_SWEEP) * breath;
+        let angle = cycle(self.start, TURN) * 360.0 - 90.0;
+
+        paint.set_color(accent.0);
+        canvas.draw_arc(oval, angle, sweep, false, &paint);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Spinner
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.acquire_indep_layer(true);
+        this.set_animating(true);
+        Spinner {
+            props: OwnedProps::props_create_with(props),
+            start: Instant::now(),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
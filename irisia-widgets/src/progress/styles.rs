@@ -0,0 +1,29 @@
+use irisia::{primitive::Pixel, skia_safe::Color, Style};
+
+/// Color of the part of a progress bar or a spinner not done yet.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleProgressTrack(#[style(default = "Color::from_rgb(0xe0, 0xe0, 0xe0)")] pub Color);
+
+/// Color of the done part of a progress bar, and of the arc of a spinner.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleProgressAccent(#[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color);
+
+/// Width of the ring of a spinner.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSpinnerThickness(#[style(default = "Pixel(3.0)")] pub Pixel);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSkeletonColor(#[style(default = "Color::from_rgb(0xe8, 0xe8, 0xe8)")] pub Color);
+
+/// Color of the shimmer passing over a skeleton.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSkeletonShimmer(#[style(default = "Color::from_rgb(0xf6, 0xf6, 0xf6)")] pub Color);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSkeletonRadius(#[style(default = "Pixel(4.0)")] pub Pixel);
@@ -0,0 +1,186 @@
+use std::ops::Range;
+
+use irisia::primitive::Pixel;
+
+/// Heights of the rows of a list, where rows of unknown height are as high
+/// as the estimate. Differences between known heights and the estimate
+/// are kept in a Fenwick tree, so offsets of rows in a long list are found
+/// in logarithmic time.
+pub(crate) struct Heights {
+    estimate: Pixel,
+    known: Vec<Option<Pixel>>,
+    tree: Vec<f32>,
+}
+
+impl Heights {
+    pub fn new(count: usize, estimate: Pixel) -> Self {
+        Heights {
+            estimate,
+            known: vec![None; count],
+            tree: vec![0.0; count],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.known.len()
+    }
+
+    /// Change the number of rows and the estimate, keeping the known
+    /// heights of remaining rows.
+    pub fn reset(&mut self, count: usize, estimate: Pixel) {
+        if count == self.len() && estimate == self.estimate {
+            return;
+        }
+
+        self.known.resize(count, None);
+        self.estimate = estimate;
+        self.tree = vec![0.0; count];
+        for (index, height) in self.known.iter().enumerate() {
+            if let Some(height) = height {
+                add(&mut self.tree, index, (*height - estimate).0);
+            }
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Pixel {
+        self.known[index].unwrap_or(self.estimate)
+    }
+
+    /// Returns whether the height changed.
+    pub fn set(&mut self, index: usize, height: Pixel) -> bool {
+        if index >= self.len() || self.known[index] == Some(height) {
+            return false;
+        }
+
+        let delta = height - self.get(index);
+        self.known[index] = Some(height);
+        add(&mut self.tree, index, delta.0);
+        true
+    }
+
+    /// Distance from the top of the first row to the top of the row of `index`.
+    pub fn offset(&self, index: usize) -> Pixel {
+        let mut sum = 0.0;
+        let mut i = index;
+        while i > 0 {
+            sum += self.tree[i - 1];
+            i &= i - 1;
+        }
+        self.estimate * index as f32 + Pixel(sum)
+    }
+
+    pub fn total(&self) -> Pixel {
+        self.offset(self.len())
+    }
+
+    /// Index of the row at `y` from the top of the first row, which is
+    /// the last row if `y` is beyond the end. There must be at least one row.
+    pub fn row_at(&self, y: Pixel) -> usize {
+        let (mut low, mut high) = (0, self.len());
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if self.offset(mid) <= y {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Rows seen through a viewport `viewport` high, scrolled `offset`
+    /// down from the top of the first row.
+    pub fn visible(&self, offset: Pixel, viewport: Pixel) -> Range<usize> {
+        if self.len() == 0 {
+            return 0..0;
+        }
+        self.row_at(offset)..self.row_at(offset + viewport) + 1
+    }
+}
+
+fn add(tree: &mut [f32], index: usize, delta: f32) {
+    let mut i = index + 1;
+    while i <= tree.len() {
+        tree[i - 1] += delta;
+        i += i & i.wrapping_neg();
+    }
+}
+
+#[test]
+fn test_estimated_offsets() {
+    let heights = Heights::new(5, Pixel(10.0));
+    assert_eq!(heights.offset(0), Pixel(0.0));
+    assert_eq!(heights.offset(3), Pixel(30.0));
+    assert_eq!(heights.total(), Pixel(50.0));
+}
+
+#[test]
+fn test_prefix_sums() {
+    let mut heights = Heights::new(7, Pixel(10.0));
+    assert!(heights.set(1, Pixel(25.0)));
+    assert!(heights.set(4, Pixel(5.0)));
+    assert!(heights.set(6, Pixel(30.0)));
+
+    let expected = [0.0, 10.0, 35.0, 45.0, 55.0, 60.0, 70.0, 100.0];
+    for (index, offset) in expected.into_iter().enumerate() {
+        assert_eq!(heights.offset(index), Pixel(offset), "offset of {index}");
+    }
+    assert_eq!(heights.get(4), Pixel(5.0));
+    assert_eq!(heights.get(5), Pixel(10.0));
+}
+
+#[test]
+fn test_update_known() {
+    let mut heights = Heights::new(4, Pixel(10.0));
+    heights.set(2, Pixel(40.0));
+    assert!(!heights.set(2, Pixel(40.0)));
+    assert!(heights.set(2, Pixel(15.0)));
+    assert!(!heights.set(4, Pixel(15.0)));
+
+    assert_eq!(heights.offset(3), Pixel(35.0));
+    assert_eq!(heights.total(), Pixel(45.0));
+}
+
+#[test]
+fn test_row_at() {
+    let mut heights = Heights::new(4, Pixel(10.0));
+    heights.set(1, Pixel(30.0));
+
+    assert_eq!(heights.row_at(Pixel(0.0)), 0);
+    assert_eq!(heights.row_at(Pixel(9.9)), 0);
+    assert_eq!(heights.row_at(Pixel(10.0)), 1);
+    assert_eq!(heights.row_at(Pixel(39.9)), 1);
+    assert_eq!(heights.row_at(Pixel(40.0)), 2);
+    assert_eq!(heights.row_at(Pixel(55.0)), 3);
+    assert_eq!(heights.row_at(Pixel(500.0)), 3);
+    assert_eq!(heights.row_at(Pixel(-5.0)), 0);
+}
+
+#[test]
+fn test_visible() {
+    let mut heights = Heights::new(6, Pixel(10.0));
+    heights.set(2, Pixel(30.0));
+
+    assert_eq!(heights.visible(Pixel(0.0), Pixel(25.0)), 0..3);
+    assert_eq!(heights.visible(Pixel(15.0), Pixel(10.0)), 1..3);
+    assert_eq!(heights.visible(Pixel(55.0), Pixel(100.0)), 3..6);
+    assert_eq!(
+        Heights::new(0, Pixel(10.0)).visible(Pixel(0.0), Pixel(25.0)),
+        0..0
+    );
+}
+
+#[test]
+fn test_reset_keeps_known() {
+    let mut heights = Heights::new(3, Pixel(10.0));
+    heights.set(0, Pixel(20.0));
+    heights.set(2, Pixel(50.0));
+
+    heights.reset(5, Pixel(12.0));
+    assert_eq!(heights.len(), 5);
+    assert_eq!(heights.offset(2), Pixel(32.0));
+    assert_eq!(heights.total(), Pixel(106.0));
+
+    heights.reset(1, Pixel(12.0));
+    assert_eq!(heights.total(), Pixel(20.0));
+}
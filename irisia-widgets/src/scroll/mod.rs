@@ -0,0 +1,128 @@
+use irisia::{
+    event::standard::WheelDelta,
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Canvas, Color, Paint, RRect},
+};
+
+use crate::label::to_rect;
+
+pub(crate) use self::heights::Heights;
+
+mod heights;
+
+pub(crate) const SCROLLBAR_WIDTH: Pixel = Pixel(4.0);
+const WHEEL_LINE: Pixel = Pixel(48.0);
+const MIN_THUMB: Pixel = Pixel(16.0);
+
+/// Distance scrolled by the wheel on each axis, positive to show content
+/// further right or below.
+pub(crate) fn wheel_delta(delta: WheelDelta) -> (Pixel, Pixel) {
+    match delta {
+        WheelDelta::Line(x, y) => (WHEEL_LINE * -x, WHEEL_LINE * -y),
+        WheelDelta::Pixel(x, y) => (-x, -y),
+    }
+}
+
+/// The furthest offset content `content` long can be scrolled to, in a
+/// viewport `viewport` long.
+pub(crate) fn max_offset(content: Pixel, viewport: Pixel) -> Pixel {
+    (content - viewport).max(Pixel(0.0))
+}
+
+pub(crate) fn clamp_offset(offset: Pixel, max: Pixel) -> Pixel {
+    offset.min(max).max(Pixel(0.0))
+}
+
+/// Offset showing the content between `start` and `end` in the viewport,
+/// which moves from `offset` as little as possible. Not clamped.
+pub(crate) fn reveal_offset(offset: Pixel, viewport: Pixel, start: Pixel, end: Pixel) -> Pixel {
+    if start < offset {
+        start
+    } else if end > offset + viewport {
+        end - viewport
+    } else {
+        offset
+    }
+}
+
+/// Draw the thumb of the scrollbar along the right edge of `viewport`,
+/// nothing if the content fits.
+pub(crate) fn draw_vertical_scrollbar(
+    canvas: &mut Canvas,
+    viewport: Region,
+    content: Pixel,
+    offset: Pixel,
+) {
+    let height = viewport.1 .1 - viewport.0 .1;
+    if content <= height {
+        return;
+    }
+
+    let ratio = height.0 / content.0;
+    let top = viewport.0 .1 + offset * ratio;
+    draw_thumb(
+        canvas,
+        (
+            Point(viewport.1 .0 - SCROLLBAR_WIDTH, top),
+            Point(viewport.1 .0, top + (height * ratio).max(MIN_THUMB)),
+        ),
+    );
+}
+
+/// Draw the thumb of the scrollbar along the bottom edge of `viewport`,
+/// nothing if the content fits.
+pub(crate) fn draw_horizontal_scrollbar(
+    canvas: &mut Canvas,
+    viewport: Region,
+    content: Pixel,
+    offset: Pixel,
+) {
+    let width = viewport.1 .0 - viewport.0 .0;
+    if content <= width {
+        return;
+    }
+
+    let ratio = width.0 / content.0;
+    let left = viewport.0 .0 + offset * ratio;
+    draw_thumb(
+        canvas,
+        (
+            Point(left, viewport.1 .1 - SCROLLBAR_WIDTH),
+            Point(left + (width * ratio).max(MIN_THUMB), viewport.1 .1),
+        ),
+    );
+}
+
+fn draw_thumb(canvas: &mut Canvas, thumb: Region) {
+    let radius = (SCROLLBAR_WIDTH / 2.0).to_physical();
+    let mut paint = Paint::default();
+    paint
+        .set_anti_alias(true)
+        .set_color(Color::from_argb(0x50, 0, 0, 0));
+    canvas.draw_rrect(RRect::new_rect_xy(to_rect(thumb), radius, radius), &paint);
+}
+
+#[test]
+fn test_offset_limits() {
+    assert_eq!(max_offset(Pixel(100.0), Pixel(40.0)), Pixel(60.0));
+    assert_eq!(max_offset(Pixel(30.0), Pixel(40.0)), Pixel(0.0));
+    assert_eq!(clamp_offset(Pixel(-5.0), Pixel(60.0)), Pixel(0.0));
+    assert_eq!(clamp_offset(Pixel(75.0), Pixel(60.0)), Pixel(60.0));
+}
+
+#[test]
+fn test_reveal_offset() {
+    let viewport = Pixel(40.0);
+    assert_eq!(
+        reveal_offset(Pixel(20.0), viewport, Pixel(30.0), Pixel(50.0)),
+        Pixel(20.0)
+    );
+    assert_eq!(
+        reveal_offset(Pixel(20.0), viewport, Pixel(10.0), Pixel(30.0)),
+        Pixel(10.0)
+    );
+    assert_eq!(
+        reveal_offset(Pixel(20.0), viewport, Pixel(60.0), Pixel(80.0)),
+        Pixel(40.0)
+    );
+}
@@ -0,0 +1,321 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Click, PointerMove, PointerOut, Wheel, WheelDelta},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Canvas, Color, Font, Paint, PaintStyle, Path, RRect},
+    winit::event::MouseButton,
+    ElModel, Event,
+};
+
+use crate::{
+    label::{draw_label, label_font, to_rect},
+    popup::{draw_surface, region_contains, PortalOwner},
+};
+
+pub(super) const ROW_HEIGHT: Pixel = Pixel(28.0);
+pub(super) const PADDING: Pixel = Pixel(4.0);
+const CHECK_COLUMN: Pixel = Pixel(28.0);
+const LABEL_INSET: Pixel = Pixel(10.0);
+const SCROLLBAR_WIDTH: Pixel = Pixel(4.0);
+const WHEEL_LINE: Pixel = Pixel(3.0 * ROW_HEIGHT.0);
+
+/// Colors and font size shared by the select box and its list.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) struct SelectLook {
+    pub font_size: Pixel,
+    pub color: Color,
+    pub background: Color,
+    pub radius: Pixel,
+    pub border: Color,
+    pub highlight: Color,
+    pub accent: Color,
+}
+
+/// The pointer moved onto a row of the list, or left it if `row` is `None`.
+#[derive(Event, Clone, Copy)]
+pub(super) struct ListHover {
+    pub row: Option<usize>,
+}
+
+/// A row of the list was clicked.
+#[derive(Event, Clone, Copy)]
+pub(super) struct ListActivate {
+    pub row: usize,
+}
+
+/// The list was scrolled by the wheel, positive to show later rows.
+#[derive(Event, Clone, Copy)]
+pub(super) struct ListScroll {
+    pub delta: Pixel,
+}
+
+/// Height of the rows shown at once in a list of `rows` rows.
+pub(super) fn viewport_height(rows: usize, max_rows: usize) -> Pixel {
+    ROW_HEIGHT * rows.clamp(1, max_rows.max(1)) as f32
+}
+
+/// Options of an opened select box, mounted in the portal of the owner.
+pub(super) struct SelectList {
+    props: OwnedProps,
+    font: Font,
+}
+
+#[irisia::props(updater = "SelectListProps", watch)]
+pub(super) struct OwnedProps {
+    #[props(must_init)]
+    rows: Vec<String>,
+
+    /// Whether the option of each row is selected
+    #[props(must_init)]
+    selected: Vec<bool>,
+
+    /// Draws a check box before each row.
+    #[props(default)]
+    multiple: bool,
+
+    #[props(default)]
+    highlighted: Option<usize>,
+
+    /// Offset of the first row from the top of the viewport.
+    #[props(default)]
+    scroll: Pixel,
+
+    /// Shown if there is no row.
+    #[props(updated, default)]
+    empty_text: String,
+
+    /// Receives [`ListHover`], [`ListActivate`] and [`ListScroll`]
+    #[props(must_init)]
+    owner: PortalOwner,
+
+    #[props(must_init)]
+    look: SelectLook,
+}
+
+impl Element for SelectList {
+    type BlankProps = SelectListProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let OwnedProps {
+            rows,
+            selected,
+            multiple,
+            highlighted,
+            scroll,
+            empty_text,
+            look,
+            ..
+        } = &self.props;
+
+        let canvas = content.canvas();
+        draw_surface(canvas, region, look.background, look.radius, true);
+
+        let viewport = viewport(region);
+        if rows.is_empty() {
+            draw_label(
+                canvas,
+                &self.font,
+                empty_text,
+                look.color.with_a(0x80),
+                viewport.0 .0 + LABEL_INSET,
+                (viewport.0 .1, viewport.0 .1 + ROW_HEIGHT),
+            );
+            return Ok(());
+        }
+
+        canvas.save();
+        canvas.clip_rect(to_rect(viewport), None, true);
+
+        let first = (scroll.0 / ROW_HEIGHT.0).floor().max(0.0) as usize;
+        let visible = ((viewport.1 .1 - viewport.0 .1).0 / ROW_HEIGHT.0).ceil() as usize + 1;
+        for row in first..rows.len().min(first + visible) {
+            let top = viewport.0 .1 + ROW_HEIGHT * row as f32 - *scroll;
+            let row_region = (
+                Point(viewport.0 .0, top),
+                Point(viewport.1 .0, top + ROW_HEIGHT),
+            );
+
+            if *highlighted == Some(row) {
+                let radius = Pixel(4.0).to_physical();
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true).set_color(look.highlight);
+                canvas.draw_rrect(
+                    RRect::new_rect_xy(to_rect(row_region), radius, radius),
+                    &paint,
+                );
+            }
+
+            let mut x = row_region.0 .0 + LABEL_INSET;
+            let color = if *multiple {
+                draw_check_box(canvas, row_region, selected[row], look);
+                x = row_region.0 .0 + CHECK_COLUMN;
+                look.color
+            } else if selected[row] {
+                look.accent
+            } else {
+                look.color
+            };
+            draw_label(
+                canvas,
+                &self.font,
+                &rows[row],
+                color,
+                x,
+                (row_region.0 .1, row_region.1 .1),
+            );
+        }
+        canvas.restore();
+
+        let content_height = ROW_HEIGHT * rows.len() as f32;
+        let viewport_height = viewport.1 .1 - viewport.0 .1;
+        if content_height > viewport_height {
+            let ratio = viewport_height.0 / content_height.0;
+            let top = viewport.0 .1 + *scroll * ratio;
+            let thumb = (
+                Point(viewport.1 .0 - SCROLLBAR_WIDTH, top),
+                Point(viewport.1 .0, top + viewport_height * ratio),
+            );
+            let radius = (SCROLLBAR_WIDTH / 2.0).to_physical();
+            let mut paint = Paint::default();
+            paint
+                .set_anti_alias(true)
+                .set_color(Color::from_argb(0x50, 0, 0, 0));
+            canvas.draw_rrect(RRect::new_rect_xy(to_rect(thumb), radius, radius), &paint);
+        }
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for SelectList
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.consume_wheel(true);
+        this.daemon(pointer_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        SelectList {
+            font: label_font(props.look.font_size),
+            props,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let font_size = self.props.look.font_size;
+        let unchanged = self.props.props_update_with(props).unchanged;
+        if self.props.look.font_size != font_size {
+            self.font = label_font(self.props.look.font_size);
+        }
+        unchanged
+    }
+}
+
+/// Region the rows are visible in.
+fn viewport((start, end): Region) -> Region {
+    (
+        Point(start.0 + PADDING, start.1 + PADDING),
+        Point(end.0 - PADDING, end.1 - PADDING),
+    )
+}
+
+fn draw_check_box(canvas: &mut Canvas, row: Region, checked: bool, look: &SelectLook) {
+    let size = Pixel(14.0);
+    let left = row.0 .0 + (CHECK_COLUMN - size) / 2.0;
+    let top = (row.0 .1 + row.1 .1 - size) / 2.0;
+    let rrect = RRect::new_rect_xy(
+        to_rect((Point(left, top), Point(left + size, top + size))),
+        Pixel(3.0).to_physical(),
+        Pixel(3.0).to_physical(),
+    );
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    if !checked {
+        paint
+            .set_color(look.border)
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(Pixel(1.0).to_physical());
+        canvas.draw_rrect(rrect, &paint);
+        return;
+    }
+
+    paint.set_color(look.accent);
+    canvas.draw_rrect(rrect, &paint);
+
+    let mut path = Path::new();
+    path.move_to(Point(left + Pixel(3.0), top + Pixel(7.0)))
+        .line_to(Point(left + Pixel(6.0), top + Pixel(10.0)))
+        .line_to(Point(left + Pixel(11.0), top + Pixel(4.0)));
+    paint
+        .set_color(Color::WHITE)
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(Pixel(1.5).to_physical());
+    canvas.draw_path(&path, &paint);
+}
+
+fn row_at(region: Region, scroll: Pixel, rows: usize, point: Point) -> Option<usize> {
+    let viewport = viewport(region);
+    if !region_contains(viewport, point) {
+        return None;
+    }
+
+    let row = ((point.1 - viewport.0 .1 + scroll).0 / ROW_HEIGHT.0).floor() as usize;
+    (row < rows).then_some(row)
+}
+
+async fn pointer_runtime(this: ElModel!(SelectList)) {
+    let ed = this.event_dispatcher().clone();
+    let mut hovered = None;
+
+    loop {
+        let (position, clicked) = tokio::select! {
+            pm = ed.recv_trusted::<PointerMove>() => (Some(pm.position), false),
+            click = ed.recv_trusted::<Click>() => {
+                if click.button != MouseButton::Left {
+                    continue;
+                }
+                (Some(click.position), true)
+            }
+            _ = ed.recv_trusted::<PointerOut>() => (None, false),
+            wheel = ed.recv_trusted::<Wheel>() => {
+                let delta = match wheel.delta {
+                    WheelDelta::Line(_, y) => WHEEL_LINE * -y,
+                    WheelDelta::Pixel(_, y) => -y,
+                };
+
+                let Some(list) = this.el_read().await
+                else {
+                    return;
+                };
+                list.props.owner.0.emit(ListScroll { delta });
+                continue;
+            }
+        };
+
+        let Some(list) = this.el_read().await
+        else {
+            return;
+        };
+
+        let OwnedProps {
+            ref rows,
+            scroll,
+            ref owner,
+            ..
+        } = list.props;
+        let row = position.and_then(|point| row_at(this.draw_region(), scroll, rows.len(), point));
+
+        if clicked {
+            if let Some(row) = row {
+                owner.0.emit(ListActivate { row });
+            }
+        } else if row != hovered {
+            hovered = row;
+            owner.0.emit(ListHover { row });
+        }
+    }
+}
@@ -0,0 +1,647 @@
+use irisia::{
+    build,
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Blured, Focused, PointerDown},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Color, Font, Paint, PaintStyle, Path, RRect},
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, MouseButton, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{
+    label::{draw_label, label_font, label_width, to_rect},
+    popup::{
+        dismiss_runtime, place, region_contains,
+        styles::{StylePopupBackground, StylePopupGap, StylePopupRadius},
+        window_region, z_index, Align, Dismiss, PortalOwner, Side,
+    },
+    textbox::styles::StyleFontSize,
+};
+
+use self::{
+    list::{
+        viewport_height, ListActivate, ListHover, ListScroll, SelectList, SelectLook, PADDING,
+        ROW_HEIGHT,
+    },
+    styles::{StyleSelectAccent, StyleSelectBorder, StyleSelectHighlight},
+};
+
+mod list;
+pub mod styles;
+
+const FIELD_PADDING: Pixel = Pixel(10.0);
+const ARROW_COLUMN: Pixel = Pixel(28.0);
+
+/// A box showing the selected options, which opens a list of all options when clicked.
+///
+/// Options are referred to by their indices in `options`. The selection is
+/// controlled: [`SelectChanged`] is emitted when the user changes it, and the
+/// owner is expected to pass the new selection back through `value`.
+pub struct Select {
+    props: OwnedProps,
+    font: Font,
+    value: Vec<usize>,
+    open: bool,
+    focused: bool,
+    /// Text typed while the list is opened, if `filterable`
+    filter: String,
+    /// Indices of the options matching the filter, one for each row of the list
+    matches: Vec<usize>,
+    /// Highlighted row of the list
+    highlighted: Option<usize>,
+    scroll: Pixel,
+}
+
+/// Emitted on the select's event dispatcher whenever the user changes the selection.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct SelectChanged {
+    /// Indices of the selected options in ascending order.
+    pub value: Vec<usize>,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct SelectStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: StylePopupBackground,
+    radius: StylePopupRadius,
+    gap: StylePopupGap,
+    border: StyleSelectBorder,
+    highlight: StyleSelectHighlight,
+    accent: StyleSelectAccent,
+}
+
+#[irisia::props(updater = "SelectProps", watch)]
+pub struct OwnedProps {
+    #[props(must_init, watch)]
+    options: Vec<String>,
+
+    /// Indices of the selected options, at most one unless `multiple`.
+    #[props(default, watch)]
+    value: Vec<usize>,
+
+    /// Allow selecting more than one option. The list stays opened
+    /// while options are toggled.
+    #[props(default)]
+    multiple: bool,
+
+    /// Filter the options by the text typed while the list is opened.
+    #[props(default)]
+    filterable: bool,
+
+    /// Shown when nothing is selected.
+    #[props(updated, default)]
+    placeholder: String,
+
+    /// Shown in the list when no option matches the filter.
+    #[props(updated, default = r#"String::from("No matches")"#)]
+    empty_text: String,
+
+    /// Most rows shown at once, the list scrolls if there are more.
+    #[props(default = "8")]
+    max_rows: usize,
+
+    #[props(read_style(stdin))]
+    style: SelectStyles,
+}
+
+enum Input {
+    Key(VirtualKeyCode),
+    Char(char),
+}
+
+impl Element for Select {
+    type BlankProps = SelectProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let look = self.look();
+        let canvas = content.canvas();
+        let radius = Pixel(4.0).to_physical();
+        let rrect = RRect::new_rect_xy(to_rect(region), radius, radius);
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_color(look.background);
+        canvas.draw_rrect(rrect, &paint);
+
+        paint
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(Pixel(1.0).to_physical())
+            .set_color(if self.focused {
+                look.accent
+            } else {
+                look.border
+            });
+        canvas.draw_rrect(rrect, &paint);
+
+        let text_region = (
+            Point(region.0 .0 + FIELD_PADDING, region.0 .1),
+            Point(region.1 .0 - ARROW_COLUMN, region.1 .1),
+        );
+        let x = text_region.0 .0;
+        let rows = (region.0 .1, region.1 .1);
+        let center = (region.0 .1 + region.1 .1) / 2.0;
+
+        canvas.save();
+        canvas.clip_rect(to_rect(text_region), None, true);
+        if self.open && self.props.filterable {
+            let caret_x = if self.filter.is_empty() {
+                x
+            } else {
+                draw_label(canvas, &self.font, &self.filter, look.color, x, rows);
+                x + label_width(&self.font, &self.filter)
+            };
+
+            let half = look.font_size * 0.6;
+            let mut paint = Paint::default();
+            paint.set_color(look.color);
+            canvas.draw_rect(
+                to_rect((
+                    Point(caret_x, center - half),
+                    Point(caret_x + Pixel(1.0), center + half),
+                )),
+                &paint,
+            );
+        } else {
+            let summary = self.summary();
+            if summary.is_empty() {
+                let placeholder = &self.props.placeholder;
+                draw_label(
+                    canvas,
+                    &self.font,
+                    placeholder,
+                    look.color.with_a(0x80),
+                    x,
+                    rows,
+                );
+            } else {
+                draw_label(canvas, &self.font, &summary, look.color, x, rows);
+            }
+        }
+        canvas.restore();
+
+        let arrow_x = region.1 .0 - ARROW_COLUMN / 2.0;
+        let dy = if self.open { Pixel(-2.0) } else { Pixel(2.0) };
+        let mut path = Path::new();
+        path.move_to(Point(arrow_x - Pixel(4.0), center - dy))
+            .line_to(Point(arrow_x, center + dy))
+            .line_to(Point(arrow_x + Pixel(4.0), center - dy));
+
+        let mut stroke = Paint::default();
+        stroke
+            .set_anti_alias(true)
+            .set_color(look.color.with_a(0xa0))
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(Pixel(1.5).to_physical());
+        canvas.draw_path(&path, &stroke);
+        Ok(())
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        if self.open {
+            self.show(this);
+        }
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Select
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+        this.daemon(dismiss_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        let mut select = Select {
+            font: label_font(props.style.font_size.map_or(Pixel(14.0), |size| size.0)),
+            props,
+            value: Vec::new(),
+            open: false,
+            focused: false,
+            filter: String::new(),
+            matches: Vec::new(),
+            highlighted: None,
+            scroll: Pixel(0.0),
+        };
+        select.sync_with_props();
+        select
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        let value_unchanged = update_result.options_unchanged && update_result.value_unchanged;
+        if !value_unchanged || !update_result.unchanged {
+            self.font = label_font(self.look().font_size);
+            self.sync_with_props();
+            if self.open {
+                self.refilter();
+                self.show(this);
+            }
+        }
+        update_result.unchanged && value_unchanged
+    }
+}
+
+impl Select {
+    fn sync_with_props(&mut self) {
+        let count = self.props.options.len();
+        let mut value: Vec<usize> = self
+            .props
+            .value
+            .iter()
+            .copied()
+            .filter(|&index| index < count)
+            .collect();
+
+        value.sort_unstable();
+        value.dedup();
+        if !self.props.multiple {
+            value.truncate(1);
+        }
+        self.value = value;
+    }
+
+    fn look(&self) -> SelectLook {
+        let style = &self.props.style;
+        SelectLook {
+            font_size: style.font_size.map_or(Pixel(14.0), |size| size.0),
+            color: style.color.map_or(Color::BLACK, |color| color.0),
+            background: style.background.0,
+            radius: style.radius.0,
+            border: style.border.0,
+            highlight: style.highlight.0,
+            accent: style.accent.0,
+        }
+    }
+
+    /// Labels of the selected options.
+    fn summary(&self) -> String {
+        self.value
+            .iter()
+            .filter_map(|&index| self.props.options.get(index))
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Match the options against the filter again, highlighting
+    /// the first selected one if nothing is typed.
+    fn refilter(&mut self) {
+        let filter = self.filter.to_lowercase();
+        self.matches = self
+            .props
+            .options
+            .iter()
+            .enumerate()
+            .filter(|(_, label)| filter.is_empty() || label.to_lowercase().contains(&filter))
+            .map(|(index, _)| index)
+            .collect();
+
+        let selected = self
+            .matches
+            .iter()
+            .position(|index| self.value.binary_search(index).is_ok());
+        self.highlighted = match selected {
+            Some(row) if filter.is_empty() => Some(row),
+            _ if self.matches.is_empty() => None,
+            _ => Some(0),
+        };
+
+        self.scroll = Pixel(0.0);
+        self.scroll_to_highlighted();
+    }
+
+    fn viewport_height(&self) -> Pixel {
+        viewport_height(self.matches.len(), self.props.max_rows)
+    }
+
+    fn scroll_by(&mut self, delta: Pixel) {
+        let max = (ROW_HEIGHT * self.matches.len() as f32 - self.viewport_height()).max(Pixel(0.0));
+        self.scroll = (self.scroll + delta).max(Pixel(0.0)).min(max);
+    }
+
+    fn scroll_to_highlighted(&mut self) {
+        let Some(row) = self.highlighted
+        else {
+            return;
+        };
+
+        let top = ROW_HEIGHT * row as f32;
+        let bottom = top + ROW_HEIGHT;
+        let viewport_height = self.viewport_height();
+        if top < self.scroll {
+            self.scroll = top;
+        } else if bottom > self.scroll + viewport_height {
+            self.scroll = bottom - viewport_height;
+        }
+    }
+
+    fn move_highlight(&mut self, this: &ElModel!(), delta: isize) {
+        let Some(last) = self.matches.len().checked_sub(1)
+        else {
+            return;
+        };
+
+        let row = match self.highlighted {
+            Some(row) => row.saturating_add_signed(delta),
+            None if delta > 0 => (delta - 1) as usize,
+            None => 0,
+        };
+        self.highlighted = Some(row.min(last));
+        self.scroll_to_highlighted();
+        self.show(this);
+    }
+
+    fn open(&mut self, this: &ElModel!()) {
+        if self.open {
+            return;
+        }
+
+        self.open = true;
+        self.filter.clear();
+        self.refilter();
+        self.show(this);
+    }
+
+    fn close(&mut self, this: &ElModel!()) {
+        if !self.open {
+            return;
+        }
+
+        self.open = false;
+        self.filter.clear();
+        this.remove_portal();
+    }
+
+    fn show(&mut self, this: &ElModel!()) {
+        let anchor = this.draw_region();
+        let size = (
+            anchor.1 .0 - anchor.0 .0,
+            self.viewport_height() + PADDING * 2.0,
+        );
+        let region = place(
+            anchor,
+            size,
+            Side::Bottom,
+            Align::Start,
+            self.props.style.gap.0,
+            window_region(this.window()),
+        );
+
+        let rows: Vec<String> = self
+            .matches
+            .iter()
+            .map(|&index| self.props.options[index].clone())
+            .collect();
+        let selected: Vec<bool> = self
+            .matches
+            .iter()
+            .map(|index| self.value.binary_search(index).is_ok())
+            .collect();
+
+        this.set_portal(
+            z_index::POPOVER,
+            build! {
+                SelectList {
+                    rows: rows,
+                    selected: selected,
+                    multiple: self.props.multiple,
+                    highlighted: self.highlighted,
+                    scroll: self.scroll,
+                    empty_text: &self.props.empty_text,
+                    owner: PortalOwner(this.event_dispatcher().clone()),
+                    look: self.look(),
+                }
+            },
+        )
+        .layout_once(region)
+        .unwrap();
+    }
+
+    /// Select the option of `row`, or toggle it if `multiple`.
+    fn activate(&mut self, this: &ElModel!(), row: usize) {
+        let Some(&index) = self.matches.get(row)
+        else {
+            return;
+        };
+
+        if self.props.multiple {
+            match self.value.binary_search(&index) {
+                Ok(position) => {
+                    self.value.remove(position);
+                }
+                Err(position) => self.value.insert(position, index),
+            }
+            self.highlighted = Some(row);
+            self.show(this);
+        } else {
+            let unchanged = self.value == [index];
+            self.value = vec![index];
+            self.close(this);
+            if unchanged {
+                return;
+            }
+        }
+
+        this.event_dispatcher().emit(SelectChanged {
+            value: self.value.clone(),
+        });
+    }
+
+    /// Enter pressed, or Space if not `filterable`.
+    fn confirm(&mut self, this: &ElModel!()) {
+        match (self.open, self.highlighted) {
+            (true, Some(row)) => self.activate(this, row),
+            (true, None) => self.close(this),
+            (false, _) => self.open(this),
+        }
+    }
+
+    /// Jump to the next option starting with `c`. It is selected
+    /// right away if the list is closed, like native select boxes.
+    fn type_ahead(&mut self, this: &ElModel!(), c: char) {
+        if !self.open {
+            self.refilter();
+        }
+
+        let prefix = c.to_lowercase().to_string();
+        let count = self.matches.len();
+        let start = self.highlighted.map_or(0, |row| row + 1);
+        let Some(row) = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&row| {
+                self.props.options[self.matches[row]]
+                    .to_lowercase()
+                    .starts_with(&prefix)
+            })
+        else {
+            return;
+        };
+
+        self.highlighted = Some(row);
+        if self.open {
+            self.scroll_to_highlighted();
+            self.show(this);
+        } else if !self.props.multiple {
+            self.activate(this, row);
+        }
+    }
+
+    fn input(&mut self, this: &ElModel!(), input: Input) {
+        let page = self.props.max_rows.max(1) as isize;
+
+        match input {
+            Input::Key(VirtualKeyCode::Down) if self.open => self.move_highlight(this, 1),
+            Input::Key(VirtualKeyCode::Up) if self.open => self.move_highlight(this, -1),
+            Input::Key(VirtualKeyCode::PageDown) if self.open => self.move_highlight(this, page),
+            Input::Key(VirtualKeyCode::PageUp) if self.open => self.move_highlight(this, -page),
+            Input::Key(VirtualKeyCode::Home) if self.open => self.move_highlight(this, isize::MIN),
+            Input::Key(VirtualKeyCode::End) if self.open => self.move_highlight(this, isize::MAX),
+            Input::Key(VirtualKeyCode::Down | VirtualKeyCode::Up) => self.open(this),
+            Input::Key(VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter) => self.confirm(this),
+            Input::Key(VirtualKeyCode::Space) if !self.props.filterable => self.confirm(this),
+            Input::Key(VirtualKeyCode::Escape) => self.close(this),
+            Input::Key(VirtualKeyCode::Back) if self.open && self.props.filterable => {
+                if self.filter.pop().is_some() {
+                    self.refilter();
+                    self.show(this);
+                }
+            }
+            Input::Char(c) if self.props.filterable => {
+                self.open(this);
+                self.filter.push(c);
+                self.refilter();
+                self.show(this);
+            }
+            Input::Char(c) if c != ' ' => self.type_ahead(this, c),
+            _ => {}
+        }
+    }
+}
+
+async fn update(this: &ElModel!(Select), f: impl FnOnce(&mut Select)) -> bool {
+    let Some(mut select) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *select);
+    true
+}
+
+async fn pointer_runtime(this: ElModel!(Select)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let alive = tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => {
+                if pd.button != MouseButton::Left {
+                    continue;
+                }
+
+                this.focus();
+                update(&this, |select| {
+                    if select.open {
+                        select.close(&this);
+                    } else {
+                        select.open(&this);
+                    }
+                })
+                .await
+            }
+            (hover, _) = ed.recv::<ListHover>() => {
+                // keep the highlighted row when the pointer leaves the list
+                if hover.row.is_none() {
+                    continue;
+                }
+
+                update(&this, |select| {
+                    if select.open && select.highlighted != hover.row {
+                        select.highlighted = hover.row;
+                        select.show(&this);
+                    }
+                })
+                .await
+            }
+            (activate, _) = ed.recv::<ListActivate>() => {
+                update(&this, |select| {
+                    if select.open {
+                        select.activate(&this, activate.row);
+                    }
+                })
+                .await
+            }
+            (scroll, _) = ed.recv::<ListScroll>() => {
+                update(&this, |select| {
+                    if select.open {
+                        select.scroll_by(scroll.delta);
+                        select.show(&this);
+                    }
+                })
+                .await
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
+
+impl Dismiss for Select {
+    // pressing on the select box is left to the pointer runtime, which toggles the list
+    fn dismissed_by(&self, this: &ElModel!(), point: Point) -> bool {
+        self.open && !region_contains(this.draw_region(), point)
+    }
+
+    fn dismiss(&mut self, this: &ElModel!()) {
+        self.close(this);
+    }
+}
+
+async fn keyboard_runtime(this: ElModel!(Select)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        ed.recv_trusted::<Focused>().await;
+        if !update(&this, |select| select.focused = true).await {
+            return;
+        }
+
+        loop {
+            let input = tokio::select! {
+                event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                    StaticWindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => Input::Key(key),
+                    StaticWindowEvent::ReceivedCharacter(c) if !c.is_control() => Input::Char(c),
+                    _ => continue,
+                },
+                _ = ed.recv_trusted::<Blured>() => break,
+            };
+
+            if !update(&this, |select| select.input(&this, input)).await {
+                return;
+            }
+        }
+
+        let blured = update(&this, |select| {
+            select.focused = false;
+            select.close(&this);
+        });
+        if !blured.await {
+            return;
+        }
+    }
+}
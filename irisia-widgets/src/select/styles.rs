@@ -0,0 +1,16 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Color of the border of the select box.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSelectBorder(#[style(default = "Color::from_rgb(0xc8, 0xc8, 0xc8)")] pub Color);
+
+/// Background of the highlighted option.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSelectHighlight(#[style(default = "Color::from_rgb(0xd6, 0xe6, 0xfd)")] pub Color);
+
+/// Color of the focus ring, the check marks and the selected option.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSelectAccent(#[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color);
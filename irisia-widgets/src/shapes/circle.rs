@@ -0,0 +1,51 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    primitive::Point,
+    skia_safe::Path as SkiaPath,
+    ElModel,
+};
+
+use super::{draw_shape, ShapeStyles};
+
+/// The largest circle centered in the draw region.
+pub struct Circle {
+    props: OwnedProps,
+}
+
+#[irisia::props(updater = "CircleProps", watch)]
+pub struct OwnedProps {
+    #[props(read_style(stdin))]
+    style: ShapeStyles,
+}
+
+impl Element for Circle {
+    type BlankProps = CircleProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let (start, end) = this.draw_region();
+        let radius = (end.0 - start.0).min(end.1 - start.1) / 2.0;
+        if radius.0 <= 0.0 {
+            return Ok(());
+        }
+
+        let center = Point((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+        let path = SkiaPath::circle(center, radius.to_physical(), None);
+        draw_shape(this, content.canvas(), path, &self.props.style);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Circle
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        Circle {
+            props: OwnedProps::props_create_with(props),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
@@ -0,0 +1,61 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    primitive::Point,
+    skia_safe::Path as SkiaPath,
+    ElModel,
+};
+
+use super::{draw_shape, styles::StyleStroke, ShapeStyles};
+
+/// A straight line between two points relative to the top left corner of
+/// the draw region. A line has no inside, so `fill` is ignored, and it is
+/// stroked by a black line 1px wide without `stroke`.
+pub struct Line {
+    props: OwnedProps,
+}
+
+#[irisia::props(updater = "LineProps", watch)]
+pub struct OwnedProps {
+    #[props(must_init)]
+    from: Point,
+
+    #[props(must_init)]
+    to: Point,
+
+    #[props(read_style(stdin))]
+    style: ShapeStyles,
+}
+
+impl Element for Line {
+    type BlankProps = LineProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let (origin, _) = this.draw_region();
+        let at = |point: Point| Point(origin.0 + point.0, origin.1 + point.1);
+
+        let mut path = SkiaPath::new();
+        path.move_to(at(self.props.from)).line_to(at(self.props.to));
+
+        let styles = ShapeStyles {
+            fill: None,
+            stroke: Some(self.props.style.stroke.clone().unwrap_or_default()),
+        };
+        draw_shape(this, content.canvas(), path, &styles);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Line
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        Line {
+            props: OwnedProps::props_create_with(props),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
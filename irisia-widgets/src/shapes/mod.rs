@@ -0,0 +1,124 @@
+use std::rc::Rc;
+
+use irisia::{
+    element::{Element, InteractShape},
+    primitive::{Pixel, Point},
+    skia_safe::{path_utils, Canvas, Color, Paint, PaintStyle, Path as SkiaPath},
+    ElModel, StyleReader,
+};
+
+use crate::box_styles::border::parse_dash_style;
+
+use self::styles::{StyleFill, StyleStroke};
+
+pub use self::{
+    circle::{Circle, CircleProps},
+    line::{Line, LineProps},
+    path::{Path, PathProps, ShapePath},
+    rect::{Rect, RectProps},
+    styles::Gradient,
+};
+
+mod circle;
+mod line;
+mod path;
+mod rect;
+pub mod styles;
+
+// Shapes are painted with `StyleFill` and `StyleStroke`, or filled black
+// without either, and only the painted area hits the pointer rather than
+// the whole draw region.
+
+#[derive(StyleReader, PartialEq)]
+struct ShapeStyles {
+    fill: Option<StyleFill>,
+    stroke: Option<StyleStroke>,
+}
+
+impl ShapeStyles {
+    fn fill_paint(&self, path: &SkiaPath) -> Option<Paint> {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        match &self.fill {
+            Some(StyleFill::Color(color)) => {
+                paint.set_color(*color);
+            }
+            Some(StyleFill::Gradient(gradient)) => {
+                paint.set_shader(gradient.shader(path.compute_tight_bounds()));
+            }
+            // a shape with neither style would be invisible
+            None if self.stroke.is_none() => {
+                paint.set_color(Color::BLACK);
+            }
+            None => return None,
+        }
+        Some(paint)
+    }
+
+    fn stroke_paint(&self) -> Option<Paint> {
+        let stroke = self.stroke.as_ref()?;
+        let width = stroke.width.to_physical();
+        if width <= 0.0 {
+            return None;
+        }
+
+        let mut paint = Paint::default();
+        paint
+            .set_anti_alias(true)
+            .set_style(PaintStyle::Stroke)
+            .set_color(stroke.color)
+            .set_stroke_width(width)
+            .set_stroke_cap(stroke.cap)
+            .set_stroke_join(stroke.join)
+            .set_path_effect(parse_dash_style(&stroke.dash, width));
+        Some(paint)
+    }
+}
+
+/// Fill and stroke `path`, then let only the painted area hit.
+fn draw_shape<El>(this: &ElModel!(El), canvas: &mut Canvas, path: SkiaPath, styles: &ShapeStyles)
+where
+    El: Element,
+{
+    let fill = styles.fill_paint(&path);
+    let stroke = styles.stroke_paint();
+
+    if let Some(paint) = &fill {
+        canvas.draw_path(&path, paint);
+    }
+    if let Some(paint) = &stroke {
+        canvas.draw_path(&path, paint);
+    }
+
+    // gaps between dashes still hit, or thin dotted lines could hardly be hit
+    let outline = stroke.map(|mut paint| {
+        paint.set_path_effect(None);
+        let mut outline = SkiaPath::new();
+        path_utils::fill_path_with_paint(&path, &paint, &mut outline, None, None);
+        outline
+    });
+
+    let mut bounds = path.compute_tight_bounds();
+    if let Some(outline) = &outline {
+        bounds.join(outline.compute_tight_bounds());
+    }
+    this.set_interact_region(Some((
+        Point(
+            Pixel::from_physical(bounds.left),
+            Pixel::from_physical(bounds.top),
+        ),
+        Point(
+            Pixel::from_physical(bounds.right),
+            Pixel::from_physical(bounds.bottom),
+        ),
+    )));
+
+    let filled = fill.is_some().then_some(path);
+    let shape: InteractShape = Rc::new(move |point| {
+        filled.as_ref().is_some_and(|path| path.contains(point))
+            || outline
+                .as_ref()
+                .is_some_and(|outline| outline.contains(point))
+    });
+    this.set_interact_shape(Some(shape));
+}
@@ -0,0 +1,101 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    primitive::Point,
+    skia_safe::Path as SkiaPath,
+    ElModel,
+};
+
+use super::{draw_shape, ShapeStyles};
+
+/// Any outline made of lines and curves, given by a [`ShapePath`].
+///
+/// ```ignore
+/// Path {
+///     path: ShapePath::new()
+///         .move_to(Point(Pixel(0.0), Pixel(20.0)))
+///         .line_to(Point(Pixel(10.0), Pixel(0.0)))
+///         .line_to(Point(Pixel(20.0), Pixel(20.0)))
+///         .close(),
+///     +style: style! {
+///         fill: Color::GREEN;
+///     },
+/// }
+/// ```
+pub struct Path {
+    props: OwnedProps,
+}
+
+#[irisia::props(updater = "PathProps", watch)]
+pub struct OwnedProps {
+    #[props(must_init)]
+    path: ShapePath,
+
+    #[props(read_style(stdin))]
+    style: ShapeStyles,
+}
+
+/// Outline of a [`Path`], with points relative to the top left corner of its
+/// draw region. Nothing outside the draw region is clipped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShapePath(SkiaPath);
+
+impl ShapePath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new contour at `point`.
+    pub fn move_to(mut self, point: Point) -> Self {
+        self.0.move_to(point);
+        self
+    }
+
+    pub fn line_to(mut self, point: Point) -> Self {
+        self.0.line_to(point);
+        self
+    }
+
+    /// A quadratic bezier curve controlled by `control`.
+    pub fn quad_to(mut self, control: Point, point: Point) -> Self {
+        self.0.quad_to(control, point);
+        self
+    }
+
+    /// A cubic bezier curve controlled by `control1` and `control2`.
+    pub fn cubic_to(mut self, control1: Point, control2: Point, point: Point) -> Self {
+        self.0.cubic_to(control1, control2, point);
+        self
+    }
+
+    /// Close the current contour with a line back to its start.
+    pub fn close(mut self) -> Self {
+        self.0.close();
+        self
+    }
+}
+
+impl Element for Path {
+    type BlankProps = PathProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let (origin, _) = this.draw_region();
+        let path = self.props.path.0.with_offset(origin);
+        draw_shape(this, content.canvas(), path, &self.props.style);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Path
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        Path {
+            props: OwnedProps::props_create_with(props),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
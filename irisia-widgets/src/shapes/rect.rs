@@ -0,0 +1,59 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    primitive::Pixel,
+    skia_safe::{Path as SkiaPath, RRect},
+    ElModel,
+};
+
+use crate::label::to_rect;
+
+use super::{draw_shape, ShapeStyles};
+
+/// A rectangle filling the draw region, with corners rounded by `radius`.
+pub struct Rect {
+    props: OwnedProps,
+}
+
+#[irisia::props(updater = "RectProps", watch)]
+pub struct OwnedProps {
+    #[props(default)]
+    radius: Pixel,
+
+    #[props(read_style(stdin))]
+    style: ShapeStyles,
+}
+
+impl Element for Rect {
+    type BlankProps = RectProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let rect = to_rect(this.draw_region());
+        if rect.is_empty() {
+            return Ok(());
+        }
+
+        let radius = self
+            .props
+            .radius
+            .to_physical()
+            .clamp(0.0, rect.width().min(rect.height()) / 2.0);
+        let path = SkiaPath::rrect(RRect::new_rect_xy(rect, radius, radius), None);
+        draw_shape(this, content.canvas(), path, &self.props.style);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Rect
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        Rect {
+            props: OwnedProps::props_create_with(props),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
@@ -0,0 +1,134 @@
+use irisia::{
+    primitive::Pixel,
+    skia_safe::{
+        paint::{Cap, Join},
+        Color, Point as SkiaPoint, Rect as SkiaRect, Shader, TileMode,
+    },
+    Style,
+};
+
+use crate::box_styles::DashStyle;
+
+/// Paint of the inside of a shape, a color or a [`Gradient`].
+///
+/// ```ignore
+/// fill: Color::RED;
+/// fill: Gradient::linear((0.0, 0.0), (1.0, 0.0))
+///     .stop(0.0, Color::RED)
+///     .stop(1.0, Color::BLUE);
+/// ```
+#[derive(Style, Clone, PartialEq)]
+pub enum StyleFill {
+    #[style(from)]
+    Color(Color),
+
+    #[style(from)]
+    Gradient(Gradient),
+}
+
+/// Outline of a shape.
+///
+/// ```ignore
+/// stroke: 2px, Color::BLACK, .dash(DashStyle::Dotted), .cap(Cap::Round);
+/// ```
+#[derive(Style, Clone, PartialEq)]
+#[style(impl_default, from = "width, [color]")]
+pub struct StyleStroke {
+    #[style(default = "Pixel(1.0)")]
+    pub width: Pixel,
+
+    #[style(default = "Color::BLACK", option)]
+    pub color: Color,
+
+    #[style(default = "DashStyle::Solid", option)]
+    pub dash: DashStyle,
+
+    #[style(default = "Cap::Butt", option)]
+    pub cap: Cap,
+
+    #[style(default = "Join::Miter", option)]
+    pub join: Join,
+}
+
+/// Colors blended along a line or around a center. Positions are relative
+/// to the bounding box of the shape, where `(0.0, 0.0)` is the top left
+/// corner and `(1.0, 1.0)` is the bottom right corner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    shape: GradientShape,
+    /// Offsets from 0 to 1 and their colors
+    stops: Vec<(f32, Color)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GradientShape {
+    Linear {
+        from: (f32, f32),
+        to: (f32, f32),
+    },
+    /// The radius is relative to the longer side of the bounding box
+    Radial {
+        center: (f32, f32),
+        radius: f32,
+    },
+}
+
+impl Gradient {
+    pub fn linear(from: (f32, f32), to: (f32, f32)) -> Self {
+        Gradient {
+            shape: GradientShape::Linear { from, to },
+            stops: Vec::new(),
+        }
+    }
+
+    /// `radius` is relative to the longer side of the bounding box.
+    pub fn radial(center: (f32, f32), radius: f32) -> Self {
+        Gradient {
+            shape: GradientShape::Radial { center, radius },
+            stops: Vec::new(),
+        }
+    }
+
+    /// Add `color` at `offset`, from 0 at the start to 1 at the end.
+    /// Stops have to be added in ascending order of offsets.
+    pub fn stop(mut self, offset: f32, color: Color) -> Self {
+        self.stops.push((offset.clamp(0.0, 1.0), color));
+        self
+    }
+
+    /// Shader of the gradient over `bounds`, `None` without any stop.
+    pub(super) fn shader(&self, bounds: SkiaRect) -> Option<Shader> {
+        if self.stops.is_empty() {
+            return None;
+        }
+
+        let at = |(x, y): (f32, f32)| {
+            SkiaPoint::new(
+                bounds.left + bounds.width() * x,
+                bounds.top + bounds.height() * y,
+            )
+        };
+        let offsets: Vec<f32> = self.stops.iter().map(|(offset, _)| *offset).collect();
+        let colors: Vec<Color> = self.stops.iter().map(|(_, color)| *color).collect();
+
+        match self.shape {
+            GradientShape::Linear { from, to } => Shader::linear_gradient(
+                (at(from), at(to)),
+                &colors[..],
+                &offsets[..],
+                TileMode::Clamp,
+                None,
+                None,
+            ),
+            GradientShape::Radial { center, radius } => Shader::radial_gradient(
+                at(center),
+                bounds.width().max(bounds.height()) * radius,
+                &colors[..],
+                &offsets[..],
+                TileMode::Clamp,
+                None,
+                None,
+            ),
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Blured, Focused, PointerCaptureLost, PointerDown, PointerMove, PointerUp},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{paint::Cap, Color, Paint},
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, MouseButton, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+use styles::*;
+
+pub mod styles;
+
+pub struct Slider {
+    props: OwnedProps,
+    value: f32,
+    upper: Option<f32>,
+    active_thumb: Thumb,
+}
+
+/// Emitted on the slider's event dispatcher whenever the user moves a thumb.
+#[derive(Event, Clone, Copy, PartialEq)]
+pub struct SliderChanged {
+    pub value: f32,
+    pub upper: Option<f32>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Thumb {
+    Lower,
+    Upper,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct SliderStyles {
+    track_thickness: StyleTrackThickness,
+    track_color: StyleTrackColor,
+    thumb_radius: StyleThumbRadius,
+    color: Option<StyleColor>,
+}
+
+#[irisia::props(updater = "SliderProps", watch)]
+pub struct OwnedProps {
+    #[props(default = "0.0")]
+    min: f32,
+
+    #[props(default = "1.0")]
+    max: f32,
+
+    /// `None` for a continuous slider.
+    #[props(default)]
+    step: Option<f32>,
+
+    #[props(default, watch)]
+    value: f32,
+
+    /// Set to `Some` to show a second thumb, which makes the
+    /// slider select the range `value..=upper`.
+    #[props(default, watch)]
+    upper: Option<f32>,
+
+    #[props(read_style(stdin))]
+    style: SliderStyles,
+}
+
+impl Element for Slider {
+    type BlankProps = SliderProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let style = &self.props.style;
+        let (start, end) = self.track(region);
+        let active_color = match &style.color {
+            Some(c) => c.0,
+            None => Color::from_rgb(0x4d, 0x90, 0xfe),
+        };
+
+        let mut paint = Paint::default();
+        paint
+            .set_anti_alias(true)
+            .set_stroke_width(style.track_thickness.0.to_physical())
+            .set_stroke_cap(Cap::Round)
+            .set_color(style.track_color.0);
+
+        let canvas = content.canvas();
+        canvas.draw_line(start, end, &paint);
+
+        let lower_point = self.thumb_point(region, self.value);
+        let (filled_start, filled_end) = match self.upper {
+            Some(upper) => (lower_point, self.thumb_point(region, upper)),
+            None => (start, lower_point),
+        };
+
+        paint.set_color(active_color);
+        canvas.draw_line(filled_start, filled_end, &paint);
+
+        paint.set_stroke_width(0.0);
+        let radius = style.thumb_radius.0.to_physical();
+        canvas.draw_circle(lower_point, radius, &paint);
+        if let Some(upper) = self.upper {
+            canvas.draw_circle(self.thumb_point(region, upper), radius, &paint);
+        }
+
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Slider
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+
+        let mut slider = Slider {
+            props: OwnedProps::props_create_with(props),
+            value: 0.0,
+            upper: None,
+            active_thumb: Thumb::Lower,
+        };
+        slider.sync_with_props();
+        slider
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        let value_unchanged = update_result.value_unchanged && update_result.upper_unchanged;
+        if !value_unchanged || !update_result.unchanged {
+            self.sync_with_props();
+        }
+        update_result.unchanged && value_unchanged
+    }
+}
+
+impl Slider {
+    fn sync_with_props(&mut self) {
+        self.value = self.normalize(self.props.value);
+        self.upper = self.props.upper.map(|upper| self.normalize(upper));
+
+        if let Some(upper) = &mut self.upper {
+            if *upper < self.value {
+                std::mem::swap(upper, &mut self.value);
+            }
+        } else {
+            self.active_thumb = Thumb::Lower;
+        }
+    }
+
+    fn normalize(&self, value: f32) -> f32 {
+        let OwnedProps { min, max, step, .. } = self.props;
+        let (low, high) = (min.min(max), min.max(max));
+
+        let value = match step {
+            Some(step) if step > 0.0 => min + ((value - min) / step).round() * step,
+            _ => value,
+        };
+
+        value.clamp(low, high)
+    }
+
+    /// Returns start and end point of the track, inset by the thumb radius
+    /// so that thumbs never leave the draw region.
+    fn track(&self, region: Region) -> (Point, Point) {
+        let radius = self.props.style.thumb_radius.0;
+        let center_y = (region.0 .1 + region.1 .1) / 2.0;
+        (
+            Point(region.0 .0 + radius, center_y),
+            Point((region.1 .0 - radius).max(region.0 .0 + radius), center_y),
+        )
+    }
+
+    fn ratio_of(&self, value: f32) -> f32 {
+        let span = self.props.max - self.props.min;
+        if span == 0.0 {
+            0.0
+        } else {
+            ((value - self.props.min) / span).clamp(0.0, 1.0)
+        }
+    }
+
+    fn thumb_point(&self, region: Region, value: f32) -> Point {
+        let (start, end) = self.track(region);
+        Point(start.0 + (end.0 - start.0) * self.ratio_of(value), start.1)
+    }
+
+    fn value_at(&self, region: Region, position: Point) -> f32 {
+        let (start, end) = self.track(region);
+        let length = end.0 - start.0;
+        let ratio = if length <= Pixel(0.0) {
+            0.0
+        } else {
+            ((position.0 - start.0).0 / length.0).clamp(0.0, 1.0)
+        };
+        self.normalize(self.props.min + (self.props.max - self.props.min) * ratio)
+    }
+
+    fn nearest_thumb(&self, value: f32) -> Thumb {
+        match self.upper {
+            Some(upper) if (upper - value).abs() < (self.value - value).abs() => Thumb::Upper,
+            Some(upper) if upper == self.value && value > upper => Thumb::Upper,
+            _ => Thumb::Lower,
+        }
+    }
+
+    /// Moves the given thumb, keeping the lower thumb below the upper one.
+    /// Returns whether the value changed.
+    fn set_thumb(&mut self, thumb: Thumb, value: f32) -> bool {
+        let value = self.normalize(value);
+        let old = (self.value, self.upper);
+
+        match (thumb, &mut self.upper) {
+            (Thumb::Upper, Some(upper)) => *upper = value.max(self.value),
+            (_, Some(upper)) => self.value = value.min(*upper),
+            (_, None) => self.value = value,
+        }
+
+        self.active_thumb = thumb;
+        old != (self.value, self.upper)
+    }
+
+    fn keyboard_step(&self) -> f32 {
+        match self.props.step {
+            Some(step) if step > 0.0 => step,
+            _ => (self.props.max - self.props.min).abs() / 100.0,
+        }
+    }
+
+    fn changed_event(&self) -> SliderChanged {
+        SliderChanged {
+            value: self.value,
+            upper: self.upper,
+        }
+    }
+}
+
+async fn pointer_runtime(this: ElModel!(Slider)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let pd = ed.recv_trusted::<PointerDown>().await;
+        if pd.button != MouseButton::Left {
+            continue;
+        }
+
+        let Some(thumb) = drag_to(&this, pd.position, None).await
+        else {
+            return;
+        };
+
+        // keep tracking after the pointer leaves the slider
+        let pointer_id = pd.pointer_id;
+        this.capture_pointer(pointer_id);
+
+        loop {
+            tokio::select! {
+                pm = ed.recv_trusted::<PointerMove>() => {
+                    if pm.pointer_id != pointer_id {
+                        continue;
+                    }
+                    if drag_to(&this, pm.position, Some(thumb)).await.is_none() {
+                        return;
+                    }
+                }
+                pu = ed.recv_trusted::<PointerUp>() => {
+                    if pu.pointer_id == pointer_id {
+                        break;
+                    }
+                }
+                pcl = ed.recv_trusted::<PointerCaptureLost>() => {
+                    if pcl.pointer_id == pointer_id {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn drag_to(this: &ElModel!(Slider), position: Point, thumb: Option<Thumb>) -> Option<Thumb> {
+    let region = this.draw_region();
+    let mut slider = this.el_write().await?;
+
+    let value = slider.value_at(region, position);
+    let thumb = thumb.unwrap_or_else(|| slider.nearest_thumb(value));
+    if slider.set_thumb(thumb, value) {
+        this.event_dispatcher().emit(slider.changed_event());
+    }
+    Some(thumb)
+}
+
+async fn keyboard_runtime(this: ElModel!(Slider)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        ed.recv_trusted::<Focused>().await;
+
+        loop {
+            let key = tokio::select! {
+                event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                    StaticWindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => key,
+                    _ => continue,
+                },
+                _ = ed.recv_trusted::<Blured>() => break,
+            };
+
+            let Some(mut slider) = this.el_write().await
+            else {
+                return;
+            };
+
+            let step = slider.keyboard_step();
+            let (min, max) = (slider.props.min, slider.props.max);
+            let thumb = slider.active_thumb;
+            let current = match (thumb, slider.upper) {
+                (Thumb::Upper, Some(upper)) => upper,
+                _ => slider.value,
+            };
+
+            let new_value = match key {
+                VirtualKeyCode::Left | VirtualKeyCode::Down => current - step,
+                VirtualKeyCode::Right | VirtualKeyCode::Up => current + step,
+                VirtualKeyCode::PageDown => current - step * 10.0,
+                VirtualKeyCode::PageUp => current + step * 10.0,
+                VirtualKeyCode::Home => min,
+                VirtualKeyCode::End => max,
+                VirtualKeyCode::Tab if slider.upper.is_some() => {
+                    slider.active_thumb = match thumb {
+                        Thumb::Lower => Thumb::Upper,
+                        Thumb::Upper => Thumb::Lower,
+                    };
+                    continue;
+                }
+                _ => continue,
+            };
+
+            if slider.set_thumb(thumb, new_value) {
+                this.event_dispatcher().emit(slider.changed_event());
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use irisia::{primitive::Pixel, skia_safe::Color, Style};
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTrackThickness(#[style(default = "Pixel(4.0)")] pub Pixel);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTrackColor(#[style(default = "Color::from_rgb(0xd0, 0xd0, 0xd0)")] pub Color);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleThumbRadius(#[style(default = "Pixel(8.0)")] pub Pixel);
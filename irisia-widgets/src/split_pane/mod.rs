@@ -0,0 +1,444 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, LayoutElements, RenderElement},
+    event::standard::{PointerCaptureLost, PointerDown, PointerMove, PointerOut, PointerUp},
+    primitive::{Pixel, Point, Region},
+    skia_safe::Paint,
+    winit::event::MouseButton,
+    ElModel, Event, StyleReader,
+};
+
+use crate::{label::to_rect, popup::region_contains};
+
+use self::styles::{StyleSplitAccent, StyleSplitDivider};
+
+pub mod styles;
+
+const DEFAULT_MIN_SIZE: Pixel = Pixel(24.0);
+/// Distance from a divider the pointer can still grab it
+const GRAB_MARGIN: Pixel = Pixel(3.0);
+
+/// Lays out its children side by side, with a divider between every two
+/// of them which can be dragged to resize the panes on its both sides.
+///
+/// Each pane takes a share of the room given by `ratios`, but no less than
+/// its minimal size. [`SplitPaneResized`] is emitted while the user drags a
+/// divider, and with a `persist_key` the ratios are also remembered for the
+/// window, so the panes are restored once the split pane is created again.
+pub struct SplitPane {
+    props: OwnedProps,
+    ratios: Vec<f32>,
+    /// Number of panes last time they were laid out
+    panes: Cell<usize>,
+    /// Index of the divider under the pointer
+    hovered: Option<usize>,
+    /// Index of the divider being dragged
+    dragging: Option<usize>,
+}
+
+/// Emitted on the event dispatcher of the split pane whenever the user
+/// resizes the panes.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct SplitPaneResized {
+    /// Share of the room of each pane, summing up to 1.
+    pub ratios: Vec<f32>,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct SplitPaneStyles {
+    divider: StyleSplitDivider,
+    accent: StyleSplitAccent,
+}
+
+#[irisia::props(updater = "SplitPaneProps", watch)]
+pub struct OwnedProps {
+    /// Stack the panes from top to bottom instead of from left to right.
+    #[props(default)]
+    vertical: bool,
+
+    /// Share of the room each pane takes. Missing entries are filled
+    /// with the average, and all panes are equally wide if empty.
+    #[props(default, watch)]
+    ratios: Vec<f32>,
+
+    /// Minimal size of each pane along the axis, 24 pixels if missing.
+    #[props(default)]
+    min_sizes: Vec<Pixel>,
+
+    /// Thickness of the dividers.
+    #[props(default = "Pixel(4.0)")]
+    divider: Pixel,
+
+    /// Key the ratios are remembered under for the window.
+    #[props(default)]
+    persist_key: Option<String>,
+
+    #[props(read_style(stdin))]
+    style: SplitPaneStyles,
+}
+
+/// Ratios of split panes with a `persist_key`, stored with `GlobalContent::data`.
+#[derive(Default)]
+struct SavedRatios(RefCell<HashMap<String, Vec<f32>>>);
+
+impl Element for SplitPane {
+    type BlankProps = SplitPaneProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let style = &self.props.style;
+        let highlighted = self.dragging.or(self.hovered);
+        let (_, dividers) = self.arrange(region);
+
+        let canvas = content.canvas();
+        let mut paint = Paint::default();
+        for (index, divider) in dividers.into_iter().enumerate() {
+            paint.set_color(if highlighted == Some(index) {
+                style.accent.0
+            } else {
+                style.divider.0
+            });
+            canvas.draw_rect(to_rect(divider), &paint);
+        }
+
+        content.render_children()
+    }
+
+    fn set_children(&self, this: &ElModel!()) {
+        self.layout(this, this.set_children(this.slot()));
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        if let Some(lc) = this.layout_children() {
+            self.layout(this, lc);
+        }
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for SplitPane
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        let saved = props.persist_key.as_ref().and_then(|key| {
+            this.global()
+                .data(SavedRatios::default)
+                .0
+                .borrow()
+                .get(key)
+                .cloned()
+        });
+
+        SplitPane {
+            ratios: saved.unwrap_or_else(|| props.ratios.clone()),
+            props,
+            panes: Cell::new(0),
+            hovered: None,
+            dragging: None,
+        }
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.ratios_unchanged {
+            self.ratios = self.props.ratios.clone();
+            self.persist(this);
+        }
+        update_result.unchanged && update_result.ratios_unchanged
+    }
+}
+
+/// `ratios` resized to `count` entries and scaled to sum up to 1.
+fn normalized(ratios: &[f32], count: usize) -> Vec<f32> {
+    let mut ratios: Vec<f32> = ratios.iter().take(count).map(|r| r.max(0.0)).collect();
+    let average = if ratios.is_empty() {
+        1.0
+    } else {
+        ratios.iter().sum::<f32>() / ratios.len() as f32
+    };
+    ratios.resize(count, average);
+
+    let sum: f32 = ratios.iter().sum();
+    if sum > 0.0 {
+        ratios.iter_mut().for_each(|ratio| *ratio /= sum);
+    } else {
+        ratios.fill(1.0 / count as f32);
+    }
+    ratios
+}
+
+impl SplitPane {
+    /// Position of `point` along the axis.
+    fn along(&self, point: Point) -> Pixel {
+        if self.props.vertical {
+            point.1
+        } else {
+            point.0
+        }
+    }
+
+    /// Part of `region` from `from` to `to` along the axis.
+    fn span(&self, (start, end): Region, from: Pixel, to: Pixel) -> Region {
+        if self.props.vertical {
+            (Point(start.0, from), Point(end.0, to))
+        } else {
+            (Point(from, start.1), Point(to, end.1))
+        }
+    }
+
+    fn min_size(&self, index: usize) -> Pixel {
+        self.props
+            .min_sizes
+            .get(index)
+            .copied()
+            .unwrap_or(DEFAULT_MIN_SIZE)
+    }
+
+    /// Room for the panes along the axis, not counting the dividers.
+    fn available(&self, (start, end): Region, count: usize) -> Pixel {
+        let dividers = self.props.divider * count.saturating_sub(1) as f32;
+        (self.along(end) - self.along(start) - dividers).max(Pixel(0.0))
+    }
+
+    /// Sizes of `count` panes along the axis.
+    fn sizes(&self, region: Region, count: usize) -> Vec<Pixel> {
+        let available = self.available(region, count).0;
+        let mins: Vec<f32> = (0..count).map(|index| self.min_size(index).0).collect();
+
+        let total_min: f32 = mins.iter().sum();
+        if total_min >= available {
+            // not even room for the minimal sizes, shrink every pane alike
+            let scale = if total_min > 0.0 {
+                available / total_min
+            } else {
+                0.0
+            };
+            return mins.iter().map(|min| Pixel(min * scale)).collect();
+        }
+
+        let mut sizes: Vec<f32> = normalized(&self.ratios, count)
+            .into_iter()
+            .map(|ratio| ratio * available)
+            .collect();
+
+        // raise panes below their minimal sizes, and take the room
+        // from the others in proportion to their sizes
+        let mut fixed = vec![false; count];
+        loop {
+            let mut deficit = 0.0;
+            for ((size, min), fixed) in sizes.iter_mut().zip(&mins).zip(&mut fixed) {
+                if !*fixed && *size < *min {
+                    deficit += *min - *size;
+                    *size = *min;
+                    *fixed = true;
+                }
+            }
+
+            let flexible: f32 = sizes
+                .iter()
+                .zip(&fixed)
+                .filter(|(_, fixed)| !**fixed)
+                .map(|(size, _)| size)
+                .sum();
+            if deficit == 0.0 || flexible <= 0.0 {
+                break;
+            }
+
+            let scale = ((flexible - deficit) / flexible).max(0.0);
+            for (size, _) in sizes.iter_mut().zip(&fixed).filter(|(_, fixed)| !**fixed) {
+                *size *= scale;
+            }
+        }
+
+        sizes.into_iter().map(Pixel).collect()
+    }
+
+    /// Regions of the panes and of the dividers between them.
+    fn arrange(&self, region: Region) -> (Vec<Region>, Vec<Region>) {
+        let count = self.panes.get();
+        let divider = self.props.divider;
+        let mut panes = Vec::with_capacity(count);
+        let mut dividers = Vec::with_capacity(count.saturating_sub(1));
+
+        let mut from = self.along(region.0);
+        for (index, size) in self.sizes(region, count).into_iter().enumerate() {
+            if index > 0 {
+                dividers.push(self.span(region, from, from + divider));
+                from = from + divider;
+            }
+            panes.push(self.span(region, from, from + size));
+            from = from + size;
+        }
+        (panes, dividers)
+    }
+
+    fn layout(&self, this: &ElModel!(), lc: LayoutElements) {
+        self.panes.set(lc.len());
+        let (panes, _) = self.arrange(this.draw_region());
+        let mut panes = panes.into_iter();
+        lc.layout(|()| panes.next()).unwrap();
+    }
+
+    fn divider_at(&self, region: Region, point: Point) -> Option<usize> {
+        let (_, dividers) = self.arrange(region);
+        dividers.into_iter().position(|divider| {
+            let from = self.along(divider.0) - GRAB_MARGIN;
+            let to = self.along(divider.1) + GRAB_MARGIN;
+            region_contains(self.span(region, from, to), point)
+        })
+    }
+
+    /// Move the divider after the pane of `index` to `position` along the axis,
+    /// resizing only the panes on its both sides. Returns whether the ratios changed.
+    fn drag(&mut self, region: Region, index: usize, position: Pixel) -> bool {
+        let count = self.panes.get();
+        let available = self.available(region, count);
+        if index + 1 >= count || available <= Pixel(0.0) {
+            return false;
+        }
+
+        let (panes, _) = self.arrange(region);
+        let mut sizes = self.sizes(region, count);
+        let combined = sizes[index] + sizes[index + 1];
+        let start = self.along(panes[index].0);
+
+        let size = (position - self.props.divider / 2.0 - start)
+            .min(combined - self.min_size(index + 1))
+            .max(self.min_size(index))
+            .min(combined);
+        sizes[index] = size;
+        sizes[index + 1] = combined - size;
+
+        let ratios: Vec<f32> = sizes.iter().map(|size| size.0 / available.0).collect();
+        if ratios == self.ratios {
+            return false;
+        }
+        self.ratios = ratios;
+        true
+    }
+
+    fn persist(&self, this: &ElModel!()) {
+        if let Some(key) = &self.props.persist_key {
+            this.global()
+                .data(SavedRatios::default)
+                .0
+                .borrow_mut()
+                .insert(key.clone(), self.ratios.clone());
+        }
+    }
+}
+
+async fn update(this: &ElModel!(SplitPane), f: impl FnOnce(&mut SplitPane)) -> bool {
+    let Some(mut pane) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *pane);
+    true
+}
+
+/// Highlight the divider at `position`, returns whether the split pane is alive.
+async fn hover(this: &ElModel!(SplitPane), position: Option<Point>) -> bool {
+    let Some(pane) = this.el_read().await
+    else {
+        return false;
+    };
+
+    let hovered = position.and_then(|position| pane.divider_at(this.draw_region(), position));
+    if hovered == pane.hovered {
+        return true;
+    }
+    drop(pane);
+    update(this, |pane| pane.hovered = hovered).await
+}
+
+async fn pointer_runtime(this: ElModel!(SplitPane)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let pd = tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => pd,
+            pm = ed.recv_trusted::<PointerMove>() => {
+                if !hover(&this, Some(pm.position)).await {
+                    return;
+                }
+                continue;
+            }
+            _ = ed.recv_trusted::<PointerOut>() => {
+                if !hover(&this, None).await {
+                    return;
+                }
+                continue;
+            }
+        };
+
+        if pd.button != MouseButton::Left {
+            continue;
+        }
+
+        let Some(pane) = this.el_read().await
+        else {
+            return;
+        };
+        let Some(index) = pane.divider_at(this.draw_region(), pd.position)
+        else {
+            continue;
+        };
+        drop(pane);
+
+        if !update(&this, |pane| pane.dragging = Some(index)).await {
+            return;
+        }
+
+        // keep dragging after the pointer leaves the split pane
+        let pointer_id = pd.pointer_id;
+        this.capture_pointer(pointer_id);
+
+        loop {
+            tokio::select! {
+                pm = ed.recv_trusted::<PointerMove>() => {
+                    if pm.pointer_id != pointer_id {
+                        continue;
+                    }
+
+                    let dragged = update(&this, |pane| {
+                        let position = pane.along(pm.position);
+                        if pane.drag(this.draw_region(), index, position) {
+                            pane.persist(&this);
+                            this.event_dispatcher().emit(SplitPaneResized {
+                                ratios: pane.ratios.clone(),
+                            });
+                        }
+                    });
+                    if !dragged.await {
+                        return;
+                    }
+                }
+                pu = ed.recv_trusted::<PointerUp>() => {
+                    if pu.pointer_id == pointer_id {
+                        break;
+                    }
+                }
+                pcl = ed.recv_trusted::<PointerCaptureLost>() => {
+                    if pcl.pointer_id == pointer_id {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if !update(&this, |pane| pane.dragging = None).await {
+            return;
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Color of the dividers between panes.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSplitDivider(#[style(default = "Color::from_rgb(0xdc, 0xdc, 0xdc)")] pub Color);
+
+/// Color of a divider under the pointer or being dragged.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSplitAccent(#[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color);
@@ -0,0 +1,127 @@
+use std::rc::Rc;
+
+use irisia::primitive::Pixel;
+
+/// Definition of a column of a [`Table`].
+///
+/// ```ignore
+/// Column::new("Size").width(Pixel(80.0)).align(CellAlign::End)
+/// ```
+///
+/// [`Table`]: super::Table
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub(super) header: String,
+    pub(super) width: Pixel,
+    pub(super) min_width: Pixel,
+    pub(super) align: CellAlign,
+    pub(super) sortable: bool,
+}
+
+impl Column {
+    pub fn new(header: impl Into<String>) -> Self {
+        Column {
+            header: header.into(),
+            width: Pixel(120.0),
+            min_width: Pixel(40.0),
+            align: CellAlign::Start,
+            sortable: true,
+        }
+    }
+
+    /// Initial width, which the user can change by dragging the right
+    /// edge of the header. Defaults to 120 pixels.
+    pub fn width(mut self, width: Pixel) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Defaults to 40 pixels.
+    pub fn min_width(mut self, min_width: Pixel) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    pub fn align(mut self, align: CellAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Whether clicking the header emits [`TableSort`]. Defaults to `true`.
+    ///
+    /// [`TableSort`]: super::TableSort
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+/// Horizontal alignment of the text in the cells and the header of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    pub fn reversed(self) -> Self {
+        match self {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        }
+    }
+}
+
+/// How many rows of a [`Table`] the user can select.
+///
+/// [`Table`]: super::Table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    None,
+    #[default]
+    Single,
+    /// Ctrl-click toggles a row, and Shift-click selects the rows
+    /// between the last clicked one and the clicked one.
+    Multiple,
+}
+
+/// Text of the cells of a [`Table`], taking the index of the row and the
+/// index of the column. Only cells in view are asked for.
+///
+/// [`Table`]: super::Table
+#[derive(Clone)]
+pub struct Cells(pub(super) Rc<dyn Fn(usize, usize) -> String>);
+
+impl Cells {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(usize, usize) -> String + 'static,
+    {
+        Cells(Rc::new(f))
+    }
+}
+
+impl From<Vec<Vec<String>>> for Cells {
+    fn from(rows: Vec<Vec<String>>) -> Self {
+        Cells::new(move |row, column| {
+            rows.get(row)
+                .and_then(|cells| cells.get(column))
+                .cloned()
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl PartialEq for Cells {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
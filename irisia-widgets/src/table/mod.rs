@@ -0,0 +1,975 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{
+        Blured, Focused, PointerCaptureLost, PointerDown, PointerMove, PointerOut, PointerUp, Wheel,
+    },
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Canvas, Color, Font, Paint, PaintStyle, Path},
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, ModifiersState, MouseButton, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{
+    label::{draw_label, label_font, label_width, to_rect},
+    popup::styles::StylePopupBackground,
+    scroll::{
+        clamp_offset, draw_horizontal_scrollbar, draw_vertical_scrollbar, max_offset,
+        reveal_offset, wheel_delta, Heights,
+    },
+    textbox::styles::StyleFontSize,
+};
+
+use self::styles::{
+    StyleTableAccent, StyleTableBorder, StyleTableHeaderBackground, StyleTableSelection,
+};
+
+pub use self::column::{CellAlign, Cells, Column, SelectionMode, SortOrder};
+
+mod column;
+pub mod styles;
+
+const CELL_PADDING: Pixel = Pixel(8.0);
+/// Distance from the edge of a header the pointer can still grab it for resizing
+const RESIZE_MARGIN: Pixel = Pixel(4.0);
+/// Distance the pointer moves before a pressed header starts being dragged
+const DRAG_THRESHOLD: Pixel = Pixel(4.0);
+const ARROW_SIZE: Pixel = Pixel(4.0);
+
+/// A grid of text with a header row, which stays on top while the rows
+/// scroll. Only rows in view are drawn, and only their cells are asked for,
+/// so tables of any length cost about the same.
+///
+/// Dragging the right edge of a header resizes the column, dragging a header
+/// moves the column, and clicking the header of a sortable column emits
+/// [`TableSort`]. Rows are selected by clicking, or by the arrow keys while
+/// focused, according to the [`SelectionMode`]. Like the sort, the selection
+/// is controlled: [`TableSelectionChanged`] is emitted, and the owner is
+/// expected to pass the new selection back through `selected`.
+pub struct Table {
+    props: OwnedProps,
+    font: Font,
+    /// Width of each column, in the order of `columns`
+    widths: Vec<Pixel>,
+    /// Indices of the columns, from left to right
+    order: Vec<usize>,
+    sort: Option<(usize, SortOrder)>,
+    /// Selected rows in ascending order
+    selected: Vec<usize>,
+    /// Row a Shift-click selects from
+    anchor: Option<usize>,
+    /// Row moved by the arrow keys
+    cursor: Option<usize>,
+    hovered: Option<usize>,
+    focused: bool,
+    heights: Heights,
+    scroll: (Pixel, Pixel),
+    dragged: Option<DraggedHeader>,
+}
+
+/// Emitted when the header of a sortable column is clicked. The owner is
+/// expected to sort the rows and pass `sort` back.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct TableSort {
+    pub column: usize,
+    pub order: SortOrder,
+}
+
+/// Emitted whenever the user changes the selection.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct TableSelectionChanged {
+    /// Indices of the selected rows in ascending order.
+    pub rows: Vec<usize>,
+}
+
+/// Emitted once the user finished resizing a column.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct ColumnResized {
+    pub column: usize,
+    pub width: Pixel,
+}
+
+/// Emitted once the user moved a column.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ColumnsReordered {
+    /// Indices of the columns, from left to right.
+    pub order: Vec<usize>,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct TableStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: StylePopupBackground,
+    header_background: StyleTableHeaderBackground,
+    border: StyleTableBorder,
+    selection: StyleTableSelection,
+    accent: StyleTableAccent,
+}
+
+#[irisia::props(updater = "TableProps", watch)]
+pub struct OwnedProps {
+    #[props(must_init, watch)]
+    columns: Vec<Column>,
+
+    #[props(must_init, watch)]
+    row_count: usize,
+
+    #[props(must_init)]
+    cells: Cells,
+
+    #[props(default)]
+    selection: SelectionMode,
+
+    #[props(default, watch)]
+    selected: Vec<usize>,
+
+    /// Column the rows are sorted by, which shows an arrow in its header.
+    #[props(default, watch)]
+    sort: Option<(usize, SortOrder)>,
+
+    #[props(default = "Pixel(28.0)")]
+    row_height: Pixel,
+
+    #[props(default = "Pixel(32.0)")]
+    header_height: Pixel,
+
+    #[props(read_style(stdin))]
+    style: TableStyles,
+}
+
+/// A header being dragged to another place.
+#[derive(Clone, Copy)]
+struct DraggedHeader {
+    /// Place of the column from the left
+    position: usize,
+    /// Distance from the left edge of the header to the pointer
+    grab: Pixel,
+    pointer_x: Pixel,
+}
+
+/// What is under the pointer.
+enum Hit {
+    Row(usize),
+    Header(usize),
+    /// Right edge of the header at the place
+    HeaderEdge(usize),
+    Nothing,
+}
+
+#[derive(Clone, Copy)]
+struct TableLook {
+    color: Color,
+    background: Color,
+    header_background: Color,
+    border: Color,
+    selection: Color,
+    accent: Color,
+}
+
+fn fill(canvas: &mut Canvas, region: Region, color: Color) {
+    let mut paint = Paint::default();
+    paint.set_color(color);
+    canvas.draw_rect(to_rect(region), &paint);
+}
+
+impl Element for Table {
+    type BlankProps = TableProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+        let (max_x, max_y) = self.max_scroll(region);
+        this.consume_wheel(max_x > Pixel(0.0) || max_y > Pixel(0.0));
+
+        let look = self.look();
+        let canvas = content.canvas();
+        fill(canvas, region, look.background);
+
+        let edges = self.column_edges(region);
+        self.draw_body(canvas, region, &edges, &look);
+        self.draw_header(canvas, region, &edges, &look);
+        self.draw_scrollbars(canvas, region);
+        Ok(())
+    }
+
+    fn draw_region_changed(&mut self, _: &ElModel!(), region: Region) {
+        self.clamp_scroll(region);
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Table
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        let mut table = Table {
+            font: label_font(font_size(&props.style)),
+            heights: Heights::new(props.row_count, props.row_height),
+            props,
+            widths: Vec::new(),
+            order: Vec::new(),
+            sort: None,
+            selected: Vec::new(),
+            anchor: None,
+            cursor: None,
+            hovered: None,
+            focused: false,
+            scroll: (Pixel(0.0), Pixel(0.0)),
+            dragged: None,
+        };
+        table.sync_columns();
+        table.sync_with_props();
+        table
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.columns_unchanged {
+            self.sync_columns();
+        }
+
+        let value_unchanged = update_result.columns_unchanged
+            && update_result.row_count_unchanged
+            && update_result.selected_unchanged
+            && update_result.sort_unchanged;
+        if !value_unchanged || !update_result.unchanged {
+            self.font = label_font(font_size(&self.props.style));
+            self.sync_with_props();
+            self.clamp_scroll(this.draw_region());
+        }
+        update_result.unchanged && value_unchanged
+    }
+}
+
+fn font_size(style: &TableStyles) -> Pixel {
+    style.font_size.map_or(Pixel(14.0), |size| size.0)
+}
+
+impl Table {
+    fn look(&self) -> TableLook {
+        let style = &self.props.style;
+        TableLook {
+            color: style.color.map_or(Color::BLACK, |color| color.0),
+            background: style.background.0,
+            header_background: style.header_background.0,
+            border: style.border.0,
+            selection: style.selection.0,
+            accent: style.accent.0,
+        }
+    }
+
+    /// Reset the widths and the order of columns, which the user may have changed.
+    fn sync_columns(&mut self) {
+        let columns = &self.props.columns;
+        self.widths = columns
+            .iter()
+            .map(|column| column.width.max(column.min_width))
+            .collect();
+        self.order = (0..columns.len()).collect();
+    }
+
+    fn sync_with_props(&mut self) {
+        let row_count = self.props.row_count;
+        self.heights.reset(row_count, self.props.row_height);
+        self.sort = self
+            .props
+            .sort
+            .filter(|(column, _)| *column < self.props.columns.len());
+
+        self.selected = match self.props.selection {
+            SelectionMode::None => Vec::new(),
+            SelectionMode::Single => self.props.selected.first().copied().into_iter().collect(),
+            SelectionMode::Multiple => self.props.selected.clone(),
+        };
+        self.selected.retain(|row| *row < row_count);
+        self.selected.sort_unstable();
+        self.selected.dedup();
+
+        for row in [&mut self.anchor, &mut self.cursor, &mut self.hovered] {
+            if row.is_some_and(|row| row >= row_count) {
+                *row = None;
+            }
+        }
+    }
+
+    fn header(&self, (start, end): Region) -> Region {
+        (
+            start,
+            Point(end.0, (start.1 + self.props.header_height).min(end.1)),
+        )
+    }
+
+    fn body(&self, region: Region) -> Region {
+        (Point(region.0 .0, self.header(region).1 .1), region.1)
+    }
+
+    /// Left edge of every column from left to right, followed by the right
+    /// edge of the last one.
+    fn column_edges(&self, region: Region) -> Vec<Pixel> {
+        let mut x = region.0 .0 - self.scroll.0;
+        let mut edges = Vec::with_capacity(self.order.len() + 1);
+        edges.push(x);
+        for &column in &self.order {
+            x = x + self.widths[column];
+            edges.push(x);
+        }
+        edges
+    }
+
+    fn content_size(&self) -> (Pixel, Pixel) {
+        let width = self
+            .widths
+            .iter()
+            .fold(Pixel(0.0), |width, column| width + *column);
+        (width, self.heights.total())
+    }
+
+    fn max_scroll(&self, region: Region) -> (Pixel, Pixel) {
+        let body = self.body(region);
+        let (width, height) = self.content_size();
+        (
+            max_offset(width, body.1 .0 - body.0 .0),
+            max_offset(height, body.1 .1 - body.0 .1),
+        )
+    }
+
+    fn clamp_scroll(&mut self, region: Region) {
+        let (max_x, max_y) = self.max_scroll(region);
+        self.scroll = (
+            clamp_offset(self.scroll.0, max_x),
+            clamp_offset(self.scroll.1, max_y),
+        );
+    }
+
+    fn scroll_by(&mut self, region: Region, dx: Pixel, dy: Pixel) {
+        self.scroll = (self.scroll.0 + dx, self.scroll.1 + dy);
+        self.clamp_scroll(region);
+    }
+
+    fn scroll_to_row(&mut self, region: Region, row: usize) {
+        let body = self.body(region);
+        let top = self.heights.offset(row);
+        self.scroll.1 = reveal_offset(
+            self.scroll.1,
+            body.1 .1 - body.0 .1,
+            top,
+            top + self.heights.get(row),
+        );
+        self.clamp_scroll(region);
+    }
+
+    fn row_at(&self, region: Region, y: Pixel) -> Option<usize> {
+        let body = self.body(region);
+        if y < body.0 .1 || y >= body.1 .1 {
+            return None;
+        }
+
+        let y = y - body.0 .1 + self.scroll.1;
+        (y < self.heights.total()).then(|| self.heights.row_at(y))
+    }
+
+    fn hit(&self, region: Region, point: Point) -> Hit {
+        let header = self.header(region);
+        if point.1 >= header.1 .1 {
+            return match self.row_at(region, point.1) {
+                Some(row) => Hit::Row(row),
+                None => Hit::Nothing,
+            };
+        }
+
+        let edges = self.column_edges(region);
+        for (position, edge) in edges.iter().enumerate().skip(1).rev() {
+            if (point.0 - *edge).0.abs() <= RESIZE_MARGIN.0 {
+                return Hit::HeaderEdge(position - 1);
+            }
+        }
+
+        edges
+            .windows(2)
+            .position(|edge| point.0 >= edge[0] && point.0 < edge[1])
+            .map_or(Hit::Nothing, Hit::Header)
+    }
+
+    fn set_selected(&mut self, this: &ElModel!(), selected: Vec<usize>) {
+        if selected != self.selected {
+            self.selected = selected;
+            this.event_dispatcher().emit(TableSelectionChanged {
+                rows: self.selected.clone(),
+            });
+        }
+    }
+
+    /// Select `row` as if clicked with `modifiers`.
+    fn select_row(&mut self, this: &ElModel!(), row: usize, modifiers: ModifiersState) {
+        self.cursor = Some(row);
+        let toggle = modifiers.ctrl() || modifiers.logo();
+
+        let selected = match self.props.selection {
+            SelectionMode::None => return,
+            SelectionMode::Multiple if modifiers.shift() => {
+                let anchor = self.anchor.unwrap_or(row);
+                (anchor.min(row)..=anchor.max(row)).collect()
+            }
+            SelectionMode::Multiple if toggle => {
+                self.anchor = Some(row);
+                let mut selected = self.selected.clone();
+                match selected.binary_search(&row) {
+                    Ok(index) => {
+                        selected.remove(index);
+                    }
+                    Err(index) => selected.insert(index, row),
+                }
+                selected
+            }
+            _ => {
+                self.anchor = Some(row);
+                vec![row]
+            }
+        };
+        self.set_selected(this, selected);
+    }
+
+    fn toggle_sort(&mut self, this: &ElModel!(), column: usize) {
+        if !self.props.columns[column].sortable {
+            return;
+        }
+
+        let order = match self.sort {
+            Some((sorted, order)) if sorted == column => order.reversed(),
+            _ => SortOrder::Ascending,
+        };
+        self.sort = Some((column, order));
+        this.event_dispatcher().emit(TableSort { column, order });
+    }
+
+    /// Place the dragged header where its middle is, returns whether the order changed.
+    fn drop_header(&mut self, region: Region, dragged: DraggedHeader) -> bool {
+        let column = self.order[dragged.position];
+        let middle = dragged.pointer_x - dragged.grab + self.widths[column] / 2.0;
+        let target = self.drop_position(region, dragged.position, middle);
+        if target == dragged.position {
+            return false;
+        }
+
+        self.order.remove(dragged.position);
+        self.order.insert(target, column);
+        true
+    }
+
+    /// Place a column at `position` will be moved to if its middle is at `x`.
+    fn drop_position(&self, region: Region, position: usize, x: Pixel) -> usize {
+        let edges = self.column_edges(region);
+        (0..self.order.len())
+            .filter(|other| *other != position)
+            .filter(|other| (edges[*other] + edges[*other + 1]) / 2.0 < x)
+            .count()
+    }
+
+    fn input(&mut self, this: &ElModel!(), key: VirtualKeyCode, modifiers: ModifiersState) {
+        let row_count = self.props.row_count;
+        if row_count == 0 {
+            return;
+        }
+
+        let region = this.draw_region();
+        let body = self.body(region);
+        let page = if self.props.row_height > Pixel(0.0) {
+            (((body.1 .1 - body.0 .1).0 / self.props.row_height.0) as usize).max(1)
+        } else {
+            1
+        };
+
+        let current = self.cursor.unwrap_or(0);
+        let row = match key {
+            VirtualKeyCode::Up => current.saturating_sub(1),
+            VirtualKeyCode::Down if self.cursor.is_none() => 0,
+            VirtualKeyCode::Down => current + 1,
+            VirtualKeyCode::PageUp => current.saturating_sub(page),
+            VirtualKeyCode::PageDown => current + page,
+            VirtualKeyCode::Home => 0,
+            VirtualKeyCode::End => row_count - 1,
+            VirtualKeyCode::A
+                if (modifiers.ctrl() || modifiers.logo())
+                    && self.props.selection == SelectionMode::Multiple =>
+            {
+                self.set_selected(this, (0..row_count).collect());
+                return;
+            }
+            VirtualKeyCode::Space => {
+                if let Some(row) = self.cursor {
+                    self.select_row(this, row, ModifiersState::CTRL);
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        let row = row.min(row_count - 1);
+        // without Ctrl the selection follows the current row
+        if modifiers.ctrl() || modifiers.logo() {
+            self.cursor = Some(row);
+        } else {
+            self.select_row(this, row, modifiers);
+        }
+        self.scroll_to_row(region, row);
+    }
+}
+
+// painting
+
+impl Table {
+    fn draw_body(&self, canvas: &mut Canvas, region: Region, edges: &[Pixel], look: &TableLook) {
+        let body = self.body(region);
+        canvas.save();
+        canvas.clip_rect(to_rect(body), None, true);
+
+        let rows = self.heights.visible(self.scroll.1, body.1 .1 - body.0 .1);
+        let last = rows.end;
+        let right = edges[edges.len() - 1].min(body.1 .0);
+
+        for row in rows {
+            let top = body.0 .1 + self.heights.offset(row) - self.scroll.1;
+            let row_height = self.heights.get(row);
+            let row_region = (Point(body.0 .0, top), Point(right, top + row_height));
+
+            if self.selected.binary_search(&row).is_ok() {
+                fill(canvas, row_region, look.selection);
+            } else if self.hovered == Some(row) {
+                fill(canvas, row_region, look.color.with_a(0x0a));
+            }
+
+            for (position, &column) in self.order.iter().enumerate() {
+                let (left, right) = (edges[position], edges[position + 1]);
+                if right <= body.0 .0 || left >= body.1 .0 {
+                    continue;
+                }
+
+                let text = (self.props.cells.0)(row, column);
+                self.draw_cell(
+                    canvas,
+                    &text,
+                    self.props.columns[column].align,
+                    look.color,
+                    (Point(left, top), Point(right, top + row_height)),
+                );
+            }
+
+            fill(
+                canvas,
+                (
+                    Point(row_region.0 .0, row_region.1 .1 - Pixel(1.0)),
+                    row_region.1,
+                ),
+                look.border,
+            );
+
+            if self.focused && self.cursor == Some(row) {
+                let mut paint = Paint::default();
+                paint
+                    .set_anti_alias(true)
+                    .set_color(look.accent)
+                    .set_style(PaintStyle::Stroke)
+                    .set_stroke_width(Pixel(1.0).to_physical());
+                let inset = Pixel(1.0);
+                let outline = (
+                    Point(row_region.0 .0 + inset, top + inset),
+                    Point(row_region.1 .0 - inset, top + row_height - inset),
+                );
+                canvas.draw_rect(to_rect(outline), &paint);
+            }
+        }
+
+        let bottom = (body.0 .1 + self.heights.offset(last) - self.scroll.1).min(body.1 .1);
+        for edge in &edges[1..] {
+            fill(
+                canvas,
+                (Point(*edge - Pixel(1.0), body.0 .1), Point(*edge, bottom)),
+                look.border,
+            );
+        }
+
+        canvas.restore();
+    }
+
+    fn draw_header(&self, canvas: &mut Canvas, region: Region, edges: &[Pixel], look: &TableLook) {
+        let header = self.header(region);
+        fill(canvas, header, look.header_background);
+
+        canvas.save();
+        canvas.clip_rect(to_rect(header), None, true);
+
+        for (position, &column) in self.order.iter().enumerate() {
+            if self
+                .dragged
+                .is_some_and(|dragged| dragged.position == position)
+            {
+                continue;
+            }
+            let cell = (
+                Point(edges[position], header.0 .1),
+                Point(edges[position + 1], header.1 .1),
+            );
+            self.draw_header_cell(canvas, column, cell, look);
+        }
+
+        fill(
+            canvas,
+            (Point(header.0 .0, header.1 .1 - Pixel(1.0)), header.1),
+            look.border,
+        );
+
+        if let Some(dragged) = self.dragged {
+            let column = self.order[dragged.position];
+            let left = dragged.pointer_x - dragged.grab;
+            let middle = left + self.widths[column] / 2.0;
+
+            // mark the place the column will be dropped at
+            let target = self.drop_position(region, dragged.position, middle);
+            let others: Vec<usize> = (0..self.order.len())
+                .filter(|other| *other != dragged.position)
+                .collect();
+            let marker = match others.get(target) {
+                Some(&other) => edges[other],
+                None => edges[others.last().map_or(0, |last| last + 1)],
+            };
+            fill(
+                canvas,
+                (
+                    Point(marker - Pixel(1.0), header.0 .1),
+                    Point(marker + Pixel(1.0), header.1 .1),
+                ),
+                look.accent,
+            );
+
+            let cell = (
+                Point(left, header.0 .1),
+                Point(left + self.widths[column], header.1 .1),
+            );
+            fill(canvas, cell, look.header_background);
+            fill(canvas, cell, look.color.with_a(0x10));
+            self.draw_header_cell(canvas, column, cell, look);
+        }
+
+        canvas.restore();
+    }
+
+    fn draw_header_cell(&self, canvas: &mut Canvas, column: usize, cell: Region, look: &TableLook) {
+        let definition = &self.props.columns[column];
+        let sorted = self
+            .sort
+            .filter(|(sorted, _)| *sorted == column)
+            .map(|(_, order)| order);
+
+        // leave room for the sort arrow on the right
+        let text_cell = match sorted {
+            Some(_) => (cell.0, Point(cell.1 .0 - ARROW_SIZE * 3.0, cell.1 .1)),
+            None => cell,
+        };
+        self.draw_cell(
+            canvas,
+            &definition.header,
+            definition.align,
+            look.color,
+            text_cell,
+        );
+
+        if let Some(order) = sorted {
+            let x = cell.1 .0 - CELL_PADDING - ARROW_SIZE;
+            let y = (cell.0 .1 + cell.1 .1) / 2.0;
+            let dy = match order {
+                SortOrder::Ascending => -ARROW_SIZE / 2.0,
+                SortOrder::Descending => ARROW_SIZE / 2.0,
+            };
+
+            let mut path = Path::new();
+            path.move_to(Point(x - ARROW_SIZE, y - dy))
+                .line_to(Point(x + ARROW_SIZE, y - dy))
+                .line_to(Point(x, y + dy))
+                .close();
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true).set_color(look.accent);
+            canvas.draw_path(&path, &paint);
+        }
+
+        fill(
+            canvas,
+            (Point(cell.1 .0 - Pixel(1.0), cell.0 .1), cell.1),
+            look.border,
+        );
+    }
+
+    fn draw_cell(
+        &self,
+        canvas: &mut Canvas,
+        text: &str,
+        align: CellAlign,
+        color: Color,
+        cell: Region,
+    ) {
+        let (left, right) = (cell.0 .0 + CELL_PADDING, cell.1 .0 - CELL_PADDING);
+        if right <= left {
+            return;
+        }
+
+        let x = match align {
+            CellAlign::Start => left,
+            CellAlign::Center => (left + right - label_width(&self.font, text)) / 2.0,
+            CellAlign::End => right - label_width(&self.font, text),
+        };
+
+        canvas.save();
+        canvas.clip_rect(
+            to_rect((Point(left, cell.0 .1), Point(right, cell.1 .1))),
+            None,
+            true,
+        );
+        draw_label(canvas, &self.font, text, color, x, (cell.0 .1, cell.1 .1));
+        canvas.restore();
+    }
+
+    fn draw_scrollbars(&self, canvas: &mut Canvas, region: Region) {
+        let body = self.body(region);
+        let (width, height) = self.content_size();
+        draw_vertical_scrollbar(canvas, body, height, self.scroll.1);
+        draw_horizontal_scrollbar(canvas, body, width, self.scroll.0);
+    }
+}
+
+// runtimes
+
+async fn update(this: &ElModel!(Table), f: impl FnOnce(&mut Table)) -> bool {
+    let Some(mut table) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *table);
+    true
+}
+
+/// Highlight the row at `position`, returns whether the table is alive.
+async fn hover(this: &ElModel!(Table), position: Option<Point>) -> bool {
+    let Some(table) = this.el_read().await
+    else {
+        return false;
+    };
+
+    let hovered = position.and_then(|position| table.row_at(this.draw_region(), position.1));
+    if hovered == table.hovered {
+        return true;
+    }
+    drop(table);
+    update(this, |table| table.hovered = hovered).await
+}
+
+async fn pointer_runtime(this: ElModel!(Table)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let pd = tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => pd,
+            pm = ed.recv_trusted::<PointerMove>() => {
+                if !hover(&this, Some(pm.position)).await {
+                    return;
+                }
+                continue;
+            }
+            _ = ed.recv_trusted::<PointerOut>() => {
+                if !hover(&this, None).await {
+                    return;
+                }
+                continue;
+            }
+            wheel = ed.recv_trusted::<Wheel>() => {
+                let (dx, dy) = wheel_delta(wheel.delta);
+                let (dx, dy) = if wheel.modifiers.shift() { (dy, dx) } else { (dx, dy) };
+
+                if !update(&this, |table| table.scroll_by(this.draw_region(), dx, dy)).await {
+                    return;
+                }
+                continue;
+            }
+        };
+
+        if pd.button != MouseButton::Left {
+            continue;
+        }
+        this.focus();
+
+        let Some(table) = this.el_read().await
+        else {
+            return;
+        };
+        let region = this.draw_region();
+        let hit = table.hit(region, pd.position);
+        drop(table);
+
+        let alive = match hit {
+            Hit::Row(row) => {
+                update(&this, |table| table.select_row(&this, row, pd.modifiers)).await
+            }
+            Hit::HeaderEdge(position) => resize_column(&this, &pd, position).await,
+            Hit::Header(position) => press_header(&this, &pd, position).await,
+            Hit::Nothing => true,
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
+
+/// Wait until the pointer pressing the table is released. Returns the
+/// position it is released at, `None` if the capture is lost.
+async fn follow_pointer(
+    this: &ElModel!(Table),
+    pd: &PointerDown,
+    mut on_move: impl FnMut(&mut Table, Point),
+) -> Option<Option<Point>> {
+    let ed = this.event_dispatcher().clone();
+    let pointer_id = pd.pointer_id;
+    this.capture_pointer(pointer_id);
+
+    loop {
+        tokio::select! {
+            pm = ed.recv_trusted::<PointerMove>() => {
+                if pm.pointer_id != pointer_id {
+                    continue;
+                }
+                if !update(this, |table| on_move(table, pm.position)).await {
+                    return None;
+                }
+            }
+            pu = ed.recv_trusted::<PointerUp>() => {
+                if pu.pointer_id == pointer_id {
+                    return Some(Some(pu.position));
+                }
+            }
+            pcl = ed.recv_trusted::<PointerCaptureLost>() => {
+                if pcl.pointer_id == pointer_id {
+                    return Some(None);
+                }
+            }
+        }
+    }
+}
+
+async fn resize_column(this: &ElModel!(Table), pd: &PointerDown, position: usize) -> bool {
+    let Some(table) = this.el_read().await
+    else {
+        return false;
+    };
+    let column = table.order[position];
+    let start_width = table.widths[column];
+    drop(table);
+
+    let resized = follow_pointer(this, pd, |table, point| {
+        let min_width = table.props.columns[column].min_width;
+        table.widths[column] = (start_width + point.0 - pd.position.0).max(min_width);
+        table.clamp_scroll(this.draw_region());
+    });
+    if resized.await.is_none() {
+        return false;
+    }
+
+    update(this, |table| {
+        let width = table.widths[column];
+        if width != start_width {
+            this.event_dispatcher()
+                .emit(ColumnResized { column, width });
+        }
+    })
+    .await
+}
+
+async fn press_header(this: &ElModel!(Table), pd: &PointerDown, position: usize) -> bool {
+    let Some(table) = this.el_read().await
+    else {
+        return false;
+    };
+    let grab = pd.position.0 - table.column_edges(this.draw_region())[position];
+    drop(table);
+
+    let moved = follow_pointer(this, pd, |table, point| {
+        let dragging =
+            table.dragged.is_some() || (point.0 - pd.position.0).0.abs() >= DRAG_THRESHOLD.0;
+        if dragging {
+            table.dragged = Some(DraggedHeader {
+                position,
+                grab,
+                pointer_x: point.0,
+            });
+        }
+    });
+    let Some(released) = moved.await
+    else {
+        return false;
+    };
+
+    update(this, |table| match (table.dragged.take(), released) {
+        (Some(dragged), Some(point)) => {
+            let dragged = DraggedHeader {
+                pointer_x: point.0,
+                ..dragged
+            };
+            if table.drop_header(this.draw_region(), dragged) {
+                this.event_dispatcher().emit(ColumnsReordered {
+                    order: table.order.clone(),
+                });
+            }
+        }
+        (None, Some(_)) => {
+            let column = table.order[position];
+            table.toggle_sort(this, column);
+        }
+        _ => {}
+    })
+    .await
+}
+
+async fn keyboard_runtime(this: ElModel!(Table)) {
+    let ed = this.event_dispatcher().clone();
+    let mut modifiers = ModifiersState::empty();
+
+    loop {
+        ed.recv_trusted::<Focused>().await;
+        if !update(&this, |table| table.focused = true).await {
+            return;
+        }
+
+        loop {
+            let key = tokio::select! {
+                event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                    StaticWindowEvent::ModifiersChanged(state) => {
+                        modifiers = state;
+                        continue;
+                    }
+                    StaticWindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => key,
+                    _ => continue,
+                },
+                _ = ed.recv_trusted::<Blured>() => break,
+            };
+
+            if !update(&this, |table| table.input(&this, key, modifiers)).await {
+                return;
+            }
+        }
+
+        if !update(&this, |table| table.focused = false).await {
+            return;
+        }
+    }
+}
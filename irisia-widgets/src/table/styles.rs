@@ -0,0 +1,24 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Background of the header row.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTableHeaderBackground(
+    #[style(default = "Color::from_rgb(0xf3, 0xf3, 0xf3)")] pub Color,
+);
+
+/// Color of the grid lines.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTableBorder(#[style(default = "Color::from_rgb(0xe0, 0xe0, 0xe0)")] pub Color);
+
+/// Background of selected rows.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTableSelection(#[style(default = "Color::from_rgb(0xd6, 0xe6, 0xfd)")] pub Color);
+
+/// Color of the sort arrows, the current row outline and the drop marker
+/// of a dragged column.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTableAccent(#[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color);
@@ -0,0 +1,344 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, LayoutElements, RenderElement},
+    event::standard::{Blured, Focused, PointerDown, PointerMove, PointerOut},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Color, Font, Paint, PaintStyle, RRect},
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, MouseButton, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{
+    label::{draw_label, label_font, label_width, to_rect},
+    popup::region_contains,
+    textbox::styles::StyleFontSize,
+};
+
+use self::styles::{StyleTabsAccent, StyleTabsBackground};
+
+pub use self::tab::{Tab, TabProps};
+
+pub mod styles;
+mod tab;
+
+const TAB_PADDING: Pixel = Pixel(16.0);
+const UNDERLINE: Pixel = Pixel(2.0);
+
+/// A strip of tab headers above the page of the selected tab.
+///
+/// Each child is the page of the tab with the same index in `labels`, and
+/// only the page of the selected tab is shown. Wrap pages in [`Tab`] to
+/// mount them only while selected; pages are still created along with the
+/// tabs. Clicking a header, or pressing Left, Right, Home or End while
+/// focused, selects a tab and emits [`TabSelected`].
+pub struct Tabs {
+    props: OwnedProps,
+    font: Font,
+    active: usize,
+    hovered: Option<usize>,
+    focused: bool,
+}
+
+/// Emitted on the event dispatcher of the tabs whenever the user selects a tab.
+#[derive(Event, Clone, Copy, PartialEq)]
+pub struct TabSelected {
+    pub index: usize,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct TabsStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: StyleTabsBackground,
+    accent: StyleTabsAccent,
+}
+
+#[irisia::props(updater = "TabsProps", watch)]
+pub struct OwnedProps {
+    /// Titles of the tabs, one for each child.
+    #[props(must_init)]
+    labels: Vec<String>,
+
+    /// Index of the selected tab.
+    #[props(default, watch)]
+    active: usize,
+
+    #[props(default = "Pixel(36.0)")]
+    header_height: Pixel,
+
+    #[props(read_style(stdin))]
+    style: TabsStyles,
+}
+
+impl Element for Tabs {
+    type BlankProps = TabsProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        let header = self.header(region);
+        this.set_interact_region(Some(header));
+
+        let style = &self.props.style;
+        let color = style.color.map_or(Color::BLACK, |color| color.0);
+        let accent = style.accent.0;
+
+        let canvas = content.canvas();
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_color(style.background.0);
+        canvas.draw_rect(to_rect(header), &paint);
+
+        paint.set_color(color.with_a(0x30));
+        canvas.draw_rect(
+            to_rect((Point(header.0 .0, header.1 .1 - Pixel(1.0)), header.1)),
+            &paint,
+        );
+
+        canvas.save();
+        canvas.clip_rect(to_rect(header), None, true);
+        for (index, tab) in self.tab_regions(region).into_iter().enumerate() {
+            let active = index == self.active;
+            if !active && self.hovered == Some(index) {
+                paint.set_color(color.with_a(0x10));
+                canvas.draw_rect(to_rect(tab), &paint);
+            }
+
+            draw_label(
+                canvas,
+                &self.font,
+                &self.props.labels[index],
+                if active { accent } else { color },
+                tab.0 .0 + TAB_PADDING,
+                (tab.0 .1, tab.1 .1),
+            );
+
+            if !active {
+                continue;
+            }
+
+            paint.set_color(accent);
+            canvas.draw_rect(
+                to_rect((Point(tab.0 .0, tab.1 .1 - UNDERLINE), tab.1)),
+                &paint,
+            );
+
+            if self.focused {
+                let inset = Pixel(3.0);
+                let ring = (
+                    Point(tab.0 .0 + inset, tab.0 .1 + inset),
+                    Point(tab.1 .0 - inset, tab.1 .1 - inset - UNDERLINE),
+                );
+                let radius = Pixel(4.0).to_physical();
+                let mut stroke = Paint::default();
+                stroke
+                    .set_anti_alias(true)
+                    .set_color(accent)
+                    .set_style(PaintStyle::Stroke)
+                    .set_stroke_width(Pixel(1.0).to_physical());
+                canvas.draw_rrect(RRect::new_rect_xy(to_rect(ring), radius, radius), &stroke);
+            }
+        }
+        canvas.restore();
+
+        content.render_children()
+    }
+
+    fn set_children(&self, this: &ElModel!()) {
+        self.layout(this, this.set_children(this.slot()));
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        if let Some(lc) = this.layout_children() {
+            self.layout(this, lc);
+        }
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Tabs
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        Tabs {
+            font: label_font(font_size(&props.style)),
+            active: props.active,
+            props,
+            hovered: None,
+            focused: false,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.active_unchanged {
+            self.active = self.props.active;
+        }
+        if !update_result.unchanged {
+            self.font = label_font(font_size(&self.props.style));
+        }
+        update_result.unchanged && update_result.active_unchanged
+    }
+}
+
+fn font_size(style: &TabsStyles) -> Pixel {
+    style.font_size.map_or(Pixel(14.0), |size| size.0)
+}
+
+impl Tabs {
+    fn header(&self, (start, end): Region) -> Region {
+        (
+            start,
+            Point(end.0, (start.1 + self.props.header_height).min(end.1)),
+        )
+    }
+
+    fn tab_regions(&self, region: Region) -> Vec<Region> {
+        let header = self.header(region);
+        let mut left = header.0 .0;
+        self.props
+            .labels
+            .iter()
+            .map(|label| {
+                let right = left + label_width(&self.font, label) + TAB_PADDING * 2.0;
+                let tab = (Point(left, header.0 .1), Point(right, header.1 .1));
+                left = right;
+                tab
+            })
+            .collect()
+    }
+
+    fn tab_at(&self, region: Region, point: Point) -> Option<usize> {
+        self.tab_regions(region)
+            .into_iter()
+            .position(|tab| region_contains(tab, point))
+    }
+
+    /// Lay out the page of the selected tab below the header, and the
+    /// others in an empty region, which hides them.
+    fn layout(&self, this: &ElModel!(), lc: LayoutElements) {
+        let region = this.draw_region();
+        let page = (Point(region.0 .0, self.header(region).1 .1), region.1);
+        let hidden = (page.0, page.0);
+
+        let mut index = 0;
+        lc.layout(|()| {
+            let region = if index == self.active { page } else { hidden };
+            index += 1;
+            Some(region)
+        })
+        .unwrap();
+    }
+
+    fn select(&mut self, this: &ElModel!(), index: usize) {
+        if index < self.props.labels.len() && index != self.active {
+            self.active = index;
+            this.event_dispatcher().emit(TabSelected { index });
+        }
+    }
+
+    fn input(&mut self, this: &ElModel!(), key: VirtualKeyCode) {
+        let index = match key {
+            VirtualKeyCode::Left => self.active.checked_sub(1),
+            VirtualKeyCode::Right => Some(self.active + 1),
+            VirtualKeyCode::Home => Some(0),
+            VirtualKeyCode::End => self.props.labels.len().checked_sub(1),
+            _ => None,
+        };
+
+        if let Some(index) = index {
+            self.select(this, index);
+        }
+    }
+}
+
+async fn update(this: &ElModel!(Tabs), f: impl FnOnce(&mut Tabs)) -> bool {
+    let Some(mut tabs) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *tabs);
+    true
+}
+
+async fn pointer_runtime(this: ElModel!(Tabs)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let alive = tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => {
+                if pd.button != MouseButton::Left {
+                    continue;
+                }
+
+                this.focus();
+                update(&this, |tabs| {
+                    if let Some(index) = tabs.tab_at(this.draw_region(), pd.position) {
+                        tabs.select(&this, index);
+                    }
+                })
+                .await
+            }
+            pm = ed.recv_trusted::<PointerMove>() => {
+                let Some(tabs) = this.el_read().await
+                else {
+                    return;
+                };
+
+                let hovered = tabs.tab_at(this.draw_region(), pm.position);
+                if hovered == tabs.hovered {
+                    continue;
+                }
+                drop(tabs);
+                update(&this, |tabs| tabs.hovered = hovered).await
+            }
+            _ = ed.recv_trusted::<PointerOut>() => {
+                update(&this, |tabs| tabs.hovered = None).await
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
+
+async fn keyboard_runtime(this: ElModel!(Tabs)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        ed.recv_trusted::<Focused>().await;
+        if !update(&this, |tabs| tabs.focused = true).await {
+            return;
+        }
+
+        loop {
+            let key = tokio::select! {
+                event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                    StaticWindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => key,
+                    _ => continue,
+                },
+                _ = ed.recv_trusted::<Blured>() => break,
+            };
+
+            if !update(&this, |tabs| tabs.input(&this, key)).await {
+                return;
+            }
+        }
+
+        if !update(&this, |tabs| tabs.focused = false).await {
+            return;
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Background of the header strip.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTabsBackground(#[style(default = "Color::from_rgb(0xf3, 0xf3, 0xf3)")] pub Color);
+
+/// Color of the label and the underline of the active tab.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTabsAccent(#[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color);
@@ -0,0 +1,93 @@
+use std::cell::Cell;
+
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate},
+    primitive::Region,
+    structure::Branch,
+    ElModel,
+};
+
+/// A page of [`Tabs`], mounting its children only while the tab is selected.
+///
+/// Mounting is not creating: the children of a tab are a slot built by the
+/// parent along with the tab, so they are created, and updated, even while
+/// the tab is not selected. An unmounted page is only neither laid out,
+/// drawn nor told about the pointer. To create the content lazily, put it in
+/// a condition on the selected tab:
+///
+/// ```ignore
+/// Tabs {
+///     labels: vec![String::from("Log"), String::from("Graph")],
+///     active: self.tab,
+///     Tab { LogView {} }
+///     Tab {
+///         if self.tab == 1 {
+///             Graph {}
+///         }
+///     }
+/// }
+/// ```
+///
+/// [`Tabs`]: super::Tabs
+pub struct Tab {
+    props: OwnedProps,
+    /// Whether the tab was selected once
+    visited: Cell<bool>,
+}
+
+#[irisia::props(updater = "TabProps", watch)]
+pub struct OwnedProps {
+    /// Keep the children mounted, but hidden, once the tab was selected,
+    /// so they keep their layout and are told the pointer left them.
+    #[props(default)]
+    keep_alive: bool,
+}
+
+impl Element for Tab {
+    type BlankProps = TabProps;
+
+    fn set_children(&self, this: &ElModel!()) {
+        self.mount(this);
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        self.mount(this);
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Tab
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        Tab {
+            props: OwnedProps::props_create_with(props),
+            visited: Cell::new(false),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
+
+impl Tab {
+    fn mount(&self, this: &ElModel!()) {
+        // the tabs lay out unselected pages in an empty region
+        let shown = !this.is_hidden();
+        if shown {
+            self.visited.set(true);
+        }
+
+        let children = if shown || (self.props.keep_alive && self.visited.get()) {
+            Branch::ArmA(this.slot())
+        } else {
+            Branch::ArmB(())
+        };
+
+        let region = this.draw_region();
+        this.set_children(children)
+            .layout(|()| Some(region))
+            .unwrap();
+    }
+}
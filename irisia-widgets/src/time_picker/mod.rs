@@ -0,0 +1,562 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Blured, Focused, PointerDown, Wheel, WheelDelta},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{Color, Font, Paint, PaintStyle, Path, RRect},
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, ModifiersState, MouseButton, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{
+    date_picker::{
+        styles::{StyleDateAccent, StyleDateBorder, StyleDateHighlight},
+        DateLocale,
+    },
+    label::{draw_label, label_font, label_width, to_rect},
+    popup::styles::StylePopupBackground,
+    textbox::styles::StyleFontSize,
+};
+
+pub use self::time::Time;
+
+mod time;
+
+const FIELD_PADDING: Pixel = Pixel(10.0);
+const ICON_COLUMN: Pixel = Pixel(28.0);
+/// Space around the text of the active segment in its highlight
+const SEGMENT_INSET: Pixel = Pixel(2.0);
+/// Pixels scrolled by a touchpad to change a segment by one
+const WHEEL_STEP: Pixel = Pixel(24.0);
+
+/// A box editing a time of the day, split into the hour, the minute and,
+/// if `twelve_hour`, the half of the day.
+///
+/// ```ignore
+/// TimePicker {
+///     value: Time::new(9, 30),
+///     min: Time::new(8, 0),
+///     max: Time::new(18, 0),
+///     minute_step: 15,
+/// }
+/// ```
+///
+/// The time is controlled: [`TimeChanged`] is emitted when the user
+/// changes it, and the owner is expected to pass the new time back through
+/// `value`. Times out of `min` and `max` are moved to the nearest one.
+///
+/// Clicking a segment makes it active. The arrow keys up and down and the
+/// wheel change it, wrapping around, and digits typed replace it, moving
+/// to the next segment once it takes no more digits. The arrow keys left
+/// and right and Tab move between segments, the first letter of the name
+/// of a half of the day switches to it, and Delete clears the time.
+pub struct TimePicker {
+    props: OwnedProps,
+    font: Font,
+    value: Option<Time>,
+    focused: bool,
+    /// Segment changed by the keyboard
+    segment: Segment,
+    /// Digits typed into the segment so far
+    typed: String,
+}
+
+/// Emitted on the time picker's event dispatcher whenever the user changes
+/// the time.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct TimeChanged {
+    pub value: Option<Time>,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct TimePickerStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: StylePopupBackground,
+    border: StyleDateBorder,
+    accent: StyleDateAccent,
+    highlight: StyleDateHighlight,
+}
+
+#[irisia::props(updater = "TimePickerProps", watch)]
+pub struct OwnedProps {
+    #[props(default, watch)]
+    value: Option<Time>,
+
+    /// Times before `min` cannot be set.
+    #[props(default)]
+    min: Option<Time>,
+
+    /// Times after `max` cannot be set.
+    #[props(default)]
+    max: Option<Time>,
+
+    /// Minutes the arrow keys and the wheel change the minute by.
+    #[props(default = "1")]
+    minute_step: u32,
+
+    /// Show hours from 1 to 12 followed by the half of the day.
+    #[props(default)]
+    twelve_hour: bool,
+
+    /// Names of the halves of the day, `AM` and `PM` by default.
+    #[props(default)]
+    locale: DateLocale,
+
+    #[props(read_style(stdin))]
+    style: TimePickerStyles,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    Hour,
+    Minute,
+    Period,
+}
+
+enum Input {
+    Key(VirtualKeyCode),
+    Char(char),
+}
+
+impl Element for TimePicker {
+    type BlankProps = TimePickerProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let style = &self.props.style;
+        let color = style.color.map_or(Color::BLACK, |color| color.0);
+        let canvas = content.canvas();
+        let radius = Pixel(4.0).to_physical();
+        let rrect = RRect::new_rect_xy(to_rect(region), radius, radius);
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_color(style.background.0);
+        canvas.draw_rrect(rrect, &paint);
+
+        paint
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(Pixel(1.0).to_physical())
+            .set_color(if self.focused {
+                style.accent.0
+            } else {
+                style.border.0
+            });
+        canvas.draw_rrect(rrect, &paint);
+
+        let rows = (region.0 .1, region.1 .1);
+        let center = (region.0 .1 + region.1 .1) / 2.0;
+        let text_color = if self.value.is_some() {
+            color
+        } else {
+            color.with_a(0x80)
+        };
+
+        canvas.save();
+        canvas.clip_rect(
+            to_rect((region.0, Point(region.1 .0 - ICON_COLUMN, region.1 .1))),
+            None,
+            true,
+        );
+        for (segment, text, x) in self.layout(region) {
+            if self.focused && segment == Some(self.segment) {
+                let half = self.font_size() * 0.7;
+                let width = label_width(&self.font, &text);
+                let highlight = RRect::new_rect_xy(
+                    to_rect((
+                        Point(x - SEGMENT_INSET, center - half),
+                        Point(x + width + SEGMENT_INSET, center + half),
+                    )),
+                    radius / 2.0,
+                    radius / 2.0,
+                );
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true).set_color(style.highlight.0);
+                canvas.draw_rrect(highlight, &paint);
+            }
+            draw_label(canvas, &self.font, &text, text_color, x, rows);
+        }
+        canvas.restore();
+
+        // a clock showing a quarter past
+        let icon = Point(region.1 .0 - ICON_COLUMN / 2.0, center);
+        let mut path = Path::new();
+        path.add_circle(icon, Pixel(6.0).to_physical(), None)
+            .move_to(Point(icon.0, icon.1 - Pixel(3.5)))
+            .line_to(icon)
+            .line_to(Point(icon.0 + Pixel(2.5), icon.1));
+
+        let mut stroke = Paint::default();
+        stroke
+            .set_anti_alias(true)
+            .set_color(color.with_a(0xa0))
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(Pixel(1.5).to_physical());
+        canvas.draw_path(&path, &stroke);
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for TimePicker
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.consume_wheel(true);
+        this.daemon(pointer_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        TimePicker {
+            font: label_font(props.style.font_size.map_or(Pixel(14.0), |size| size.0)),
+            value: props.value,
+            props,
+            focused: false,
+            segment: Segment::Hour,
+            typed: String::new(),
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if update_result.unchanged {
+            return true;
+        }
+
+        self.font = label_font(self.font_size());
+        if !update_result.value_unchanged {
+            self.value = self.props.value;
+            self.typed.clear();
+        }
+        if !self.segments().contains(&self.segment) {
+            self.segment = Segment::Hour;
+        }
+        false
+    }
+}
+
+impl TimePicker {
+    fn font_size(&self) -> Pixel {
+        self.props
+            .style
+            .font_size
+            .map_or(Pixel(14.0), |size| size.0)
+    }
+
+    fn segments(&self) -> &'static [Segment] {
+        if self.props.twelve_hour {
+            &[Segment::Hour, Segment::Minute, Segment::Period]
+        } else {
+            &[Segment::Hour, Segment::Minute]
+        }
+    }
+
+    fn text(&self, segment: Segment) -> String {
+        if segment == self.segment && !self.typed.is_empty() {
+            return self.typed.clone();
+        }
+
+        let Some(time) = self.value
+        else {
+            return String::from("--");
+        };
+
+        match segment {
+            Segment::Hour if self.props.twelve_hour => {
+                format!("{:02}", (time.hour() + 11) % 12 + 1)
+            }
+            Segment::Hour => format!("{:02}", time.hour()),
+            Segment::Minute => format!("{:02}", time.minute()),
+            Segment::Period => self.props.locale.period(time.hour() >= 12).to_string(),
+        }
+    }
+
+    /// Texts of the segments and the separators between, with where they
+    /// start.
+    fn layout(&self, region: Region) -> Vec<(Option<Segment>, String, Pixel)> {
+        let mut pieces = vec![
+            (Some(Segment::Hour), self.text(Segment::Hour)),
+            (None, String::from(":")),
+            (Some(Segment::Minute), self.text(Segment::Minute)),
+        ];
+        if self.props.twelve_hour {
+            pieces.push((None, String::from(" ")));
+            pieces.push((Some(Segment::Period), self.text(Segment::Period)));
+        }
+
+        let mut x = region.0 .0 + FIELD_PADDING;
+        pieces
+            .into_iter()
+            .map(|(segment, text)| {
+                let start = x;
+                x = x + label_width(&self.font, &text);
+                (segment, text, start)
+            })
+            .collect()
+    }
+
+    fn segment_at(&self, region: Region, point: Point) -> Option<Segment> {
+        self.layout(region)
+            .into_iter()
+            .find(|(segment, text, x)| {
+                let width = label_width(&self.font, text);
+                segment.is_some()
+                    && point.0 >= *x - SEGMENT_INSET
+                    && point.0 <= *x + width + SEGMENT_INSET
+            })
+            .and_then(|(segment, ..)| segment)
+    }
+
+    fn move_segment(&mut self, delta: isize) {
+        let segments = self.segments();
+        let index = segments
+            .iter()
+            .position(|segment| *segment == self.segment)
+            .unwrap_or(0);
+        let index = index.saturating_add_signed(delta).min(segments.len() - 1);
+        self.segment = segments[index];
+        self.typed.clear();
+    }
+
+    /// The nearest time within `min` and `max`.
+    fn clamp(&self, time: Time) -> Time {
+        let time = self.props.min.map_or(time, |min| time.max(min));
+        self.props.max.map_or(time, |max| time.min(max))
+    }
+
+    /// Change the time, and emit [`TimeChanged`] if it changed.
+    fn set(&mut self, this: &ElModel!(), time: Option<Time>) {
+        let time = time.map(|time| self.clamp(time));
+        if time != self.value {
+            self.value = time;
+            this.event_dispatcher().emit(TimeChanged { value: time });
+        }
+    }
+
+    /// Change `segment` by `delta` steps, or set the earliest time if there
+    /// is no time.
+    fn step(&mut self, this: &ElModel!(), segment: Segment, delta: i32) {
+        self.typed.clear();
+        let Some(time) = self.value
+        else {
+            let earliest = self.clamp(Time::MIDNIGHT);
+            self.set(this, Some(earliest));
+            return;
+        };
+
+        let time = match segment {
+            Segment::Hour => time.add_minutes(delta * 60),
+            Segment::Minute => {
+                let step = self.props.minute_step.clamp(1, 60) as i32;
+                let minute = time.minute() as i32;
+                // the first step down from between steps lands on the step below
+                let snapped = minute - minute % step;
+                let minute = if delta < 0 && snapped < minute {
+                    snapped + step * (delta + 1)
+                } else {
+                    snapped + step * delta
+                };
+                time.add_minutes(minute.rem_euclid(60) - time.minute() as i32)
+            }
+            Segment::Period => time.add_minutes(12 * 60),
+        };
+        self.set(this, Some(time));
+    }
+
+    fn type_digit(&mut self, this: &ElModel!(), digit: u32) {
+        let max = match self.segment {
+            Segment::Hour if self.props.twelve_hour => 12,
+            Segment::Hour => 23,
+            Segment::Minute => 59,
+            Segment::Period => return,
+        };
+
+        self.typed.push(char::from_digit(digit, 10).unwrap_or('0'));
+        let mut number: u32 = self.typed.parse().unwrap_or(0);
+        if number > max {
+            // start over with this digit
+            self.typed = digit.to_string();
+            number = digit;
+        }
+
+        let time = self.value.unwrap_or(Time::MIDNIGHT);
+        let typed = match self.segment {
+            Segment::Hour if self.props.twelve_hour => {
+                let after_noon = if time.hour() >= 12 { 12 } else { 0 };
+                (number > 0)
+                    .then(|| Time::new(number % 12 + after_noon, time.minute()))
+                    .flatten()
+            }
+            Segment::Hour => Time::new(number, time.minute()),
+            _ => Time::new(time.hour(), number),
+        };
+        if let Some(typed) = typed {
+            self.set(this, Some(typed));
+        }
+
+        // no more digits fit
+        if self.typed.len() >= 2 || number * 10 > max {
+            self.typed.clear();
+            if self.segment == Segment::Hour {
+                self.segment = Segment::Minute;
+            } else if self.props.twelve_hour {
+                self.segment = Segment::Period;
+            }
+        }
+    }
+
+    /// Switch to the half of the day whose name starts with `c`.
+    fn type_period(&mut self, this: &ElModel!(), c: char) {
+        let Some(time) = self.value
+        else {
+            return;
+        };
+
+        let starts_with = |after_noon: bool| {
+            let name = self.props.locale.period(after_noon).to_lowercase();
+            name.starts_with(&c.to_lowercase().to_string())
+        };
+        let after_noon = time.hour() >= 12;
+        if starts_with(!after_noon) && !starts_with(after_noon) {
+            self.step(this, Segment::Period, 1);
+        }
+    }
+
+    fn input(&mut self, this: &ElModel!(), input: Input, modifiers: ModifiersState) {
+        match input {
+            Input::Key(VirtualKeyCode::Up) => self.step(this, self.segment, 1),
+            Input::Key(VirtualKeyCode::Down) => self.step(this, self.segment, -1),
+            Input::Key(VirtualKeyCode::Left) => self.move_segment(-1),
+            Input::Key(VirtualKeyCode::Right) => self.move_segment(1),
+            Input::Key(VirtualKeyCode::Tab) => {
+                self.move_segment(if modifiers.shift() { -1 } else { 1 })
+            }
+            Input::Key(VirtualKeyCode::Back | VirtualKeyCode::Delete) => {
+                self.typed.clear();
+                self.set(this, None);
+            }
+            Input::Key(VirtualKeyCode::Escape) => self.typed.clear(),
+            Input::Char(c) => match c.to_digit(10) {
+                Some(digit) => self.type_digit(this, digit),
+                None if self.props.twelve_hour => self.type_period(this, c),
+                None => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+async fn update(this: &ElModel!(TimePicker), f: impl FnOnce(&mut TimePicker)) -> bool {
+    let Some(mut time_picker) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *time_picker);
+    true
+}
+
+async fn pointer_runtime(this: ElModel!(TimePicker)) {
+    let ed = this.event_dispatcher().clone();
+    let mut wheel_pixels = Pixel(0.0);
+
+    loop {
+        let alive = tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => {
+                if pd.button != MouseButton::Left {
+                    continue;
+                }
+
+                this.focus();
+                update(&this, |time_picker| {
+                    let segment = time_picker.segment_at(this.draw_region(), pd.position);
+                    if let Some(segment) = segment {
+                        time_picker.segment = segment;
+                        time_picker.typed.clear();
+                    }
+                })
+                .await
+            }
+            wheel = ed.recv_trusted::<Wheel>() => {
+                let delta = match wheel.delta {
+                    WheelDelta::Line(_, y) if y != 0.0 => y.signum() as i32,
+                    WheelDelta::Pixel(_, y) => {
+                        wheel_pixels = wheel_pixels + y;
+                        if wheel_pixels.0.abs() < WHEEL_STEP.0 {
+                            continue;
+                        }
+                        let delta = wheel_pixels.0.signum() as i32;
+                        wheel_pixels = Pixel(0.0);
+                        delta
+                    }
+                    _ => continue,
+                };
+
+                update(&this, |time_picker| {
+                    let segment = time_picker
+                        .segment_at(this.draw_region(), wheel.position)
+                        .unwrap_or(time_picker.segment);
+                    time_picker.step(&this, segment, delta);
+                })
+                .await
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
+
+async fn keyboard_runtime(this: ElModel!(TimePicker)) {
+    let ed = this.event_dispatcher().clone();
+    let mut modifiers = ModifiersState::empty();
+
+    loop {
+        ed.recv_trusted::<Focused>().await;
+        if !update(&this, |time_picker| time_picker.focused = true).await {
+            return;
+        }
+
+        loop {
+            let input = tokio::select! {
+                event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                    StaticWindowEvent::ModifiersChanged(state) => {
+                        modifiers = state;
+                        continue;
+                    }
+                    StaticWindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => Input::Key(key),
+                    StaticWindowEvent::ReceivedCharacter(c) if !c.is_control() => Input::Char(c),
+                    _ => continue,
+                },
+                _ = ed.recv_trusted::<Blured>() => break,
+            };
+
+            if !update(&this, |time_picker| {
+                time_picker.input(&this, input, modifiers)
+            })
+            .await
+            {
+                return;
+            }
+        }
+
+        let blured = update(&this, |time_picker| {
+            time_picker.focused = false;
+            time_picker.typed.clear();
+        });
+        if !blured.await {
+            return;
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+const MINUTES_PER_DAY: i32 = 24 * 60;
+
+/// A time of a day to the minute, from `00:00` to `23:59`. Times are
+/// ordered from midnight, and shown as `09:30`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+}
+
+impl Time {
+    pub const MIDNIGHT: Time = Time { hour: 0, minute: 0 };
+
+    /// Returns `None` if the hour is not in `0..24` or the minute is not
+    /// in `0..60`.
+    pub fn new(hour: u32, minute: u32) -> Option<Time> {
+        (hour < 24 && minute < 60).then_some(Time {
+            hour: hour as u8,
+            minute: minute as u8,
+        })
+    }
+
+    pub fn hour(self) -> u32 {
+        self.hour as u32
+    }
+
+    pub fn minute(self) -> u32 {
+        self.minute as u32
+    }
+
+    /// The time `minutes` minutes later, wrapped around midnight.
+    pub fn add_minutes(self, minutes: i32) -> Time {
+        let minutes = (self.minutes() + minutes).rem_euclid(MINUTES_PER_DAY);
+        Time {
+            hour: (minutes / 60) as u8,
+            minute: (minutes % 60) as u8,
+        }
+    }
+
+    /// Minutes since midnight.
+    fn minutes(self) -> i32 {
+        self.hour as i32 * 60 + self.minute as i32
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
@@ -0,0 +1,317 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Click, PointerEntered, PointerOut},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{
+        textlayout::{FontCollection, Paragraph},
+        Color, Font, Paint, PaintStyle, Path, RRect,
+    },
+    winit::event::MouseButton,
+    ElModel, Event,
+};
+
+use crate::{
+    label::{build_paragraph, draw_label, font_collection, label_font, label_width, to_rect},
+    popup::{draw_surface, region_contains, PortalOwner},
+};
+
+use super::Severity;
+
+pub(super) const WIDTH: Pixel = Pixel(320.0);
+const MIN_HEIGHT: Pixel = Pixel(44.0);
+const PADDING: Pixel = Pixel(12.0);
+const RADIUS: Pixel = Pixel(6.0);
+const STRIPE_INSET: Pixel = Pixel(6.0);
+const STRIPE_WIDTH: Pixel = Pixel(4.0);
+const BUTTON_SIZE: Pixel = Pixel(24.0);
+const BUTTON_PADDING: Pixel = Pixel(8.0);
+const BUTTON_GAP: Pixel = Pixel(4.0);
+
+/// Colors and font size shared by every toast.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) struct ToastLook {
+    pub font_size: Pixel,
+    pub color: Color,
+    pub background: Color,
+    pub accent: Color,
+}
+
+pub(super) const LOOK: ToastLook = ToastLook {
+    font_size: Pixel(14.0),
+    color: Color::from_rgb(0x20, 0x20, 0x20),
+    background: Color::WHITE,
+    accent: Color::from_rgb(0x2f, 0x6f, 0xed),
+};
+
+/// The pointer entered or left the toast of `id`.
+#[derive(Event, Clone, Copy)]
+pub(super) struct ToastHover {
+    pub id: u64,
+    pub hovered: bool,
+}
+
+/// The action button, or the close button if `action` is false,
+/// of the toast of `id` was clicked.
+#[derive(Event, Clone, Copy)]
+pub(super) struct ToastPress {
+    pub id: u64,
+    pub action: bool,
+}
+
+/// Places of the buttons and the message inside a toast.
+struct Parts {
+    close: Region,
+    action: Option<Region>,
+    message_left: Pixel,
+    message_width: Pixel,
+}
+
+fn parts(region: Region, font: &Font, action: Option<&str>) -> Parts {
+    let (start, end) = region;
+    let top = start.1 + (MIN_HEIGHT - BUTTON_SIZE) / 2.0;
+    let button = |left: Pixel, right: Pixel| (Point(left, top), Point(right, top + BUTTON_SIZE));
+
+    let close = button(
+        end.0 - BUTTON_GAP * 2.0 - BUTTON_SIZE,
+        end.0 - BUTTON_GAP * 2.0,
+    );
+    let mut right = close.0 .0 - BUTTON_GAP;
+    let action = action.map(|label| {
+        let region = button(
+            right - label_width(font, label) - BUTTON_PADDING * 2.0,
+            right,
+        );
+        right = region.0 .0 - BUTTON_GAP;
+        region
+    });
+
+    let message_left = start.0 + STRIPE_INSET + STRIPE_WIDTH + PADDING;
+    Parts {
+        close,
+        action,
+        message_left,
+        message_width: (right - message_left).max(Pixel(0.0)),
+    }
+}
+
+/// Height of a toast `width` wide showing `message`.
+pub(super) fn toast_height(
+    font_collection: &FontCollection,
+    message: &str,
+    action: Option<&str>,
+    width: Pixel,
+    look: &ToastLook,
+) -> Pixel {
+    let region = (Point(Pixel(0.0), Pixel(0.0)), Point(width, MIN_HEIGHT));
+    let parts = parts(region, &label_font(look.font_size), action);
+    let paragraph = build_paragraph(
+        font_collection,
+        message,
+        look.font_size,
+        look.color,
+        parts.message_width,
+    );
+    (Pixel::from_physical(paragraph.height().ceil()) + PADDING * 2.0).max(MIN_HEIGHT)
+}
+
+/// One toast of the stack, mounted in the portal of the toast queue.
+pub(super) struct ToastItem {
+    props: ItemProps,
+    font_collection: FontCollection,
+    font: Font,
+    /// Message wrapped in the width it holds
+    paragraph: Option<(Pixel, Paragraph)>,
+}
+
+#[irisia::props(updater = "ToastItemProps", watch)]
+pub(super) struct ItemProps {
+    #[props(updated, must_init, watch)]
+    message: String,
+
+    #[props(must_init)]
+    severity: Severity,
+
+    #[props(default, watch)]
+    action: Option<String>,
+
+    /// From 0 while entering or leaving to 1 once fully shown.
+    #[props(must_init)]
+    opacity: f32,
+
+    #[props(must_init)]
+    id: u64,
+
+    /// Receives [`ToastHover`] and [`ToastPress`]
+    #[props(must_init)]
+    owner: PortalOwner,
+
+    #[props(must_init)]
+    look: ToastLook,
+}
+
+impl Element for ToastItem {
+    type BlankProps = ToastItemProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+
+        let parts = self.parts(region);
+        let ItemProps {
+            message,
+            severity,
+            action,
+            opacity,
+            look,
+            ..
+        } = &self.props;
+
+        let canvas = content.canvas();
+        canvas.save_layer_alpha_f(None, *opacity);
+        draw_surface(canvas, region, look.background, RADIUS, true);
+
+        let stripe = (
+            Point(region.0 .0 + STRIPE_INSET, region.0 .1 + STRIPE_INSET),
+            Point(
+                region.0 .0 + STRIPE_INSET + STRIPE_WIDTH,
+                region.1 .1 - STRIPE_INSET,
+            ),
+        );
+        let radius = (STRIPE_WIDTH / 2.0).to_physical();
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_color(severity.color());
+        canvas.draw_rrect(RRect::new_rect_xy(to_rect(stripe), radius, radius), &paint);
+
+        // the toast slides while entering or leaving, the text only
+        // needs wrapping again if the room of it changes
+        if !matches!(&self.paragraph, Some((width, _)) if *width == parts.message_width) {
+            let paragraph = build_paragraph(
+                &self.font_collection,
+                message,
+                look.font_size,
+                look.color,
+                parts.message_width,
+            );
+            self.paragraph = Some((parts.message_width, paragraph));
+        }
+        let (_, paragraph) = self.paragraph.as_mut().unwrap();
+        let height = Pixel::from_physical(paragraph.height());
+        let top = region.0 .1 + ((MIN_HEIGHT - height) / 2.0).max(PADDING);
+        paragraph.paint(canvas, Point(parts.message_left, top));
+
+        if let (Some(label), Some(button)) = (action, parts.action) {
+            draw_label(
+                canvas,
+                &self.font,
+                label,
+                look.accent,
+                button.0 .0 + BUTTON_PADDING,
+                (button.0 .1, button.1 .1),
+            );
+        }
+
+        let (start, end) = parts.close;
+        let inset = Pixel(8.0);
+        let mut path = Path::new();
+        path.move_to(Point(start.0 + inset, start.1 + inset))
+            .line_to(Point(end.0 - inset, end.1 - inset))
+            .move_to(Point(end.0 - inset, start.1 + inset))
+            .line_to(Point(start.0 + inset, end.1 - inset));
+
+        let mut stroke = Paint::default();
+        stroke
+            .set_anti_alias(true)
+            .set_color(look.color.with_a(0x80))
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(Pixel(1.5).to_physical());
+        canvas.draw_path(&path, &stroke);
+
+        canvas.restore();
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for ToastItem
+where
+    ItemProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(item_runtime(this.clone()));
+
+        let props = ItemProps::props_create_with(props);
+        ToastItem {
+            font_collection: font_collection(),
+            font: label_font(props.look.font_size),
+            paragraph: None,
+            props,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let look = self.props.look;
+        let update_result = self.props.props_update_with(props);
+        let text_unchanged = update_result.message_unchanged && update_result.action_unchanged;
+        if self.props.look != look {
+            self.font = label_font(self.props.look.font_size);
+        }
+        if !text_unchanged || self.props.look != look {
+            self.paragraph = None;
+        }
+        update_result.unchanged && text_unchanged
+    }
+}
+
+impl ToastItem {
+    fn parts(&self, region: Region) -> Parts {
+        parts(region, &self.font, self.props.action.as_deref())
+    }
+}
+
+async fn item_runtime(this: ElModel!(ToastItem)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let hovered = tokio::select! {
+            click = ed.recv_trusted::<Click>() => {
+                if click.button != MouseButton::Left {
+                    continue;
+                }
+
+                let Some(item) = this.el_read().await
+                else {
+                    return;
+                };
+
+                let parts = item.parts(this.draw_region());
+                let action = if region_contains(parts.close, click.position) {
+                    false
+                } else if parts
+                    .action
+                    .is_some_and(|button| region_contains(button, click.position))
+                {
+                    true
+                } else {
+                    continue;
+                };
+
+                item.props.owner.0.emit(ToastPress {
+                    id: item.props.id,
+                    action,
+                });
+                continue;
+            }
+            _ = ed.recv_trusted::<PointerEntered>() => true,
+            _ = ed.recv_trusted::<PointerOut>() => false,
+        };
+
+        let Some(item) = this.el_read().await
+        else {
+            return;
+        };
+
+        item.props.owner.0.emit(ToastHover {
+            id: item.props.id,
+            hovered,
+        });
+    }
+}
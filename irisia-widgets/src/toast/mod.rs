@@ -0,0 +1,462 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+    time::{Duration, Instant},
+};
+
+use irisia::{
+    build,
+    element::{DetachedPortal, GlobalContent},
+    event::EventDispatcher,
+    primitive::{Pixel, Point},
+    skia_safe::{textlayout::FontCollection, Color},
+    Event,
+};
+use tokio::sync::Notify;
+
+use crate::{
+    label::font_collection,
+    popup::{window_region, z_index, PortalOwner},
+};
+
+use self::item::{toast_height, ToastHover, ToastItem, ToastPress, LOOK, WIDTH};
+
+mod item;
+
+const DEFAULT_DURATION: Duration = Duration::from_secs(4);
+const ANIMATION: Duration = Duration::from_millis(200);
+const FRAME: Duration = Duration::from_millis(16);
+const MARGIN: Pixel = Pixel(16.0);
+const GAP: Pixel = Pixel(8.0);
+const SLIDE: Pixel = Pixel(40.0);
+
+/// Decides the color of the stripe on the left side of a toast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Severity {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn color(self) -> Color {
+        match self {
+            Severity::Info => Color::from_rgb(0x2f, 0x6f, 0xed),
+            Severity::Success => Color::from_rgb(0x2e, 0x9d, 0x57),
+            Severity::Warning => Color::from_rgb(0xe8, 0x9a, 0x0c),
+            Severity::Error => Color::from_rgb(0xd9, 0x3b, 0x3b),
+        }
+    }
+}
+
+/// A short notification shown by [`ToastExt::toast`].
+#[derive(Debug, Clone)]
+pub struct Toast {
+    message: String,
+    severity: Severity,
+    duration: Option<Duration>,
+    action: Option<String>,
+}
+
+impl Toast {
+    pub fn new(message: impl Into<String>) -> Self {
+        Toast {
+            message: message.into(),
+            severity: Severity::Info,
+            duration: Some(DEFAULT_DURATION),
+            action: None,
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(message)
+    }
+
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(message).severity(Severity::Success)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(message).severity(Severity::Warning)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(message).severity(Severity::Error)
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// How long the toast stays, not counting the time the pointer is on it.
+    /// Defaults to 4 seconds.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Keep the toast until it is closed by the user or [`ToastHandle::dismiss`].
+    pub fn sticky(mut self) -> Self {
+        self.duration = None;
+        self
+    }
+
+    /// Show a button beside the message, see [`ToastHandle::closed`].
+    pub fn action(mut self, label: impl Into<String>) -> Self {
+        self.action = Some(label.into());
+        self
+    }
+}
+
+impl From<&str> for Toast {
+    fn from(message: &str) -> Self {
+        Toast::new(message)
+    }
+}
+
+impl From<String> for Toast {
+    fn from(message: String) -> Self {
+        Toast::new(message)
+    }
+}
+
+/// Shows toasts stacked in the bottom right corner of a window.
+///
+/// ```ignore
+/// use irisia_widgets::toast::{Toast, ToastExt};
+///
+/// this.global().toast("Saved");
+///
+/// let handle = this.global().toast(Toast::error("Upload failed").action("Retry"));
+/// if handle.closed().await {
+///     // retry
+/// }
+/// ```
+pub trait ToastExt {
+    fn toast(&self, toast: impl Into<Toast>) -> ToastHandle;
+}
+
+impl ToastExt for Rc<GlobalContent> {
+    fn toast(&self, toast: impl Into<Toast>) -> ToastHandle {
+        let queue = self.data(|| ToastQueue::new(self));
+        ToastQueue::push(&queue, toast.into())
+    }
+}
+
+/// Handle of a shown toast.
+#[derive(Clone)]
+pub struct ToastHandle {
+    id: u64,
+    queue: Rc<ToastQueue>,
+    outcome: Rc<Outcome>,
+}
+
+impl ToastHandle {
+    /// Hide the toast before its duration elapses.
+    pub fn dismiss(&self) {
+        self.queue.close(self.id, false);
+        self.queue.owner.emit(QueueChanged);
+    }
+
+    /// Wait until the toast starts hiding. Returns `true` if its action
+    /// button was clicked, or `false` if it was closed in other ways.
+    pub async fn closed(&self) -> bool {
+        loop {
+            let notified = self.outcome.notify.notified();
+            if let Some(action) = self.outcome.action.get() {
+                return action;
+            }
+            notified.await;
+        }
+    }
+}
+
+#[derive(Default)]
+struct Outcome {
+    action: Cell<Option<bool>>,
+    notify: Notify,
+}
+
+impl Outcome {
+    fn finish(&self, action: bool) {
+        self.action.set(Some(action));
+        self.notify.notify_waiters();
+    }
+}
+
+/// A toast was pushed or dismissed by a handle.
+#[derive(Event, Clone, Copy)]
+struct QueueChanged;
+
+/// Toasts of a window, stored with [`GlobalContent::data`].
+struct ToastQueue {
+    global: Weak<GlobalContent>,
+    /// Receives [`QueueChanged`], [`ToastHover`] and [`ToastPress`]
+    owner: EventDispatcher,
+    state: RefCell<QueueState>,
+}
+
+struct QueueState {
+    entries: Vec<Entry>,
+    next_id: u64,
+    hovered: Option<u64>,
+    running: bool,
+    portal: Option<DetachedPortal>,
+    font_collection: FontCollection,
+}
+
+struct Entry {
+    id: u64,
+    toast: Toast,
+    remaining: Option<Duration>,
+    /// Progress of the enter animation, played backwards while leaving.
+    shown: f32,
+    leaving: bool,
+    /// Width and height of the toast last time it was laid out
+    size: Option<(Pixel, Pixel)>,
+    outcome: Rc<Outcome>,
+}
+
+impl ToastQueue {
+    fn new(global: &Rc<GlobalContent>) -> Self {
+        ToastQueue {
+            global: Rc::downgrade(global),
+            owner: EventDispatcher::new(),
+            state: RefCell::new(QueueState {
+                entries: Vec::new(),
+                next_id: 0,
+                hovered: None,
+                running: false,
+                portal: None,
+                font_collection: font_collection(),
+            }),
+        }
+    }
+
+    fn push(this: &Rc<Self>, toast: Toast) -> ToastHandle {
+        let outcome = Rc::new(Outcome::default());
+        let mut state = this.state.borrow_mut();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.entries.push(Entry {
+            id,
+            remaining: toast.duration,
+            toast,
+            shown: 0.0,
+            leaving: false,
+            size: None,
+            outcome: outcome.clone(),
+        });
+
+        if state.running {
+            this.owner.emit(QueueChanged);
+        } else {
+            state.running = true;
+            tokio::task::spawn_local(queue_runtime(this.clone()));
+        }
+
+        ToastHandle {
+            id,
+            queue: this.clone(),
+            outcome,
+        }
+    }
+
+    fn close(&self, id: u64, action: bool) {
+        let mut state = self.state.borrow_mut();
+        let Some(entry) = state
+            .entries
+            .iter_mut()
+            .find(|entry| entry.id == id && !entry.leaving)
+        else {
+            return;
+        };
+
+        entry.leaving = true;
+        entry.outcome.finish(action);
+    }
+
+    fn hover(&self, hover: ToastHover) {
+        let mut state = self.state.borrow_mut();
+        if hover.hovered {
+            state.hovered = Some(hover.id);
+        } else if state.hovered == Some(hover.id) {
+            state.hovered = None;
+        }
+    }
+
+    /// Advance the timers and animations by `elapsed` then mount the toasts.
+    /// Returns `None` once there is no toast left, or the time to the next frame
+    /// otherwise, which is `Some(None)` if nothing changes until an event comes.
+    fn frame(&self, elapsed: Duration) -> Option<Option<Duration>> {
+        let mut state = self.state.borrow_mut();
+        state.advance(elapsed);
+
+        let global = self.global.upgrade();
+        match global {
+            Some(global) if !state.entries.is_empty() => {
+                self.mount(&mut *state, &global);
+                Some(state.next_frame())
+            }
+            _ => {
+                state.portal = None;
+                state.running = false;
+                None
+            }
+        }
+    }
+
+    fn mount(&self, state: &mut QueueState, global: &Rc<GlobalContent>) {
+        let window = window_region(global.window());
+        let width = WIDTH
+            .min(window.1 .0 - window.0 .0 - MARGIN * 2.0)
+            .max(Pixel(0.0));
+
+        let QueueState {
+            entries,
+            portal,
+            font_collection,
+            ..
+        } = state;
+
+        // the newest toast is at the bottom, others are pushed upwards
+        let mut bottom = window.1 .1 - MARGIN;
+        let mut regions = Vec::with_capacity(entries.len());
+        for entry in entries.iter_mut().rev() {
+            let height = match entry.size {
+                Some((w, height)) if w == width => height,
+                _ => {
+                    let height = toast_height(
+                        font_collection,
+                        &entry.toast.message,
+                        entry.toast.action.as_deref(),
+                        width,
+                        &LOOK,
+                    );
+                    entry.size = Some((width, height));
+                    height
+                }
+            };
+
+            let progress = ease(entry.shown);
+            let left = window.1 .0 - MARGIN - width + SLIDE * (1.0 - progress);
+            regions.push((Point(left, bottom - height), Point(left + width, bottom)));
+            bottom = bottom - (height + GAP) * progress;
+        }
+        regions.reverse();
+
+        let mut regions = regions.into_iter();
+        let owner = PortalOwner(self.owner.clone());
+        portal
+            .get_or_insert_with(|| DetachedPortal::new(global))
+            .set(
+                z_index::TOAST,
+                build! {
+                    for entry in entries.iter() {
+                        @key entry.id;
+                        ToastItem {
+                            message: &entry.toast.message,
+                            severity: entry.toast.severity,
+                            action: entry.toast.action.clone(),
+                            opacity: ease(entry.shown),
+                            id: entry.id,
+                            owner: owner.clone(),
+                            look: LOOK,
+                        }
+                    }
+                },
+            )
+            .layout(|()| regions.next())
+            .unwrap();
+    }
+}
+
+impl QueueState {
+    fn advance(&mut self, elapsed: Duration) {
+        let step = elapsed.as_secs_f32() / ANIMATION.as_secs_f32();
+        let paused = self.hovered.is_some();
+
+        for entry in &mut self.entries {
+            if entry.leaving {
+                entry.shown = (entry.shown - step).max(0.0);
+                continue;
+            }
+
+            entry.shown = (entry.shown + step).min(1.0);
+            if paused {
+                continue;
+            }
+
+            if let Some(remaining) = &mut entry.remaining {
+                *remaining = remaining.saturating_sub(elapsed);
+                if remaining.is_zero() {
+                    entry.leaving = true;
+                    entry.outcome.finish(false);
+                }
+            }
+        }
+
+        self.entries
+            .retain(|entry| !entry.leaving || entry.shown > 0.0);
+
+        // removed toasts may never report the pointer is out
+        if let Some(id) = self.hovered {
+            if !self.entries.iter().any(|entry| entry.id == id) {
+                self.hovered = None;
+            }
+        }
+    }
+
+    fn next_frame(&self) -> Option<Duration> {
+        if self
+            .entries
+            .iter()
+            .any(|entry| entry.leaving || entry.shown < 1.0)
+        {
+            return Some(FRAME);
+        }
+
+        if self.hovered.is_some() {
+            return None;
+        }
+
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.remaining)
+            .min()
+    }
+}
+
+fn ease(progress: f32) -> f32 {
+    1.0 - (1.0 - progress).powi(3)
+}
+
+async fn queue_runtime(queue: Rc<ToastQueue>) {
+    let mut last = Instant::now();
+
+    loop {
+        let now = Instant::now();
+        let Some(next_frame) = queue.frame(now - last)
+        else {
+            return;
+        };
+        last = now;
+
+        tokio::select! {
+            _ = sleep(next_frame) => {}
+            _ = queue.owner.recv::<QueueChanged>() => {}
+            (hover, _) = queue.owner.recv::<ToastHover>() => queue.hover(hover),
+            (press, _) = queue.owner.recv::<ToastPress>() => queue.close(press.id, press.action),
+        }
+    }
+}
+
+async fn sleep(duration: Option<Duration>) {
+    match duration {
+        Some(duration) => tokio::time::sleep(duration).await,
+        None => std::future::pending().await,
+    }
+}
@@ -0,0 +1,282 @@
+use std::time::Duration;
+
+use irisia::{
+    build,
+    element::{props::PropsUpdateWith, Element, ElementUpdate, LayoutElements, RenderElement},
+    primitive::{Pixel, Point, Region},
+    skia_safe::{
+        textlayout::{FontCollection, Paragraph},
+        Color,
+    },
+    style::StyleColor,
+    ElModel, StyleReader,
+};
+
+use crate::{
+    label::{build_paragraph, font_collection},
+    popup::{
+        draw_surface, place,
+        styles::{StylePopupBackground, StylePopupGap, StylePopupRadius},
+        window_region, z_index, Align, Side,
+    },
+    textbox::styles::StyleFontSize,
+};
+
+const PADDING: Pixel = Pixel(6.0);
+const MAX_WIDTH: Pixel = Pixel(320.0);
+
+/// Shows a short text beside its children once the pointer stays on
+/// them for a while, and hides it when the pointer is out or pressed.
+pub struct Tooltip {
+    props: OwnedProps,
+    font_collection: FontCollection,
+    shown: bool,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct TooltipStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    background: Option<StylePopupBackground>,
+    radius: StylePopupRadius,
+    gap: StylePopupGap,
+}
+
+#[irisia::props(updater = "TooltipProps", watch)]
+pub struct OwnedProps {
+    #[props(updated, must_init)]
+    text: String,
+
+    /// Preferred side, flipped if there is no enough room.
+    #[props(default = "Side::Top")]
+    side: Side,
+
+    /// How long the pointer needs to stay before showing.
+    #[props(default = "Duration::from_millis(500)")]
+    delay: Duration,
+
+    #[props(read_style(stdin))]
+    style: TooltipStyles,
+}
+
+impl Element for Tooltip {
+    type BlankProps = TooltipProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        this.set_interact_region(Some(this.draw_region()));
+        content.render_children()
+    }
+
+    fn set_children(&self, this: &ElModel!()) {
+        layout_anchor(this, this.set_children(this.slot()));
+        if self.shown {
+            self.show(this);
+        }
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), _: Region) {
+        if let Some(lc) = this.layout_children() {
+            layout_anchor(this, lc);
+        }
+        if self.shown {
+            self.show(this);
+        }
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for Tooltip
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(hover_runtime(this.clone()));
+
+        Tooltip {
+            props: OwnedProps::props_create_with(props),
+            font_collection: font_collection(),
+            shown: false,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        self.props.props_update_with(props).unchanged
+    }
+}
+
+impl Tooltip {
+    fn font_size(&self) -> Pixel {
+        self.props
+            .style
+            .font_size
+            .map_or(Pixel(13.0), |size| size.0)
+    }
+
+    fn color(&self) -> Color {
+        self.props.style.color.map_or(Color::WHITE, |color| color.0)
+    }
+
+    fn background(&self) -> Color {
+        self.props
+            .style
+            .background
+            .map_or(Color::from_rgb(0x32, 0x32, 0x32), |bg| bg.0)
+    }
+
+    fn show(&self, this: &ElModel!()) {
+        let paragraph = build_paragraph(
+            &self.font_collection,
+            &self.props.text,
+            self.font_size(),
+            self.color(),
+            MAX_WIDTH,
+        );
+
+        // one more physical pixel so that the text won't wrap in the bubble
+        let size = (
+            Pixel::from_physical(paragraph.longest_line().ceil() + 1.0) + PADDING * 2.0,
+            Pixel::from_physical(paragraph.height().ceil()) + PADDING * 2.0,
+        );
+
+        let region = place(
+            this.draw_region(),
+            size,
+            self.props.side,
+            Align::Center,
+            self.props.style.gap.0,
+            window_region(this.window()),
+        );
+
+        this.set_portal(
+            z_index::TOOLTIP,
+            build! {
+                TooltipBubble {
+                    text: &self.props.text,
+                    font_size: self.font_size(),
+                    color: self.color(),
+                    background: self.background(),
+                    radius: self.props.style.radius.0,
+                }
+            },
+        )
+        .layout_once(region)
+        .unwrap();
+    }
+}
+
+fn layout_anchor(this: &ElModel!(Tooltip), lc: LayoutElements) {
+    let region = this.draw_region();
+    lc.layout(|()| Some(region)).unwrap();
+}
+
+async fn hover_runtime(this: ElModel!(Tooltip)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let Some(delay) = this.el_read().await.map(|tooltip| tooltip.props.delay)
+        else {
+            return;
+        };
+
+        ed.hover_for(delay).await;
+        if !set_shown(&this, true).await {
+            return;
+        }
+
+        ed.hover_canceled().await;
+        if !set_shown(&this, false).await {
+            return;
+        }
+    }
+}
+
+async fn set_shown(this: &ElModel!(Tooltip), shown: bool) -> bool {
+    let Some(mut tooltip) = this.el_write().await
+    else {
+        return false;
+    };
+
+    tooltip.shown = shown;
+    if shown {
+        tooltip.show(this);
+    } else {
+        this.remove_portal();
+    }
+    true
+}
+
+struct TooltipBubble {
+    props: BubbleProps,
+    font_collection: FontCollection,
+    paragraph: Option<Paragraph>,
+}
+
+#[irisia::props(updater = "TooltipBubbleProps", watch)]
+struct BubbleProps {
+    #[props(updated, must_init)]
+    text: String,
+
+    #[props(must_init)]
+    font_size: Pixel,
+
+    #[props(must_init)]
+    color: Color,
+
+    #[props(must_init)]
+    background: Color,
+
+    #[props(must_init)]
+    radius: Pixel,
+}
+
+impl Element for TooltipBubble {
+    type BlankProps = TooltipBubbleProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        let canvas = content.canvas();
+        draw_surface(
+            canvas,
+            region,
+            self.props.background,
+            self.props.radius,
+            true,
+        );
+
+        let paragraph = self.paragraph.get_or_insert_with(|| {
+            build_paragraph(
+                &self.font_collection,
+                &self.props.text,
+                self.props.font_size,
+                self.props.color,
+                (region.1 .0 - region.0 .0 - PADDING * 2.0).max(Pixel(0.0)),
+            )
+        });
+        paragraph.paint(canvas, Point(region.0 .0 + PADDING, region.0 .1 + PADDING));
+        Ok(())
+    }
+
+    fn draw_region_changed(&mut self, _: &ElModel!(), _: Region) {
+        self.paragraph = None;
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for TooltipBubble
+where
+    BubbleProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        TooltipBubble {
+            props: BubbleProps::props_create_with(props),
+            font_collection: font_collection(),
+            paragraph: None,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let unchanged = self.props.props_update_with(props).unchanged;
+        if !unchanged {
+            self.paragraph = None;
+        }
+        unchanged
+    }
+}
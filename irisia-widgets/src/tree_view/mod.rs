@@ -0,0 +1,605 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use irisia::{
+    build,
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::{Blured, Focused, PointerDown, PointerMove, PointerOut, Wheel},
+    primitive::{Pixel, Point, Region},
+    skia_safe::Color,
+    style::StyleColor,
+    winit::event::{ElementState, KeyboardInput, MouseButton, VirtualKeyCode},
+    ElModel, Event, StaticWindowEvent, StyleReader,
+};
+
+use crate::{
+    label::to_rect,
+    scroll::{
+        clamp_offset, draw_vertical_scrollbar, max_offset, reveal_offset, wheel_delta, Heights,
+    },
+    textbox::styles::StyleFontSize,
+};
+
+use self::{
+    node::TreeChildren,
+    row::{RowKind, TreeLook, TreeRow},
+    styles::{StyleTreeAccent, StyleTreeGuide, StyleTreeSelection},
+};
+
+pub use self::node::{TreeLoader, TreeNode};
+
+mod node;
+mod row;
+pub mod styles;
+
+const FRAME: Duration = Duration::from_millis(16);
+/// Turns of the loading spinner in each frame
+const SPIN_SPEED: f32 = 1.0 / 60.0;
+
+/// A tree of nodes that can be expanded and collapsed, with one row for
+/// every node whose ancestors are all expanded. Only rows in view are
+/// built, so trees of any size cost about the same.
+///
+/// Clicking the arrow of a node, or pressing Enter while it is selected,
+/// expands or collapses it and emits [`TreeToggled`]. Clicking a row or
+/// moving with the arrow keys selects a node and emits [`TreeSelected`].
+/// Left collapses the selected node or goes to its parent, Right expands it
+/// or goes to its first child.
+///
+/// Children of [lazy](TreeNode::lazy) nodes are loaded by `loader` once they
+/// are expanded, while a spinner is shown in place of the arrow.
+pub struct TreeView {
+    props: OwnedProps,
+    /// Visible nodes from top to bottom
+    rows: Vec<FlatRow>,
+    expanded: HashSet<String>,
+    selected: Option<String>,
+    /// Children of lazy nodes given by the loader
+    loaded: HashMap<String, Vec<TreeNode>>,
+    loading: HashSet<String>,
+    /// Changes with the roots, so that children loaded for old roots are dropped
+    generation: u64,
+    /// Turns of the loading spinner
+    phase: f32,
+    spinning: bool,
+    hovered: Option<usize>,
+    focused: bool,
+    heights: Heights,
+    scroll: Pixel,
+}
+
+/// Emitted whenever the user selects a node.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct TreeSelected {
+    pub key: String,
+}
+
+/// Emitted whenever the user expands or collapses a node.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct TreeToggled {
+    pub key: String,
+    pub expanded: bool,
+}
+
+#[derive(StyleReader, PartialEq)]
+struct TreeStyles {
+    font_size: Option<StyleFontSize>,
+    color: Option<StyleColor>,
+    selection: StyleTreeSelection,
+    guide: StyleTreeGuide,
+    accent: StyleTreeAccent,
+}
+
+#[irisia::props(updater = "TreeViewProps", watch)]
+pub struct OwnedProps {
+    #[props(must_init, watch)]
+    roots: Vec<TreeNode>,
+
+    /// Loads children of lazy nodes. Lazy nodes have no children without it.
+    #[props(default)]
+    loader: Option<TreeLoader>,
+
+    /// Keys of the nodes expanded at first.
+    #[props(default, watch)]
+    expanded: Vec<String>,
+
+    /// Key of the selected node.
+    #[props(default, watch)]
+    selected: Option<String>,
+
+    #[props(default = "Pixel(26.0)")]
+    row_height: Pixel,
+
+    /// Width of each level of indentation.
+    #[props(default = "Pixel(16.0)")]
+    indent: Pixel,
+
+    #[props(read_style(stdin))]
+    style: TreeStyles,
+}
+
+/// A visible node.
+struct FlatRow {
+    key: String,
+    label: String,
+    depth: usize,
+    kind: RowKind,
+    /// Index of the row of the parent
+    parent: Option<usize>,
+}
+
+impl Element for TreeView {
+    type BlankProps = TreeViewProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+        this.consume_wheel(self.max_scroll(region) > Pixel(0.0));
+
+        let canvas = content.canvas();
+        canvas.save();
+        canvas.clip_rect(to_rect(region), None, true);
+        let result = content.render_children();
+        content.canvas().restore();
+        result?;
+
+        draw_vertical_scrollbar(content.canvas(), region, self.heights.total(), self.scroll);
+        Ok(())
+    }
+
+    fn set_children(&self, this: &ElModel!()) {
+        let look = self.look();
+        let region = this.draw_region();
+        let visible = self.heights.visible(self.scroll, region.1 .1 - region.0 .1);
+        let lc = this.set_children(build! {
+            for (index, row) in self.rows.iter().enumerate().take(visible.end).skip(visible.start) {
+                @key row.key.clone();
+                TreeRow {
+                    label: row.label.clone(),
+                    depth: row.depth,
+                    kind: row.kind,
+                    phase: if row.kind == RowKind::Loading { self.phase } else { 0.0 },
+                    selected: self.is_selected(row),
+                    focused: self.focused && self.is_selected(row),
+                    hovered: self.hovered == Some(index),
+                    look: look,
+                }
+            }
+        });
+
+        // rows touching the viewport only at an edge are laid out in an
+        // empty region, which hides them
+        let top = region.0 .1 - self.scroll;
+        let mut rows = visible;
+        lc.layout(|()| {
+            let index = rows.next()?;
+            let start = top + self.heights.offset(index);
+            let end = start + self.heights.get(index);
+            if end <= region.0 .1 || start >= region.1 .1 {
+                return Some((region.0, region.0));
+            }
+            Some((Point(region.0 .0, start), Point(region.1 .0, end)))
+        })
+        .unwrap();
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), region: Region) {
+        self.clamp_scroll(region);
+        self.set_children(this);
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for TreeView
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(pointer_runtime(this.clone()));
+        this.daemon(keyboard_runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        let mut tree = TreeView {
+            rows: Vec::new(),
+            expanded: props.expanded.iter().cloned().collect(),
+            selected: props.selected.clone(),
+            heights: Heights::new(0, props.row_height),
+            props,
+            loaded: HashMap::new(),
+            loading: HashSet::new(),
+            generation: 0,
+            phase: 0.0,
+            spinning: false,
+            hovered: None,
+            focused: false,
+            scroll: Pixel(0.0),
+        };
+        tree.refresh(this);
+        tree
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.roots_unchanged {
+            self.generation += 1;
+            self.loaded.clear();
+            self.loading.clear();
+        }
+        if !update_result.expanded_unchanged {
+            self.expanded = self.props.expanded.iter().cloned().collect();
+        }
+        if !update_result.selected_unchanged {
+            self.selected = self.props.selected.clone();
+        }
+
+        let value_unchanged = update_result.roots_unchanged
+            && update_result.expanded_unchanged
+            && update_result.selected_unchanged;
+        if !value_unchanged || !update_result.unchanged {
+            self.refresh(this);
+        }
+        update_result.unchanged && value_unchanged
+    }
+}
+
+fn font_size(style: &TreeStyles) -> Pixel {
+    style.font_size.map_or(Pixel(14.0), |size| size.0)
+}
+
+impl TreeView {
+    fn look(&self) -> TreeLook {
+        let style = &self.props.style;
+        TreeLook {
+            font_size: font_size(style),
+            color: style.color.map_or(Color::BLACK, |color| color.0),
+            selection: style.selection.0,
+            guide: style.guide.0,
+            accent: style.accent.0,
+            indent: self.props.indent,
+        }
+    }
+
+    fn is_selected(&self, row: &FlatRow) -> bool {
+        self.selected.as_deref() == Some(row.key.as_str())
+    }
+
+    fn selected_row(&self) -> Option<usize> {
+        self.rows.iter().position(|row| self.is_selected(row))
+    }
+
+    fn children<'a>(&'a self, node: &'a TreeNode) -> Option<&'a [TreeNode]> {
+        match &node.children {
+            TreeChildren::Leaf => None,
+            TreeChildren::Known(children) => Some(children),
+            TreeChildren::Lazy => self.loaded.get(&node.key).map(Vec::as_slice),
+        }
+    }
+
+    /// Collect the visible nodes, and start loading the children of
+    /// expanded lazy nodes which are not loaded yet.
+    fn refresh(&mut self, this: &ElModel!()) {
+        let mut rows = Vec::new();
+        let mut unloaded = Vec::new();
+        self.flatten(&self.props.roots, 0, None, &mut rows, &mut unloaded);
+        self.rows = rows;
+        self.heights.reset(self.rows.len(), self.props.row_height);
+
+        for key in unloaded {
+            self.load(this, key);
+        }
+
+        self.hovered = self.hovered.filter(|hovered| *hovered < self.rows.len());
+        self.clamp_scroll(this.draw_region());
+    }
+
+    fn flatten(
+        &self,
+        nodes: &[TreeNode],
+        depth: usize,
+        parent: Option<usize>,
+        rows: &mut Vec<FlatRow>,
+        unloaded: &mut Vec<String>,
+    ) {
+        for node in nodes {
+            let expanded = self.expanded.contains(&node.key);
+            let children = self.children(node);
+            let kind = match (&node.children, children) {
+                (TreeChildren::Leaf, _) => RowKind::Leaf,
+                _ if !expanded => RowKind::Collapsed,
+                (TreeChildren::Lazy, None) if self.props.loader.is_some() => {
+                    if !self.loading.contains(&node.key) {
+                        unloaded.push(node.key.clone());
+                    }
+                    RowKind::Loading
+                }
+                _ => RowKind::Expanded,
+            };
+
+            let index = rows.len();
+            rows.push(FlatRow {
+                key: node.key.clone(),
+                label: node.label.clone(),
+                depth,
+                kind,
+                parent,
+            });
+
+            if let (RowKind::Expanded, Some(children)) = (kind, children) {
+                self.flatten(children, depth + 1, Some(index), rows, unloaded);
+            }
+        }
+    }
+
+    /// Ask the loader for the children of the node of `key`.
+    fn load(&mut self, this: &ElModel!(), key: String) {
+        let Some(loader) = &self.props.loader
+        else {
+            return;
+        };
+
+        let children = loader.load(key.clone());
+        let generation = self.generation;
+        self.loading.insert(key.clone());
+
+        let tree = this.clone();
+        this.daemon(async move {
+            let children = children.await;
+            update(&tree, |this| {
+                if this.generation == generation && this.loading.remove(&key) {
+                    this.loaded.insert(key, children);
+                    this.refresh(&tree);
+                }
+            })
+            .await;
+        });
+
+        if !self.spinning {
+            self.spinning = true;
+            this.daemon(spin(this.clone()));
+        }
+    }
+
+    fn toggle(&mut self, this: &ElModel!(), index: usize) {
+        let row = &self.rows[index];
+        let expanded = match row.kind {
+            RowKind::Leaf => return,
+            RowKind::Collapsed => true,
+            RowKind::Expanded | RowKind::Loading => false,
+        };
+
+        let key = row.key.clone();
+        if expanded {
+            self.expanded.insert(key.clone());
+        } else {
+            self.expanded.remove(&key);
+            // drop the loading, which starts again on the next expanding
+            self.loading.remove(&key);
+        }
+
+        self.refresh(this);
+        this.event_dispatcher().emit(TreeToggled { key, expanded });
+    }
+
+    fn select(&mut self, this: &ElModel!(), index: usize) {
+        let key = &self.rows[index].key;
+        if self.selected.as_ref() != Some(key) {
+            self.selected = Some(key.clone());
+            this.event_dispatcher()
+                .emit(TreeSelected { key: key.clone() });
+        }
+        self.scroll_to_row(this.draw_region(), index);
+    }
+
+    fn input(&mut self, this: &ElModel!(), key: VirtualKeyCode) {
+        let Some(last) = self.rows.len().checked_sub(1)
+        else {
+            return;
+        };
+
+        let Some(current) = self.selected_row()
+        else {
+            if matches!(
+                key,
+                VirtualKeyCode::Up | VirtualKeyCode::Down | VirtualKeyCode::Home
+            ) {
+                self.select(this, 0);
+            } else if key == VirtualKeyCode::End {
+                self.select(this, last);
+            }
+            return;
+        };
+
+        let FlatRow { kind, parent, .. } = self.rows[current];
+        match key {
+            VirtualKeyCode::Up => self.select(this, current.saturating_sub(1)),
+            VirtualKeyCode::Down => self.select(this, (current + 1).min(last)),
+            VirtualKeyCode::Home => self.select(this, 0),
+            VirtualKeyCode::End => self.select(this, last),
+            VirtualKeyCode::Right => match kind {
+                RowKind::Collapsed => self.toggle(this, current),
+                RowKind::Expanded
+                    if self
+                        .rows
+                        .get(current + 1)
+                        .is_some_and(|next| next.parent == Some(current)) =>
+                {
+                    self.select(this, current + 1)
+                }
+                _ => {}
+            },
+            VirtualKeyCode::Left => match (kind, parent) {
+                (RowKind::Expanded | RowKind::Loading, _) => self.toggle(this, current),
+                (_, Some(parent)) => self.select(this, parent),
+                _ => {}
+            },
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Space => {
+                self.toggle(this, current)
+            }
+            _ => {}
+        }
+    }
+
+    fn row_at(&self, region: Region, y: Pixel) -> Option<usize> {
+        if y < region.0 .1 || y >= region.1 .1 {
+            return None;
+        }
+
+        let y = y - region.0 .1 + self.scroll;
+        (y < self.heights.total()).then(|| self.heights.row_at(y))
+    }
+
+    /// Whether `x` is on the arrow of the row.
+    fn on_arrow(&self, region: Region, index: usize, x: Pixel) -> bool {
+        let left = region.0 .0 + self.props.indent * self.rows[index].depth as f32;
+        x >= left && x < left + self.props.indent
+    }
+
+    fn max_scroll(&self, region: Region) -> Pixel {
+        max_offset(self.heights.total(), region.1 .1 - region.0 .1)
+    }
+
+    fn clamp_scroll(&mut self, region: Region) {
+        self.scroll = clamp_offset(self.scroll, self.max_scroll(region));
+    }
+
+    fn scroll_to_row(&mut self, region: Region, index: usize) {
+        let top = self.heights.offset(index);
+        self.scroll = reveal_offset(
+            self.scroll,
+            region.1 .1 - region.0 .1,
+            top,
+            top + self.heights.get(index),
+        );
+        self.clamp_scroll(region);
+    }
+}
+
+async fn update(this: &ElModel!(TreeView), f: impl FnOnce(&mut TreeView)) -> bool {
+    let Some(mut tree) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *tree);
+    true
+}
+
+/// Turn the spinners of loading nodes until nothing is loading.
+async fn spin(this: ElModel!(TreeView)) {
+    loop {
+        tokio::time::sleep(FRAME).await;
+
+        let mut spinning = false;
+        let alive = update(&this, |tree| {
+            spinning = !tree.loading.is_empty();
+            if spinning {
+                tree.phase = (tree.phase + SPIN_SPEED).fract();
+            } else {
+                tree.spinning = false;
+            }
+        })
+        .await;
+
+        if !alive || !spinning {
+            return;
+        }
+    }
+}
+
+async fn pointer_runtime(this: ElModel!(TreeView)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        let alive = tokio::select! {
+            pd = ed.recv_trusted::<PointerDown>() => {
+                if pd.button != MouseButton::Left {
+                    continue;
+                }
+
+                this.focus();
+                update(&this, |tree| {
+                    let region = this.draw_region();
+                    let Some(index) = tree.row_at(region, pd.position.1)
+                    else {
+                        return;
+                    };
+
+                    if tree.on_arrow(region, index, pd.position.0) {
+                        tree.toggle(&this, index);
+                    } else {
+                        tree.select(&this, index);
+                    }
+                })
+                .await
+            }
+            pm = ed.recv_trusted::<PointerMove>() => {
+                let Some(tree) = this.el_read().await
+                else {
+                    return;
+                };
+
+                let hovered = tree.row_at(this.draw_region(), pm.position.1);
+                if hovered == tree.hovered {
+                    continue;
+                }
+                drop(tree);
+                update(&this, |tree| tree.hovered = hovered).await
+            }
+            _ = ed.recv_trusted::<PointerOut>() => {
+                update(&this, |tree| tree.hovered = None).await
+            }
+            wheel = ed.recv_trusted::<Wheel>() => {
+                let (_, delta) = wheel_delta(wheel.delta);
+                update(&this, |tree| {
+                    let region = this.draw_region();
+                    tree.scroll = tree.scroll + delta;
+                    tree.clamp_scroll(region);
+                    tree.hovered = tree.row_at(region, wheel.position.1);
+                })
+                .await
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
+
+async fn keyboard_runtime(this: ElModel!(TreeView)) {
+    let ed = this.event_dispatcher().clone();
+
+    loop {
+        ed.recv_trusted::<Focused>().await;
+        if !update(&this, |tree| tree.focused = true).await {
+            return;
+        }
+
+        loop {
+            let key = tokio::select! {
+                event = ed.recv_trusted::<StaticWindowEvent>() => match event {
+                    StaticWindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => key,
+                    _ => continue,
+                },
+                _ = ed.recv_trusted::<Blured>() => break,
+            };
+
+            if !update(&this, |tree| tree.input(&this, key)).await {
+                return;
+            }
+        }
+
+        if !update(&this, |tree| tree.focused = false).await {
+            return;
+        }
+    }
+}
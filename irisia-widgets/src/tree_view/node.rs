@@ -0,0 +1,95 @@
+use std::{future::Future, pin::Pin, rc::Rc};
+
+/// A node of a [`TreeView`], identified by a key unique in the whole tree.
+///
+/// ```ignore
+/// TreeNode::branch("src", "src", [
+///     TreeNode::leaf("src/lib.rs", "lib.rs"),
+///     TreeNode::lazy("src/widgets", "widgets"),
+/// ])
+/// ```
+///
+/// [`TreeView`]: super::TreeView
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode {
+    pub(super) key: String,
+    pub(super) label: String,
+    pub(super) children: TreeChildren,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum TreeChildren {
+    Leaf,
+    Known(Vec<TreeNode>),
+    /// Asked for from the loader the first time the node is expanded
+    Lazy,
+}
+
+impl TreeNode {
+    /// A node that cannot be expanded.
+    pub fn leaf(key: impl Into<String>, label: impl Into<String>) -> Self {
+        TreeNode {
+            key: key.into(),
+            label: label.into(),
+            children: TreeChildren::Leaf,
+        }
+    }
+
+    pub fn branch(
+        key: impl Into<String>,
+        label: impl Into<String>,
+        children: impl IntoIterator<Item = TreeNode>,
+    ) -> Self {
+        TreeNode {
+            key: key.into(),
+            label: label.into(),
+            children: TreeChildren::Known(children.into_iter().collect()),
+        }
+    }
+
+    /// A node whose children are loaded by the [`TreeLoader`] of the tree
+    /// once the node is expanded.
+    pub fn lazy(key: impl Into<String>, label: impl Into<String>) -> Self {
+        TreeNode {
+            key: key.into(),
+            label: label.into(),
+            children: TreeChildren::Lazy,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+type LoadFuture = Pin<Box<dyn Future<Output = Vec<TreeNode>>>>;
+
+/// Loads the children of lazy nodes, taking the key of the expanded node.
+/// The node shows a spinner until the returned future completes, and the
+/// children are kept until the roots of the tree change.
+///
+/// ```ignore
+/// TreeLoader::new(|key| async move { read_dir(&key).await })
+/// ```
+#[derive(Clone)]
+pub struct TreeLoader(Rc<dyn Fn(String) -> LoadFuture>);
+
+impl TreeLoader {
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: Fn(String) -> Fut + 'static,
+        Fut: Future<Output = Vec<TreeNode>> + 'static,
+    {
+        TreeLoader(Rc::new(move |key| Box::pin(f(key))))
+    }
+
+    pub(super) fn load(&self, key: String) -> LoadFuture {
+        (self.0)(key)
+    }
+}
+
+impl PartialEq for TreeLoader {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
@@ -0,0 +1,200 @@
+use irisia::{
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    primitive::{Pixel, Point},
+    skia_safe::{Canvas, Color, Font, Paint, PaintStyle, Path},
+    ElModel,
+};
+
+use crate::label::{draw_label, label_font, to_rect};
+
+const CHEVRON_SIZE: Pixel = Pixel(4.0);
+const SPINNER_RADIUS: Pixel = Pixel(5.0);
+
+/// Colors and sizes shared by all rows of a tree.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) struct TreeLook {
+    pub font_size: Pixel,
+    pub color: Color,
+    pub selection: Color,
+    pub guide: Color,
+    pub accent: Color,
+    /// Width of each level of indentation
+    pub indent: Pixel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RowKind {
+    Leaf,
+    Collapsed,
+    Expanded,
+    /// Expanded, waiting for the children from the loader
+    Loading,
+}
+
+/// A node of a tree view shown as a row. Rows are keyed by the keys of
+/// their nodes, so expanding a node leaves the rows of other nodes untouched.
+pub(super) struct TreeRow {
+    props: OwnedProps,
+    font: Font,
+}
+
+#[irisia::props(updater = "TreeRowProps", watch)]
+pub(super) struct OwnedProps {
+    #[props(must_init)]
+    label: String,
+
+    #[props(must_init)]
+    depth: usize,
+
+    #[props(must_init)]
+    kind: RowKind,
+
+    /// Turns of the spinner shown while loading, only changes while loading.
+    #[props(default)]
+    phase: f32,
+
+    #[props(default)]
+    selected: bool,
+
+    /// Draws the focus ring.
+    #[props(default)]
+    focused: bool,
+
+    #[props(default)]
+    hovered: bool,
+
+    #[props(must_init, watch)]
+    look: TreeLook,
+}
+
+impl Element for TreeRow {
+    type BlankProps = TreeRowProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        let OwnedProps {
+            ref label,
+            depth,
+            kind,
+            phase,
+            selected,
+            focused,
+            hovered,
+            look,
+        } = self.props;
+
+        let canvas = content.canvas();
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+
+        if selected {
+            paint.set_color(look.selection);
+            canvas.draw_rect(to_rect(region), &paint);
+        } else if hovered {
+            paint.set_color(look.color.with_a(0x0a));
+            canvas.draw_rect(to_rect(region), &paint);
+        }
+
+        let (top, bottom) = (region.0 .1, region.1 .1);
+        let middle = (top + bottom) / 2.0;
+
+        // a guide in the middle of every level of indentation
+        paint.set_color(look.guide);
+        for level in 0..depth {
+            let x = region.0 .0 + look.indent * (level as f32 + 0.5);
+            canvas.draw_rect(
+                to_rect((Point(x, top), Point(x + Pixel(1.0), bottom))),
+                &paint,
+            );
+        }
+
+        let center = Point(region.0 .0 + look.indent * (depth as f32 + 0.5), middle);
+        match kind {
+            RowKind::Leaf => {}
+            RowKind::Collapsed | RowKind::Expanded => {
+                draw_chevron(canvas, center, kind == RowKind::Expanded, look.color)
+            }
+            RowKind::Loading => draw_spinner(canvas, center, phase, look.accent),
+        }
+
+        let x = region.0 .0 + look.indent * (depth + 1) as f32;
+        canvas.save();
+        canvas.clip_rect(to_rect(region), None, true);
+        draw_label(canvas, &self.font, label, look.color, x, (top, bottom));
+        canvas.restore();
+
+        if focused {
+            paint
+                .set_color(look.accent)
+                .set_style(PaintStyle::Stroke)
+                .set_stroke_width(Pixel(1.0).to_physical());
+            let inset = Pixel(1.0);
+            let outline = (
+                Point(region.0 .0 + inset, top + inset),
+                Point(region.1 .0 - inset, bottom - inset),
+            );
+            canvas.draw_rect(to_rect(outline), &paint);
+        }
+        Ok(())
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for TreeRow
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(_: &ElModel!(), props: Pr) -> Self {
+        let props = OwnedProps::props_create_with(props);
+        TreeRow {
+            font: label_font(props.look.font_size),
+            props,
+        }
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let update_result = self.props.props_update_with(props);
+        if !update_result.look_unchanged {
+            self.font = label_font(self.props.look.font_size);
+        }
+        update_result.unchanged && update_result.look_unchanged
+    }
+}
+
+/// A triangle pointing right, or down if `expanded`.
+fn draw_chevron(canvas: &mut Canvas, center: Point, expanded: bool, color: Color) {
+    let Point(x, y) = center;
+    let size = CHEVRON_SIZE;
+
+    let mut path = Path::new();
+    if expanded {
+        path.move_to(Point(x - size, y - size / 2.0))
+            .line_to(Point(x + size, y - size / 2.0))
+            .line_to(Point(x, y + size / 2.0));
+    } else {
+        path.move_to(Point(x - size / 2.0, y - size))
+            .line_to(Point(x - size / 2.0, y + size))
+            .line_to(Point(x + size / 2.0, y));
+    }
+    path.close();
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(color.with_a(0xa0));
+    canvas.draw_path(&path, &paint);
+}
+
+/// Three quarters of a circle turned by `phase` turns.
+fn draw_spinner(canvas: &mut Canvas, center: Point, phase: f32, color: Color) {
+    let Point(x, y) = center;
+    let oval = (
+        Point(x - SPINNER_RADIUS, y - SPINNER_RADIUS),
+        Point(x + SPINNER_RADIUS, y + SPINNER_RADIUS),
+    );
+
+    let mut paint = Paint::default();
+    paint
+        .set_anti_alias(true)
+        .set_color(color)
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(Pixel(2.0).to_physical());
+    canvas.draw_arc(to_rect(oval), phase.fract() * 360.0, 270.0, false, &paint);
+}
@@ -0,0 +1,16 @@
+use irisia::{skia_safe::Color, Style};
+
+/// Background of the selected node.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTreeSelection(#[style(default = "Color::from_rgb(0xd6, 0xe6, 0xfd)")] pub Color);
+
+/// Color of the indentation guides.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTreeGuide(#[style(default = "Color::from_rgb(0xe0, 0xe0, 0xe0)")] pub Color);
+
+/// Color of the focus ring and the loading spinner.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTreeAccent(#[style(default = "Color::from_rgb(0x2f, 0x6f, 0xed)")] pub Color);
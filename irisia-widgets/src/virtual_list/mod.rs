@@ -0,0 +1,285 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
+
+use irisia::{
+    build,
+    element::{props::PropsUpdateWith, Element, ElementUpdate, RenderElement},
+    event::standard::Wheel,
+    primitive::{Pixel, Point, Region},
+    ElModel,
+};
+
+use crate::{
+    label::to_rect,
+    scroll::{clamp_offset, draw_vertical_scrollbar, max_offset, wheel_delta, Heights},
+};
+
+use self::row::{build_row, DeclaredHeights};
+
+pub use self::row::{RowBuilder, RowHandle, VirtualRow};
+
+mod row;
+pub mod styles;
+
+const FRAME: Duration = Duration::from_millis(16);
+/// Part of the remaining distance scrolled in each frame
+const SMOOTHING: f32 = 0.3;
+
+/// A scrolling list that only creates the rows around the viewport, so
+/// that lists of any length cost about the same.
+///
+/// Children of rows are built by `row` from the index of the row. Rows
+/// scrolled out are reused for rows scrolled in, and `overscan` rows on each
+/// end are built ahead but hidden. Every row is `row_height` high, unless
+/// its content has a [`StyleRowHeight`], in which case `row_height` is only
+/// the estimate until the row is built.
+///
+/// [`StyleRowHeight`]: styles::StyleRowHeight
+pub struct VirtualList {
+    props: OwnedProps,
+    heights: Heights,
+    /// Scroll offset currently shown
+    scroll: Pixel,
+    /// Scroll offset the list is moving towards
+    target: Pixel,
+    /// Index and key of every built row, in order
+    rows: Vec<(usize, usize)>,
+    next_key: usize,
+    /// Models of the built rows by key
+    row_models: RefCell<HashMap<usize, RowHandle>>,
+    declared: Rc<DeclaredHeights>,
+}
+
+#[irisia::props(updater = "VirtualListProps", watch)]
+pub struct OwnedProps {
+    /// Number of rows.
+    #[props(must_init)]
+    count: usize,
+
+    #[props(must_init)]
+    row: RowBuilder,
+
+    #[props(default = "Pixel(24.0)")]
+    row_height: Pixel,
+
+    /// Rows built beyond each end of the viewport.
+    #[props(default = "4")]
+    overscan: usize,
+}
+
+impl Element for VirtualList {
+    type BlankProps = VirtualListProps;
+
+    fn render(&mut self, this: &ElModel!(), mut content: RenderElement) -> irisia::Result<()> {
+        let region = this.draw_region();
+        this.set_interact_region(Some(region));
+        this.consume_wheel(self.max_scroll(region) > Pixel(0.0));
+
+        let canvas = content.canvas();
+        canvas.save();
+        canvas.clip_rect(to_rect(region), None, true);
+        let result = content.render_children();
+        content.canvas().restore();
+        result?;
+
+        draw_vertical_scrollbar(content.canvas(), region, self.heights.total(), self.scroll);
+        Ok(())
+    }
+
+    fn set_children(&self, this: &ElModel!()) {
+        let lc = this.set_children(build! {
+            for row in self.rows.iter() {
+                @key row.1;
+                VirtualRow {
+                    +oncreate: move |model: &RowHandle| {
+                        self.row_models.borrow_mut().insert(row.1, model.clone());
+                    },
+                }
+            }
+        });
+
+        let mut row_models = self.row_models.borrow_mut();
+        row_models.retain(|key, _| self.rows.iter().any(|row| row.1 == *key));
+        for &(index, key) in &self.rows {
+            build_row(&row_models[&key], index, &self.props.row, &self.declared);
+        }
+        drop(row_models);
+
+        // rows in the overscan are laid out in an empty region, which hides them
+        let region = this.draw_region();
+        let top = region.0 .1 - self.scroll;
+        let mut rows = self.rows.iter();
+        lc.layout(|()| {
+            let &(index, _) = rows.next()?;
+            let start = top + self.heights.offset(index);
+            let end = start + self.heights.get(index);
+            if end <= region.0 .1 || start >= region.1 .1 {
+                return Some((region.0, region.0));
+            }
+            Some((Point(region.0 .0, start), Point(region.1 .0, end)))
+        })
+        .unwrap();
+    }
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), region: Region) {
+        self.refresh(region);
+        self.set_children(this);
+    }
+}
+
+impl<Pr> ElementUpdate<Pr> for VirtualList
+where
+    OwnedProps: PropsUpdateWith<Pr>,
+{
+    fn el_create(this: &ElModel!(), props: Pr) -> Self {
+        this.daemon(runtime(this.clone()));
+
+        let props = OwnedProps::props_create_with(props);
+        let mut list = VirtualList {
+            heights: Heights::new(props.count, props.row_height),
+            props,
+            scroll: Pixel(0.0),
+            target: Pixel(0.0),
+            rows: Vec::new(),
+            next_key: 0,
+            row_models: RefCell::new(HashMap::new()),
+            declared: Rc::new(DeclaredHeights::default()),
+        };
+        list.refresh(this.draw_region());
+        list
+    }
+
+    fn el_update(&mut self, this: &ElModel!(), props: Pr, _equality_matters: bool) -> bool {
+        let unchanged = self.props.props_update_with(props).unchanged;
+        if !unchanged {
+            self.heights.reset(self.props.count, self.props.row_height);
+            self.refresh(this.draw_region());
+        }
+        unchanged
+    }
+}
+
+impl VirtualList {
+    fn max_scroll(&self, region: Region) -> Pixel {
+        max_offset(self.heights.total(), region.1 .1 - region.0 .1)
+    }
+
+    /// Clamp the scroll offsets, then decide the rows to build, reusing the
+    /// keys of rows scrolled out for rows scrolled in.
+    fn refresh(&mut self, region: Region) {
+        let max_scroll = self.max_scroll(region);
+        self.target = clamp_offset(self.target, max_scroll);
+        self.scroll = clamp_offset(self.scroll, max_scroll);
+
+        let visible = self.heights.visible(self.scroll, region.1 .1 - region.0 .1);
+        let overscan = self.props.overscan;
+        let (first, last) = (
+            visible.start.saturating_sub(overscan),
+            (visible.end + overscan).min(self.heights.len()),
+        );
+
+        let old: HashMap<usize, usize> = self.rows.iter().copied().collect();
+        let mut free: Vec<usize> = self
+            .rows
+            .iter()
+            .filter(|(index, _)| !(first..last).contains(index))
+            .map(|&(_, key)| key)
+            .collect();
+
+        let mut next_key = self.next_key;
+        self.rows = (first..last)
+            .map(|index| {
+                let key = old
+                    .get(&index)
+                    .copied()
+                    .or_else(|| free.pop())
+                    .unwrap_or_else(|| {
+                        next_key += 1;
+                        next_key - 1
+                    });
+                (index, key)
+            })
+            .collect();
+        self.next_key = next_key;
+    }
+
+    fn scroll_by(&mut self, region: Region, delta: Pixel) {
+        self.target = clamp_offset(self.target + delta, self.max_scroll(region));
+    }
+
+    /// Move the scroll offset a step towards the target.
+    fn step(&mut self, region: Region) {
+        let distance = self.target - self.scroll;
+        self.scroll = if distance.0.abs() < 0.5 {
+            self.target
+        } else {
+            self.scroll + distance * SMOOTHING
+        };
+        self.refresh(region);
+    }
+}
+
+async fn update(this: &ElModel!(VirtualList), f: impl FnOnce(&mut VirtualList)) -> bool {
+    let Some(mut list) = this.el_write().await
+    else {
+        return false;
+    };
+
+    f(&mut *list);
+    true
+}
+
+async fn runtime(this: ElModel!(VirtualList)) {
+    let ed = this.event_dispatcher().clone();
+    let Some(declared) = this.el_read().await.map(|list| list.declared.clone())
+    else {
+        return;
+    };
+
+    loop {
+        let Some(list) = this.el_read().await
+        else {
+            return;
+        };
+        let scrolling = list.scroll != list.target;
+        drop(list);
+
+        let alive = tokio::select! {
+            wheel = ed.recv_trusted::<Wheel>() => {
+                let (_, delta) = wheel_delta(wheel.delta);
+                update(&this, |list| list.scroll_by(this.draw_region(), delta)).await
+            }
+            _ = tokio::time::sleep(FRAME), if scrolling => {
+                update(&this, |list| list.step(this.draw_region())).await
+            }
+            _ = declared.notify.notified() => {
+                let Some(list) = this.el_read().await
+                else {
+                    return;
+                };
+
+                // rows declare their heights every time they are built,
+                // only lay out again if any of them changed
+                let heights = declared.take();
+                let changed = heights.iter().any(|&(index, height)| {
+                    index < list.heights.len() && list.heights.get(index) != height
+                });
+                if !changed {
+                    continue;
+                }
+                drop(list);
+
+                update(&this, |list| {
+                    for (index, height) in heights {
+                        list.heights.set(index, height);
+                    }
+                    list.refresh(this.draw_region());
+                })
+                .await
+            }
+        };
+
+        if !alive {
+            return;
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::{cell::RefCell, rc::Rc};
+
+use irisia::{
+    element::{Element, ElementUpdate, LayoutElements, RcElementModel},
+    primitive::{Pixel, Region},
+    ElModel,
+};
+use tokio::sync::Notify;
+
+use super::styles::StyleRowHeight;
+
+/// Model of a row of a [`VirtualList`], passed to the row builder.
+///
+/// [`VirtualList`]: super::VirtualList
+pub type RowHandle = RcElementModel<VirtualRow, (), ()>;
+
+/// Builds the children of a row of a [`VirtualList`] from the index of the
+/// row. A row is reused for other indices once scrolled out, so the builder
+/// is called again every time the index of the row changes.
+///
+/// ```ignore
+/// RowBuilder::new(move |index, row| {
+///     row.set_children(build! {
+///         TextBox {
+///             text: lines[index].clone(),
+///         }
+///     })
+/// })
+/// ```
+///
+/// [`VirtualList`]: super::VirtualList
+#[derive(Clone)]
+pub struct RowBuilder(Rc<dyn Fn(usize, &RowHandle) -> LayoutElements<'_>>);
+
+impl RowBuilder {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(usize, &RowHandle) -> LayoutElements<'_> + 'static,
+    {
+        RowBuilder(Rc::new(f))
+    }
+}
+
+impl PartialEq for RowBuilder {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Heights declared by the content of rows with [`StyleRowHeight`], waiting
+/// to be taken by the list.
+#[derive(Default)]
+pub(super) struct DeclaredHeights {
+    pending: RefCell<Vec<(usize, Pixel)>>,
+    pub notify: Notify,
+}
+
+impl DeclaredHeights {
+    pub fn take(&self) -> Vec<(usize, Pixel)> {
+        std::mem::take(&mut *self.pending.borrow_mut())
+    }
+}
+
+/// A row of a virtual list. Its children are built by the list, through
+/// the row builder, every time the list lays out its rows.
+pub struct VirtualRow;
+
+impl Element for VirtualRow {
+    type BlankProps = ();
+
+    // children are built by `build_row`, whose type differs from the default
+    fn set_children(&self, _: &ElModel!()) {}
+
+    fn draw_region_changed(&mut self, this: &ElModel!(), region: Region) {
+        if let Some(lc) = this.layout_children() {
+            lc.layout(|()| Some(region)).unwrap();
+        }
+    }
+}
+
+impl ElementUpdate<()> for VirtualRow {
+    fn el_create(_: &ElModel!(), _: ()) -> Self {
+        VirtualRow
+    }
+
+    fn el_update(&mut self, _: &ElModel!(), _: (), _equality_matters: bool) -> bool {
+        true
+    }
+}
+
+/// Build the children of `row` as the row of `index`, and queue the height
+/// the content declares, if any.
+pub(super) fn build_row(
+    row: &RowHandle,
+    index: usize,
+    builder: &RowBuilder,
+    declared: &DeclaredHeights,
+) {
+    let lc = (builder.0)(index, row);
+
+    let mut height = None;
+    lc.peek_styles(|style: Option<StyleRowHeight>| {
+        if let Some(StyleRowHeight(h)) = style {
+            height = Some(height.map_or(h, |height: Pixel| height.max(h)));
+        }
+    });
+
+    let region = row.draw_region();
+    lc.layout(|()| Some(region)).unwrap();
+
+    if let Some(height) = height {
+        declared.pending.borrow_mut().push((index, height));
+        declared.notify.notify_one();
+    }
+}
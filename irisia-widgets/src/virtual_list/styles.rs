@@ -0,0 +1,10 @@
+use irisia::{primitive::Pixel, Style};
+
+/// Height of a row of a [`VirtualList`], set on the content of the row.
+/// Rows whose content has no such style are as high as the `row_height`
+/// of the list.
+///
+/// [`VirtualList`]: super::VirtualList
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from)]
+pub struct StyleRowHeight(pub Pixel);
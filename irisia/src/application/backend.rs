@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc, time::Duration};
 
 use irisia_backend::{
     skia_safe::{colors::WHITE, Canvas},
@@ -18,7 +18,11 @@ use crate::{
 
 use super::{
     content::GlobalContent,
-    event_comp::{global::focusing::Focusing, GlobalEventMgr},
+    event_comp::{
+        global::{capture::PointerCapture, drag::DragMgr, focusing::Focusing},
+        GlobalEventMgr,
+    },
+    overlay::Overlay,
     redraw_scheduler::RedrawScheduler,
     Window,
 };
@@ -37,12 +41,22 @@ where
         self.gc
             .redraw_scheduler
             .borrow_mut()
-            .redraw(canvas, interval)?;
+            .redraw(canvas, interval, &self.gc.animations)?;
 
         // composite
+        let frame = self.gc.animations.next_frame();
         canvas.reset_matrix();
         canvas.clear(WHITE);
-        self.root_element.composite(canvas)
+        self.root_element.composite(canvas, frame)?;
+        self.gc.overlay.composite(canvas, frame)?;
+
+        if self.gc.animations.any_shown_in(frame) {
+            self.gc.window().request_redraw();
+        }
+
+        self.gc.drag.draw_preview(canvas);
+        self.gc.answer_frame_samples(canvas);
+        Ok(())
     }
 
     fn on_window_event(&mut self, event: StaticWindowEvent) {
@@ -52,7 +66,8 @@ where
         }
 
         if let Some(npe) = self.gem.emit_event(event, &self.gc) {
-            if !self.root_element.emit_event(&npe) {
+            let overlay_entered = self.gc.overlay.emit_event(&npe);
+            if !self.root_element.emit_event(&npe) && !overlay_entered {
                 npe.focus_on(None);
             }
         }
@@ -60,6 +75,11 @@ where
 
     fn on_destroy(&mut self) {
         self.gc.event_dispatcher().emit_trusted(WindowDestroyed);
+
+        // data may hold elements, which hold the global content in turn.
+        // taken out first, for its drop may access the data again
+        let data = self.gc.data.take();
+        drop(data);
     }
 }
 
@@ -89,9 +109,15 @@ where
             let gc = Rc::new(GlobalContent {
                 global_ed: ev_disp,
                 focusing: Focusing::new(),
+                pointer_capture: PointerCapture::new(),
+                drag: DragMgr::new(),
+                overlay: Overlay::new(),
                 window,
                 redraw_scheduler: RefCell::new(redraw_scheduler),
+                animations: Default::default(),
                 close_handle,
+                data: RefCell::new(HashMap::new()),
+                frame_samples: RefCell::new(Vec::new()),
             });
 
             let root_element = <DropProtection<El, (), ()> as UpdateWith<
@@ -1,20 +1,39 @@
-use std::{cell::RefCell, rc::Rc, sync::Arc};
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    rc::{Rc, Weak},
+    sync::Arc,
+};
 
-use irisia_backend::{window_handle::CloseHandle, WinitWindow};
+use irisia_backend::{
+    skia_safe::{AlphaType, Canvas, Color, ColorType, ImageInfo},
+    window_handle::CloseHandle,
+    WinitWindow,
+};
+use tokio::sync::oneshot;
 
-use crate::event::EventDispatcher;
+use crate::{event::EventDispatcher, primitive::Point};
 
 use super::{
-    event_comp::global::focusing::Focusing,
-    redraw_scheduler::{RedrawObject, RedrawScheduler},
+    event_comp::global::{capture::PointerCapture, drag::DragMgr, focusing::Focusing},
+    overlay::Overlay,
+    redraw_scheduler::{AnimationTarget, Animations, RedrawObject, RedrawScheduler},
 };
 
 pub struct GlobalContent {
     pub(super) focusing: Focusing,
+    pub(super) pointer_capture: PointerCapture,
+    pub(super) drag: DragMgr,
+    pub(super) overlay: Overlay,
     pub(super) global_ed: EventDispatcher,
     pub(super) window: Arc<WinitWindow>,
     pub(super) close_handle: CloseHandle,
     pub(super) redraw_scheduler: RefCell<RedrawScheduler>,
+    pub(super) animations: Animations,
+    pub(super) data: RefCell<HashMap<TypeId, Rc<dyn Any>>>,
+    /// Points to read from the next frame, and where to send their colors
+    pub(super) frame_samples: RefCell<Vec<(Point, oneshot::Sender<Option<Color>>)>>,
 }
 
 impl GlobalContent {
@@ -22,14 +41,63 @@ impl GlobalContent {
         self.focusing.blur();
     }
 
+    /// Returns the event dispatcher of the focused element, if any.
+    pub fn focused(&self) -> Option<EventDispatcher> {
+        self.focusing.focused()
+    }
+
+    /// Let the element of `ed` being focused on, like the one saved by
+    /// [`Self::focused`] before moving the focus elsewhere.
+    pub fn focus(&self, ed: EventDispatcher) {
+        self.focusing.focus(ed);
+    }
+
     pub(crate) fn focusing(&self) -> &Focusing {
         &self.focusing
     }
 
+    pub(crate) fn pointer_capture(&self) -> &PointerCapture {
+        &self.pointer_capture
+    }
+
+    pub(crate) fn drag(&self) -> &DragMgr {
+        &self.drag
+    }
+
+    pub(crate) fn overlay(&self) -> &Overlay {
+        &self.overlay
+    }
+
+    /// Returns whether something is being dragged, from either an element
+    /// or the operating system.
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_active()
+    }
+
+    /// Cancels the current drag, if any. No element will receive the data.
+    pub fn cancel_drag(&self) {
+        self.drag.cancel();
+        self.redraw_drag_preview();
+    }
+
+    /// Request a redraw if the drag preview moved or ended.
+    pub(crate) fn redraw_drag_preview(&self) {
+        if self.drag.take_preview_changed() {
+            self.window.request_redraw();
+        }
+    }
+
     pub(crate) fn request_redraw(&self, ro: Rc<dyn RedrawObject>) {
         self.redraw_scheduler.borrow_mut().request_redraw(ro)
     }
 
+    pub(crate) fn set_animating(&self, target: Weak<dyn AnimationTarget>, animating: bool) {
+        self.animations.set(target, animating);
+        if animating {
+            self.window.request_redraw();
+        }
+    }
+
     /// Returns a reference to the global event dispatcher
     pub fn event_dispatcher(&self) -> &EventDispatcher {
         &self.global_ed
@@ -49,4 +117,45 @@ impl GlobalContent {
     pub fn window(&self) -> &WinitWindow {
         &self.window
     }
+
+    /// Returns the color at `point` of the next frame of the window, which
+    /// is requested to be drawn. Returns `None` if the point is outside of
+    /// the window, or the window is closed before the frame.
+    pub async fn sample_frame(&self, point: Point) -> Option<Color> {
+        let (tx, rx) = oneshot::channel();
+        self.frame_samples.borrow_mut().push((point, tx));
+        self.window.request_redraw();
+        rx.await.ok().flatten()
+    }
+
+    /// Answer the samples requested with the frame just drawn.
+    pub(super) fn answer_frame_samples(&self, canvas: &mut Canvas) {
+        let size = self.window.inner_size();
+        for (point, tx) in self.frame_samples.borrow_mut().drain(..) {
+            let (x, y) = (point.0.to_physical(), point.1.to_physical());
+            let inside = x >= 0.0 && y >= 0.0 && x < size.width as f32 && y < size.height as f32;
+
+            let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Unpremul, None);
+            let mut pixel = [0u8; 4];
+            let color = (inside && canvas.read_pixels(&info, &mut pixel, 4, (x as i32, y as i32)))
+                .then(|| Color::from_argb(pixel[3], pixel[0], pixel[1], pixel[2]));
+            let _ = tx.send(color);
+        }
+    }
+
+    /// Returns the data of type `T` attached to this window, attaching the
+    /// one returned by `init` at the first call. Services shared by all
+    /// elements of a window, like a notification queue, are stored here.
+    /// Data is dropped once the window destroyed.
+    pub fn data<T: 'static>(&self, init: impl FnOnce() -> T) -> Rc<T> {
+        if let Some(data) = self.data.borrow().get(&TypeId::of::<T>()) {
+            return data.clone().downcast().unwrap();
+        }
+
+        let data = Rc::new(init());
+        self.data
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), data.clone());
+        data
+    }
 }
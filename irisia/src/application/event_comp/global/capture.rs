@@ -0,0 +1,67 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use crate::event::{
+    standard::{PointerCaptureLost, PointerId},
+    EventDispatcher,
+};
+
+pub struct PointerCapture(RefCell<HashMap<PointerId, EventDispatcher>>);
+
+impl PointerCapture {
+    pub fn new() -> Self {
+        PointerCapture(Default::default())
+    }
+
+    pub fn capture(&self, pointer_id: PointerId, ed: EventDispatcher) {
+        let mut guard = self.0.borrow_mut();
+
+        match guard.get(&pointer_id) {
+            Some(old_ed) if ed.ptr_eq(old_ed) => {}
+            _ => {
+                release(&mut guard, pointer_id);
+                guard.insert(pointer_id, ed);
+            }
+        }
+    }
+
+    pub fn release(&self, pointer_id: PointerId) {
+        release(&mut self.0.borrow_mut(), pointer_id)
+    }
+
+    pub fn release_checked(&self, pointer_id: PointerId, ed: &EventDispatcher) {
+        let mut guard = self.0.borrow_mut();
+        if let Some(captured) = guard.get(&pointer_id) {
+            if captured.is_same(ed) {
+                release(&mut guard, pointer_id);
+            }
+        }
+    }
+
+    /// Release all pointers captured by the event dispatcher.
+    pub fn release_all_of(&self, ed: &EventDispatcher) {
+        let mut guard = self.0.borrow_mut();
+        let captured: Vec<PointerId> = guard
+            .iter()
+            .filter(|(_, captured)| captured.is_same(ed))
+            .map(|(id, _)| *id)
+            .collect();
+
+        for pointer_id in captured {
+            release(&mut guard, pointer_id);
+        }
+    }
+
+    pub fn captured_by(&self, pointer_id: PointerId, ed: &EventDispatcher) -> bool {
+        matches!(self.0.borrow().get(&pointer_id), Some(captured) if captured.is_same(ed))
+    }
+
+    pub fn is_captured(&self, pointer_id: PointerId) -> bool {
+        self.0.borrow().contains_key(&pointer_id)
+    }
+}
+
+fn release(map: &mut HashMap<PointerId, EventDispatcher>, pointer_id: PointerId) {
+    if let Some(ed) = map.remove(&pointer_id) {
+        ed.emit_trusted(PointerCaptureLost { pointer_id });
+    }
+}
@@ -0,0 +1,363 @@
+use std::{
+    cell::{Cell, RefCell},
+    path::PathBuf,
+};
+
+use irisia_backend::skia_safe::Canvas;
+
+use crate::{
+    dom::DropFilter,
+    event::{
+        standard::{
+            DragData, DragDrop, DragEnd, DragEnter, DragLeave, DragOver, DraggedFiles,
+            DropAcceptor, PointerId,
+        },
+        EventDispatcher,
+    },
+    primitive::Point,
+};
+
+pub(crate) type DragPreview = Box<dyn Fn(&mut Canvas, Point)>;
+
+pub(crate) struct DragMgr {
+    session: RefCell<Option<DragSession>>,
+    /// Files of the last external drop. Winit emits one `DroppedFile` per
+    /// file, but they were delivered together already.
+    dropped_files: RefCell<Vec<PathBuf>>,
+    /// Whether the preview moved or ended since the last redraw requested
+    preview_changed: Cell<bool>,
+}
+
+struct DragSession {
+    data: DragData,
+    /// `None` if dragged from outside the window
+    source: Option<EventDispatcher>,
+    pointer_id: PointerId,
+    preview: Option<DragPreview>,
+    position: Option<Point>,
+    /// Innermost first
+    hovering: Vec<(EventDispatcher, DropAcceptor)>,
+    pending_drop: bool,
+}
+
+impl DragMgr {
+    pub fn new() -> Self {
+        DragMgr {
+            session: RefCell::new(None),
+            dropped_files: RefCell::new(Vec::new()),
+            preview_changed: Cell::new(false),
+        }
+    }
+
+    pub fn start(
+        &self,
+        source: EventDispatcher,
+        pointer_id: PointerId,
+        data: DragData,
+        preview: Option<DragPreview>,
+    ) {
+        self.cancel();
+        *self.session.borrow_mut() =
+            Some(DragSession::new(data, Some(source), pointer_id, preview));
+    }
+
+    pub fn is_dragging(&self, pointer_id: PointerId) -> bool {
+        matches!(&*self.session.borrow(), Some(s) if s.pointer_id == pointer_id)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.session.borrow().is_some()
+    }
+
+    pub fn hover_file(&self, path: PathBuf) {
+        let mut guard = self.session.borrow_mut();
+
+        match &mut *guard {
+            Some(session) if session.source.is_none() => {
+                let mut files = files_of(&session.data);
+                if !files.contains(&path) {
+                    files.push(path);
+                    session.data = DragData::new(DraggedFiles(files));
+                }
+            }
+            Some(_) => {}
+            None => {
+                self.dropped_files.borrow_mut().clear();
+                *guard = Some(DragSession::new(
+                    DragData::new(DraggedFiles(vec![path])),
+                    None,
+                    PointerId::Mouse,
+                    None,
+                ));
+            }
+        }
+    }
+
+    pub fn drop_file(&self, path: PathBuf) {
+        let mut guard = self.session.borrow_mut();
+
+        match &mut *guard {
+            Some(session) if session.source.is_none() => {
+                let mut files = files_of(&session.data);
+                if !files.contains(&path) {
+                    files.push(path);
+                    session.data = DragData::new(DraggedFiles(files));
+                }
+                session.pending_drop = true;
+            }
+            Some(_) => {}
+            None => {
+                if self.dropped_files.borrow().contains(&path) {
+                    return;
+                }
+
+                let mut session = DragSession::new(
+                    DragData::new(DraggedFiles(vec![path])),
+                    None,
+                    PointerId::Mouse,
+                    None,
+                );
+                session.pending_drop = true;
+                *guard = Some(session);
+            }
+        }
+    }
+
+    pub fn pending_drop(&self) -> bool {
+        matches!(&*self.session.borrow(), Some(s) if s.pending_drop)
+    }
+
+    /// Update elements under the dragging pointer, innermost first, along
+    /// with their drop filters.
+    pub fn update(
+        &self,
+        targets: Vec<(EventDispatcher, Option<DropFilter>)>,
+        position: Option<Point>,
+    ) {
+        let mut guard = self.session.borrow_mut();
+        let Some(session) = &mut *guard
+        else {
+            return;
+        };
+
+        let targets = match position {
+            Some(_) => targets,
+            None => Vec::new(),
+        };
+
+        let moved = session.position != position;
+        if moved && session.preview.is_some() {
+            self.preview_changed.set(true);
+        }
+        session.position = position;
+
+        let old_hovering = std::mem::take(&mut session.hovering);
+        for (ed, acceptor) in &old_hovering {
+            if !targets.iter().any(|(t, _)| t.is_same(ed)) {
+                ed.emit_trusted(DragLeave {
+                    data: session.data.clone(),
+                });
+                acceptor.reject();
+            }
+        }
+
+        let Some(position) = position
+        else {
+            return;
+        };
+
+        for (ed, filter) in targets {
+            match old_hovering.iter().find(|(old, _)| old.is_same(&ed)) {
+                Some((_, acceptor)) => {
+                    if moved {
+                        ed.emit_trusted(DragOver {
+                            data: session.data.clone(),
+                            position,
+                            acceptor: acceptor.clone(),
+                        });
+                    }
+                    session.hovering.push((ed, acceptor.clone()));
+                }
+                None => {
+                    // decided at once by the filter, for the pointer may be
+                    // released before the element receives the event
+                    let acceptor = DropAcceptor::default();
+                    if filter.is_some_and(|filter| filter(&session.data)) {
+                        acceptor.accept();
+                    }
+                    ed.emit_trusted(DragEnter {
+                        data: session.data.clone(),
+                        position,
+                        acceptor: acceptor.clone(),
+                    });
+                    session.hovering.push((ed, acceptor));
+                }
+            }
+        }
+    }
+
+    /// Drop onto the innermost element which accepted, and return whether
+    /// any accepted.
+    pub fn finish(&self) -> bool {
+        let Some(session) = self.session.borrow_mut().take()
+        else {
+            return false;
+        };
+
+        let target = session
+            .hovering
+            .iter()
+            .position(|(_, acceptor)| acceptor.is_accepted());
+
+        for (index, (ed, _)) in session.hovering.iter().enumerate() {
+            match (Some(index) == target, session.position) {
+                (true, Some(position)) => ed.emit_trusted(DragDrop {
+                    data: session.data.clone(),
+                    position,
+                }),
+                _ => ed.emit_trusted(DragLeave {
+                    data: session.data.clone(),
+                }),
+            }
+        }
+
+        if session.source.is_none() {
+            *self.dropped_files.borrow_mut() = files_of(&session.data);
+        }
+        let dropped = target.is_some();
+        self.end(session, dropped);
+        dropped
+    }
+
+    pub fn cancel(&self) {
+        let Some(session) = self.session.borrow_mut().take()
+        else {
+            return;
+        };
+
+        for (ed, _) in &session.hovering {
+            ed.emit_trusted(DragLeave {
+                data: session.data.clone(),
+            });
+        }
+        self.end(session, false);
+    }
+
+    fn end(&self, session: DragSession, dropped: bool) {
+        if let Some(source) = &session.source {
+            source.emit_trusted(DragEnd { dropped });
+        }
+
+        if session.preview.is_some() {
+            self.preview_changed.set(true);
+        }
+    }
+
+    /// Whether the preview moved or ended since the last call, so that the
+    /// window needs to be redrawn.
+    pub fn take_preview_changed(&self) -> bool {
+        self.preview_changed.replace(false)
+    }
+
+    pub fn draw_preview(&self, canvas: &mut Canvas) {
+        if let Some(DragSession {
+            preview: Some(preview),
+            position: Some(position),
+            ..
+        }) = &*self.session.borrow()
+        {
+            canvas.reset_matrix();
+            preview(canvas, *position);
+        }
+    }
+}
+
+impl DragSession {
+    fn new(
+        data: DragData,
+        source: Option<EventDispatcher>,
+        pointer_id: PointerId,
+        preview: Option<DragPreview>,
+    ) -> Self {
+        DragSession {
+            data,
+            source,
+            pointer_id,
+            preview,
+            position: None,
+            hovering: Vec::new(),
+            pending_drop: false,
+        }
+    }
+}
+
+fn files_of(data: &DragData) -> Vec<PathBuf> {
+    data.downcast_ref::<DraggedFiles>()
+        .map(|files| files.0.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+const POSITION: Point = Point(crate::primitive::Pixel(10.0), crate::primitive::Pixel(10.0));
+
+#[test]
+fn test_filter_accepts_at_enter() {
+    use std::rc::Rc;
+
+    let drag = DragMgr::new();
+    drag.start(
+        EventDispatcher::new(),
+        PointerId::Mouse,
+        DragData::new(1u32),
+        None,
+    );
+
+    // released in the frame it entered, before the target receives any event
+    let filter: DropFilter = Rc::new(|data| data.is::<u32>());
+    drag.update(vec![(EventDispatcher::new(), Some(filter))], Some(POSITION));
+    assert!(drag.finish());
+    assert!(!drag.is_active());
+}
+
+#[test]
+fn test_acceptor_without_filter() {
+    let drag = DragMgr::new();
+    drag.start(
+        EventDispatcher::new(),
+        PointerId::Mouse,
+        DragData::new(1u32),
+        None,
+    );
+    drag.update(vec![(EventDispatcher::new(), None)], Some(POSITION));
+    assert!(!drag.finish());
+
+    drag.start(
+        EventDispatcher::new(),
+        PointerId::Mouse,
+        DragData::new(1u32),
+        None,
+    );
+    drag.update(vec![(EventDispatcher::new(), None)], Some(POSITION));
+    if let Some(session) = &*drag.session.borrow() {
+        session.hovering[0].1.accept();
+    }
+    assert!(drag.finish());
+}
+
+#[test]
+fn test_drop_files() {
+    use std::rc::Rc;
+
+    let drag = DragMgr::new();
+    drag.hover_file(PathBuf::from("a.txt"));
+    drag.drop_file(PathBuf::from("a.txt"));
+    assert!(drag.pending_drop());
+
+    let filter: DropFilter = Rc::new(|data| data.is::<DraggedFiles>());
+    drag.update(vec![(EventDispatcher::new(), Some(filter))], Some(POSITION));
+    assert!(drag.finish());
+
+    // winit emits the file once more after the drop
+    drag.drop_file(PathBuf::from("a.txt"));
+    assert!(!drag.pending_drop());
+}
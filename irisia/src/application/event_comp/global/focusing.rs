@@ -1,11 +1,20 @@
 use std::sync::{Arc, Mutex as StdMutex};
 
+use smallvec::SmallVec;
+
 use crate::event::{
     standard::{Blured, Focused},
     EventDispatcher,
 };
 
-pub struct Focusing(Arc<StdMutex<Option<EventDispatcher>>>);
+pub struct Focusing(Arc<StdMutex<FocusState>>);
+
+#[derive(Default)]
+struct FocusState {
+    focused: Option<EventDispatcher>,
+    /// Elements capturing keys, the last one receives them
+    key_captures: Vec<EventDispatcher>,
+}
 
 impl Focusing {
     pub fn new() -> Self {
@@ -15,27 +24,57 @@ impl Focusing {
     pub fn focus(&self, ed: EventDispatcher) {
         let mut guard = self.0.lock().unwrap();
 
-        match &*guard {
+        match &guard.focused {
             Some(old_ed) if ed.ptr_eq(old_ed) => {}
             _ => {
-                blur(&mut guard);
+                blur(&mut guard.focused);
                 ed.emit_trusted(Focused);
-                *guard = Some(ed);
+                guard.focused = Some(ed);
             }
         }
     }
 
+    pub fn focused(&self) -> Option<EventDispatcher> {
+        self.0.lock().unwrap().focused.clone()
+    }
+
     pub fn blur(&self) {
-        blur(&mut self.0.lock().unwrap())
+        blur(&mut self.0.lock().unwrap().focused)
     }
 
     pub fn blur_checked(&self, ed: &EventDispatcher) {
         let mut guard = self.0.lock().unwrap();
-        if let Some(focused) = &mut *guard {
+        if let Some(focused) = &guard.focused {
             if focused.is_same(ed) {
-                blur(&mut guard);
+                blur(&mut guard.focused);
+            }
+        }
+    }
+
+    /// Let keys go to `ed` as well as the focused element, until released.
+    /// Captured again, `ed` becomes the latest.
+    pub fn capture_keys(&self, ed: EventDispatcher) {
+        let mut guard = self.0.lock().unwrap();
+        guard.key_captures.retain(|captured| !captured.is_same(&ed));
+        guard.key_captures.push(ed);
+    }
+
+    pub fn release_keys(&self, ed: &EventDispatcher) {
+        let mut guard = self.0.lock().unwrap();
+        guard.key_captures.retain(|captured| !captured.is_same(ed));
+    }
+
+    /// Elements to emit keyboard events to: the focused element, and the
+    /// one capturing keys last.
+    pub fn key_targets(&self) -> SmallVec<[EventDispatcher; 2]> {
+        let guard = self.0.lock().unwrap();
+        let mut targets: SmallVec<[EventDispatcher; 2]> = guard.focused.iter().cloned().collect();
+        if let Some(captured) = guard.key_captures.last() {
+            if !targets.iter().any(|focused| focused.is_same(captured)) {
+                targets.push(captured.clone());
             }
         }
+        targets
     }
 }
 
@@ -44,3 +83,41 @@ fn blur(ed: &mut Option<EventDispatcher>) {
         ed.emit_trusted(Blured);
     }
 }
+
+#[test]
+fn test_key_targets() {
+    let focusing = Focusing::new();
+    let dialog = EventDispatcher::new();
+    let content = EventDispatcher::new();
+    assert!(focusing.key_targets().is_empty());
+
+    // a child of a modal is focused, but the modal still needs Escape and Tab
+    focusing.capture_keys(dialog.clone());
+    focusing.focus(content.clone());
+    let targets = focusing.key_targets();
+    assert_eq!(targets.len(), 2);
+    assert!(targets[0].is_same(&content) && targets[1].is_same(&dialog));
+
+    focusing.focus(dialog.clone());
+    assert_eq!(focusing.key_targets().len(), 1);
+
+    focusing.release_keys(&dialog);
+    focusing.focus(content.clone());
+    let targets = focusing.key_targets();
+    assert_eq!(targets.len(), 1);
+    assert!(targets[0].is_same(&content));
+}
+
+#[test]
+fn test_latest_key_capture() {
+    let focusing = Focusing::new();
+    let outer = EventDispatcher::new();
+    let inner = EventDispatcher::new();
+
+    focusing.capture_keys(outer.clone());
+    focusing.capture_keys(inner.clone());
+    assert!(focusing.key_targets()[0].is_same(&inner));
+
+    focusing.release_keys(&inner);
+    assert!(focusing.key_targets()[0].is_same(&outer));
+}
@@ -1,27 +1,56 @@
+use std::collections::HashMap;
+
 use irisia_backend::{
-    winit::event::{ElementState, MouseButton, Touch, TouchPhase},
+    winit::{
+        event::{ElementState, Force, ModifiersState, MouseButton, Touch, TouchPhase},
+        window::CursorIcon,
+    },
     StaticWindowEvent,
 };
+use smallvec::{smallvec, SmallVec};
 
 use crate::{
     application::content::GlobalContent,
     event::{
         standard::{
-            CloseRequested, PointerDown, PointerEntered, PointerMove, PointerOut, PointerUp,
+            CloseRequested, PointerDown, PointerEntered, PointerId, PointerKind, PointerMove,
+            PointerOut, PointerUp, Wheel,
         },
         EventDispatcher,
     },
-    primitive::{Pixel, Point},
+    primitive::Point,
 };
 
-use self::new_event::{NewPointerEvent, PointerStateChange};
+use self::new_event::{NewPointerEvent, PointerInfo, PointerStateChange};
 
+pub(crate) mod capture;
+pub(crate) mod drag;
 pub(crate) mod focusing;
 pub(crate) mod new_event;
 
 pub(crate) struct GlobalEventMgr {
-    last_cursor_position: Option<Point>,
-    pointer_state: PointerState,
+    pointers: HashMap<PointerId, PointerRecord>,
+    modifiers: ModifiersState,
+    cursor_icon: CursorIcon,
+}
+
+struct PointerRecord {
+    position: Option<Point>,
+    /// Kept after the pointer left the viewport
+    last_position: Option<Point>,
+    state: PointerState,
+    pressed_buttons: SmallVec<[MouseButton; 2]>,
+}
+
+impl Default for PointerRecord {
+    fn default() -> Self {
+        PointerRecord {
+            position: None,
+            last_position: None,
+            state: PointerState::OutOfViewport,
+            pressed_buttons: SmallVec::new(),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -31,11 +60,14 @@ pub(crate) enum PointerState {
     OutOfViewport,
 }
 
+type PointerBehavior = (PointerInfo, Option<Point>, PointerState, PointerStateChange);
+
 impl GlobalEventMgr {
     pub fn new() -> Self {
         GlobalEventMgr {
-            last_cursor_position: None,
-            pointer_state: PointerState::OutOfViewport,
+            pointers: HashMap::new(),
+            modifiers: ModifiersState::empty(),
+            cursor_icon: CursorIcon::Default,
         }
     }
 
@@ -45,15 +77,37 @@ impl GlobalEventMgr {
         event: StaticWindowEvent,
         gc: &'a GlobalContent,
     ) -> Option<NewPointerEvent<'a>> {
-        match cursor_behavior(&event, self.pointer_state, self.last_cursor_position) {
-            Some((new_position, new_pointer_state)) => {
-                let npe = NewPointerEvent::new(event, self, gc, new_position, new_pointer_state);
-                emit_physical_pointer_event(
-                    &gc.global_ed,
+        match &event {
+            StaticWindowEvent::ModifiersChanged(modifiers) => self.modifiers = *modifiers,
+            StaticWindowEvent::HoveredFile(path) => gc.drag.hover_file(path.clone()),
+            StaticWindowEvent::DroppedFile(path) => gc.drag.drop_file(path.clone()),
+            StaticWindowEvent::HoveredFileCancelled => gc.drag.cancel(),
+            _ => {}
+        }
+
+        let behavior = match &event {
+            StaticWindowEvent::Touch(touch) => self.touch_behavior(touch),
+            _ => self.mouse_behavior(&event),
+        };
+
+        match behavior {
+            Some((pointer, new_position, new_pointer_state, change)) => {
+                if let StaticWindowEvent::HoveredFile(_) | StaticWindowEvent::DroppedFile(_) =
+                    &event
+                {
+                    gc.global_ed.emit_trusted(event.clone());
+                }
+
+                let npe = NewPointerEvent::new(
+                    event,
+                    self,
+                    gc,
+                    pointer,
                     new_position,
-                    npe.cursor_delta,
-                    npe.pointer_state_change,
+                    new_pointer_state,
+                    change,
                 );
+                emit_physical_pointer_event(&gc.global_ed, &npe);
                 Some(npe)
             }
             None => {
@@ -61,104 +115,273 @@ impl GlobalEventMgr {
                     StaticWindowEvent::CloseRequested => {
                         gc.global_ed.emit_trusted(CloseRequested(gc.close_handle))
                     }
+                    // the cursor was never in the window, so no element can receive it
+                    StaticWindowEvent::DroppedFile(_) if gc.drag.pending_drop() => {
+                        gc.drag.finish();
+                    }
                     _ => {}
                 }
+                gc.redraw_drag_preview();
+
+                if let StaticWindowEvent::KeyboardInput { .. }
+                | StaticWindowEvent::ReceivedCharacter(_)
+                | StaticWindowEvent::Ime(_)
+                | StaticWindowEvent::ModifiersChanged(_) = &event
+                {
+                    for target in gc.focusing.key_targets() {
+                        target.emit_trusted(event.clone());
+                    }
+                }
 
                 gc.global_ed.emit_trusted(event);
                 None
             }
         }
     }
+
+    fn mouse_behavior(&mut self, event: &StaticWindowEvent) -> Option<PointerBehavior> {
+        let record = self.pointers.entry(PointerId::Mouse).or_default();
+        let old_state = record.state;
+        let old_position = record.position;
+        let entering = matches!(old_state, PointerState::OutOfViewport);
+
+        let behavior = match event {
+            StaticWindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button,
+                ..
+            } => {
+                if !record.pressed_buttons.contains(button) {
+                    record.pressed_buttons.push(*button);
+                }
+
+                if entering {
+                    (
+                        old_position,
+                        PointerState::Pressing,
+                        PointerStateChange::EnterViewport,
+                    )
+                } else {
+                    (
+                        old_position,
+                        PointerState::Pressing,
+                        PointerStateChange::Press(*button),
+                    )
+                }
+            }
+
+            StaticWindowEvent::MouseInput {
+                state: ElementState::Released,
+                button,
+                ..
+            } => {
+                record.pressed_buttons.retain(|b| b != button);
+                let new_state = pressed_state(&record.pressed_buttons);
+
+                if entering {
+                    (old_position, new_state, PointerStateChange::EnterViewport)
+                } else {
+                    (
+                        old_position,
+                        new_state,
+                        PointerStateChange::Release(*button),
+                    )
+                }
+            }
+
+            StaticWindowEvent::CursorMoved { position, .. } => {
+                let new_position = Some(Point::from(*position));
+                if entering {
+                    let new_state = pressed_state(&record.pressed_buttons);
+                    (new_position, new_state, PointerStateChange::EnterViewport)
+                } else {
+                    (new_position, old_state, PointerStateChange::Unchange)
+                }
+            }
+
+            StaticWindowEvent::MouseWheel { delta, .. } => {
+                if entering {
+                    return None;
+                }
+                (old_position, old_state, PointerStateChange::Wheel(*delta))
+            }
+
+            // hit-test again to route files dragged from outside. Some platforms
+            // report no cursor position during the drag, so the files go to
+            // where the cursor was seen last
+            StaticWindowEvent::HoveredFile(_) | StaticWindowEvent::DroppedFile(_) => {
+                let position = Some(old_position.or(record.last_position)?);
+                (position, old_state, PointerStateChange::Probe)
+            }
+
+            StaticWindowEvent::CursorLeft { .. } => {
+                if entering {
+                    return None;
+                }
+                (
+                    None,
+                    PointerState::OutOfViewport,
+                    PointerStateChange::LeaveViewport,
+                )
+            }
+
+            _ => return None,
+        };
+
+        Some((PointerInfo::MOUSE, behavior.0, behavior.1, behavior.2))
+    }
+
+    fn touch_behavior(&mut self, touch: &Touch) -> Option<PointerBehavior> {
+        let Touch {
+            phase,
+            location,
+            force,
+            id,
+            ..
+        } = *touch;
+
+        let pointer = PointerInfo {
+            id: PointerId::Touch(id),
+            kind: match force {
+                // only styluses report the altitude angle
+                Some(Force::Calibrated {
+                    altitude_angle: Some(_),
+                    ..
+                }) => PointerKind::Pen,
+                _ => PointerKind::Touch,
+            },
+            force,
+        };
+        let position = Some(Point::from(location));
+
+        let behavior = match phase {
+            TouchPhase::Started => {
+                let record = self.pointers.entry(pointer.id).or_default();
+                record.pressed_buttons = smallvec![MouseButton::Left];
+                (
+                    position,
+                    PointerState::Pressing,
+                    PointerStateChange::Press(MouseButton::Left),
+                )
+            }
+            TouchPhase::Moved => {
+                let record = self.pointers.get(&pointer.id)?;
+                (position, record.state, PointerStateChange::Unchange)
+            }
+            TouchPhase::Ended => {
+                let record = self.pointers.get_mut(&pointer.id)?;
+                record.pressed_buttons.clear();
+                (
+                    position,
+                    PointerState::Release,
+                    PointerStateChange::Release(MouseButton::Left),
+                )
+            }
+            TouchPhase::Cancelled => {
+                self.pointers.get(&pointer.id)?;
+                (
+                    None,
+                    PointerState::OutOfViewport,
+                    PointerStateChange::LeaveViewport,
+                )
+            }
+        };
+
+        Some((pointer, behavior.0, behavior.1, behavior.2))
+    }
 }
 
-fn emit_physical_pointer_event(
-    ed: &EventDispatcher,
-    position: Option<Point>,
-    delta: Option<(Pixel, Pixel)>,
-    new_pointer_state: PointerStateChange,
-) {
-    match (new_pointer_state, position) {
-        (PointerStateChange::EnterViewport, _) => ed.emit_trusted(PointerEntered),
-        (PointerStateChange::Press, Some(position)) => ed.emit_trusted(PointerDown {
+fn pressed_state(buttons: &[MouseButton]) -> PointerState {
+    if buttons.is_empty() {
+        PointerState::Release
+    } else {
+        PointerState::Pressing
+    }
+}
+
+fn emit_physical_pointer_event(ed: &EventDispatcher, npe: &NewPointerEvent) {
+    let PointerInfo {
+        id: pointer_id,
+        kind: pointer_kind,
+        force,
+    } = npe.pointer;
+    let modifiers = npe.modifiers;
+
+    match (npe.pointer_state_change, npe.new_position) {
+        (PointerStateChange::EnterViewport, _) => ed.emit_trusted(PointerEntered { pointer_id }),
+        (PointerStateChange::Press(button), Some(position)) => ed.emit_trusted(PointerDown {
             is_current: false,
             position,
+            button,
+            modifiers,
+            pointer_id,
+            pointer_kind,
+            force,
         }),
         (PointerStateChange::Unchange, Some(position)) => ed.emit_trusted(PointerMove {
             is_current: false,
-            delta: delta.unwrap_or_default(),
+            delta: npe.cursor_delta.unwrap_or_default(),
             position,
+            modifiers,
+            pointer_id,
+            pointer_kind,
+            force,
         }),
-        (PointerStateChange::Release, Some(position)) => ed.emit_trusted(PointerUp {
+        (PointerStateChange::Release(button), Some(position)) => {
+            ed.emit_trusted(PointerUp {
+                is_current: false,
+                position,
+                button,
+                modifiers,
+                pointer_id,
+                pointer_kind,
+                force,
+            });
+
+            if npe.pointer_lifted() {
+                ed.emit_trusted(PointerOut { pointer_id });
+            }
+        }
+        (PointerStateChange::Wheel(delta), Some(position)) => ed.emit_trusted(Wheel {
             is_current: false,
+            delta: delta.into(),
             position,
+            modifiers,
         }),
-        (PointerStateChange::LeaveViewport, None) => ed.emit_trusted(PointerOut),
+        (PointerStateChange::LeaveViewport, None) => ed.emit_trusted(PointerOut { pointer_id }),
+        (PointerStateChange::Probe, _) => {}
         _ => {
             unreachable!("unexpected new-pointer-state and optioned position combination")
         }
     }
 }
 
-fn cursor_behavior(
-    event: &StaticWindowEvent,
-    old_state: PointerState,
-    old_position: Option<Point>,
-) -> Option<(Option<Point>, PointerState)> {
-    let mut new_pointer_state = old_state;
-
-    let mut new_position: Option<Point> = match &event {
-        StaticWindowEvent::Touch(touch) => Some(touch.location.into()),
-        _ => old_position,
-    };
-
-    match event {
-        StaticWindowEvent::MouseInput {
-            state: ElementState::Pressed,
-            button: MouseButton::Left,
-            ..
-        }
-        | StaticWindowEvent::Touch(Touch {
-            phase: TouchPhase::Started,
-            ..
-        }) => {
-            new_pointer_state = PointerState::Pressing;
-        }
+#[test]
+fn test_drop_files_out_of_viewport() {
+    use std::path::PathBuf;
 
-        StaticWindowEvent::CursorMoved { position, .. } => {
-            if let PointerState::OutOfViewport = new_pointer_state {
-                new_pointer_state = PointerState::Release;
-            }
-            new_position = Some(Point::from(*position))
-        }
+    use crate::primitive::Pixel;
 
-        StaticWindowEvent::Touch(Touch {
-            phase: TouchPhase::Moved,
-            ..
-        }) => {}
+    let dropped = StaticWindowEvent::DroppedFile(PathBuf::from("a.txt"));
+    let mut gem = GlobalEventMgr::new();
+    assert!(gem.mouse_behavior(&dropped).is_none());
 
-        StaticWindowEvent::MouseInput {
-            state: ElementState::Released,
-            button: MouseButton::Left,
-            ..
-        }
-        | StaticWindowEvent::Touch(Touch {
-            phase: TouchPhase::Ended,
-            ..
-        }) => {
-            new_pointer_state = PointerState::Release;
-        }
-
-        StaticWindowEvent::CursorLeft { .. }
-        | StaticWindowEvent::Touch(Touch {
-            phase: TouchPhase::Cancelled,
-            ..
-        }) => {
-            new_position.take();
-            new_pointer_state = PointerState::OutOfViewport;
-        }
-
-        _ => return None,
-    }
-
-    Some((new_position, new_pointer_state))
+    // the cursor left the window before the files were dragged in
+    let position = Point(Pixel(10.0), Pixel(20.0));
+    gem.pointers.insert(
+        PointerId::Mouse,
+        PointerRecord {
+            last_position: Some(position),
+            ..Default::default()
+        },
+    );
+    assert!(matches!(
+        gem.mouse_behavior(&dropped),
+        Some((
+            _,
+            Some(found),
+            PointerState::OutOfViewport,
+            PointerStateChange::Probe
+        )) if found == position
+    ));
 }
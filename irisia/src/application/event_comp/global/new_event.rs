@@ -1,10 +1,20 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 
-use irisia_backend::StaticWindowEvent;
+use irisia_backend::{
+    winit::{
+        event::{Force, ModifiersState, MouseButton, MouseScrollDelta},
+        window::CursorIcon,
+    },
+    StaticWindowEvent,
+};
 
 use crate::{
     application::content::GlobalContent,
-    event::EventDispatcher,
+    dom::DropFilter,
+    event::{
+        standard::{PointerId, PointerKind},
+        EventDispatcher,
+    },
     primitive::{Pixel, Point},
 };
 
@@ -14,11 +24,34 @@ pub struct NewPointerEvent<'a> {
     pub(crate) event: StaticWindowEvent,
     pub(crate) gem: &'a mut GlobalEventMgr,
     pub(crate) global_content: &'a GlobalContent,
+    pub(crate) pointer: PointerInfo,
     pub(crate) new_position: Option<Point>,
     pub(crate) cursor_delta: Option<(Pixel, Pixel)>,
+    pub(crate) modifiers: ModifiersState,
     new_focused: Cell<NewFocused>,
     pub(crate) new_pointer_state: PointerState,
     pub(crate) pointer_state_change: PointerStateChange,
+    dragging: bool,
+    drag_targets: RefCell<Vec<(EventDispatcher, Option<DropFilter>)>>,
+    cursor_icon: Cell<Option<CursorIcon>>,
+    occluded: Cell<bool>,
+    wheel_consumed: Cell<bool>,
+    hidden: Cell<bool>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PointerInfo {
+    pub id: PointerId,
+    pub kind: PointerKind,
+    pub force: Option<Force>,
+}
+
+impl PointerInfo {
+    pub const MOUSE: Self = PointerInfo {
+        id: PointerId::Mouse,
+        kind: PointerKind::Mouse,
+        force: None,
+    };
 }
 
 enum NewFocused {
@@ -30,10 +63,13 @@ enum NewFocused {
 #[derive(Clone, Copy, Debug)]
 pub(crate) enum PointerStateChange {
     Unchange,
-    Press,
-    Release,
+    Press(MouseButton),
+    Release(MouseButton),
+    Wheel(MouseScrollDelta),
     LeaveViewport,
     EnterViewport,
+    /// Nothing changed, but elements under the pointer need to be found.
+    Probe,
 }
 
 impl<'a> NewPointerEvent<'a> {
@@ -41,24 +77,33 @@ impl<'a> NewPointerEvent<'a> {
         event: StaticWindowEvent,
         gem: &'a mut GlobalEventMgr,
         gc: &'a GlobalContent,
+        pointer: PointerInfo,
         new_position: Option<Point>,
         new_pointer_state: PointerState,
+        pointer_state_change: PointerStateChange,
     ) -> Self {
         let cursor_delta = gem
-            .last_cursor_position
+            .pointers
+            .get(&pointer.id)
+            .and_then(|record| record.position)
             .zip(new_position)
             .map(|(old, new)| (new.0 - old.0, new.1 - old.1));
 
         NewPointerEvent {
             event,
+            pointer,
             new_position,
             cursor_delta,
             new_focused: Cell::new(NewFocused::Unchanged),
             new_pointer_state,
-            pointer_state_change: PointerStateChange::difference_between(
-                gem.pointer_state,
-                new_pointer_state,
-            ),
+            pointer_state_change,
+            modifiers: gem.modifiers,
+            dragging: gc.drag.is_dragging(pointer.id),
+            drag_targets: RefCell::new(Vec::new()),
+            cursor_icon: Cell::new(None),
+            occluded: Cell::new(false),
+            wheel_consumed: Cell::new(false),
+            hidden: Cell::new(false),
             gem,
             global_content: gc,
         }
@@ -70,34 +115,144 @@ impl<'a> NewPointerEvent<'a> {
             None => NewFocused::Blur,
         });
     }
-}
 
-impl PointerStateChange {
-    fn difference_between(old: PointerState, new: PointerState) -> Self {
-        use PointerState::*;
-
-        match (old, new) {
-            (Release, Pressing) => Self::Press,
-            (Pressing, Release) => Self::Release,
-            (OutOfViewport, Pressing | Release) => Self::EnterViewport,
-            (Pressing | Release, OutOfViewport) => Self::LeaveViewport,
-            (Release, Release) | (Pressing, Pressing) | (OutOfViewport, OutOfViewport) => {
-                Self::Unchange
+    /// Register an element under the pointer as a drop target candidate,
+    /// with its drop filter. Inner elements should be registered first.
+    pub(crate) fn hover_drag_target<F>(&self, ed: &EventDispatcher, filter: F)
+    where
+        F: FnOnce() -> Option<DropFilter>,
+    {
+        if self.dragging {
+            self.drag_targets.borrow_mut().push((ed.clone(), filter()));
+        }
+    }
+
+    /// Whether files dragged from outside are routed by the last position
+    /// of the cursor, which is out of the viewport. Elements there are
+    /// only found as drop targets, not entered by the pointer.
+    pub(crate) fn probing_outside(&self) -> bool {
+        matches!(
+            (self.pointer_state_change, self.new_pointer_state),
+            (PointerStateChange::Probe, PointerState::OutOfViewport)
+        )
+    }
+
+    /// Offer the cursor icon of an element under the mouse. Inner elements
+    /// should offer first, and the first icon offered will be used.
+    pub(crate) fn hover_cursor_icon<F>(&self, f: F)
+    where
+        F: FnOnce() -> Option<CursorIcon>,
+    {
+        if self.pointer.id == PointerId::Mouse && self.cursor_icon.get().is_none() {
+            self.cursor_icon.set(f());
+        }
+    }
+
+    /// Let the pointer be covered by an overlay, so that elements
+    /// handling the event later can no longer be physically entered.
+    pub(crate) fn occlude(&self) {
+        self.occluded.set(true);
+    }
+
+    pub(crate) fn occluded(&self) -> bool {
+        self.occluded.get()
+    }
+
+    /// Stop the wheel scrolling from being emitted to elements handling
+    /// the event later, which are the ancestors of the consumer.
+    pub(crate) fn consume_wheel(&self) {
+        self.wheel_consumed.set(true);
+    }
+
+    pub(crate) fn wheel_consumed(&self) -> bool {
+        self.wheel_consumed.get()
+    }
+
+    /// Run `f` for a subtree, which is hidden if `hidden` or inside a
+    /// hidden subtree. Elements in a hidden subtree are told to be out.
+    pub(crate) fn hide_subtree<R>(&self, hidden: bool, f: impl FnOnce() -> R) -> R {
+        let outer = self.hidden.replace(self.hidden.get() || hidden);
+        let ret = f();
+        self.hidden.set(outer);
+        ret
+    }
+
+    pub(crate) fn hidden(&self) -> bool {
+        self.hidden.get()
+    }
+
+    fn update_cursor_icon(&mut self) {
+        if self.pointer.id != PointerId::Mouse || self.probing_outside() {
+            return;
+        }
+
+        let icon = self.cursor_icon.get().unwrap_or_default();
+        if self.gem.cursor_icon != icon {
+            self.gem.cursor_icon = icon;
+            self.global_content.window.set_cursor_icon(icon);
+        }
+    }
+
+    fn update_drag(&self) {
+        let drag = &self.global_content.drag;
+        drag.update(self.drag_targets.take(), self.new_position);
+
+        match self.pointer_state_change {
+            PointerStateChange::Release(_) => {
+                drag.finish();
+            }
+            PointerStateChange::LeaveViewport => drag.cancel(),
+            PointerStateChange::Probe if drag.pending_drop() => {
+                drag.finish();
             }
+            _ => {}
         }
+        self.global_content.redraw_drag_preview();
+    }
+
+    /// Whether a finger or stylus leaves the screen, after which
+    /// the pointer id is no longer valid.
+    pub(crate) fn pointer_lifted(&self) -> bool {
+        self.pointer.id != PointerId::Mouse
+            && matches!(self.pointer_state_change, PointerStateChange::Release(_))
     }
 }
 
 impl Drop for NewPointerEvent<'_> {
     fn drop(&mut self) {
-        self.gem.last_cursor_position = self.new_position;
-
         match self.new_focused.replace(NewFocused::Unchanged) {
             NewFocused::Unchanged => (),
             NewFocused::ChangeTo(ed) => self.global_content.focusing.focus(ed),
             NewFocused::Blur => self.global_content.focusing.blur(),
         }
 
-        self.gem.pointer_state = self.new_pointer_state;
+        if self.dragging {
+            self.update_drag();
+        }
+
+        self.update_cursor_icon();
+
+        let released = matches!(
+            self.new_pointer_state,
+            PointerState::Release | PointerState::OutOfViewport
+        );
+
+        if let PointerStateChange::Release(_) | PointerStateChange::LeaveViewport =
+            self.pointer_state_change
+        {
+            if released {
+                self.global_content.pointer_capture.release(self.pointer.id);
+            }
+        }
+
+        if self.pointer.id != PointerId::Mouse && released {
+            self.gem.pointers.remove(&self.pointer.id);
+            return;
+        }
+
+        let record = self.gem.pointers.entry(self.pointer.id).or_default();
+        record.position = self.new_position;
+        record.last_position = self.new_position.or(record.last_position);
+        record.state = self.new_pointer_state;
     }
 }
@@ -1,19 +1,32 @@
+use irisia_backend::winit::event::MouseButton;
+use smallvec::SmallVec;
+
 use crate::{
     event::{
         standard::{
-            PointerDown, PointerEntered, PointerLeft, PointerMove, PointerOut, PointerOver,
-            PointerUp,
+            Click, PointerDown, PointerEntered, PointerId, PointerLeft, PointerMove, PointerOut,
+            PointerOver, PointerUp, Wheel,
         },
         EventDispatcher,
     },
-    primitive::{Pixel, Point, Region},
+    primitive::{Point, Region},
 };
 
-use super::{global::new_event::PointerStateChange, NewPointerEvent};
+use super::{
+    global::{capture::PointerCapture, new_event::PointerStateChange},
+    NewPointerEvent,
+};
 
 pub(crate) struct NodeEventMgr {
     ed: EventDispatcher,
+    pointers: SmallVec<[PointerTrack; 1]>,
+}
+
+/// Entering state of a single pointer on this element.
+struct PointerTrack {
+    pointer_id: PointerId,
     current_state: State,
+    pressed_buttons: SmallVec<[MouseButton; 2]>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -27,7 +40,7 @@ impl NodeEventMgr {
     pub fn new(ed: EventDispatcher) -> Self {
         Self {
             ed,
-            current_state: State::Untracked,
+            pointers: SmallVec::new(),
         }
     }
 
@@ -37,65 +50,197 @@ impl NodeEventMgr {
         region: Option<Region>,
         logically_entered: bool,
     ) -> bool {
+        let pointer_id = update.pointer.id;
+        let index = match self
+            .pointers
+            .iter()
+            .position(|t| t.pointer_id == pointer_id)
+        {
+            Some(index) => index,
+            None => {
+                self.pointers.push(PointerTrack::new(pointer_id));
+                self.pointers.len() - 1
+            }
+        };
+
+        let track = &mut self.pointers[index];
+        let entered = track.update_and_emit(&self.ed, update, region, logically_entered);
+
+        if track.is_idle() {
+            self.pointers.swap_remove(index);
+        }
+        entered
+    }
+}
+
+impl PointerTrack {
+    fn new(pointer_id: PointerId) -> Self {
+        PointerTrack {
+            pointer_id,
+            current_state: State::Untracked,
+            pressed_buttons: SmallVec::new(),
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        matches!(self.current_state, State::Untracked) && self.pressed_buttons.is_empty()
+    }
+
+    fn update_and_emit(
+        &mut self,
+        ed: &EventDispatcher,
+        update: &NewPointerEvent,
+        region: Option<Region>,
+        logically_entered: bool,
+    ) -> bool {
+        let capture = update.global_content.pointer_capture();
+        if update.hidden() {
+            self.hide(ed, capture);
+            return false;
+        }
+
+        if update.probing_outside() {
+            return match (update.new_position, region) {
+                (Some(p), Some(region)) => {
+                    !update.occluded() && p.abs_ge(region.0) && p.abs_le(region.1)
+                }
+                (Some(_), None) => logically_entered,
+                (None, _) => false,
+            };
+        }
+
+        if capture.is_captured(self.pointer_id) {
+            if capture.captured_by(self.pointer_id, ed) {
+                return self.emit_captured(ed, update, region);
+            }
+
+            // the pointer belongs to another element until the capture released
+            self.update_state(ed, State::Untracked);
+            self.forget_released_button(update);
+            return logically_entered;
+        }
+
         let position = match (update.new_position, region) {
-            (Some(p), Some(region)) if p.abs_ge(region.0) && p.abs_le(region.1) => {
-                self.update_state(State::PhysicallyEnter);
+            (Some(p), Some(region))
+                if !update.occluded() && p.abs_ge(region.0) && p.abs_le(region.1) =>
+            {
+                self.update_state(ed, State::PhysicallyEnter);
                 p
             }
             (Some(p), None) if logically_entered => {
-                self.update_state(State::LogicallyEnter);
+                self.update_state(ed, State::LogicallyEnter);
                 p
             }
             _ => {
-                self.update_state(State::Untracked);
+                self.update_state(ed, State::Untracked);
+                self.forget_released_button(update);
                 return false;
             }
         };
 
-        self.emit_physical_pointer_event(
-            update.pointer_state_change,
-            position,
-            update.cursor_delta,
-            logically_entered,
-        );
+        self.emit_physical_pointer_event(ed, update, position, logically_entered);
 
-        self.ed.emit_trusted(update.event.clone());
+        ed.emit_trusted(update.event.clone());
 
-        if let (PointerStateChange::Press, false) = (update.pointer_state_change, logically_entered)
+        if let (PointerStateChange::Press(_), false) =
+            (update.pointer_state_change, logically_entered)
         {
             // TODO: the element may cannot be focused on, set `None` instead.
-            update.focus_on(Some(self.ed.clone()));
+            update.focus_on(Some(ed.clone()));
         }
 
+        self.leave_if_lifted(ed, update);
         true
     }
 
-    fn update_state(&mut self, new_state: State) {
+    fn emit_captured(
+        &mut self,
+        ed: &EventDispatcher,
+        update: &NewPointerEvent,
+        region: Option<Region>,
+    ) -> bool {
+        let Some(position) = update.new_position
+        else {
+            self.update_state(ed, State::Untracked);
+            return false;
+        };
+
+        // captured pointer is always logically entered, no matter where it is
+        let physically_entered = match region {
+            Some(region) => {
+                !update.occluded() && position.abs_ge(region.0) && position.abs_le(region.1)
+            }
+            None => false,
+        };
+        self.update_state(
+            ed,
+            if physically_entered {
+                State::PhysicallyEnter
+            } else {
+                State::LogicallyEnter
+            },
+        );
+
+        self.emit_physical_pointer_event(ed, update, position, false);
+        ed.emit_trusted(update.event.clone());
+
+        if let PointerStateChange::Press(_) = update.pointer_state_change {
+            update.focus_on(Some(ed.clone()));
+        }
+
+        self.leave_if_lifted(ed, update);
+        true
+    }
+
+    /// Hidden elements can neither hold nor be pressed by a pointer, so
+    /// release the capture and forget the buttons, which will never click.
+    fn hide(&mut self, ed: &EventDispatcher, capture: &PointerCapture) {
+        capture.release_checked(self.pointer_id, ed);
+        self.pressed_buttons.clear();
+        self.update_state(ed, State::Untracked);
+    }
+
+    /// A lifted finger will never come back, so leave immediately
+    /// rather than waiting for the next event of this pointer.
+    fn leave_if_lifted(&mut self, ed: &EventDispatcher, update: &NewPointerEvent) {
+        if update.pointer_lifted() {
+            self.pressed_buttons.clear();
+            self.update_state(ed, State::Untracked);
+        }
+    }
+
+    fn forget_released_button(&mut self, update: &NewPointerEvent) {
+        if let PointerStateChange::Release(button) = update.pointer_state_change {
+            self.pressed_buttons.retain(|b| *b != button);
+        }
+    }
+
+    fn update_state(&mut self, ed: &EventDispatcher, new_state: State) {
         use State::*;
 
-        let ed = &self.ed;
+        let pointer_id = self.pointer_id;
         let old_state = std::mem::replace(&mut self.current_state, new_state);
 
         match (old_state, self.current_state) {
             (Untracked, LogicallyEnter) => {
-                ed.emit_trusted(PointerEntered);
+                ed.emit_trusted(PointerEntered { pointer_id });
             }
             (LogicallyEnter, PhysicallyEnter) => {
-                ed.emit_trusted(PointerOver);
+                ed.emit_trusted(PointerOver { pointer_id });
             }
             (Untracked, PhysicallyEnter) => {
-                ed.emit_trusted(PointerEntered);
-                ed.emit_trusted(PointerOver);
+                ed.emit_trusted(PointerEntered { pointer_id });
+                ed.emit_trusted(PointerOver { pointer_id });
             }
             (PhysicallyEnter, Untracked) => {
-                ed.emit_trusted(PointerLeft);
-                ed.emit_trusted(PointerOut);
+                ed.emit_trusted(PointerLeft { pointer_id });
+                ed.emit_trusted(PointerOut { pointer_id });
             }
             (PhysicallyEnter, LogicallyEnter) => {
-                ed.emit_trusted(PointerLeft);
+                ed.emit_trusted(PointerLeft { pointer_id });
             }
             (LogicallyEnter, Untracked) => {
-                ed.emit_trusted(PointerOut);
+                ed.emit_trusted(PointerOut { pointer_id });
             }
             (Untracked, Untracked)
             | (LogicallyEnter, LogicallyEnter)
@@ -104,21 +249,39 @@ impl NodeEventMgr {
     }
 
     fn emit_physical_pointer_event(
-        &self,
-        psc: PointerStateChange,
+        &mut self,
+        ed: &EventDispatcher,
+        update: &NewPointerEvent,
         position: Point,
-        delta: Option<(Pixel, Pixel)>,
         logically_entered: bool,
     ) {
-        match psc {
-            PointerStateChange::EnterViewport { .. } | PointerStateChange::LeaveViewport => {}
-            PointerStateChange::Press => self.ed.emit_trusted(PointerDown {
-                is_current: logically_entered,
-                position,
-            }),
-            PointerStateChange::Unchange => self.ed.emit_trusted(PointerMove {
+        let modifiers = update.modifiers;
+        let pointer_id = self.pointer_id;
+        let pointer_kind = update.pointer.kind;
+        let force = update.pointer.force;
+
+        match update.pointer_state_change {
+            PointerStateChange::EnterViewport
+            | PointerStateChange::LeaveViewport
+            | PointerStateChange::Probe => {}
+            PointerStateChange::Press(button) => {
+                if !self.pressed_buttons.contains(&button) {
+                    self.pressed_buttons.push(button);
+                }
+
+                ed.emit_trusted(PointerDown {
+                    is_current: logically_entered,
+                    position,
+                    button,
+                    modifiers,
+                    pointer_id,
+                    pointer_kind,
+                    force,
+                })
+            }
+            PointerStateChange::Unchange => ed.emit_trusted(PointerMove {
                 is_current: logically_entered,
-                delta: delta.unwrap_or_else(|| {
+                delta: update.cursor_delta.unwrap_or_else(|| {
                     if cfg!(debug_assertions) {
                         unreachable!("delta distance must be exist")
                     } else {
@@ -126,11 +289,73 @@ impl NodeEventMgr {
                     }
                 }),
                 position,
+                modifiers,
+                pointer_id,
+                pointer_kind,
+                force,
             }),
-            PointerStateChange::Release => self.ed.emit_trusted(PointerUp {
+            PointerStateChange::Release(button) => {
+                ed.emit_trusted(PointerUp {
+                    is_current: logically_entered,
+                    position,
+                    button,
+                    modifiers,
+                    pointer_id,
+                    pointer_kind,
+                    force,
+                });
+
+                let pressed_here = self.pressed_buttons.contains(&button);
+                self.pressed_buttons.retain(|b| *b != button);
+                if pressed_here {
+                    ed.emit_trusted(Click {
+                        is_current: logically_entered,
+                        position,
+                        button,
+                        modifiers,
+                        pointer_id,
+                        pointer_kind,
+                        force,
+                    });
+                }
+            }
+            PointerStateChange::Wheel(_) if update.wheel_consumed() => {}
+            PointerStateChange::Wheel(delta) => ed.emit_trusted(Wheel {
                 is_current: logically_entered,
+                delta: delta.into(),
                 position,
+                modifiers,
             }),
         }
     }
 }
+
+#[test]
+fn test_hide_while_hovered() {
+    let ed = EventDispatcher::new();
+    let capture = PointerCapture::new();
+    let mut track = PointerTrack::new(PointerId::Mouse);
+
+    track.update_state(&ed, State::PhysicallyEnter);
+    track.pressed_buttons.push(MouseButton::Left);
+    capture.capture(PointerId::Mouse, ed.clone());
+
+    track.hide(&ed, &capture);
+    assert!(track.is_idle());
+    assert!(!capture.is_captured(PointerId::Mouse));
+}
+
+#[test]
+fn test_hide_keeps_others_capture() {
+    let ed = EventDispatcher::new();
+    let other = EventDispatcher::new();
+    let capture = PointerCapture::new();
+    let mut track = PointerTrack::new(PointerId::Mouse);
+
+    track.update_state(&ed, State::LogicallyEnter);
+    capture.capture(PointerId::Mouse, other.clone());
+
+    track.hide(&ed, &capture);
+    assert!(track.is_idle());
+    assert!(capture.captured_by(PointerId::Mouse, &other));
+}
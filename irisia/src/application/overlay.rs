@@ -0,0 +1,82 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use irisia_backend::skia_safe::Canvas;
+
+use crate::{dom::portal::Portal, Result};
+
+use super::event_comp::NewPointerEvent;
+
+/// Portals mounted above the main element tree. Portals with greater
+/// z-index are painted later and hit-tested earlier, portals with
+/// equal z-index are ordered by mounting time.
+pub(crate) struct Overlay {
+    portals: RefCell<Vec<Weak<Portal>>>,
+}
+
+impl Overlay {
+    pub fn new() -> Self {
+        Overlay {
+            portals: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn mount(&self, portal: &Rc<Portal>) {
+        let mut portals = self.portals.borrow_mut();
+        let ptr = Rc::as_ptr(portal);
+        if !portals.iter().any(|p| p.as_ptr() == ptr) {
+            portals.push(Rc::downgrade(portal));
+        }
+    }
+
+    pub fn unmount(&self, portal: &Portal) {
+        let ptr = portal as *const Portal;
+        self.portals
+            .borrow_mut()
+            .retain(|p| p.as_ptr() != ptr && p.strong_count() != 0);
+    }
+
+    /// Portals in painting order
+    fn sorted(&self) -> Vec<Rc<Portal>> {
+        let mut portals: Vec<Rc<Portal>> = self
+            .portals
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+        portals.sort_by_key(|p| p.z_index());
+        portals
+    }
+
+    /// Whether `portal` is mounted and above all other portals having an owner.
+    pub fn is_top_owned(&self, portal: &Portal) -> bool {
+        self.sorted()
+            .iter()
+            .rev()
+            .find(|p| p.owner().is_some())
+            .is_some_and(|top| std::ptr::eq(&**top, portal))
+    }
+
+    pub fn composite(&self, canvas: &mut Canvas, frame: u64) -> Result<()> {
+        for portal in self.sorted() {
+            portal.composite(canvas, frame)?;
+        }
+        Ok(())
+    }
+
+    /// Emit the event to portals from top to bottom. Once any of them
+    /// entered, elements beneath will regard the pointer as occluded.
+    /// Returns whether any portal entered.
+    pub fn emit_event(&self, npe: &NewPointerEvent) -> bool {
+        let mut entered = false;
+        for portal in self.sorted().iter().rev() {
+            if portal.emit_event(npe) {
+                entered = true;
+                npe.occlude();
+            }
+        }
+        entered
+    }
+}
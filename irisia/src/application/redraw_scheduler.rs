@@ -1,4 +1,10 @@
-use std::{collections::HashMap, rc::Rc, sync::Arc, time::Duration};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::anyhow;
 use irisia_backend::{
@@ -31,10 +37,38 @@ impl RedrawScheduler {
         self.list.insert(Rc::as_ptr(&ro), ro);
     }
 
-    pub fn redraw(&mut self, canvas: &mut Canvas, interval: Duration) -> Result<()> {
+    /// Redraw the requested layers, along with the layers of animated
+    /// elements shown in the last frame. Others are paused until shown.
+    pub fn redraw(
+        &mut self,
+        canvas: &mut Canvas,
+        interval: Duration,
+        animations: &Animations,
+    ) -> Result<()> {
         let mut errors = Vec::new();
         self.redraw_req_sent = false;
 
+        // animations are borrowed shortly, so that they can be stopped while rendering
+        let list = &mut self.list;
+        let frame = animations.frame.get();
+        animations.targets.borrow_mut().retain(|_, target| {
+            let Some(target) = target.upgrade()
+            else {
+                return false;
+            };
+            if !target.shown_in(frame) {
+                return true;
+            }
+
+            match target.animated_layer() {
+                Some(layer) => {
+                    list.insert(Rc::as_ptr(&layer), layer);
+                    true
+                }
+                None => false,
+            }
+        });
+
         for (_, ro) in self.list.drain() {
             canvas.clear(TRANSPARENT);
             canvas.reset_matrix();
@@ -71,3 +105,48 @@ fn fmt_errors(errors: &[anyhow::Error]) -> Result<()> {
 pub(crate) trait RedrawObject {
     fn redraw(&self, canvas: &mut Canvas, interval: Duration) -> Result<()>;
 }
+
+/// Elements redrawn in every frame, keyed by their addresses.
+#[derive(Default)]
+pub(crate) struct Animations {
+    targets: RefCell<HashMap<*const (), Weak<dyn AnimationTarget>>>,
+    /// Number of the frame composited last time
+    frame: Cell<u64>,
+}
+
+impl Animations {
+    pub fn set(&self, target: Weak<dyn AnimationTarget>, animating: bool) {
+        let key = target.as_ptr() as *const ();
+        let mut map = self.targets.borrow_mut();
+        if animating {
+            map.insert(key, target);
+        } else {
+            map.remove(&key);
+        }
+    }
+
+    /// Start compositing a new frame, returns the number of it.
+    pub fn next_frame(&self) -> u64 {
+        self.frame.set(self.frame.get() + 1);
+        self.frame.get()
+    }
+
+    /// Whether any target was shown in the frame `frame`, which is to
+    /// be redrawn in the next frame.
+    pub fn any_shown_in(&self, frame: u64) -> bool {
+        self.targets
+            .borrow()
+            .values()
+            .filter_map(Weak::upgrade)
+            .any(|target| target.shown_in(frame))
+    }
+}
+
+pub(crate) trait AnimationTarget {
+    /// The layer to redraw in the next frame, `None` if the target is gone.
+    fn animated_layer(&self) -> Option<Rc<dyn RedrawObject>>;
+
+    /// Whether the target was seen in the frame `frame`. Targets on
+    /// independent layers are hidden along with their ancestors.
+    fn shown_in(&self, frame: u64) -> bool;
+}
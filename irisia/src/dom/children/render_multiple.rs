@@ -7,7 +7,7 @@ use crate::{
     dom::{layer::LayerRebuilder, DropProtection},
     element::Element,
     primitive::Region,
-    structure::{Visit, VisitLen, Visitor},
+    structure::{slot::Slot, Visit, VisitLen, Visitor},
     style::{style_box::InsideStyleBox, StyleContainer},
     Result,
 };
@@ -131,3 +131,103 @@ where
         Ok(())
     }
 }
+
+// A slot is shared with the element it comes from, and its content is only
+// known to be `RenderMultiple`, so it is visited as a whole. This lets slots
+// be chained or branched with other children.
+
+impl<T: RenderMultiple> VisitLen for Slot<T> {
+    fn len(&self) -> usize {
+        RenderMultiple::len(&*self.0.borrow())
+    }
+}
+
+impl<'a, 'lr, T: RenderMultiple> Visit<RenderHelper<'a, 'lr>> for Slot<T> {
+    fn visit(&self, visitor: &mut RenderHelper<'a, 'lr>) -> Result<()> {
+        self.0.borrow().render(visitor.lr, visitor.interval)
+    }
+}
+
+impl<'a, T: RenderMultiple> Visit<LayoutHelper<'a>> for Slot<T> {
+    fn visit(&self, visitor: &mut LayoutHelper<'a>) -> Result<()> {
+        self.0.borrow().layout(visitor.0)
+    }
+}
+
+impl<'a, T: RenderMultiple> Visit<PeekStyles<'a>> for Slot<T> {
+    fn visit(&self, visitor: &mut PeekStyles<'a>) -> Result<()> {
+        self.0.borrow().peek_styles(visitor.0);
+        Ok(())
+    }
+}
+
+impl<'a, 'root, T: RenderMultiple> Visit<EmitEventHelper<'a, 'root>> for Slot<T> {
+    fn visit(&self, visitor: &mut EmitEventHelper<'a, 'root>) -> Result<()> {
+        *visitor.children_entered |= self.0.borrow().emit_event(visitor.npe);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+struct Leaves(std::cell::Cell<usize>);
+
+#[cfg(test)]
+impl RenderMultiple for Leaves {
+    fn render(&self, _: &mut LayerRebuilder, _: Duration) -> Result<()> {
+        unreachable!()
+    }
+
+    fn peek_styles(&self, f: &mut dyn FnMut(&dyn InsideStyleBox)) {
+        (0..self.0.get()).for_each(|_| f(&()));
+    }
+
+    fn len(&self) -> usize {
+        self.0.get()
+    }
+
+    fn layout(&self, f: &mut dyn FnMut(&dyn InsideStyleBox) -> Option<Region>) -> Result<()> {
+        for _ in 0..self.0.get() {
+            f(&()).ok_or_else(|| anyhow!("unexpected end of layouter"))?;
+        }
+        Ok(())
+    }
+
+    fn emit_event(&self, _: &NewPointerEvent) -> bool {
+        unreachable!()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[test]
+fn test_visit_chained_slots() {
+    use crate::structure::{Branch, Chain};
+
+    let slot = Slot::new(Leaves(std::cell::Cell::new(2)));
+    let children = Chain::new(
+        Slot(slot.0.clone()),
+        Chain::new(
+            Branch::<Slot<Leaves>, ()>::ArmA(Slot::new(Leaves(std::cell::Cell::new(1)))),
+            (),
+        ),
+    );
+    assert_eq!(RenderMultiple::len(&children), 3);
+
+    let mut peeked = 0;
+    children.peek_styles(&mut |_| peeked += 1);
+    assert_eq!(peeked, 3);
+
+    // the slot is shared with the element it comes from
+    slot.0.borrow().0.set(3);
+    let mut laid_out = 0;
+    children
+        .layout(&mut |_| {
+            laid_out += 1;
+            Some(Region::default())
+        })
+        .unwrap();
+    assert_eq!(laid_out, 4);
+    assert!(children.layout(&mut |_| None).is_err());
+}
@@ -1,5 +1,5 @@
 use std::{
-    cell::{Cell, RefCell},
+    cell::{Cell, OnceCell, RefCell},
     rc::{Rc, Weak},
 };
 
@@ -16,7 +16,10 @@ use crate::{
     Element,
 };
 
-use super::{children::ChildrenBox, layer::SharedLayerCompositer, RenderMultiple};
+use super::{
+    children::ChildrenBox, layer::SharedLayerCompositer, portal::Portal, DropFilter, InteractShape,
+    RenderMultiple,
+};
 
 pub struct ElementModel<El, Sty, Sc>
 where
@@ -32,7 +35,11 @@ where
     pub(super) slot_cache: Slot<Sc>,
     pub(super) draw_region: Cell<Region>,
     pub(super) interact_region: Cell<Option<Region>>,
+    pub(super) interact_shape: RefCell<Option<InteractShape>>,
+    pub(super) drop_filter: RefCell<Option<DropFilter>>,
     pub(super) acquire_independent_layer: Cell<bool>,
+    pub(super) consume_wheel: Cell<bool>,
+    pub(super) portal: OnceCell<Rc<Portal>>,
     pub(super) in_cell: RefCell<InsideRefCell<Sty>>,
 }
 
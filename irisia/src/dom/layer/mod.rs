@@ -1,4 +1,7 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use anyhow::anyhow;
 use irisia_backend::skia_safe::{
@@ -17,21 +20,31 @@ pub(crate) type SharedLayerCompositer = Rc<RefCell<LayerCompositer>>;
 
 pub(crate) struct LayerCompositer {
     layers: Queue,
+    /// Number of the frame this layer was composited in last time
+    composited: Cell<u64>,
 }
 
 impl LayerCompositer {
     pub fn new() -> SharedLayerCompositer {
         Rc::new(RefCell::new(Self {
             layers: Queue::new(),
+            composited: Cell::new(0),
         }))
     }
 
+    /// Whether the layer was composited in the frame `frame`. Layers of
+    /// hidden elements, and layers inside them, are not composited.
+    pub fn composited_in(&self, frame: u64) -> bool {
+        self.composited.get() == frame
+    }
+
     pub fn rebuild<'a>(&'a mut self, canvas: &'a mut Canvas) -> LayerRebuilder<'a> {
         self.layers.clear();
         LayerRebuilder::new(self, canvas)
     }
 
-    pub fn composite(&self, canvas: &mut Canvas) -> Result<()> {
+    pub fn composite(&self, canvas: &mut Canvas, frame: u64) -> Result<()> {
+        self.composited.set(frame);
         let mut paint = Paint::default();
         paint.set_blend_mode(BlendMode::DstOver);
         let rec = SaveLayerRec::default()
@@ -49,7 +62,7 @@ impl LayerCompositer {
                 Layer::Extern { layer, matrix } => {
                     canvas.save();
                     canvas.set_matrix(matrix);
-                    layer.borrow().composite(canvas)?;
+                    layer.borrow().composite(canvas, frame)?;
                     canvas.restore();
                 }
             }
@@ -7,10 +7,14 @@ use anyhow::anyhow;
 use irisia_backend::skia_safe::Canvas;
 
 use crate::{
-    application::{event_comp::NewPointerEvent, redraw_scheduler::RedrawObject},
+    application::{
+        event_comp::NewPointerEvent,
+        redraw_scheduler::{AnimationTarget, RedrawObject},
+    },
     element::{Element, RenderElement},
-    primitive::Region,
-    style::StyleContainer,
+    event::standard::DragData,
+    primitive::{Point, Region},
+    style::{StyleContainer, StyleCursor},
     Result,
 };
 
@@ -28,11 +32,18 @@ pub(crate) mod children;
 mod data_structure;
 mod drop_protection;
 pub(crate) mod layer;
+pub(crate) mod portal;
 pub mod pub_handle;
 pub(crate) mod update;
 
 pub type RcElementModel<El, Sty, Sc> = Rc<data_structure::ElementModel<El, Sty, Sc>>;
 
+/// Tells whether a point inside the interact region hits the element.
+pub type InteractShape = Rc<dyn Fn(Point) -> bool>;
+
+/// Tells whether an element accepts the data dragged onto it.
+pub type DropFilter = Rc<dyn Fn(&DragData) -> bool>;
+
 impl<El, Sty, Sc> ElementModel<El, Sty, Sc>
 where
     El: Element,
@@ -44,6 +55,10 @@ where
         lr: &mut LayerRebuilder,
         interval: Duration,
     ) -> Result<()> {
+        if self.is_hidden() {
+            return Ok(());
+        }
+
         let mut in_cell_ref = self.in_cell.borrow_mut();
         let in_cell = &mut *in_cell_ref;
 
@@ -83,7 +98,36 @@ where
         self.interact_region.set(region)
     }
 
-    pub(crate) fn composite(&self, canvas: &mut Canvas) -> Result<()> {
+    /// Narrow the interact region down to the points `shape` returns `true`
+    /// for, like the points inside a circle. The interact region still has
+    /// to be set as the bounding box of the shape. Set `None` to let the
+    /// whole interact region hit.
+    pub fn set_interact_shape(&self, shape: Option<InteractShape>) {
+        *self.interact_shape.borrow_mut() = shape;
+    }
+
+    /// Accept drags whose data `filter` returns `true` for as soon as they
+    /// enter this element, before [`DragEnter`] is received. A drop in the
+    /// same frame as entering reaches this element only this way, for the
+    /// [`DropAcceptor`] may be set too late. Set `None` to decide by the
+    /// acceptor only.
+    ///
+    /// [`DragEnter`]: crate::event::standard::DragEnter
+    /// [`DropAcceptor`]: crate::event::standard::DropAcceptor
+    pub fn set_drop_filter(&self, filter: Option<DropFilter>) {
+        *self.drop_filter.borrow_mut() = filter;
+    }
+
+    /// Interact region, if the pointer of `npe` is on the interact shape.
+    fn hit_region(&self, npe: &NewPointerEvent) -> Option<Region> {
+        let region = self.interact_region.get()?;
+        match (&*self.interact_shape.borrow(), npe.new_position) {
+            (Some(shape), Some(position)) if !shape(position) => None,
+            _ => Some(region),
+        }
+    }
+
+    pub(crate) fn composite(&self, canvas: &mut Canvas, frame: u64) -> Result<()> {
         let in_cell = self.in_cell.borrow();
         match &in_cell.indep_layer {
             Some(il) => {
@@ -91,7 +135,7 @@ where
                     in_cell.parent_layer.is_none(),
                     "illegal to call `composite` on non-root element"
                 );
-                il.borrow().composite(canvas)
+                il.borrow().composite(canvas, frame)
             }
             None => panic_on_debug("cannot call `composite` on elements have no independent layer"),
         }
@@ -101,16 +145,32 @@ where
     pub fn emit_event(&self, npe: &NewPointerEvent) -> bool {
         let mut in_cell = self.in_cell.borrow_mut();
 
-        let children_logically_entered = match &mut in_cell.expanded_children {
-            Some(children_box) => children_box.as_render_multiple().emit_event(npe),
-            None => false,
-        };
+        // still walked while hidden, so that the pointer is told to be out
+        let entered = npe.hide_subtree(self.is_hidden(), || {
+            let children_logically_entered = match &mut in_cell.expanded_children {
+                Some(children_box) => children_box.as_render_multiple().emit_event(npe),
+                None => false,
+            };
 
-        in_cell.event_mgr.update_and_emit(
-            npe,
-            self.interact_region.get(),
-            children_logically_entered,
-        )
+            in_cell
+                .event_mgr
+                .update_and_emit(npe, self.hit_region(npe), children_logically_entered)
+        });
+
+        if entered && self.consume_wheel.get() {
+            npe.consume_wheel();
+        }
+
+        if entered {
+            npe.hover_drag_target(&self.ed, || self.drop_filter.borrow().clone());
+            npe.hover_cursor_icon(|| {
+                in_cell
+                    .styles
+                    .get_style::<StyleCursor>()
+                    .map(|cursor| cursor.0)
+            });
+        }
+        entered
     }
 
     fn get_children_layer(&self, in_cell: &InsideRefCell<Sty>) -> Weak<dyn RedrawObject>
@@ -132,6 +192,16 @@ where
         Sty: 'static,
         Sc: 'static,
     {
+        self.global_content
+            .pointer_capture()
+            .release_all_of(&self.ed);
+        self.global_content.focusing().blur_checked(&self.ed);
+        self.global_content.focusing().release_keys(&self.ed);
+
+        if let Some(portal) = self.portal.get() {
+            portal.clear();
+        }
+
         let this = self.clone();
         tokio::task::spawn_local(async move {
             this.el_alive.set(false);
@@ -179,3 +249,24 @@ where
         )
     }
 }
+
+impl<El, Sty, Sc> AnimationTarget for ElementModel<El, Sty, Sc>
+where
+    El: Element,
+    Sty: StyleContainer + 'static,
+    Sc: RenderMultiple + 'static,
+{
+    fn animated_layer(&self) -> Option<Rc<dyn RedrawObject>> {
+        if !self.el_alive.get() {
+            return None;
+        }
+        self.get_children_layer(&self.in_cell.borrow()).upgrade()
+    }
+
+    fn shown_in(&self, frame: u64) -> bool {
+        match &self.in_cell.borrow().indep_layer {
+            Some(il) => il.borrow().composited_in(frame),
+            None => !self.is_hidden(),
+        }
+    }
+}
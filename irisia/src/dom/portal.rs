@@ -0,0 +1,195 @@
+use std::{
+    cell::{Cell, RefCell, RefMut},
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use irisia_backend::skia_safe::Canvas;
+
+use crate::{
+    application::{
+        content::GlobalContent,
+        event_comp::{global::new_event::PointerStateChange, NewPointerEvent},
+        redraw_scheduler::RedrawObject,
+    },
+    event::{standard::PortalPressedOutside, EventDispatcher},
+    Result,
+};
+
+use super::{
+    children::{ChildrenBox, ChildrenNodes},
+    layer::{LayerCompositer, SharedLayerCompositer},
+    pub_handle::LayoutElements,
+    EMUpdateContent, RenderMultiple,
+};
+
+/// Children of an element mounted on the overlay of the window.
+/// They are rendered and composited independently from the main tree.
+pub(crate) struct Portal {
+    this: Weak<Self>,
+    /// Weak, since the window may hold the portal through its data, like
+    /// a toast stack attached with [`GlobalContent::data`]
+    global_content: Weak<GlobalContent>,
+    /// Event dispatcher of the element owning this portal
+    owner: Option<EventDispatcher>,
+    z_index: Cell<i32>,
+    layer: SharedLayerCompositer,
+    children: RefCell<Option<ChildrenBox>>,
+}
+
+impl Portal {
+    pub fn new(global_content: &Rc<GlobalContent>, owner: Option<EventDispatcher>) -> Rc<Self> {
+        Rc::new_cyclic(|this| Portal {
+            this: this.clone(),
+            global_content: Rc::downgrade(global_content),
+            owner,
+            z_index: Cell::new(0),
+            layer: LayerCompositer::new(),
+            children: RefCell::new(None),
+        })
+    }
+
+    pub fn z_index(&self) -> i32 {
+        self.z_index.get()
+    }
+
+    pub fn set<Ch>(&self, z_index: i32, children: Ch) -> RefMut<dyn RenderMultiple>
+    where
+        Ch: ChildrenNodes,
+    {
+        let this = self.this.upgrade().expect("portal unexpectedly dropped");
+        let global_content = self
+            .global_content
+            .upgrade()
+            .expect("window unexpectedly dropped");
+        self.z_index.set(z_index);
+        global_content.overlay().mount(&this);
+        global_content.request_redraw(this);
+
+        let updater = EMUpdateContent {
+            global_content: &global_content,
+            parent_layer: Some(self.this.clone() as _),
+        };
+
+        RefMut::map(self.children.borrow_mut(), |option| match option {
+            Some(cb) => {
+                let model = cb
+                    .as_render_multiple()
+                    .as_any()
+                    .downcast_mut::<Ch::Model>()
+                    .expect("the type of portal children is not equal to previous's, these two is expected to be the same");
+
+                children.update_model(model, updater, &mut false);
+                model
+            }
+            place @ None => place
+                .insert(ChildrenBox::new(children.create_model(updater)))
+                .as_render_multiple()
+                .as_any()
+                .downcast_mut::<Ch::Model>()
+                .unwrap(),
+        })
+    }
+
+    pub fn layout_children(&self) -> Option<RefMut<dyn RenderMultiple>> {
+        RefMut::filter_map(self.children.borrow_mut(), |option| {
+            option.as_mut().map(|cb| cb.as_render_multiple())
+        })
+        .ok()
+    }
+
+    /// Unmount from the overlay and drop all children.
+    pub fn clear(&self) {
+        let global_content = self.global_content.upgrade();
+        if let Some(gc) = &global_content {
+            gc.overlay().unmount(self);
+        }
+        let children = self.children.borrow_mut().take();
+
+        if children.is_some() {
+            drop(children);
+            if let Some(gc) = &global_content {
+                gc.window().request_redraw();
+            }
+        }
+    }
+
+    pub(crate) fn composite(&self, canvas: &mut Canvas, frame: u64) -> Result<()> {
+        canvas.reset_matrix();
+        self.layer.borrow().composite(canvas, frame)
+    }
+
+    pub(crate) fn owner(&self) -> Option<&EventDispatcher> {
+        self.owner.as_ref()
+    }
+
+    /// Returns whether any child element is logically entered
+    pub(crate) fn emit_event(&self, npe: &NewPointerEvent) -> bool {
+        let entered = match &mut *self.children.borrow_mut() {
+            Some(cb) => cb.as_render_multiple().emit_event(npe),
+            None => false,
+        };
+
+        if let (false, Some(owner), PointerStateChange::Press(button), Some(position)) = (
+            entered,
+            &self.owner,
+            npe.pointer_state_change,
+            npe.new_position,
+        ) {
+            owner.emit_trusted(PortalPressedOutside {
+                position,
+                button,
+                pointer_id: npe.pointer.id,
+            });
+        }
+        entered
+    }
+}
+
+/// A portal not owned by any element, for popups opened by plain async
+/// functions rather than elements. Children are dropped along with the handle.
+pub struct DetachedPortal(Rc<Portal>);
+
+impl DetachedPortal {
+    pub fn new(global_content: &Rc<GlobalContent>) -> Self {
+        DetachedPortal(Portal::new(global_content, None))
+    }
+
+    /// Mount `children` on the overlay, see [`ElementModel::set_portal`].
+    ///
+    /// [`ElementModel::set_portal`]: crate::dom::ElementModel::set_portal
+    pub fn set<Ch>(&self, z_index: i32, children: Ch) -> LayoutElements
+    where
+        Ch: ChildrenNodes,
+    {
+        LayoutElements {
+            refmut: self.0.set(z_index, children),
+        }
+    }
+
+    /// Get mounted children for layouting. `None` if nothing mounted.
+    pub fn layout(&self) -> Option<LayoutElements> {
+        self.0
+            .layout_children()
+            .map(|refmut| LayoutElements { refmut })
+    }
+}
+
+impl Drop for DetachedPortal {
+    fn drop(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl RedrawObject for Portal {
+    fn redraw(&self, canvas: &mut Canvas, interval: Duration) -> Result<()> {
+        let mut children = self.children.borrow_mut();
+        let mut layer = self.layer.borrow_mut();
+        let mut rebuilder = layer.rebuild(canvas);
+
+        match &mut *children {
+            Some(cb) => cb.as_render_multiple().render(&mut rebuilder, interval),
+            None => Ok(()),
+        }
+    }
+}
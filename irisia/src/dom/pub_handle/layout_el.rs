@@ -6,7 +6,7 @@ use crate::{
 
 #[must_use]
 pub struct LayoutElements<'a> {
-    pub(super) refmut: RefMut<'a, dyn RenderMultiple>,
+    pub(crate) refmut: RefMut<'a, dyn RenderMultiple>,
 }
 
 impl<'a> LayoutElements<'a> {
@@ -1,4 +1,4 @@
-use irisia_backend::WinitWindow;
+use irisia_backend::{skia_safe::Canvas, WinitWindow};
 use std::{cell::RefMut, future::Future, rc::Rc};
 use tokio::{
     sync::{RwLockMappedWriteGuard, RwLockReadGuard, RwLockWriteGuard},
@@ -6,9 +6,13 @@ use tokio::{
 };
 
 use crate::{
-    application::content::GlobalContent,
-    event::{standard::ElementAbandoned, EdProvider, EventDispatcher, Listen},
-    primitive::Region,
+    application::{content::GlobalContent, event_comp::global::drag::DragPreview},
+    event::{
+        standard::{DragData, ElementAbandoned, PointerId},
+        EdProvider, EventDispatcher, Listen,
+    },
+    primitive::{Point, Region},
+    structure::slot::Slot,
     style::StyleContainer,
     Element, StyleReader,
 };
@@ -18,6 +22,7 @@ pub use self::{layout_el::LayoutElements, write_guard::ElWriteGuard};
 use super::{
     children::{ChildrenBox, ChildrenNodes},
     data_structure::ElementModel,
+    portal::Portal,
     EMUpdateContent, RcElementModel, RenderMultiple,
 };
 
@@ -68,7 +73,9 @@ where
         Listen::new(self)
     }
 
-    pub fn slot(&self) -> impl ChildrenNodes + '_ {
+    /// Children given to this element, which can be set as they are or
+    /// chained and branched with other children.
+    pub fn slot(&self) -> &Slot<Sc> {
         &self.slot_cache
     }
 
@@ -77,7 +84,11 @@ where
         &self.ed
     }
 
-    /// Let this element being focused on.
+    /// Let this element being focused on. Keyboard inputs, received
+    /// characters, IME events and modifier changes are emitted to the focused
+    /// element as [`StaticWindowEvent`]s, besides the window's event dispatcher.
+    ///
+    /// [`StaticWindowEvent`]: crate::StaticWindowEvent
     pub fn focus(&self) {
         self.global_content.focusing().focus(self.ed.clone());
     }
@@ -88,6 +99,89 @@ where
         self.global_content.focusing().blur_checked(&self.ed);
     }
 
+    /// Let the keyboard events go to this element as well as the focused
+    /// one, until [`Self::release_keys`] called. Intended for modals, which
+    /// handle keys like Escape no matter which element inside is focused.
+    /// Only the element captured keys last receives them.
+    pub fn capture_keys(&self) {
+        self.global_content.focusing().capture_keys(self.ed.clone());
+    }
+
+    /// Stop capturing keys. Does nothing if this element did not capture.
+    pub fn release_keys(&self) {
+        self.global_content.focusing().release_keys(&self.ed);
+    }
+
+    /// Let all events of the pointer go to this element, no matter whether
+    /// the pointer is inside its interact region, until [`Self::release_pointer`]
+    /// called or the pointer released. Other elements will receive no events
+    /// of this pointer in the meanwhile, but other pointers are not affected.
+    ///
+    /// Useful when dragging. A [`PointerCaptureLost`] event will be emitted
+    /// once the capture released.
+    ///
+    /// [`PointerCaptureLost`]: crate::event::standard::PointerCaptureLost
+    pub fn capture_pointer(&self, pointer_id: PointerId) {
+        self.global_content
+            .pointer_capture()
+            .capture(pointer_id, self.ed.clone());
+    }
+
+    /// Release the pointer captured by this element. Does nothing if
+    /// the pointer is not captured by this element.
+    pub fn release_pointer(&self, pointer_id: PointerId) {
+        self.global_content
+            .pointer_capture()
+            .release_checked(pointer_id, &self.ed);
+    }
+
+    /// Query whether the pointer is captured by this element.
+    pub fn has_pointer_capture(&self, pointer_id: PointerId) -> bool {
+        self.global_content
+            .pointer_capture()
+            .captured_by(pointer_id, &self.ed)
+    }
+
+    /// Start dragging `data` with the pointer, which is usually pressed on this
+    /// element. Elements under the pointer will receive [`DragEnter`],
+    /// [`DragOver`] and [`DragLeave`], and the innermost one accepted will receive
+    /// [`DragDrop`] once the pointer released. This element will receive [`DragEnd`]
+    /// at last.
+    ///
+    /// Capture of the pointer will be released so that other elements can
+    /// receive its events.
+    ///
+    /// [`DragEnter`]: crate::event::standard::DragEnter
+    /// [`DragOver`]: crate::event::standard::DragOver
+    /// [`DragLeave`]: crate::event::standard::DragLeave
+    /// [`DragDrop`]: crate::event::standard::DragDrop
+    /// [`DragEnd`]: crate::event::standard::DragEnd
+    pub fn start_drag(&self, pointer_id: PointerId, data: DragData) {
+        self.start_drag_inner(pointer_id, data, None);
+    }
+
+    /// Like [`Self::start_drag`], but draws a preview above all elements
+    /// following the pointer. The preview is called with the pointer position.
+    pub fn start_drag_with_preview<F>(&self, pointer_id: PointerId, data: DragData, preview: F)
+    where
+        F: Fn(&mut Canvas, Point) + 'static,
+    {
+        self.start_drag_inner(pointer_id, data, Some(Box::new(preview)));
+    }
+
+    fn start_drag_inner(
+        &self,
+        pointer_id: PointerId,
+        data: DragData,
+        preview: Option<DragPreview>,
+    ) {
+        self.release_pointer(pointer_id);
+        self.global_content
+            .drag()
+            .start(self.ed.clone(), pointer_id, data, preview);
+        self.global_content.redraw_drag_preview();
+    }
+
     /// Get global content of the window.
     pub fn global(&self) -> &Rc<GlobalContent> {
         &self.global_content
@@ -104,6 +198,13 @@ where
         self.draw_region.get()
     }
 
+    /// Whether the element was laid out in an empty region, which hides it.
+    /// Hidden elements, as well as their children, are neither drawn nor
+    /// hit-tested, but stay mounted.
+    pub fn is_hidden(&self) -> bool {
+        hides(self.draw_region.get())
+    }
+
     /// Get styles bind to this element
     pub fn styles<Sr>(&self) -> Sr
     where
@@ -128,7 +229,7 @@ where
     /// the layer. Independent layer may not exists while result is `true`
     /// and may exists while result is `false`.
     pub fn indep_layer_acquired(&self) -> bool {
-        self.acquire_independent_layer.take()
+        self.acquire_independent_layer.get()
     }
 
     /// Set `true` to acquire independent render layer for performance optimizations.
@@ -147,6 +248,34 @@ where
         self.set_dirty();
     }
 
+    /// Set `true` to stop [`Wheel`] events at this element, so that its
+    /// ancestors will not receive the wheel scrolled over it. Intended for
+    /// scroll containers, which should only consume the wheel when they
+    /// are able to scroll.
+    ///
+    /// [`Wheel`]: crate::event::standard::Wheel
+    pub fn consume_wheel(&self, consume: bool) {
+        self.consume_wheel.set(consume);
+    }
+
+    /// Set `true` to redraw this element in every frame, until set `false`
+    /// or the element dropped. [`RenderElement::interval`] tells the time
+    /// passed since the last frame.
+    ///
+    /// Elements on independent layers pause while not shown, like when
+    /// hidden along with any of their ancestors, and resume once shown.
+    ///
+    /// Only the layer this element is drawn on is redrawn, so animated
+    /// elements are recommended to [acquire an independent layer], which
+    /// keeps the rest of the window from being redrawn as well.
+    ///
+    /// [`RenderElement::interval`]: crate::element::RenderElement::interval
+    /// [acquire an independent layer]: Self::acquire_indep_layer
+    pub fn set_animating(&self, animating: bool) {
+        self.global_content
+            .set_animating(self.this.clone() as _, animating);
+    }
+
     /// Spwan a daemon task on `fut`.
     ///
     /// The spawned task will be cancelled when element dropped,
@@ -211,6 +340,59 @@ where
 
         LayoutElements { refmut }
     }
+
+    /// Mount `children` on the overlay of the window. Unlike children set by
+    /// [`Self::set_children`], they are painted above the whole element tree,
+    /// and receive pointer events before it, so that elements beneath their
+    /// interact regions cannot be entered. Portals with a greater `z_index`
+    /// are placed above others.
+    ///
+    /// The children are still owned by this element, and will be dropped
+    /// along with it or by [`Self::remove_portal`]. Their draw regions are
+    /// relative to the window rather than this element.
+    ///
+    /// Useful for dropdowns, tooltips and modal dialogs.
+    pub fn set_portal<Ch>(&self, z_index: i32, children: Ch) -> LayoutElements
+    where
+        Ch: ChildrenNodes,
+    {
+        let portal = self
+            .portal
+            .get_or_init(|| Portal::new(&self.global_content, Some(self.ed.clone())));
+
+        LayoutElements {
+            refmut: portal.set(z_index, children),
+        }
+    }
+
+    /// Get children mounted by [`Self::set_portal`] for layouting.
+    /// `None` if no portal mounted.
+    pub fn layout_portal(&self) -> Option<LayoutElements> {
+        self.portal
+            .get()
+            .and_then(|portal| portal.layout_children())
+            .map(|refmut| LayoutElements { refmut })
+    }
+
+    /// Whether the portal mounted by [`Self::set_portal`] is above all other
+    /// portals owned by elements. Portals owned by no element, like
+    /// [`DetachedPortal`], are not taken into account.
+    ///
+    /// [`DetachedPortal`]: crate::element::DetachedPortal
+    pub fn portal_on_top(&self) -> bool {
+        match self.portal.get() {
+            Some(portal) => self.global_content.overlay().is_top_owned(portal),
+            None => false,
+        }
+    }
+
+    /// Unmount and drop children mounted by [`Self::set_portal`].
+    /// Does nothing if no portal mounted.
+    pub fn remove_portal(&self) {
+        if let Some(portal) = self.portal.get() {
+            portal.clear();
+        }
+    }
 }
 
 impl<El, Sty, Sc> EdProvider for RcElementModel<El, Sty, Sc>
@@ -234,3 +416,19 @@ where
         self.alive()
     }
 }
+
+/// Only an empty region hides, a zero width or height still lays out a line.
+fn hides((start, end): Region) -> bool {
+    start == end
+}
+
+#[test]
+fn test_empty_region_hides() {
+    use crate::primitive::Pixel;
+
+    let at = Point(Pixel(12.0), Pixel(4.0));
+    assert!(hides((at, at)));
+    assert!(hides(Region::default()));
+    assert!(!hides((at, at + Point(Pixel(0.0), Pixel(8.0)))));
+    assert!(!hides((Point::default(), at)));
+}
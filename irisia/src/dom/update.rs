@@ -1,5 +1,5 @@
 use std::{
-    cell::{Cell, RefCell},
+    cell::{Cell, OnceCell, RefCell},
     marker::PhantomData,
     rc::{Rc, Weak},
 };
@@ -127,7 +127,11 @@ where
             })),
             draw_region: Default::default(),
             interact_region: Cell::new(None),
+            interact_shape: RefCell::new(None),
+            drop_filter: RefCell::new(None),
             acquire_independent_layer: Cell::new(false),
+            consume_wheel: Cell::new(false),
+            portal: OnceCell::new(),
         });
 
         // hold the lock prevent from being accessed
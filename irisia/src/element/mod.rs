@@ -3,7 +3,10 @@ use crate::{dom::RenderMultiple, primitive::Region, Result};
 pub use self::{props::PropsUpdateWith, render_element::RenderElement};
 pub use crate::{
     application::content::GlobalContent,
-    dom::{one_child, pub_handle::LayoutElements, RcElementModel},
+    dom::{
+        one_child, portal::DetachedPortal, pub_handle::LayoutElements, DropFilter, InteractShape,
+        RcElementModel,
+    },
 };
 
 pub mod props;
@@ -8,6 +8,11 @@ use super::EventDispatcher;
 
 impl EventDispatcher {
     pub async fn hover(&self) {
+        self.hover_for(Duration::from_secs(1)).await
+    }
+
+    /// Like [`Self::hover`], but waits for `duration` rather than 1 second.
+    pub async fn hover_for(&self, duration: Duration) {
         tokio::select! {
             _ = self.recv_trusted::<PointerUp>() => {},
             _ = self.recv_trusted::<PointerMove>() => {}
@@ -21,7 +26,7 @@ impl EventDispatcher {
                 _ = self.recv_trusted::<PointerOut>() => {
                     self.recv_trusted::<PointerEntered>().await;
                 }
-                _ = tokio::time::sleep(Duration::from_secs(1)) => {
+                _ = tokio::time::sleep(duration) => {
                     break;
                 }
             }
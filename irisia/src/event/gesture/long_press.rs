@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+use crate as irisia;
+use crate::{
+    event::{standard::PointerId, EventDispatcher},
+    primitive::{Pixel, Point},
+    Event,
+};
+
+use super::{GestureInput, InputKind, Recognition, Recognizer};
+
+/// A pointer held still for a while.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct LongPress {
+    pub position: Point,
+    pub pointer_id: PointerId,
+}
+
+pub struct LongPressRecognizer {
+    slop: Pixel,
+    duration: Duration,
+    pressed: Option<(PointerId, Point, Instant)>,
+    fired: bool,
+    pending: Option<LongPress>,
+}
+
+impl LongPressRecognizer {
+    pub fn new() -> Self {
+        LongPressRecognizer {
+            slop: Pixel(8.0),
+            duration: Duration::from_millis(500),
+            pressed: None,
+            fired: false,
+            pending: None,
+        }
+    }
+
+    /// Maximum distance the pointer can move while holding. Defaults to 8px.
+    pub fn slop(mut self, slop: Pixel) -> Self {
+        self.slop = slop;
+        self
+    }
+
+    /// Time to hold before recognized. Defaults to 500ms.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+impl Default for LongPressRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recognizer for LongPressRecognizer {
+    fn handle(&mut self, input: &GestureInput) -> Recognition {
+        if self.fired {
+            return Recognition::Accept;
+        }
+
+        match input.kind {
+            InputKind::Down if input.pointers.len() > 1 => Recognition::Reject,
+            InputKind::Down => {
+                let pointer = &input.pointer;
+                self.pressed = Some((pointer.id, pointer.start, input.time));
+                Recognition::Possible
+            }
+            InputKind::Move if input.pointer.travel() > self.slop => Recognition::Reject,
+            InputKind::Move => Recognition::Possible,
+            InputKind::Up | InputKind::Cancel => Recognition::Reject,
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        match self.pressed {
+            Some((_, _, pressed_at)) if !self.fired => Some(pressed_at + self.duration),
+            _ => None,
+        }
+    }
+
+    fn timeout(&mut self, _: Instant) -> Recognition {
+        let Some((pointer_id, position, _)) = self.pressed
+        else {
+            return Recognition::Reject;
+        };
+
+        self.fired = true;
+        self.pending = Some(LongPress {
+            position,
+            pointer_id,
+        });
+        Recognition::Accept
+    }
+
+    fn flush(&mut self, ed: &EventDispatcher) {
+        if let Some(long_press) = self.pending.take() {
+            ed.emit_trusted(long_press);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.pressed = None;
+        self.fired = false;
+        self.pending = None;
+    }
+}
@@ -0,0 +1,598 @@
+//! Gesture recognizers turning pointer streams into typed gesture events.
+//!
+//! Recognizers are put into a [`GestureArena`] in order of priority, then the arena
+//! runs as a daemon of an element:
+//!
+//! ```ignore
+//! this.daemon(
+//!     GestureArena::new()
+//!         .with(PinchRecognizer::new())
+//!         .with(PanRecognizer::new())
+//!         .with(DoubleTapRecognizer::new())
+//!         .with(TapRecognizer::new())
+//!         .run(this.clone()),
+//! );
+//! ```
+//!
+//! Exclusive recognizers compete for the pointers. One of them wins as soon
+//! as it accepts, or when it is ready and every recognizer before it has
+//! rejected. Once a winner decided, the others are reset until all pointers lifted.
+
+use std::time::{Duration, Instant};
+
+use irisia_backend::winit::event::MouseButton;
+use tokio::time::sleep_until;
+
+use crate::{
+    element::{AsChildren, RcElementModel},
+    event::{
+        standard::{PointerCaptureLost, PointerDown, PointerId, PointerMove, PointerUp},
+        EventDispatcher,
+    },
+    primitive::{Pixel, Point},
+    style::StyleContainer,
+    Element,
+};
+
+pub use self::{
+    long_press::{LongPress, LongPressRecognizer},
+    pan::{Pan, PanRecognizer},
+    pinch::{Pinch, PinchRecognizer},
+    swipe::{Swipe, SwipeDirection, SwipeRecognizer},
+    tap::{DoubleTap, DoubleTapRecognizer, Tap, TapRecognizer},
+};
+
+mod long_press;
+mod pan;
+mod pinch;
+mod swipe;
+mod tap;
+
+/// Velocity older than this is considered stale.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GesturePhase {
+    Began,
+    Changed,
+    Ended,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Down,
+    Move,
+    Up,
+    /// The pointer left the window or the touch was cancelled by the system.
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TrackedPointer {
+    pub id: PointerId,
+    pub start: Point,
+    pub start_time: Instant,
+    pub position: Point,
+    /// Logical pixels per second.
+    pub velocity: (Pixel, Pixel),
+    last_time: Instant,
+}
+
+impl TrackedPointer {
+    fn new(id: PointerId, position: Point, now: Instant) -> Self {
+        TrackedPointer {
+            id,
+            start: position,
+            start_time: now,
+            position,
+            velocity: Default::default(),
+            last_time: now,
+        }
+    }
+
+    fn update(&mut self, position: Point, now: Instant) {
+        let elapsed = now.duration_since(self.last_time);
+        let secs = elapsed.as_secs_f32();
+
+        if secs > 0.0 {
+            let delta = position - self.position;
+            let current = (delta.0 / secs, delta.1 / secs);
+            self.velocity = if elapsed > VELOCITY_WINDOW {
+                current
+            } else {
+                (
+                    current.0 * 0.8 + self.velocity.0 * 0.2,
+                    current.1 * 0.8 + self.velocity.1 * 0.2,
+                )
+            };
+        }
+
+        self.position = position;
+        self.last_time = now;
+    }
+
+    /// Distance between current position and the position pressed.
+    pub fn travel(&self) -> Pixel {
+        self.position.abs_diff(self.start)
+    }
+}
+
+pub struct GestureInput<'a> {
+    pub kind: InputKind,
+    pub time: Instant,
+    /// The pointer caused this input.
+    pub pointer: TrackedPointer,
+    /// Pointers being pressed after this input applied, in order of pressing.
+    pub pointers: &'a [TrackedPointer],
+}
+
+impl GestureInput<'_> {
+    /// Center of all pressed pointers.
+    pub fn centroid(&self) -> Option<Point> {
+        centroid(self.pointers)
+    }
+}
+
+fn centroid(pointers: &[TrackedPointer]) -> Option<Point> {
+    if pointers.is_empty() {
+        return None;
+    }
+
+    let sum = pointers
+        .iter()
+        .fold(Point::default(), |sum, p| sum + p.position);
+    let count = pointers.len() as f32;
+    Some(Point(sum.0 / count, sum.1 / count))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recognition {
+    /// Not decided yet.
+    Possible,
+    /// Recognized, but gives way to recognizers before it until they rejected.
+    Ready,
+    /// Recognized and wins immediately.
+    Accept,
+    /// Not this gesture, no more inputs will be received in this round.
+    Reject,
+}
+
+pub trait Recognizer: 'static {
+    fn handle(&mut self, input: &GestureInput) -> Recognition;
+
+    /// Returns an instant at which [`Recognizer::timeout`] should be called.
+    fn deadline(&self) -> Option<Instant> {
+        None
+    }
+
+    fn timeout(&mut self, now: Instant) -> Recognition {
+        let _ = now;
+        Recognition::Reject
+    }
+
+    /// Emit events recognized so far. Only called on the winner, or
+    /// on non-exclusive recognizers which are ready or accepted.
+    fn flush(&mut self, ed: &EventDispatcher);
+
+    /// Clear all states, called when the round ends or when beaten.
+    fn reset(&mut self);
+
+    /// Non-exclusive recognizers neither compete with nor will be beaten
+    /// by others.
+    fn exclusive(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryState {
+    Possible,
+    Ready,
+    Accepted,
+    Rejected,
+}
+
+struct Entry {
+    recognizer: Box<dyn Recognizer>,
+    state: EntryState,
+}
+
+impl Entry {
+    fn apply(&mut self, recognition: Recognition) {
+        match recognition {
+            Recognition::Possible => {}
+            Recognition::Ready => {
+                if self.state == EntryState::Possible {
+                    self.state = EntryState::Ready;
+                }
+            }
+            Recognition::Accept => self.state = EntryState::Accepted,
+            Recognition::Reject => self.state = EntryState::Rejected,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct GestureArena {
+    entries: Vec<Entry>,
+    pointers: Vec<TrackedPointer>,
+    winner: Option<usize>,
+}
+
+impl GestureArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a recognizer, which has lower priority than those added before.
+    pub fn with<R: Recognizer>(mut self, recognizer: R) -> Self {
+        self.entries.push(Entry {
+            recognizer: Box::new(recognizer),
+            state: EntryState::Possible,
+        });
+        self
+    }
+
+    /// Run the arena on the element. Pointers pressed on the element are
+    /// captured until released, so gestures keep going outside the element.
+    /// Gesture events are emitted to the element.
+    pub async fn run<El, Sty, Sc>(mut self, this: RcElementModel<El, Sty, Sc>)
+    where
+        El: Element,
+        Sty: StyleContainer + 'static,
+        Sc: AsChildren + 'static,
+    {
+        let ed = this.event_dispatcher().clone();
+        loop {
+            let deadline = self.next_deadline();
+            let wake_at = deadline.unwrap_or_else(Instant::now);
+            let tracking = !self.pointers.is_empty();
+
+            let (kind, pointer_id, position) = tokio::select! {
+                // a released pointer also loses its capture, which must not
+                // be taken as cancelled before the release handled
+                biased;
+
+                pd = ed.recv_trusted::<PointerDown>() => {
+                    if pd.button != MouseButton::Left {
+                        continue;
+                    }
+                    this.capture_pointer(pd.pointer_id);
+                    (InputKind::Down, pd.pointer_id, pd.position)
+                }
+                pm = ed.recv_trusted::<PointerMove>(), if tracking => {
+                    (InputKind::Move, pm.pointer_id, pm.position)
+                }
+                pu = ed.recv_trusted::<PointerUp>(), if tracking => {
+                    if pu.button != MouseButton::Left {
+                        continue;
+                    }
+                    this.release_pointer(pu.pointer_id);
+                    (InputKind::Up, pu.pointer_id, pu.position)
+                }
+                lost = ed.recv_trusted::<PointerCaptureLost>(), if tracking => {
+                    match self.pointer(lost.pointer_id) {
+                        Some(p) => (InputKind::Cancel, lost.pointer_id, p.position),
+                        None => continue,
+                    }
+                }
+                _ = sleep_until(wake_at.into()), if deadline.is_some() => {
+                    self.timeout(&ed);
+                    continue;
+                }
+            };
+
+            self.process(kind, pointer_id, position, &ed);
+        }
+    }
+
+    fn pointer(&self, id: PointerId) -> Option<&TrackedPointer> {
+        self.pointers.iter().find(|p| p.id == id)
+    }
+
+    fn participating(&self, index: usize) -> bool {
+        let entry = &self.entries[index];
+        entry.state != EntryState::Rejected
+            && (!entry.recognizer.exclusive() || self.winner.map_or(true, |w| w == index))
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        (0..self.entries.len())
+            .filter(|&index| self.participating(index))
+            .filter_map(|index| self.entries[index].recognizer.deadline())
+            .min()
+    }
+
+    fn process(&mut self, kind: InputKind, id: PointerId, position: Point, ed: &EventDispatcher) {
+        let now = Instant::now();
+
+        let pointer = match kind {
+            InputKind::Down => {
+                if self.pointer(id).is_some() {
+                    return;
+                }
+                let pointer = TrackedPointer::new(id, position, now);
+                self.pointers.push(pointer);
+                pointer
+            }
+            InputKind::Move => match self.pointers.iter_mut().find(|p| p.id == id) {
+                Some(pointer) => {
+                    pointer.update(position, now);
+                    *pointer
+                }
+                None => return,
+            },
+            InputKind::Up | InputKind::Cancel => {
+                let Some(index) = self.pointers.iter().position(|p| p.id == id)
+                else {
+                    return;
+                };
+                let mut pointer = self.pointers.remove(index);
+                if now.duration_since(pointer.last_time) > VELOCITY_WINDOW {
+                    // the pointer rested before lifted
+                    pointer.velocity = Default::default();
+                }
+                pointer.position = position;
+                pointer
+            }
+        };
+
+        let input = GestureInput {
+            kind,
+            time: now,
+            pointer,
+            pointers: &self.pointers,
+        };
+
+        for index in 0..self.entries.len() {
+            if self.participating(index) {
+                let entry = &mut self.entries[index];
+                let recognition = entry.recognizer.handle(&input);
+                entry.apply(recognition);
+            }
+        }
+
+        self.resolve(ed);
+    }
+
+    fn timeout(&mut self, ed: &EventDispatcher) {
+        let now = Instant::now();
+
+        for index in 0..self.entries.len() {
+            if !self.participating(index) {
+                continue;
+            }
+
+            let entry = &mut self.entries[index];
+            if entry.recognizer.deadline().is_some_and(|d| d <= now) {
+                let recognition = entry.recognizer.timeout(now);
+                entry.apply(recognition);
+            }
+        }
+
+        self.resolve(ed);
+    }
+
+    fn resolve(&mut self, ed: &EventDispatcher) {
+        if self.winner.is_none() {
+            self.winner = self.find_winner(false);
+            if let Some(winner) = self.winner {
+                self.beat_others(winner);
+            }
+        }
+
+        self.flush(ed);
+
+        let round_over = self.pointers.is_empty()
+            && (0..self.entries.len())
+                .filter(|&index| self.participating(index))
+                .all(|index| self.entries[index].recognizer.deadline().is_none());
+
+        if round_over {
+            // no one is able to accept anymore, so the first ready one wins
+            if self.winner.is_none() {
+                self.winner = self.find_winner(true);
+                self.flush(ed);
+            }
+
+            for entry in &mut self.entries {
+                entry.recognizer.reset();
+                entry.state = EntryState::Possible;
+            }
+            self.winner = None;
+        }
+    }
+
+    fn find_winner(&self, sweep: bool) -> Option<usize> {
+        let mut exclusive = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.recognizer.exclusive());
+
+        if let Some((index, _)) = exclusive
+            .clone()
+            .find(|(_, entry)| entry.state == EntryState::Accepted)
+        {
+            return Some(index);
+        }
+
+        if sweep {
+            exclusive
+                .find(|(_, entry)| entry.state == EntryState::Ready)
+                .map(|(index, _)| index)
+        } else {
+            exclusive
+                .find(|(_, entry)| entry.state != EntryState::Rejected)
+                .filter(|(_, entry)| entry.state == EntryState::Ready)
+                .map(|(index, _)| index)
+        }
+    }
+
+    fn beat_others(&mut self, winner: usize) {
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            if index != winner && entry.recognizer.exclusive() {
+                entry.recognizer.reset();
+                entry.state = EntryState::Rejected;
+            }
+        }
+    }
+
+    fn flush(&mut self, ed: &EventDispatcher) {
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            let should_flush = if entry.recognizer.exclusive() {
+                self.winner == Some(index)
+            } else {
+                matches!(entry.state, EntryState::Ready | EntryState::Accepted)
+            };
+
+            if should_flush {
+                entry.recognizer.flush(ed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+struct Scripted {
+    name: &'static str,
+    script: Vec<Recognition>,
+    exclusive: bool,
+    log: std::rc::Rc<std::cell::RefCell<Vec<String>>>,
+}
+
+#[cfg(test)]
+impl Recognizer for Scripted {
+    fn handle(&mut self, _: &GestureInput) -> Recognition {
+        if self.script.is_empty() {
+            Recognition::Possible
+        } else {
+            self.script.remove(0)
+        }
+    }
+
+    fn flush(&mut self, _: &EventDispatcher) {
+        self.log.borrow_mut().push(format!("flush {}", self.name));
+    }
+
+    fn reset(&mut self) {
+        self.log.borrow_mut().push(format!("reset {}", self.name));
+    }
+
+    fn exclusive(&self) -> bool {
+        self.exclusive
+    }
+}
+
+/// Arena of recognizers answering each input with the next recognition
+/// in their scripts, and a log of the flushes and resets in order.
+#[cfg(test)]
+fn scripted_arena(
+    scripts: &[(&'static str, bool, &[Recognition])],
+) -> (GestureArena, std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+    let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let arena = scripts
+        .iter()
+        .fold(GestureArena::new(), |arena, &(name, exclusive, script)| {
+            arena.with(Scripted {
+                name,
+                script: script.to_vec(),
+                exclusive,
+                log: log.clone(),
+            })
+        });
+    (arena, log)
+}
+
+#[cfg(test)]
+fn input(arena: &mut GestureArena, kind: InputKind) {
+    let position = Point(Pixel(10.0), Pixel(10.0));
+    arena.process(kind, PointerId::Mouse, position, &EventDispatcher::new());
+}
+
+#[test]
+fn test_accept_wins_immediately() {
+    use Recognition::*;
+    let (mut arena, log) = scripted_arena(&[("a", true, &[Possible]), ("b", true, &[Accept])]);
+
+    input(&mut arena, InputKind::Down);
+    assert_eq!(arena.winner, Some(1));
+    assert_eq!(log.take(), ["reset a", "flush b"]);
+
+    input(&mut arena, InputKind::Up);
+    assert_eq!(arena.winner, None);
+    assert_eq!(log.take(), ["flush b", "reset a", "reset b"]);
+}
+
+#[test]
+fn test_ready_waits_for_earlier() {
+    use Recognition::*;
+    let (mut arena, log) =
+        scripted_arena(&[("a", true, &[Possible, Reject]), ("b", true, &[Ready])]);
+
+    input(&mut arena, InputKind::Down);
+    assert_eq!(arena.winner, None);
+    assert!(log.take().is_empty());
+
+    input(&mut arena, InputKind::Up);
+    assert_eq!(log.take(), ["reset a", "flush b", "reset a", "reset b"]);
+}
+
+#[test]
+fn test_first_ready_wins_at_round_end() {
+    use Recognition::*;
+    let (mut arena, log) = scripted_arena(&[
+        ("a", true, &[]),
+        ("b", true, &[Ready]),
+        ("c", true, &[Ready]),
+    ]);
+
+    input(&mut arena, InputKind::Down);
+    input(&mut arena, InputKind::Up);
+    assert_eq!(log.take(), ["flush b", "reset a", "reset b", "reset c"]);
+}
+
+#[test]
+fn test_non_exclusive_not_beaten() {
+    use Recognition::*;
+    let (mut arena, log) =
+        scripted_arena(&[("a", true, &[Possible, Accept]), ("n", false, &[Ready])]);
+
+    input(&mut arena, InputKind::Down);
+    assert_eq!(log.take(), ["flush n"]);
+
+    input(&mut arena, InputKind::Up);
+    assert_eq!(arena.winner, None);
+    assert_eq!(log.take(), ["flush a", "flush n", "reset a", "reset n"]);
+}
+
+#[test]
+fn test_rejected_participate_in_next_round() {
+    use Recognition::*;
+    let (mut arena, log) = scripted_arena(&[("a", true, &[Reject, Accept]), ("b", true, &[Ready])]);
+
+    input(&mut arena, InputKind::Down);
+    assert_eq!(arena.winner, Some(1));
+    input(&mut arena, InputKind::Up);
+    assert!(arena
+        .entries
+        .iter()
+        .all(|entry| entry.state == EntryState::Possible));
+    log.take();
+
+    input(&mut arena, InputKind::Down);
+    assert_eq!(arena.winner, Some(0));
+    assert_eq!(log.take(), ["reset b", "flush a"]);
+}
+
+#[test]
+fn test_untracked_pointer_ignored() {
+    use Recognition::*;
+    let (mut arena, log) = scripted_arena(&[("a", true, &[Accept])]);
+
+    input(&mut arena, InputKind::Move);
+    input(&mut arena, InputKind::Cancel);
+    assert_eq!(arena.winner, None);
+    assert!(log.take().is_empty());
+}
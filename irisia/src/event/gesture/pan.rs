@@ -0,0 +1,136 @@
+use crate as irisia;
+use crate::{
+    event::EventDispatcher,
+    primitive::{Pixel, Point},
+    Event,
+};
+
+use super::{GestureInput, GesturePhase, InputKind, Recognition, Recognizer};
+
+/// Pointers dragged. With multiple pointers pressed, the center
+/// of them is tracked.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Pan {
+    pub phase: GesturePhase,
+    pub position: Point,
+    /// Movement since the last `Pan` event.
+    pub delta: (Pixel, Pixel),
+    /// Logical pixels per second.
+    pub velocity: (Pixel, Pixel),
+}
+
+pub struct PanRecognizer {
+    slop: Pixel,
+    started: bool,
+    last_position: Option<Point>,
+    pending: Vec<Pan>,
+}
+
+impl PanRecognizer {
+    pub fn new() -> Self {
+        PanRecognizer {
+            slop: Pixel(8.0),
+            started: false,
+            last_position: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Distance a pointer needs to move before panning starts. Defaults to 8px.
+    pub fn slop(mut self, slop: Pixel) -> Self {
+        self.slop = slop;
+        self
+    }
+
+    fn push(&mut self, phase: GesturePhase, position: Point, velocity: (Pixel, Pixel)) {
+        let delta = match self.last_position {
+            Some(last) => (position - last).into(),
+            None => Default::default(),
+        };
+
+        self.last_position = Some(position);
+        self.pending.push(Pan {
+            phase,
+            position,
+            delta,
+            velocity,
+        });
+    }
+}
+
+impl Default for PanRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recognizer for PanRecognizer {
+    fn handle(&mut self, input: &GestureInput) -> Recognition {
+        let velocity = average_velocity(input);
+
+        match (input.kind, input.centroid()) {
+            // pointers changed, rebase to avoid the center jumping
+            (InputKind::Down, centroid) => {
+                self.last_position = centroid;
+            }
+
+            (InputKind::Move, Some(centroid)) => {
+                if self.started {
+                    self.push(GesturePhase::Changed, centroid, velocity);
+                } else if input.pointer.travel() > self.slop {
+                    self.started = true;
+                    self.push(GesturePhase::Began, centroid, velocity);
+                }
+            }
+
+            (InputKind::Up | InputKind::Cancel, None) => {
+                if !self.started {
+                    return Recognition::Reject;
+                }
+
+                let phase = match input.kind {
+                    InputKind::Cancel => GesturePhase::Cancelled,
+                    _ => GesturePhase::Ended,
+                };
+                self.push(phase, input.pointer.position, velocity);
+            }
+
+            (_, centroid) => {
+                self.last_position = centroid;
+            }
+        }
+
+        if self.started {
+            Recognition::Accept
+        } else {
+            Recognition::Possible
+        }
+    }
+
+    fn flush(&mut self, ed: &EventDispatcher) {
+        for pan in self.pending.drain(..) {
+            ed.emit_trusted(pan);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.started = false;
+        self.last_position = None;
+        self.pending.clear();
+    }
+}
+
+fn average_velocity(input: &GestureInput) -> (Pixel, Pixel) {
+    if input.pointers.is_empty() {
+        return input.pointer.velocity;
+    }
+
+    let count = input.pointers.len() as f32;
+    let (x, y) = input
+        .pointers
+        .iter()
+        .fold((Pixel(0.0), Pixel(0.0)), |(x, y), p| {
+            (x + p.velocity.0, y + p.velocity.1)
+        });
+    (x / count, y / count)
+}
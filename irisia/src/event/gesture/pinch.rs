@@ -0,0 +1,150 @@
+use crate as irisia;
+use crate::{
+    event::EventDispatcher,
+    primitive::{Pixel, Point},
+    Event,
+};
+
+use super::{GestureInput, GesturePhase, InputKind, Recognition, Recognizer, TrackedPointer};
+
+/// Two pointers moved toward or away from each other.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Pinch {
+    pub phase: GesturePhase,
+    /// Scale relative to the distance when the gesture began.
+    pub scale: f32,
+    /// Middle point of the two pointers.
+    pub center: Point,
+}
+
+pub struct PinchRecognizer {
+    slop: Pixel,
+    started: bool,
+    /// Distance between the two pointers when they were pressed.
+    base_distance: Option<Pixel>,
+    /// Scale reached before the pointers changed.
+    base_scale: f32,
+    scale: f32,
+    pending: Vec<Pinch>,
+}
+
+impl PinchRecognizer {
+    pub fn new() -> Self {
+        PinchRecognizer {
+            slop: Pixel(8.0),
+            started: false,
+            base_distance: None,
+            base_scale: 1.0,
+            scale: 1.0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Change of distance between the two pointers needed before
+    /// pinching starts. Defaults to 8px.
+    pub fn slop(mut self, slop: Pixel) -> Self {
+        self.slop = slop;
+        self
+    }
+
+    fn rebase(&mut self, pointers: &[TrackedPointer]) {
+        self.base_scale = self.scale;
+        self.base_distance = pair(pointers).map(|(a, b)| a.abs_diff(b));
+    }
+}
+
+impl Default for PinchRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recognizer for PinchRecognizer {
+    fn handle(&mut self, input: &GestureInput) -> Recognition {
+        match input.kind {
+            InputKind::Down => self.rebase(input.pointers),
+
+            InputKind::Move => {
+                if let (Some((a, b)), Some(base)) = (pair(input.pointers), self.base_distance) {
+                    let distance = a.abs_diff(b);
+                    let center = Point((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+
+                    if base.0 > 0.0 {
+                        self.scale = self.base_scale * distance.0 / base.0;
+                    }
+
+                    if self.started {
+                        self.pending.push(Pinch {
+                            phase: GesturePhase::Changed,
+                            scale: self.scale,
+                            center,
+                        });
+                    } else if (distance - base).0.abs() > self.slop.0 {
+                        self.started = true;
+                        self.pending.push(Pinch {
+                            phase: GesturePhase::Began,
+                            scale: self.scale,
+                            center,
+                        });
+                    }
+                }
+            }
+
+            InputKind::Up | InputKind::Cancel => {
+                if self.started && input.pointers.len() < 2 {
+                    let center = match input.pointers.first() {
+                        Some(other) => {
+                            let (a, b) = (other.position, input.pointer.position);
+                            Point((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0)
+                        }
+                        None => input.pointer.position,
+                    };
+
+                    self.pending.push(Pinch {
+                        phase: match input.kind {
+                            InputKind::Cancel => GesturePhase::Cancelled,
+                            _ => GesturePhase::Ended,
+                        },
+                        scale: self.scale,
+                        center,
+                    });
+                    self.started = false;
+                    self.base_distance = None;
+                    return Recognition::Accept;
+                }
+
+                if !self.started && input.pointers.is_empty() {
+                    return Recognition::Reject;
+                }
+                self.rebase(input.pointers);
+            }
+        }
+
+        if self.started {
+            Recognition::Accept
+        } else {
+            Recognition::Possible
+        }
+    }
+
+    fn flush(&mut self, ed: &EventDispatcher) {
+        for pinch in self.pending.drain(..) {
+            ed.emit_trusted(pinch);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.started = false;
+        self.base_distance = None;
+        self.base_scale = 1.0;
+        self.scale = 1.0;
+        self.pending.clear();
+    }
+}
+
+fn pair(pointers: &[TrackedPointer]) -> Option<(Point, Point)> {
+    match pointers {
+        [a, b, ..] => Some((a.position, b.position)),
+        _ => None,
+    }
+}
@@ -0,0 +1,112 @@
+use crate as irisia;
+use crate::{event::EventDispatcher, primitive::Pixel, Event};
+
+use super::{GestureInput, InputKind, Recognition, Recognizer};
+
+/// A pointer flung quickly and released.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Swipe {
+    pub direction: SwipeDirection,
+    /// Logical pixels per second when released.
+    pub velocity: (Pixel, Pixel),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Recognizes swipes. Unlike other recognizers, it is not exclusive, so that
+/// a swipe can still be recognized at the end of a pan.
+pub struct SwipeRecognizer {
+    min_distance: Pixel,
+    min_velocity: Pixel,
+    pending: Option<Swipe>,
+}
+
+impl SwipeRecognizer {
+    pub fn new() -> Self {
+        SwipeRecognizer {
+            min_distance: Pixel(40.0),
+            min_velocity: Pixel(500.0),
+            pending: None,
+        }
+    }
+
+    /// Minimum distance between pressed and released. Defaults to 40px.
+    pub fn min_distance(mut self, min_distance: Pixel) -> Self {
+        self.min_distance = min_distance;
+        self
+    }
+
+    /// Minimum velocity in pixels per second when released. Defaults to 500px.
+    pub fn min_velocity(mut self, min_velocity: Pixel) -> Self {
+        self.min_velocity = min_velocity;
+        self
+    }
+}
+
+impl Default for SwipeRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recognizer for SwipeRecognizer {
+    fn handle(&mut self, input: &GestureInput) -> Recognition {
+        match input.kind {
+            InputKind::Down if input.pointers.len() > 1 => Recognition::Reject,
+            InputKind::Down | InputKind::Move => Recognition::Possible,
+            InputKind::Cancel => Recognition::Reject,
+            InputKind::Up => {
+                let pointer = &input.pointer;
+                let (dx, dy) = (
+                    pointer.position.0 - pointer.start.0,
+                    pointer.position.1 - pointer.start.1,
+                );
+                let (vx, vy) = pointer.velocity;
+                let horizontal = dx.0.abs() >= dy.0.abs();
+
+                let (distance, speed) = if horizontal {
+                    (dx.0.abs(), vx.0.abs())
+                } else {
+                    (dy.0.abs(), vy.0.abs())
+                };
+
+                if distance < self.min_distance.0 || speed < self.min_velocity.0 {
+                    return Recognition::Reject;
+                }
+
+                let direction = match (horizontal, dx.0 > 0.0, dy.0 > 0.0) {
+                    (true, true, _) => SwipeDirection::Right,
+                    (true, false, _) => SwipeDirection::Left,
+                    (false, _, true) => SwipeDirection::Down,
+                    (false, _, false) => SwipeDirection::Up,
+                };
+
+                self.pending = Some(Swipe {
+                    direction,
+                    velocity: pointer.velocity,
+                });
+                Recognition::Accept
+            }
+        }
+    }
+
+    fn flush(&mut self, ed: &EventDispatcher) {
+        if let Some(swipe) = self.pending.take() {
+            ed.emit_trusted(swipe);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.pending = None;
+    }
+
+    fn exclusive(&self) -> bool {
+        false
+    }
+}
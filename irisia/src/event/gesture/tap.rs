@@ -0,0 +1,202 @@
+use std::time::{Duration, Instant};
+
+use crate as irisia;
+use crate::{
+    event::{standard::PointerId, EventDispatcher},
+    primitive::{Pixel, Point},
+    Event,
+};
+
+use super::{GestureInput, InputKind, Recognition, Recognizer};
+
+/// A pointer pressed and released quickly without moving.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Tap {
+    pub position: Point,
+    pub pointer_id: PointerId,
+}
+
+/// Two taps in a short interval at nearly the same position.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DoubleTap {
+    pub position: Point,
+    pub pointer_id: PointerId,
+}
+
+pub struct TapRecognizer {
+    slop: Pixel,
+    max_duration: Duration,
+    pressed: bool,
+    pending: Option<Tap>,
+}
+
+impl TapRecognizer {
+    pub fn new() -> Self {
+        TapRecognizer {
+            slop: Pixel(8.0),
+            max_duration: Duration::from_millis(500),
+            pressed: false,
+            pending: None,
+        }
+    }
+
+    /// Maximum distance the pointer can move before released. Defaults to 8px.
+    pub fn slop(mut self, slop: Pixel) -> Self {
+        self.slop = slop;
+        self
+    }
+
+    /// Maximum time between pressed and released. Defaults to 500ms.
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = max_duration;
+        self
+    }
+}
+
+impl Default for TapRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recognizer for TapRecognizer {
+    fn handle(&mut self, input: &GestureInput) -> Recognition {
+        match input.kind {
+            InputKind::Down => {
+                if self.pressed || self.pending.is_some() || input.pointers.len() > 1 {
+                    return Recognition::Reject;
+                }
+                self.pressed = true;
+                Recognition::Possible
+            }
+            InputKind::Move if input.pointer.travel() > self.slop => Recognition::Reject,
+            InputKind::Move => Recognition::Possible,
+            InputKind::Up => {
+                let pointer = &input.pointer;
+                if pointer.travel() > self.slop
+                    || input.time.duration_since(pointer.start_time) > self.max_duration
+                {
+                    return Recognition::Reject;
+                }
+
+                self.pressed = false;
+                self.pending = Some(Tap {
+                    position: pointer.position,
+                    pointer_id: pointer.id,
+                });
+                Recognition::Ready
+            }
+            InputKind::Cancel => Recognition::Reject,
+        }
+    }
+
+    fn flush(&mut self, ed: &EventDispatcher) {
+        if let Some(tap) = self.pending.take() {
+            ed.emit_trusted(tap);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.pressed = false;
+        self.pending = None;
+    }
+}
+
+pub struct DoubleTapRecognizer {
+    tap: TapRecognizer,
+    interval: Duration,
+    first_tap: Option<(Tap, Instant)>,
+    pending: Option<DoubleTap>,
+}
+
+impl DoubleTapRecognizer {
+    pub fn new() -> Self {
+        DoubleTapRecognizer {
+            tap: TapRecognizer::new(),
+            interval: Duration::from_millis(300),
+            first_tap: None,
+            pending: None,
+        }
+    }
+
+    /// Maximum distance a pointer can move during each tap, and half of the
+    /// maximum distance between the two taps. Defaults to 8px.
+    pub fn slop(mut self, slop: Pixel) -> Self {
+        self.tap.slop = slop;
+        self
+    }
+
+    /// Maximum time between pressed and released of each tap. Defaults to 500ms.
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.tap.max_duration = max_duration;
+        self
+    }
+
+    /// Maximum time between the first tap released and the second
+    /// tap pressed. Defaults to 300ms.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+impl Default for DoubleTapRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recognizer for DoubleTapRecognizer {
+    fn handle(&mut self, input: &GestureInput) -> Recognition {
+        if let (InputKind::Down, Some((first, released_at))) = (input.kind, &self.first_tap) {
+            let too_late = input.time.duration_since(*released_at) > self.interval;
+            let too_far = input.pointer.position.abs_diff(first.position) > self.tap.slop * 2.0;
+            if too_late || too_far {
+                return Recognition::Reject;
+            }
+        }
+
+        match self.tap.handle(input) {
+            Recognition::Ready => {}
+            other => return other,
+        }
+
+        let Some(second) = self.tap.pending.take()
+        else {
+            return Recognition::Reject;
+        };
+
+        match self.first_tap.take() {
+            Some((first, _)) => {
+                self.pending = Some(DoubleTap {
+                    position: first.position,
+                    pointer_id: second.pointer_id,
+                });
+                Recognition::Accept
+            }
+            None => {
+                self.first_tap = Some((second, input.time));
+                Recognition::Possible
+            }
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        match (&self.first_tap, self.tap.pressed) {
+            (Some((_, released_at)), false) => Some(*released_at + self.interval),
+            _ => None,
+        }
+    }
+
+    fn flush(&mut self, ed: &EventDispatcher) {
+        if let Some(double_tap) = self.pending.take() {
+            ed.emit_trusted(double_tap);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.tap.reset();
+        self.first_tap = None;
+        self.pending = None;
+    }
+}
@@ -9,6 +9,7 @@ use self::event_dispatcher::lock::EventDispatcherLock;
 pub(crate) use listen::EdProvider;
 
 pub mod event_dispatcher;
+pub mod gesture;
 mod listen;
 pub mod metadata;
 pub mod standard;
@@ -0,0 +1,95 @@
+use std::{
+    any::Any,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate as irisia;
+use crate::{primitive::Point, Event};
+
+/// Type-erased payload of a drag. Files dragged from outside the
+/// window are carried as [`DraggedFiles`].
+#[derive(Clone)]
+pub struct DragData(Arc<dyn Any + Send + Sync>);
+
+impl DragData {
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        DragData(Arc::new(value))
+    }
+
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.is::<T>()
+    }
+
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+/// Payload of files dragged from the operating system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DraggedFiles(pub Vec<PathBuf>);
+
+/// Handle for a drop target to tell whether it accepts the drag. Only the
+/// innermost hovered element which accepted will receive [`DragDrop`].
+///
+/// The acceptor is set once the event is received, which may be after the
+/// drop. Elements which can decide by the data alone should rather set a
+/// [drop filter].
+///
+/// [drop filter]: crate::dom::ElementModel::set_drop_filter
+#[derive(Clone, Default)]
+pub struct DropAcceptor(Arc<AtomicBool>);
+
+impl DropAcceptor {
+    pub fn accept(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn reject(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Emitted to an element and all its ancestors when a drag moved onto it.
+#[derive(Event, Clone)]
+pub struct DragEnter {
+    pub data: DragData,
+    pub position: Point,
+    pub acceptor: DropAcceptor,
+}
+
+/// Emitted to hovered elements when a drag moved.
+#[derive(Event, Clone)]
+pub struct DragOver {
+    pub data: DragData,
+    pub position: Point,
+    pub acceptor: DropAcceptor,
+}
+
+#[derive(Event, Clone)]
+pub struct DragLeave {
+    pub data: DragData,
+}
+
+/// Emitted to the innermost hovered element which accepted the drag,
+/// when the dragging pointer released or files dropped.
+#[derive(Event, Clone)]
+pub struct DragDrop {
+    pub data: DragData,
+    pub position: Point,
+}
+
+/// Emitted to the element started the drag.
+#[derive(Event, Clone, Copy)]
+pub struct DragEnd {
+    /// Whether the data was dropped on an accepting element.
+    pub dropped: bool,
+}
@@ -1,10 +1,16 @@
 use irisia_backend::window_handle::CloseHandle;
+use irisia_backend::winit::event::{Force, ModifiersState, MouseButton, MouseScrollDelta};
 use irisia_backend::StaticWindowEvent;
 
 use crate as irisia;
 use crate::primitive::{Pixel, Point};
 use crate::Event;
 
+pub use self::drag::{
+    DragData, DragDrop, DragEnd, DragEnter, DragLeave, DragOver, DraggedFiles, DropAcceptor,
+};
+
+mod drag;
 //pub mod window_event;
 
 impl Event for StaticWindowEvent {}
@@ -22,16 +28,41 @@ pub struct Blured;
 #[derive(Event, Clone, Copy)]
 pub struct Focused;
 
+/// Identifies a pointer. The mouse is always [`PointerId::Mouse`], and each
+/// finger or stylus on a touch screen owns a distinct id until it lifted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerId {
+    Mouse,
+    Touch(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerKind {
+    Mouse,
+    Touch,
+    Pen,
+}
+
 #[derive(Event, Clone)]
 pub struct PointerDown {
     pub is_current: bool,
     pub position: Point,
+    pub button: MouseButton,
+    pub modifiers: ModifiersState,
+    pub pointer_id: PointerId,
+    pub pointer_kind: PointerKind,
+    pub force: Option<Force>,
 }
 
 #[derive(Event, Clone, Copy)]
 pub struct PointerUp {
     pub is_current: bool,
     pub position: Point,
+    pub button: MouseButton,
+    pub modifiers: ModifiersState,
+    pub pointer_id: PointerId,
+    pub pointer_kind: PointerKind,
+    pub force: Option<Force>,
 }
 
 #[derive(Event, Clone, Copy)]
@@ -39,23 +70,93 @@ pub struct PointerMove {
     pub is_current: bool,
     pub delta: (Pixel, Pixel),
     pub position: Point,
+    pub modifiers: ModifiersState,
+    pub pointer_id: PointerId,
+    pub pointer_kind: PointerKind,
+    pub force: Option<Force>,
 }
 
+/// Emitted to the element under the pointer and all its ancestors
+/// when the mouse wheel or touchpad scrolled, up to the innermost
+/// element that [consumes the wheel].
+///
+/// [consumes the wheel]: crate::dom::ElementModel::consume_wheel
 #[derive(Event, Clone, Copy)]
-pub struct PointerEntered;
+pub struct Wheel {
+    pub is_current: bool,
+    pub delta: WheelDelta,
+    pub position: Point,
+    pub modifiers: ModifiersState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WheelDelta {
+    /// Amount in lines or rows to scroll.
+    Line(f32, f32),
+
+    /// Amount in pixels to scroll, usually reported by touchpads.
+    Pixel(Pixel, Pixel),
+}
+
+impl From<MouseScrollDelta> for WheelDelta {
+    fn from(value: MouseScrollDelta) -> Self {
+        match value {
+            MouseScrollDelta::LineDelta(x, y) => WheelDelta::Line(x, y),
+            MouseScrollDelta::PixelDelta(pos) => WheelDelta::Pixel(
+                Pixel::from_physical(pos.x as _),
+                Pixel::from_physical(pos.y as _),
+            ),
+        }
+    }
+}
 
 #[derive(Event, Clone, Copy)]
-pub struct PointerOut;
+pub struct PointerEntered {
+    pub pointer_id: PointerId,
+}
 
 #[derive(Event, Clone, Copy)]
-pub struct PointerOver;
+pub struct PointerOut {
+    pub pointer_id: PointerId,
+}
 
 #[derive(Event, Clone, Copy)]
-pub struct PointerLeft;
+pub struct PointerOver {
+    pub pointer_id: PointerId,
+}
 
+#[derive(Event, Clone, Copy)]
+pub struct PointerLeft {
+    pub pointer_id: PointerId,
+}
+
+/// Emitted to the element which captured the pointer when the capture
+/// is released, either manually or because the pointer was released.
+#[derive(Event, Clone, Copy)]
+pub struct PointerCaptureLost {
+    pub pointer_id: PointerId,
+}
+
+/// Emitted to the element owning a mounted portal when a pointer pressed
+/// outside the children of the portal, including presses on portals above.
+/// Usually used to dismiss popups.
+#[derive(Event, Clone, Copy)]
+pub struct PortalPressedOutside {
+    pub position: Point,
+    pub button: MouseButton,
+    pub pointer_id: PointerId,
+}
+
+/// Emitted when a button pressed and released on the same element.
 #[derive(Event, Clone, Copy)]
 pub struct Click {
     pub is_current: bool,
+    pub position: Point,
+    pub button: MouseButton,
+    pub modifiers: ModifiersState,
+    pub pointer_id: PointerId,
+    pub pointer_kind: PointerKind,
+    pub force: Option<Force>,
 }
 
 #[derive(Event, Clone, Copy)]
@@ -1,6 +1,6 @@
 use crate::{update_with::UpdateWith, Result};
 
-pub use self::{branch::Branch, chain::Chain, once::Once, repeat::Repeat};
+pub use self::{branch::Branch, chain::Chain, once::Once, repeat::Repeat, slot::Slot};
 
 pub mod branch;
 pub mod chain;
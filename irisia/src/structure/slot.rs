@@ -1,7 +1,6 @@
 use std::cell::{RefCell, RefMut};
 use std::rc::Rc;
 
-use crate::dom::RenderMultiple;
 use crate::{dom::EMUpdateContent, update_with::SpecificUpdate};
 
 use super::{MapVisit, UpdateWith};
@@ -40,44 +39,6 @@ impl<T> Slot<T> {
     }
 }
 
-impl<T> RenderMultiple for Slot<T>
-where
-    T: RenderMultiple,
-{
-    fn render(
-        &self,
-        lr: &mut crate::dom::layer::LayerRebuilder,
-        interval: std::time::Duration,
-    ) -> crate::Result<()> {
-        self.0.borrow().render(lr, interval)
-    }
-
-    fn emit_event(&self, npe: &crate::application::event_comp::NewPointerEvent) -> bool {
-        self.0.borrow().emit_event(npe)
-    }
-
-    fn layout(
-        &self,
-        f: &mut dyn FnMut(
-            &dyn crate::style::style_box::InsideStyleBox,
-        ) -> Option<crate::primitive::Region>,
-    ) -> crate::Result<()> {
-        self.0.borrow().layout(f)
-    }
-
-    fn len(&self) -> usize {
-        self.0.borrow().len()
-    }
-
-    fn peek_styles(&self, f: &mut dyn FnMut(&dyn crate::style::style_box::InsideStyleBox)) {
-        self.0.borrow().peek_styles(f)
-    }
-
-    fn as_any(&mut self) -> &mut dyn std::any::Any {
-        self
-    }
-}
-
 impl<T> SpecificUpdate for &Slot<T> {
     type UpdateTo = Slot<T>;
 }
@@ -34,7 +34,10 @@ use self::style_box::InsideStyleBox;
 pub use self::{branch::Branch, chain::Chain, once::Once, style_box::StyleBox};
 
 use crate::{self as irisia, primitive::Pixel, Style as DeriveStyle};
-use irisia_backend::skia_safe::Color;
+use irisia_backend::{
+    skia_safe::{Color, Color4f},
+    winit::window::CursorIcon,
+};
 
 pub use reader::StyleReader;
 
@@ -44,6 +47,82 @@ pub trait Style: Clone + 'static {}
 #[style(from)]
 pub struct StyleColor(pub Color);
 
+/// A [`Color4f`], like the value of a color picker, is rounded to 8 bits
+/// per channel.
+impl From<(Color4f,)> for StyleColor {
+    fn from((color,): (Color4f,)) -> Self {
+        StyleColor(color.to_color())
+    }
+}
+
+impl From<Color4f> for StyleColor {
+    fn from(color: Color4f) -> Self {
+        StyleColor(color.to_color())
+    }
+}
+
+/// Cursor icon shown when the pointer hovers on the element. Elements
+/// without this style use the cursor of their parent.
+///
+/// ```ignore
+/// irisia::style! {
+///     cursor: .pointer;
+/// }
+/// ```
+#[derive(Debug, DeriveStyle, Clone, Copy, PartialEq)]
+#[style(from, from = "", impl_default)]
+pub struct StyleCursor(#[style(default)] pub CursorIcon);
+
+impl StyleCursor {
+    pub fn pointer(&mut self) {
+        self.0 = CursorIcon::Hand;
+    }
+
+    pub fn text(&mut self) {
+        self.0 = CursorIcon::Text;
+    }
+
+    pub fn grab(&mut self) {
+        self.0 = CursorIcon::Grab;
+    }
+
+    pub fn grabbing(&mut self) {
+        self.0 = CursorIcon::Grabbing;
+    }
+
+    pub fn crosshair(&mut self) {
+        self.0 = CursorIcon::Crosshair;
+    }
+
+    pub fn move_(&mut self) {
+        self.0 = CursorIcon::Move;
+    }
+
+    pub fn not_allowed(&mut self) {
+        self.0 = CursorIcon::NotAllowed;
+    }
+
+    pub fn wait(&mut self) {
+        self.0 = CursorIcon::Wait;
+    }
+
+    pub fn col_resize(&mut self) {
+        self.0 = CursorIcon::ColResize;
+    }
+
+    pub fn row_resize(&mut self) {
+        self.0 = CursorIcon::RowResize;
+    }
+
+    pub fn ew_resize(&mut self) {
+        self.0 = CursorIcon::EwResize;
+    }
+
+    pub fn ns_resize(&mut self) {
+        self.0 = CursorIcon::NsResize;
+    }
+}
+
 #[derive(Debug, DeriveStyle, Clone, Copy, PartialEq)]
 pub enum XAxisBound {
     #[style(option)]